@@ -41,7 +41,7 @@ export default function App() {
     [state.villains, activeIndices],
   );
 
-  const { equities: rawEquities, mode, samples, isCalculating, error, recalc } = useEquity(
+  const { equities: rawEquities, isExact, samples, isCalculating, error, recalc } = useEquity(
     boardStr,
     heroStr,
     villainStrs,
@@ -170,7 +170,7 @@ export default function App() {
           isCalculating={isCalculating}
           disabledCards={globalDisabledCards}
           error={error}
-          mode={mode}
+          isExact={isExact}
           samples={samples}
           onSetVillain={(i, v) =>
             dispatch({ type: "SET_VILLAIN", index: i, value: v })
@@ -9,7 +9,7 @@ interface VillainsSectionProps {
   isCalculating: boolean;
   disabledCards: string[];
   error: string | null;
-  mode: string | null;
+  isExact: boolean | null;
   samples: number | null;
   onSetVillain: (index: number, value: (string | null)[]) => void;
   onSetVillainRange: (index: number, range: string) => void;
@@ -25,7 +25,7 @@ export function VillainsSection({
   isCalculating,
   disabledCards,
   error,
-  mode,
+  isExact,
   samples,
   onSetVillain,
   onSetVillainRange,
@@ -58,9 +58,10 @@ export function VillainsSection({
         <div className="px-1 min-h-[20px]">
           {error ? (
             <p className="text-sm text-red-500">{error}</p>
-          ) : mode && samples !== null ? (
+          ) : isExact !== null && samples !== null ? (
             <p className="text-xs text-stone-400 dark:text-stone-500">
-              {mode} &middot; {samples.toLocaleString()} samples
+              {isExact ? "exact" : "estimated"} &middot;{" "}
+              {samples.toLocaleString()} samples
             </p>
           ) : null}
         </div>
@@ -4,6 +4,7 @@ import { estimateEquity, type EquityResult } from "../lib/wasm";
 interface UseEquityResult {
   equities: number[] | null;
   mode: string | null;
+  isExact: boolean | null;
   samples: number | null;
   isCalculating: boolean;
   error: string | null;
@@ -37,10 +38,11 @@ export function useEquity(
     }
 
     const seq = ++seqRef.current;
+    const controller = new AbortController();
 
     const timer = setTimeout(() => {
       setIsCalculating(true);
-      estimateEquity(board, hero, currentVillains, iterations)
+      estimateEquity(board, hero, currentVillains, iterations, controller.signal)
         .then((res) => {
           if (seq !== seqRef.current) return;
           setResult(res);
@@ -59,6 +61,8 @@ export function useEquity(
 
     return () => {
       clearTimeout(timer);
+      // Stop a stale calculation so the worker is free for the new inputs
+      controller.abort();
     };
   }, [board, hero, villainsKey, villainsCount, iterations, recalcKey]);
 
@@ -67,6 +71,7 @@ export function useEquity(
   return {
     equities: result?.equities ?? null,
     mode: result?.mode ?? null,
+    isExact: result?.isExact ?? null,
     samples: result?.samples ?? null,
     isCalculating,
     error,
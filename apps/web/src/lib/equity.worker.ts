@@ -1,47 +1,14 @@
-import init, { estimate_equity } from "../wasm-pkg/snapcall_wasm";
-
-let ready: Promise<void> | null = null;
-
-function ensureInit(): Promise<void> {
-  if (ready === null) {
-    ready = init().then(() => undefined);
-  }
-  return ready;
-}
-
-export interface WorkerRequest {
-  id: number;
-  board: string;
-  hero: string;
-  villains: string[];
-  iterations: number;
-}
-
-export interface WorkerResponse {
-  id: number;
-  result?: { equities: number[]; mode: string; samples: number };
-  error?: string;
-}
-
-self.onmessage = async (e: MessageEvent<WorkerRequest>) => {
-  const { id, board, hero, villains, iterations } = e.data;
-  try {
-    await ensureInit();
-    const res = estimate_equity(board, hero, villains, iterations);
-    const response: WorkerResponse = {
-      id,
-      result: {
-        equities: Array.from(res.equities),
-        mode: res.mode,
-        samples: res.samples,
-      },
-    };
-    self.postMessage(response);
-  } catch (err: unknown) {
-    const response: WorkerResponse = {
-      id,
-      error: err instanceof Error ? err.message : String(err),
-    };
-    self.postMessage(response);
+import init, { installEquityWorker } from "../wasm-pkg/snapcall_wasm";
+
+// The message protocol (`EquityWorkerRequest` / `EquityWorkerResponse`) is
+// implemented in Rust; see bindings/wasm/src/worker.rs. Requests that arrive
+// while the module loads are replayed once the Rust handler is installed.
+const early: unknown[] = [];
+self.onmessage = (e: MessageEvent) => early.push(e.data);
+
+init().then(() => {
+  installEquityWorker();
+  for (const data of early) {
+    self.dispatchEvent(new MessageEvent("message", { data }));
   }
-};
+});
@@ -1,10 +1,15 @@
-import type { WorkerRequest, WorkerResponse } from "./equity.worker";
+import type {
+  EquityWorkerRequest,
+  EquityWorkerResponse,
+  EquityWorkerResult,
+} from "../wasm-pkg/snapcall_wasm";
 
-export interface EquityResult {
-  equities: number[];
-  mode: string;
-  samples: number;
-}
+/** `EquityWorkerResult` with plain arrays, as React state expects. */
+export type EquityResult = {
+  [K in keyof EquityWorkerResult]: EquityWorkerResult[K] extends Float64Array
+    ? number[]
+    : EquityWorkerResult[K];
+};
 
 const worker = new Worker(
   new URL("./equity.worker.ts", import.meta.url),
@@ -17,35 +22,67 @@ const pending = new Map<number, {
   reject: (e: Error) => void;
 }>();
 
-worker.onmessage = (e: MessageEvent<WorkerResponse>) => {
-  const { id, result, error } = e.data;
-  const entry = pending.get(id);
+worker.onmessage = (e: MessageEvent<EquityWorkerResponse>) => {
+  const msg = e.data;
+  if (msg.type === "progress") return;
+  const entry = pending.get(msg.id);
   if (!entry) return;
-  pending.delete(id);
-  if (error) {
-    entry.reject(new Error(error));
+  pending.delete(msg.id);
+  if (msg.type === "error") {
+    entry.reject(Object.assign(new Error(msg.message), { code: msg.code }));
   } else {
-    entry.resolve(result!);
+    entry.resolve({
+      equities: Array.from(msg.equities),
+      wins: Array.from(msg.wins),
+      ties: Array.from(msg.ties),
+      mode: msg.mode,
+      isExact: msg.isExact,
+      samples: msg.samples,
+      elapsedMs: msg.elapsedMs,
+    });
   }
 };
 
+function post(msg: EquityWorkerRequest) {
+  worker.postMessage(msg);
+}
+
 export function estimateEquity(
   board: string,
   hero: string,
   villains: string[],
   iterations: number = 100000,
+  signal?: AbortSignal,
 ): Promise<EquityResult> {
   const id = nextId++;
   return new Promise<EquityResult>((resolve, reject) => {
-    const timer = setTimeout(() => {
+    const cancel = (error: Error) => {
       pending.delete(id);
-      reject(new Error("Equity calculation timed out after 30s"));
-    }, 30_000);
+      post({ type: "cancel", id });
+      reject(error);
+    };
+    const onAbort = () =>
+      cancel(Object.assign(new Error("Calculation cancelled"), {
+        name: "AbortError",
+        code: "cancelled",
+      }));
+    if (signal?.aborted) {
+      onAbort();
+      return;
+    }
+    const timer = setTimeout(
+      () => cancel(new Error("Equity calculation timed out after 30s")),
+      30_000,
+    );
+    const done = () => {
+      clearTimeout(timer);
+      signal?.removeEventListener("abort", onAbort);
+    };
+    signal?.addEventListener("abort", onAbort);
     pending.set(id, {
-      resolve: (v) => { clearTimeout(timer); resolve(v); },
-      reject: (e) => { clearTimeout(timer); reject(e); },
+      resolve: (v) => { done(); resolve(v); },
+      reject: (e) => { done(); reject(e); },
     });
-    const msg: WorkerRequest = { id, board, hero, villains, iterations };
-    worker.postMessage(msg);
+    post({ type: "equity", id, board, hero, villains, iterations });
   });
 }
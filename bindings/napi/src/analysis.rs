@@ -0,0 +1,95 @@
+use napi::bindgen_prelude::{Either, Null};
+use napi_derive::napi;
+use snapcall_core::{board_texture, find_draws as core_find_draws, parse_cards};
+
+use crate::error;
+
+/// Hero's draws on a flop or turn, from `find_draws`.
+#[napi(object)]
+pub struct DrawInfo {
+    pub flush_draw: bool,
+    /// `"open_ended"` (an OESD), `"double_gutshot"`, or `"gutshot"`; `null`
+    /// when there is no straight draw.
+    pub straight_draw: Either<String, Null>,
+    /// A flush draw plus any straight draw.
+    pub combo_draw: bool,
+    /// Three to a flush on the flop, with a hole card.
+    pub backdoor_flush: bool,
+    /// A straight two more ranks complete on the flop, with no straight draw
+    /// already.
+    pub backdoor_straight: bool,
+    /// Unseen cards that give hero a straight or flush, e.g. `["5c", "Th"]`.
+    pub outs: Vec<String>,
+}
+
+/// Board texture from `analyze_board`.
+#[napi(object)]
+pub struct BoardTexture {
+    /// Two or more cards share a rank.
+    pub paired: bool,
+    /// `"rainbow"`, `"two_tone"`, or `"monotone"` (three or more of one
+    /// suit: a flush is possible).
+    pub suits: String,
+    /// Most board ranks in one five-rank straight window (1-5).
+    pub connectedness: u32,
+    /// Some two hole cards make a straight.
+    pub straight_possible: bool,
+    /// Distinct straights (by top card) some two hole cards make.
+    pub straights: u32,
+    /// Suits with three or more board cards.
+    pub flushes: u32,
+    /// Suits with exactly two board cards before the river.
+    pub flush_draws: u32,
+    /// `"high"` (top card ten or higher), `"medium"` (seven to nine), or
+    /// `"low"`.
+    pub high_card: String,
+    /// Rank of the top card, e.g. `"A"`.
+    pub top_rank: String,
+    /// 0 for a static board (e.g. `"Kc7d2s"`) up to 8 for a dynamic one
+    /// (e.g. `"9s8s7s"`).
+    pub wetness: u32,
+}
+
+/// Hero's flush and straight draws and the cards that complete them, for a
+/// flop or turn. Draws the board makes on its own are not counted.
+///
+/// Throws (see `estimate_equity`) unless hero is two cards and the board
+/// three or four.
+#[napi(js_name = "find_draws")]
+pub fn find_draws(hero: String, board: String) -> error::Result<DrawInfo> {
+    let hero = parse_cards(&hero).map_err(error::to_napi)?;
+    let board = parse_cards(&board).map_err(error::to_napi)?;
+    let draws = core_find_draws(&hero, &board).map_err(error::to_napi)?;
+    Ok(DrawInfo {
+        flush_draw: draws.flush_draw,
+        straight_draw: draws
+            .straight_draw
+            .map_or(Either::B(Null), |kind| Either::A(kind.to_string())),
+        combo_draw: draws.is_combo_draw(),
+        backdoor_flush: draws.backdoor_flush,
+        backdoor_straight: draws.backdoor_straight,
+        outs: draws.outs.iter().map(ToString::to_string).collect(),
+    })
+}
+
+/// Classifies a flop, turn, or river, e.g. `analyze_board("Ah7h2c")`.
+///
+/// Throws (see `estimate_equity`) unless the board is three to five
+/// distinct cards.
+#[napi(js_name = "analyze_board")]
+pub fn analyze_board(board: String) -> error::Result<BoardTexture> {
+    let board = parse_cards(&board).map_err(error::to_napi)?;
+    let texture = board_texture(&board).map_err(error::to_napi)?;
+    Ok(BoardTexture {
+        paired: texture.paired,
+        suits: texture.suits.to_string(),
+        connectedness: texture.connectedness.into(),
+        straight_possible: texture.straight_possible(),
+        straights: texture.straights.into(),
+        flushes: texture.flushes.into(),
+        flush_draws: texture.flush_draws.into(),
+        high_card: texture.high_card.to_string(),
+        top_rank: texture.top_rank.to_char().to_string(),
+        wetness: texture.wetness().into(),
+    })
+}
@@ -0,0 +1,91 @@
+use napi_derive::napi;
+use serde::{Deserialize, Serialize};
+use snapcall_core::{estimate_equity_with_options, parse_cards, EquityOptions, SnapError};
+
+use crate::error;
+
+/// Sample budget for scenarios that do not set `iterations`.
+const DEFAULT_ITERATIONS: usize = 100_000;
+
+/// One entry of the JSON array passed to `estimate_equity_batch`; the same
+/// shape as the WASM package's `BatchScenario`.
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct BatchScenario {
+    #[serde(default)]
+    board: String,
+    hero: String,
+    villains: Vec<String>,
+    iterations: Option<usize>,
+    seed: Option<u64>,
+    dead: Option<String>,
+}
+
+#[derive(Serialize)]
+#[serde(untagged)]
+enum BatchResult {
+    Equity {
+        equities: Vec<f64>,
+        wins: Vec<f64>,
+        ties: Vec<f64>,
+        mode: String,
+        samples: usize,
+    },
+    Failed {
+        error: BatchError,
+    },
+}
+
+#[derive(Serialize)]
+struct BatchError {
+    code: &'static str,
+    message: String,
+}
+
+/// Runs many equity scenarios in one call: `scenarios` is a JSON array of
+/// `{board?, hero, villains, iterations?, seed?, dead?}` and the return value
+/// a JSON array with, per scenario in order, either
+/// `{equities, wins, ties, mode, samples}` or `{error: {code, message}}`.
+///
+/// A bad scenario yields an `error` entry instead of failing the batch; only
+/// JSON that is not an array of scenarios throws, with code
+/// `invalid_argument`.
+#[napi(js_name = "estimate_equity_batch")]
+pub fn estimate_equity_batch(scenarios: String) -> error::Result<String> {
+    let scenarios: Vec<BatchScenario> = serde_json::from_str(&scenarios).map_err(|e| {
+        error::to_napi(SnapError::InvalidArgument(format!(
+            "Batch must be a JSON array of scenarios: {e}"
+        )))
+    })?;
+    let results: Vec<BatchResult> = scenarios
+        .iter()
+        .map(|scenario| {
+            run(scenario).unwrap_or_else(|err| BatchResult::Failed {
+                error: BatchError {
+                    code: err.code(),
+                    message: err.to_string(),
+                },
+            })
+        })
+        .collect();
+    serde_json::to_string(&results)
+        .map_err(|e| error::to_napi(SnapError::InvalidArgument(e.to_string())))
+}
+
+fn run(scenario: &BatchScenario) -> Result<BatchResult, SnapError> {
+    let options = EquityOptions {
+        seed: scenario.seed,
+        dead_cards: parse_cards(scenario.dead.as_deref().unwrap_or_default())?,
+        ..EquityOptions::new(scenario.iterations.unwrap_or(DEFAULT_ITERATIONS))
+    };
+    let villains: Vec<&str> = scenario.villains.iter().map(String::as_str).collect();
+    let result =
+        estimate_equity_with_options(&scenario.board, &scenario.hero, &villains, &options)?;
+    Ok(BatchResult::Equity {
+        equities: result.equities,
+        wins: result.wins,
+        ties: result.ties,
+        mode: result.mode.to_string(),
+        samples: result.samples,
+    })
+}
@@ -0,0 +1,39 @@
+use napi_derive::napi;
+use snapcall_core::combo_breakdown as core_combo_breakdown;
+
+use crate::{equity_options, error};
+
+/// Hero's equity against one villain combo, from `combo_breakdown`.
+#[napi(object)]
+pub struct ComboEquity {
+    /// Villain's cards, higher first, e.g. `"QhQd"`.
+    pub combo: String,
+    /// Hero's equity against this combo in percent; below 50 means it beats
+    /// hero.
+    pub equity: f64,
+    /// Share of the live villain range, from 0 to 1; all weights sum to 1.
+    pub weight: f64,
+}
+
+/// Hero's equity against each combo of a villain range, for "what beats
+/// me" tables. Each combo is solved with `iterations` (and `seed`) as in
+/// `estimate_equity`.
+#[napi(js_name = "combo_breakdown")]
+pub fn combo_breakdown(
+    board: String,
+    hero: String,
+    villain: String,
+    iterations: u32,
+    seed: Option<u32>,
+) -> error::Result<Vec<ComboEquity>> {
+    let combos = core_combo_breakdown(&board, &hero, &villain, &equity_options(iterations, seed))
+        .map_err(error::to_napi)?;
+    Ok(combos
+        .into_iter()
+        .map(|entry| ComboEquity {
+            combo: format!("{}{}", entry.combo[0], entry.combo[1]),
+            equity: entry.equity,
+            weight: entry.weight,
+        })
+        .collect())
+}
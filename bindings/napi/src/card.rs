@@ -0,0 +1,54 @@
+use napi_derive::napi;
+use snapcall_core::{format_card, parse_cards, Card, SnapError, SuitStyle};
+
+use crate::error;
+
+/// A single playing card.
+#[napi]
+pub struct JsCard {
+    card: Card,
+}
+
+#[napi]
+impl JsCard {
+    /// Parses one card such as `"Ah"` or `"td"` (case-insensitive).
+    ///
+    /// Throws an `Error` with code `invalid_card` for anything else,
+    /// including more than one card.
+    #[napi(factory)]
+    pub fn parse(text: String) -> error::Result<JsCard> {
+        let cards = parse_cards(&text).map_err(error::to_napi)?;
+        match cards.as_slice() {
+            [card] => Ok(JsCard { card: *card }),
+            _ => Err(error::to_napi(SnapError::InvalidCard(format!(
+                "Expected exactly one card, got {} in '{}'",
+                cards.len(),
+                text
+            )))),
+        }
+    }
+
+    /// Rank character: `"A"`, `"K"`, …, `"T"`, `"9"`, …, `"2"`.
+    #[napi(getter)]
+    pub fn rank(&self) -> String {
+        self.card.value.to_char().to_string()
+    }
+
+    /// Suit letter: `"s"`, `"h"`, `"d"`, or `"c"`.
+    #[napi(getter)]
+    pub fn suit(&self) -> String {
+        self.card.suit.to_char().to_string()
+    }
+
+    /// Rank plus suit symbol, e.g. `"A♥"`.
+    #[napi(js_name = "toUnicode")]
+    pub fn to_unicode(&self) -> String {
+        format_card(self.card, SuitStyle::Unicode)
+    }
+
+    /// Two-character form accepted everywhere else in the API, e.g. `"Ah"`.
+    #[napi(js_name = "toCompact")]
+    pub fn to_compact(&self) -> String {
+        self.card.to_string()
+    }
+}
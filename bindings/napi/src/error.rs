@@ -0,0 +1,50 @@
+use napi::bindgen_prelude::JsError;
+use napi::Env;
+use snapcall_core::SnapError;
+
+/// What synchronous exports return: a thrown JS `Error` whose `code` is the
+/// WASM package's `SnapErrorCode` (`"invalid_card"`, `"invalid_range"`, …).
+pub(crate) type Result<T> = napi::Result<T, &'static str>;
+
+/// Converts a core error into a thrown JS `Error` whose `code` is
+/// [`SnapError::code`] and whose message is the core error's, as in the
+/// WASM package.
+pub(crate) fn to_napi(err: SnapError) -> napi::Error<&'static str> {
+    napi::Error::new(err.code(), err.to_string())
+}
+
+/// Why an [`AsyncTask`](napi::bindgen_prelude::AsyncTask) calculation
+/// stopped early. Tasks can only fail with a plain `Status` error, so they
+/// carry this as their output and reject in `resolve`, where a coded JS
+/// error can be built.
+pub enum Failure {
+    /// Bad input or a resource limit.
+    Snap(SnapError),
+    /// The caller's `AbortSignal` fired.
+    Cancelled,
+    /// A progress or update callback threw; rejected with what it threw.
+    Callback(napi::Error),
+}
+
+impl From<SnapError> for Failure {
+    fn from(err: SnapError) -> Self {
+        Failure::Snap(err)
+    }
+}
+
+impl Failure {
+    /// The rejection for this failure: a coded `Error` as from [`to_napi`],
+    /// or one with `code` `"cancelled"` (the WASM `AbortError`) when
+    /// cancelled.
+    pub(crate) fn into_rejection(self, env: Env) -> napi::Error {
+        let coded = match self {
+            Failure::Snap(err) => to_napi(err),
+            Failure::Cancelled => napi::Error::new("cancelled", "Calculation cancelled"),
+            Failure::Callback(err) => return err,
+        };
+        // Keeping a reference to the JS object is the only way to reject with
+        // a `code` that is not a napi `Status`
+        let js = JsError::from(coded).into_unknown(env);
+        napi::Error::from(js)
+    }
+}
@@ -0,0 +1,125 @@
+use napi_derive::napi;
+use snapcall_core::{
+    best_hand, deal_random_hand, parse_cards, BestHand, Card, HandCategory, Street,
+};
+
+use crate::error;
+
+/// The best five-card hand out of 5-7 cards, from `evaluate_hand`.
+#[napi(object)]
+pub struct HandRank {
+    /// Stable id to localize or style by, from `"high_card"` to
+    /// `"straight_flush"`.
+    pub category: String,
+    /// 0 (high card) to 8 (straight flush); a higher index always wins.
+    pub category_index: u32,
+    /// English category name, e.g. `"Full House"`.
+    pub name: String,
+    /// English description, e.g. `"Aces full of Kings"`.
+    pub description: String,
+    /// The five cards, those making the hand first, e.g.
+    /// `["Ah", "Ad", "Ks", "Kc", "9c"]`.
+    pub cards: Vec<String>,
+    /// Leading entries of `cards` that form the category itself.
+    pub made: Vec<String>,
+    /// Ranks of the remaining cards, which only break ties, e.g. `["Q", "9"]`.
+    pub kickers: Vec<String>,
+}
+
+/// A random hand from `deal`.
+#[napi(object)]
+pub struct DealtHand {
+    /// Each seat's two hole cards, e.g. `[["Ah", "Kd"], ["7c", "7s"]]`.
+    pub hole_cards: Vec<Vec<String>>,
+    /// Board cards showing on the requested street (none preflop).
+    pub board: Vec<String>,
+    /// Each seat's best hand with the board; all `null` preflop.
+    pub hands: Vec<Option<HandRank>>,
+    /// Seats holding the best hand, more than one on a tie (preflop: hole
+    /// cards only).
+    pub winners: Vec<u32>,
+}
+
+/// Evaluates the best five-card hand among 5–7 cards, e.g.
+/// `evaluate_hand("AhAdKcKs2h9c")`.
+///
+/// Throws (see `estimate_equity`) unless the input is five to seven
+/// distinct cards.
+#[napi(js_name = "evaluate_hand")]
+pub fn evaluate_hand(cards: String) -> error::Result<HandRank> {
+    let cards = parse_cards(&cards).map_err(error::to_napi)?;
+    let best = best_hand(&cards).map_err(error::to_napi)?;
+    Ok(hand_rank(&best))
+}
+
+/// Deals `num_players` random hands and the board up to `street`
+/// (`"preflop"`, `"flop"`, `"turn"`, or `"river"`), with each seat's made
+/// hand and the winners. The same `seed` always deals the same hand.
+///
+/// Throws (see `estimate_equity`) for an unknown street or a player count
+/// outside 2–22.
+#[napi(js_name = "deal")]
+pub fn deal(
+    num_players: u32,
+    #[napi(ts_arg_type = "\"preflop\" | \"flop\" | \"turn\" | \"river\"")] street: String,
+    seed: Option<u32>,
+) -> error::Result<DealtHand> {
+    let street: Street = street.parse().map_err(error::to_napi)?;
+    let dealt = deal_random_hand(num_players as usize, street, seed.map(u64::from))
+        .map_err(error::to_napi)?;
+
+    let hands = dealt
+        .hole_cards
+        .iter()
+        .map(|hole| {
+            let mut cards = hole.to_vec();
+            cards.extend_from_slice(&dealt.board);
+            // Preflop there are only two cards, too few for a five-card hand
+            best_hand(&cards).ok().map(|best| hand_rank(&best))
+        })
+        .collect();
+    Ok(DealtHand {
+        hole_cards: dealt
+            .hole_cards
+            .iter()
+            .map(|hole| card_names(hole))
+            .collect(),
+        board: card_names(&dealt.board),
+        hands,
+        winners: dealt.winners.iter().map(|&seat| seat as u32).collect(),
+    })
+}
+
+fn hand_rank(best: &BestHand) -> HandRank {
+    HandRank {
+        category: category_id(best.category).to_string(),
+        category_index: best.category as u32,
+        name: best.category.name().to_string(),
+        description: best.describe(),
+        cards: card_names(&best.cards),
+        made: card_names(best.made()),
+        kickers: best
+            .kickers()
+            .iter()
+            .map(|c| c.value.to_char().to_string())
+            .collect(),
+    }
+}
+
+fn category_id(category: HandCategory) -> &'static str {
+    match category {
+        HandCategory::HighCard => "high_card",
+        HandCategory::OnePair => "one_pair",
+        HandCategory::TwoPair => "two_pair",
+        HandCategory::ThreeOfAKind => "three_of_a_kind",
+        HandCategory::Straight => "straight",
+        HandCategory::Flush => "flush",
+        HandCategory::FullHouse => "full_house",
+        HandCategory::FourOfAKind => "four_of_a_kind",
+        HandCategory::StraightFlush => "straight_flush",
+    }
+}
+
+fn card_names(cards: &[Card]) -> Vec<String> {
+    cards.iter().map(ToString::to_string).collect()
+}
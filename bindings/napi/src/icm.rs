@@ -0,0 +1,18 @@
+use napi_derive::napi;
+use snapcall_core::{estimate_icm_equity, DEFAULT_ICM_TRIALS};
+
+use crate::error;
+
+/// Prize equity of each player under the Independent Chip Model, in the
+/// units of `payouts` and in the order of `stacks`.
+///
+/// `payouts[k]` pays place `k + 1`; players with an empty stack have busted
+/// and share the bottom places. Exact for up to 20 players with chips;
+/// larger fields are estimated from sampled finishing orders.
+///
+/// Throws an `Error` with code `invalid_argument` for negative stacks or
+/// payouts.
+#[napi(js_name = "calculate_icm")]
+pub fn calculate_icm(stacks: Vec<f64>, payouts: Vec<f64>) -> error::Result<Vec<f64>> {
+    estimate_icm_equity(&stacks, &payouts, DEFAULT_ICM_TRIALS, None).map_err(error::to_napi)
+}
@@ -0,0 +1,292 @@
+//! Node.js native addon for the SnapCall engine, for server-side JS where
+//! the WASM package is too slow.
+//!
+//! Exports keep the WASM package's names, argument order, result shapes,
+//! and error `code`s, so code can switch between the two. The async exports
+//! take one extra, trailing `threads` argument.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, Arc};
+use std::time::Instant;
+
+use napi::bindgen_prelude::{
+    AsyncTask, FnArgs, Function, JsObjectValue, JsValuesTupleIntoVec, Object,
+};
+use napi::threadsafe_function::{
+    ThreadsafeFunction, ThreadsafeFunctionCallMode, UnknownReturnValue,
+};
+use napi::{Env, Status, Task};
+use napi_derive::napi;
+use snapcall_core::{
+    equity_vs_random as core_equity_vs_random, estimate_equity_with_options, EquityChunks,
+    EquityEstimateMode, EquityOptions, EquityResult, SnapError,
+};
+
+use crate::error::Failure;
+
+mod analysis;
+mod batch;
+mod breakdown;
+mod card;
+mod error;
+mod hand;
+mod icm;
+mod range;
+mod stream;
+
+pub use analysis::{analyze_board, find_draws, BoardTexture, DrawInfo};
+pub use batch::estimate_equity_batch;
+pub use breakdown::{combo_breakdown, ComboEquity};
+pub use card::JsCard;
+pub use hand::{deal, evaluate_hand, DealtHand, HandRank};
+pub use icm::calculate_icm;
+pub use range::{range_grid, RangeGridCell};
+pub use stream::{stream_equity, EquityUpdate};
+
+/// Equity for every player plus how it was computed. Per-player lists are
+/// in seat order (hero first) and in percent.
+#[napi(object)]
+pub struct EstimateResult {
+    /// Share of the pot each player can expect; sums to 100.
+    pub equities: Vec<f64>,
+    /// Share of samples each player wins outright.
+    pub wins: Vec<f64>,
+    /// Share of samples each player splits the pot.
+    pub ties: Vec<f64>,
+    /// `"exact"` or `"monte_carlo"`.
+    pub mode: String,
+    /// Whether `equities` are exact rather than a Monte Carlo estimate.
+    pub is_exact: bool,
+    pub samples: u32,
+    /// Wall-clock time spent in the engine, in milliseconds.
+    pub elapsed_ms: f64,
+}
+
+impl EstimateResult {
+    fn new(result: EquityResult, start: Instant) -> Self {
+        Self {
+            mode: result.mode.to_string(),
+            is_exact: result.mode == EquityEstimateMode::ExactEnumeration,
+            samples: u32::try_from(result.samples).unwrap_or(u32::MAX),
+            elapsed_ms: start.elapsed().as_secs_f64() * 1000.0,
+            equities: result.equities,
+            wins: result.wins,
+            ties: result.ties,
+        }
+    }
+}
+
+/// Estimates equity for hero against one or more villains.
+///
+/// Passing a `seed` makes Monte Carlo results identical on every call;
+/// without one each call samples afresh. Throws an `Error` whose `code` is
+/// `invalid_card`, `invalid_hand`, `invalid_range`, `invalid_argument`, or
+/// `resource_limit`, as the WASM `SnapError` does.
+#[napi(js_name = "estimate_equity")]
+pub fn estimate_equity(
+    board: String,
+    hero: String,
+    villains: Vec<String>,
+    iterations: u32,
+    seed: Option<u32>,
+) -> error::Result<EstimateResult> {
+    let v_refs: Vec<&str> = villains.iter().map(String::as_str).collect();
+    let start = Instant::now();
+    let result =
+        estimate_equity_with_options(&board, &hero, &v_refs, &equity_options(iterations, seed))
+            .map_err(error::to_napi)?;
+    Ok(EstimateResult::new(result, start))
+}
+
+/// Hero's equity against `num_opponents` random hands, as in
+/// `estimate_equity` with unknown villains but faster.
+#[napi(js_name = "equity_vs_random")]
+pub fn equity_vs_random(
+    hero: String,
+    board: String,
+    num_opponents: u32,
+    iterations: u32,
+    seed: Option<u32>,
+) -> error::Result<EstimateResult> {
+    let start = Instant::now();
+    let result = core_equity_vs_random(
+        &board,
+        &hero,
+        num_opponents as usize,
+        &equity_options(iterations, seed),
+    )
+    .map_err(error::to_napi)?;
+    Ok(EstimateResult::new(result, start))
+}
+
+/// Monte Carlo samples run between two progress reports or abort checks.
+const ASYNC_CHUNK_SAMPLES: usize = 10_000;
+
+/// Like `estimate_equity`, but solves on the libuv thread pool in chunks of
+/// 10 000 samples, so the event loop stays free.
+///
+/// `on_progress(done, total)` is called after every chunk with sample
+/// counts. Aborting `signal` stops the run at the next chunk and rejects
+/// with an `Error` whose `code` is `cancelled`. `threads` (1 when unset,
+/// at most `os.availableParallelism()`) spreads each chunk's sampling over
+/// that many threads; a seeded result depends on it as well as `seed`.
+#[napi(js_name = "calculate_equity_async")]
+#[allow(clippy::too_many_arguments)] // flat positional arguments mirror the WASM export
+pub fn calculate_equity_async(
+    env: Env,
+    board: String,
+    hero: String,
+    villains: Vec<String>,
+    iterations: u32,
+    #[napi(ts_arg_type = "((done: number, total: number) => void) | null | undefined")]
+    on_progress: Option<Callback<FnArgs<(u32, u32)>>>,
+    #[napi(ts_arg_type = "AbortSignal | null | undefined")] signal: Option<Object>,
+    seed: Option<u32>,
+    threads: Option<u32>,
+) -> napi::Result<AsyncTask<EquityTask>> {
+    let report = move |done: u32, _: &EquityResult| match &on_progress {
+        Some(callback) => call_and_wait(callback, (done, iterations).into()),
+        None => Ok(()),
+    };
+    Ok(AsyncTask::new(EquityTask {
+        board,
+        hero,
+        villains,
+        options: equity_options(iterations, seed),
+        threads,
+        chunk_samples: ASYNC_CHUNK_SAMPLES,
+        aborted: watch_signal(&env, signal)?,
+        after_chunk: Box::new(report),
+    }))
+}
+
+/// A JS callback handed to the thread pool, called with `T` and waited on.
+pub(crate) type Callback<T> = ThreadsafeFunction<T, UnknownReturnValue, T, Status, false>;
+
+/// Calls `callback` on the JS thread and blocks until it has run, so
+/// reports arrive in order and before the promise settles. What the
+/// callback throws is returned as [`Failure::Callback`].
+pub(crate) fn call_and_wait<T: 'static + JsValuesTupleIntoVec>(
+    callback: &Callback<T>,
+    value: T,
+) -> Result<(), Failure> {
+    let (sender, receiver) = mpsc::channel();
+    let status = callback.call_with_return_value(
+        value,
+        ThreadsafeFunctionCallMode::Blocking,
+        move |returned, _| {
+            // The receiver only goes away if the task has already stopped
+            let _ = sender.send(returned.map(|_| ()));
+            Ok(())
+        },
+    );
+    if status != Status::Ok {
+        return Err(Failure::Callback(napi::Error::from_status(status)));
+    }
+    match receiver.recv() {
+        Ok(returned) => returned.map_err(Failure::Callback),
+        Err(_) => Err(Failure::Callback(napi::Error::from_status(Status::Closing))),
+    }
+}
+
+/// An event listener that takes no arguments.
+type Listener<'env> = Function<'env, (), ()>;
+
+/// A flag set once `signal` (a DOM-style `AbortSignal`) fires, including
+/// when it has already fired.
+fn watch_signal(env: &Env, signal: Option<Object>) -> napi::Result<Arc<AtomicBool>> {
+    let aborted = Arc::new(AtomicBool::new(false));
+    let Some(signal) = signal else {
+        return Ok(aborted);
+    };
+    if signal.get_named_property::<bool>("aborted")? {
+        aborted.store(true, Ordering::Relaxed);
+        return Ok(aborted);
+    }
+    let flag = Arc::clone(&aborted);
+    let listener = env.create_function_from_closure::<(), (), _>("onabort", move |_| {
+        flag.store(true, Ordering::Relaxed);
+        Ok(())
+    })?;
+    let add_listener: Function<FnArgs<(&str, Listener)>, ()> =
+        signal.get_named_property("addEventListener")?;
+    add_listener.apply(signal, ("abort", listener).into())?;
+    Ok(aborted)
+}
+
+/// What an [`EquityTask`] calls after each chunk with the samples done and
+/// the running result.
+type AfterChunk = dyn FnMut(u32, &EquityResult) -> Result<(), Failure> + Send;
+
+/// The work behind [`calculate_equity_async`] and `stream_equity`: runs
+/// core's [`EquityChunks`] and calls `after_chunk(done, running_result)`
+/// after each chunk, stopping when it fails or `aborted` is set.
+pub struct EquityTask {
+    board: String,
+    hero: String,
+    villains: Vec<String>,
+    options: EquityOptions,
+    threads: Option<u32>,
+    chunk_samples: usize,
+    aborted: Arc<AtomicBool>,
+    after_chunk: Box<AfterChunk>,
+}
+
+impl EquityTask {
+    fn run(&mut self) -> Result<EstimateResult, Failure> {
+        self.options.threads = thread_count(self.threads)?;
+        let check_aborted = || match self.aborted.load(Ordering::Relaxed) {
+            true => Err(Failure::Cancelled),
+            false => Ok(()),
+        };
+        check_aborted()?;
+        let v_refs: Vec<&str> = self.villains.iter().map(String::as_str).collect();
+        let start = Instant::now();
+        let mut chunks = EquityChunks::new(
+            &self.board,
+            &self.hero,
+            &v_refs,
+            &self.options,
+            self.chunk_samples,
+        )?;
+        while let Some((done, running)) = chunks.next_chunk()? {
+            (self.after_chunk)(u32::try_from(done).unwrap_or(u32::MAX), running)?;
+            check_aborted()?;
+        }
+        Ok(EstimateResult::new(chunks.into_result()?, start))
+    }
+}
+
+impl Task for EquityTask {
+    type Output = Result<EstimateResult, Failure>;
+    type JsValue = EstimateResult;
+
+    fn compute(&mut self) -> napi::Result<Self::Output> {
+        Ok(self.run())
+    }
+
+    fn resolve(&mut self, env: Env, output: Self::Output) -> napi::Result<Self::JsValue> {
+        output.map_err(|failure| failure.into_rejection(env))
+    }
+}
+
+/// Validates a `threads` argument: 1 when unset, and between 1 and the
+/// number of cores otherwise.
+fn thread_count(threads: Option<u32>) -> Result<usize, SnapError> {
+    let cores = std::thread::available_parallelism().map_or(1, usize::from);
+    match threads.map_or(1, |t| t as usize) {
+        t @ 1.. if t <= cores => Ok(t),
+        t => Err(SnapError::InvalidArgument(format!(
+            "threads must be between 1 and {cores} on this machine, got {t}"
+        ))),
+    }
+}
+
+/// Automatic mode selection with an optional seed from JS (a `u32`, since
+/// `u64` would surface as a `BigInt`).
+fn equity_options(iterations: u32, seed: Option<u32>) -> EquityOptions {
+    EquityOptions {
+        seed: seed.map(u64::from),
+        ..EquityOptions::new(iterations as usize)
+    }
+}
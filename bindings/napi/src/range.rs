@@ -0,0 +1,51 @@
+use napi_derive::napi;
+use snapcall_core::{expand_weighted_range, range_grid as core_range_grid, HandClass};
+
+use crate::error;
+
+/// One cell of the 13×13 grid returned by `range_grid`.
+#[napi(object)]
+pub struct RangeGridCell {
+    /// Class label such as `"AA"`, `"AKs"`, or `"T9o"`.
+    pub class: String,
+    /// Combos of this class in the range.
+    pub combos: u32,
+    /// Combos of this class in a full deck (6, 4, or 12).
+    pub total: u32,
+    /// Share of the class in the range, from 0 to 1, counting combo weights
+    /// (`"AKs:0.5"`).
+    pub weight: f64,
+    /// Whether any combo of the class is in the range.
+    pub included: bool,
+}
+
+/// Expands a range (or exact hand) into the conventional 13×13 grid: rows
+/// and columns run from ace to deuce, pairs on the diagonal, suited hands
+/// above it and offsuit hands below.
+#[napi(js_name = "range_grid")]
+pub fn range_grid(range: String) -> error::Result<Vec<Vec<RangeGridCell>>> {
+    let weighted = expand_weighted_range(&range).map_err(error::to_napi)?;
+    let combos: Vec<_> = weighted.iter().map(|(combo, _)| *combo).collect();
+    let mut weights = [[0.0; 13]; 13];
+    for (combo, weight) in &weighted {
+        let (row, col) = HandClass::from_cards(combo[0], combo[1]).grid_position();
+        weights[row][col] += weight;
+    }
+    Ok(core_range_grid(&combos)
+        .into_iter()
+        .zip(weights)
+        .map(|(grid_row, weight_row)| {
+            grid_row
+                .into_iter()
+                .zip(weight_row)
+                .map(|(cell, weight)| RangeGridCell {
+                    class: cell.class.to_string(),
+                    combos: cell.combos as u32,
+                    total: cell.total as u32,
+                    weight: weight / cell.total as f64,
+                    included: cell.combos > 0,
+                })
+                .collect()
+        })
+        .collect())
+}
@@ -0,0 +1,83 @@
+use napi::bindgen_prelude::{AsyncTask, Object};
+use napi::Env;
+use napi_derive::napi;
+use snapcall_core::{EquityResult, SnapError};
+
+use crate::{call_and_wait, equity_options, error, watch_signal, Callback, EquityTask};
+
+/// Normal quantile of the 95% confidence interval in streamed updates.
+const CONFIDENCE_Z: f64 = 1.96;
+
+/// Running estimate passed to the `stream_equity` callback.
+#[napi(object)]
+pub struct EquityUpdate {
+    /// Samples run so far.
+    pub samples: u32,
+    /// The requested total.
+    pub total: u32,
+    /// Per-player equity in percent, hero first; sums to 100.
+    pub equities: Vec<f64>,
+    /// Lower 95% confidence bound around each equity, in percent.
+    pub low: Vec<f64>,
+    /// Upper 95% confidence bound around each equity, in percent.
+    pub high: Vec<f64>,
+}
+
+/// Like `calculate_equity_async`, but calls `on_update` with the running
+/// estimate and its 95% confidence interval every `every` samples, for
+/// displays that refine while the calculation runs.
+///
+/// Exact spots report once, with zero-width intervals. Throws (see
+/// `estimate_equity`) when `every` is 0; `signal`, `seed`, and `threads`
+/// work as in `calculate_equity_async`.
+#[napi(js_name = "stream_equity")]
+#[allow(clippy::too_many_arguments)] // flat positional arguments mirror the WASM export
+pub fn stream_equity(
+    env: Env,
+    board: String,
+    hero: String,
+    villains: Vec<String>,
+    iterations: u32,
+    every: u32,
+    #[napi(ts_arg_type = "(update: EquityUpdate) => void")] on_update: Callback<EquityUpdate>,
+    #[napi(ts_arg_type = "AbortSignal | null | undefined")] signal: Option<Object>,
+    seed: Option<u32>,
+    threads: Option<u32>,
+) -> napi::Result<AsyncTask<EquityTask>> {
+    if every == 0 {
+        let err = SnapError::InvalidArgument("Update interval must be at least 1 sample".into());
+        return Err(error::Failure::Snap(err).into_rejection(env));
+    }
+    let report = move |done: u32, running: &EquityResult| {
+        call_and_wait(&on_update, update(done, iterations, running))
+    };
+    Ok(AsyncTask::new(EquityTask {
+        board,
+        hero,
+        villains,
+        options: equity_options(iterations, seed),
+        threads,
+        chunk_samples: every as usize,
+        aborted: watch_signal(&env, signal)?,
+        after_chunk: Box::new(report),
+    }))
+}
+
+fn update(done: u32, total: u32, running: &EquityResult) -> EquityUpdate {
+    let margins = running.margin_of_error(CONFIDENCE_Z);
+    let bound = |sign: f64| -> Vec<f64> {
+        running
+            .equities
+            .iter()
+            .zip(&margins)
+            .map(|(e, m)| (e + sign * m).clamp(0.0, 100.0))
+            .collect()
+    };
+    EquityUpdate {
+        samples: done,
+        total,
+        equities: running.equities.clone(),
+        low: bound(-1.0),
+        high: bound(1.0),
+    }
+}
@@ -0,0 +1,123 @@
+// Smoke test for the Node addon. Build it first, then run:
+//
+//   cargo build -p snapcall-napi --release
+//   node --test bindings/napi/test/
+//
+// SNAPCALL_NAPI overrides the path of the compiled library.
+import assert from "node:assert/strict";
+import { createRequire } from "node:module";
+import { copyFileSync, mkdtempSync } from "node:fs";
+import { availableParallelism, tmpdir } from "node:os";
+import { join } from "node:path";
+import { test } from "node:test";
+
+const root = new URL("../../../", import.meta.url).pathname;
+const library = {
+  linux: "libsnapcall_napi.so",
+  darwin: "libsnapcall_napi.dylib",
+  win32: "snapcall_napi.dll",
+}[process.platform];
+// Node only loads addons with a `.node` extension
+const addon = join(mkdtempSync(join(tmpdir(), "snapcall-")), "snapcall.node");
+copyFileSync(process.env.SNAPCALL_NAPI ?? join(root, "target/release", library), addon);
+const snapcall = createRequire(import.meta.url)(addon);
+
+test("estimate_equity matches the WASM result shape", () => {
+  const result = snapcall.estimate_equity("", "AsAd", ["KcKd"], 20_000, 7);
+  assert.equal(result.mode, "monte_carlo");
+  assert.equal(result.isExact, false);
+  assert.equal(result.samples, 20_000);
+  assert.ok(result.equities[0] > 75 && result.equities[0] < 88);
+  assert.deepEqual(snapcall.estimate_equity("", "AsAd", ["KcKd"], 20_000, 7).equities, result.equities);
+});
+
+test("errors carry the WASM error codes", () => {
+  assert.throws(() => snapcall.estimate_equity("AsXx7d", "AhKh", ["KcKd"], 1000), { code: "invalid_card" });
+  assert.throws(() => snapcall.estimate_equity("", "AsAd", ["QQ-"], 1000), { code: "invalid_range" });
+  assert.throws(() => snapcall.JsCard.parse("AhKd"), { code: "invalid_card" });
+});
+
+test("equity_vs_random takes hero first", () => {
+  const result = snapcall.equity_vs_random("AsAd", "", 2, 10_000, 1);
+  assert.equal(result.equities.length, 3);
+  assert.ok(result.equities[0] > 60);
+});
+
+test("analysis, hands, ICM, and cards", () => {
+  const draws = snapcall.find_draws("AhKh", "Qh7h2c");
+  assert.equal(draws.flushDraw, true);
+  assert.equal(draws.straightDraw, null);
+  assert.equal(snapcall.analyze_board("9s8s7s").suits, "monotone");
+  const hand = snapcall.evaluate_hand("AhAdKcKs2h9c");
+  assert.equal(hand.category, "two_pair");
+  assert.equal(hand.cards.length, 5);
+  const dealt = snapcall.deal(3, "preflop", 5);
+  assert.equal(dealt.holeCards.length, 3);
+  assert.deepEqual(dealt.hands, [null, null, null]);
+  assert.equal(snapcall.deal(3, "river", 5).board.length, 5);
+  const icm = snapcall.calculate_icm([100, 100], [70, 30]);
+  assert.ok(Math.abs(icm[0] - 50) < 1e-9);
+  const card = snapcall.JsCard.parse("ah");
+  assert.equal(card.toCompact(), "Ah");
+  assert.equal(card.toUnicode(), "A♥");
+});
+
+test("estimate_equity_batch reports each scenario", () => {
+  const results = JSON.parse(
+    snapcall.estimate_equity_batch(
+      JSON.stringify([
+        { hero: "AsAd", villains: ["KcKd"], iterations: 5_000, seed: 1 },
+        { hero: "AsAd", villains: ["QQ-"] },
+      ]),
+    ),
+  );
+  assert.equal(results[0].samples, 5_000);
+  assert.equal(results[1].error.code, "invalid_range");
+  assert.throws(() => snapcall.estimate_equity_batch("{}"), { code: "invalid_argument" });
+});
+
+test("calculate_equity_async reports progress and bounds threads", async () => {
+  const progress = [];
+  const result = await snapcall.calculate_equity_async(
+    "", "AsAd", ["KcKd"], 25_000, (done, total) => progress.push([done, total]), null, 3, availableParallelism(),
+  );
+  assert.equal(result.samples, 25_000);
+  assert.deepEqual(progress, [[10_000, 25_000], [20_000, 25_000], [25_000, 25_000]]);
+  await assert.rejects(
+    snapcall.calculate_equity_async("", "AsAd", ["KcKd"], 1000, null, null, null, availableParallelism() + 1),
+    { code: "invalid_argument" },
+  );
+  await assert.rejects(
+    snapcall.calculate_equity_async("", "AsAd", ["KcKd"], 1000, null, null, null, 0),
+    { code: "invalid_argument" },
+  );
+});
+
+test("an aborted signal cancels", async () => {
+  const controller = new AbortController();
+  controller.abort();
+  await assert.rejects(
+    snapcall.calculate_equity_async("", "AsAd", ["KcKd"], 1000, null, controller.signal),
+    { code: "cancelled" },
+  );
+  const later = new AbortController();
+  await assert.rejects(
+    snapcall.calculate_equity_async("", "AsAd", ["KcKd"], 10_000_000, () => later.abort(), later.signal),
+    { code: "cancelled" },
+  );
+});
+
+test("stream_equity sends running estimates", async () => {
+  const updates = [];
+  const result = await snapcall.stream_equity("", "AsAd", ["KcKd"], 20_000, 5_000, (u) => updates.push(u), null, 4);
+  assert.equal(result.samples, 20_000);
+  assert.deepEqual(updates.map((u) => u.samples), [5_000, 10_000, 15_000, 20_000]);
+  const last = updates.at(-1);
+  assert.ok(last.low[0] <= last.equities[0] && last.equities[0] <= last.high[0]);
+  await assert.rejects(
+    snapcall.stream_equity("", "AsAd", ["KcKd"], 20_000, 5_000, () => {
+      throw new Error("stop");
+    }),
+    { message: "stop" },
+  );
+});
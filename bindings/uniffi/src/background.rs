@@ -0,0 +1,56 @@
+use std::future::Future;
+use std::panic::{self, AssertUnwindSafe};
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, Waker};
+use std::thread;
+
+/// Runs `work` on its own thread and resolves with its result, so async
+/// exports never block the foreign executor that polls them. A panic in
+/// `work` resumes when the future is polled, where UniFFI reports it.
+pub(crate) fn spawn<T, F>(work: F) -> Background<T>
+where
+    T: Send + 'static,
+    F: FnOnce() -> T + Send + 'static,
+{
+    let shared = Arc::new(Mutex::new(Shared {
+        result: None,
+        waker: None,
+    }));
+    let worker = Arc::clone(&shared);
+    thread::spawn(move || {
+        let result = panic::catch_unwind(AssertUnwindSafe(work));
+        let mut shared = worker.lock().unwrap_or_else(|e| e.into_inner());
+        shared.result = Some(result);
+        if let Some(waker) = shared.waker.take() {
+            waker.wake();
+        }
+    });
+    Background { shared }
+}
+
+struct Shared<T> {
+    result: Option<thread::Result<T>>,
+    waker: Option<Waker>,
+}
+
+/// The future returned by [`spawn`].
+pub(crate) struct Background<T> {
+    shared: Arc<Mutex<Shared<T>>>,
+}
+
+impl<T> Future for Background<T> {
+    type Output = T;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<T> {
+        let mut shared = self.shared.lock().unwrap_or_else(|e| e.into_inner());
+        match shared.result.take() {
+            Some(Ok(value)) => Poll::Ready(value),
+            Some(Err(payload)) => panic::resume_unwind(payload),
+            None => {
+                shared.waker = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+}
@@ -0,0 +1,81 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+use snapcall_core::{parse_cards, EquityOptions};
+
+use crate::equity::{solve, FfiEquityResult};
+use crate::{background, CalculationHandle, SnapError};
+
+/// One spot of an [`ffi_calculate_equity_batch`] call.
+#[derive(Debug, Clone, uniffi::Record)]
+pub struct FfiEquityRequest {
+    #[uniffi(default = "")]
+    pub board: String,
+    pub hero: String,
+    pub villains: Vec<String>,
+    pub iterations: u32,
+    #[uniffi(default = None)]
+    pub seed: Option<u64>,
+    /// Exposed or mucked cards removed from the deck, e.g. `"2c7d"`.
+    #[uniffi(default = "")]
+    pub dead: String,
+}
+
+/// Solves every request in one crossing, spread over the device's cores,
+/// e.g. every all-in of a session for a review screen. Results are in
+/// request order.
+///
+/// Fails with the error of the first bad request, so validate input that may
+/// be malformed before batching it. Cancelling `handle` makes it fail with
+/// [`SnapError::Cancelled`]; every worker stops at its next chunk boundary.
+#[uniffi::export(default(handle = None))]
+pub async fn ffi_calculate_equity_batch(
+    requests: Vec<FfiEquityRequest>,
+    handle: Option<Arc<CalculationHandle>>,
+) -> Result<Vec<FfiEquityResult>, SnapError> {
+    background::spawn(move || solve_all(&requests, handle.as_deref())).await
+}
+
+fn solve_all(
+    requests: &[FfiEquityRequest],
+    handle: Option<&CalculationHandle>,
+) -> Result<Vec<FfiEquityResult>, SnapError> {
+    let workers = thread::available_parallelism()
+        .map_or(1, |n| n.get())
+        .min(requests.len());
+    let next = AtomicUsize::new(0);
+    let results = Mutex::new(Vec::with_capacity(requests.len()));
+    thread::scope(|scope| {
+        for _ in 0..workers {
+            scope.spawn(|| {
+                // Each worker claims the next unsolved request until none are left
+                loop {
+                    let index = next.fetch_add(1, Ordering::Relaxed);
+                    let Some(request) = requests.get(index) else {
+                        break;
+                    };
+                    let result = run(request, handle);
+                    let mut results = results.lock().unwrap_or_else(|e| e.into_inner());
+                    results.push((index, result));
+                }
+            });
+        }
+    });
+    let mut results = results.into_inner().unwrap_or_else(|e| e.into_inner());
+    results.sort_by_key(|(index, _)| *index);
+    results.into_iter().map(|(_, result)| result).collect()
+}
+
+fn run(
+    request: &FfiEquityRequest,
+    handle: Option<&CalculationHandle>,
+) -> Result<FfiEquityResult, SnapError> {
+    let options = EquityOptions {
+        seed: request.seed,
+        dead_cards: parse_cards(&request.dead)?,
+        ..EquityOptions::new(request.iterations as usize)
+    };
+    let villains: Vec<&str> = request.villains.iter().map(String::as_str).collect();
+    solve(&request.board, &request.hero, &villains, &options, handle)
+}
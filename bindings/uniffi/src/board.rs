@@ -0,0 +1,87 @@
+use snapcall_core::{board_texture, parse_cards, HighCard, SuitPattern};
+
+use crate::SnapError;
+
+/// How a board's suits are spread.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, uniffi::Enum)]
+pub enum FfiSuitPattern {
+    /// No two cards share a suit.
+    Rainbow,
+    /// At most two cards of any suit.
+    TwoTone,
+    /// Three or more cards of one suit: a flush is possible.
+    Monotone,
+}
+
+impl From<SuitPattern> for FfiSuitPattern {
+    fn from(suits: SuitPattern) -> Self {
+        match suits {
+            SuitPattern::Rainbow => Self::Rainbow,
+            SuitPattern::TwoTone => Self::TwoTone,
+            SuitPattern::Monotone => Self::Monotone,
+        }
+    }
+}
+
+/// Where a board's top card falls.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, uniffi::Enum)]
+pub enum FfiHighCard {
+    /// Ten or higher.
+    High,
+    /// Seven to nine.
+    Medium,
+    /// Six or lower.
+    Low,
+}
+
+impl From<HighCard> for FfiHighCard {
+    fn from(high: HighCard) -> Self {
+        match high {
+            HighCard::High => Self::High,
+            HighCard::Medium => Self::Medium,
+            HighCard::Low => Self::Low,
+        }
+    }
+}
+
+/// Texture of a flop, turn, or river, for tagging and filtering hands.
+#[derive(Debug, Clone, uniffi::Record)]
+pub struct FfiBoardTexture {
+    /// Two or more cards share a rank.
+    pub paired: bool,
+    pub suits: FfiSuitPattern,
+    /// Most board ranks in one five-rank straight window (1-5).
+    pub connectedness: u8,
+    /// Some two hole cards make a straight.
+    pub straight_possible: bool,
+    /// Distinct straights (by top card) some two hole cards make.
+    pub straights: u8,
+    /// Suits with three or more board cards.
+    pub flushes: u8,
+    /// Suits with exactly two board cards before the river.
+    pub flush_draws: u8,
+    pub high_card: FfiHighCard,
+    /// Rank of the top card, e.g. `"A"`.
+    pub top_rank: String,
+    /// 0 for a static board (e.g. `"Kc7d2s"`) up to 8 for a dynamic one
+    /// (e.g. `"9s8s7s"`).
+    pub wetness: u8,
+}
+
+/// Classifies a flop, turn, or river, e.g. `"Ah7h2c"`.
+#[uniffi::export]
+pub fn ffi_analyze_board(board: String) -> Result<FfiBoardTexture, SnapError> {
+    let texture = board_texture(&parse_cards(&board)?)?;
+    Ok(FfiBoardTexture {
+        paired: texture.paired,
+        suits: texture.suits.into(),
+        connectedness: texture.connectedness,
+        straight_possible: texture.straight_possible(),
+        straights: texture.straights,
+        flushes: texture.flushes,
+        flush_draws: texture.flush_draws,
+        high_card: texture.high_card.into(),
+        top_rank: texture.top_rank.to_char().to_string(),
+        wetness: texture.wetness(),
+    })
+}
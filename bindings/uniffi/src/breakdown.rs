@@ -0,0 +1,98 @@
+use std::sync::Arc;
+
+use snapcall_core::{combo_breakdown_with, equity_when_called, ContinueRange, EquityOptions};
+
+use crate::equity::{equity_options, estimate};
+use crate::range::combo_name;
+use crate::{background, CalculationHandle, SnapError};
+
+/// Hero's equity against one villain combo.
+#[derive(Debug, Clone, uniffi::Record)]
+pub struct FfiComboEquity {
+    /// The villain's two cards, e.g. `"AhKd"`.
+    pub combo: String,
+    /// Hero's share of the pot against `combo`, in percent; below 50 means
+    /// the combo is ahead.
+    pub equity: f64,
+    /// The combo's share of the live villain range; weights sum to 1.
+    pub weight: f64,
+}
+
+/// Hero's equity against each live combo of the `villain` range on `board`,
+/// in range-grid order, for "which parts of villain's range beat you"
+/// tables. Expect roughly `iterations` samples per combo when Monte Carlo is
+/// needed. Cancelling `handle` makes it fail with [`SnapError::Cancelled`].
+#[uniffi::export(default(handle = None))]
+pub async fn ffi_combo_breakdown(
+    hero: String,
+    villain: String,
+    board: String,
+    iterations: u32,
+    seed: Option<u64>,
+    handle: Option<Arc<CalculationHandle>>,
+) -> Result<Vec<FfiComboEquity>, SnapError> {
+    let options = equity_options(iterations, seed);
+    background::spawn(move || combo_equities(&hero, &villain, &board, &options, handle.as_deref()))
+        .await
+}
+
+/// Solves every combo through [`estimate`], so a `handle` is checked between
+/// combos and between chunks of each.
+pub(crate) fn combo_equities(
+    hero: &str,
+    villain: &str,
+    board: &str,
+    options: &EquityOptions,
+    handle: Option<&CalculationHandle>,
+) -> Result<Vec<FfiComboEquity>, SnapError> {
+    let breakdown = combo_breakdown_with(board, hero, villain, options, |combo| {
+        estimate(board, hero, &[combo], options, handle)
+    })?;
+    Ok(breakdown
+        .into_iter()
+        .map(|c| FfiComboEquity {
+            combo: combo_name(&c.combo),
+            equity: c.equity,
+            weight: c.weight,
+        })
+        .collect())
+}
+
+/// Hero's equity against the part of villain's range that calls.
+#[derive(Debug, Clone, uniffi::Record)]
+pub struct FfiCalledEquity {
+    /// Hero's equity in percent against the calling combos.
+    pub equity: f64,
+    /// Share of villain's live range that calls, in percent.
+    pub continue_percent: f64,
+    pub samples: u64,
+}
+
+/// Hero's equity on `board` against only the combos of the `villain` range
+/// that are also in `continue_range`, e.g. the hands that call a bet.
+#[uniffi::export]
+pub async fn ffi_equity_when_called(
+    hero: String,
+    villain: String,
+    continue_range: String,
+    board: String,
+    iterations: u32,
+    seed: Option<u64>,
+) -> Result<FfiCalledEquity, SnapError> {
+    let options = equity_options(iterations, seed);
+    background::spawn(move || {
+        let called = equity_when_called(
+            &board,
+            &hero,
+            &villain,
+            ContinueRange::Range(&continue_range),
+            &options,
+        )?;
+        Ok(FfiCalledEquity {
+            equity: called.equity,
+            continue_percent: called.continue_percent,
+            samples: called.samples as u64,
+        })
+    })
+    .await
+}
@@ -0,0 +1,81 @@
+use std::sync::Arc;
+
+use snapcall_core::EquityOptions;
+
+use crate::breakdown::{combo_equities, FfiComboEquity};
+use crate::equity::{solve, split_players, FfiEquityResult};
+use crate::{background, CalculationHandle, FfiEngineConfig, FfiSolverPreference, SnapError};
+
+/// Equity settings an app configures once and reuses for every spot, instead
+/// of passing them on each call.
+///
+/// Every solve is Texas Hold'em. There is no variant setting yet: core only
+/// evaluates Hold'em, and the `variant` parameter is planned in
+/// `docs/ROADMAP.md` once it supports Short Deck and Omaha.
+#[derive(Debug, uniffi::Object)]
+pub struct EquityCalculator {
+    options: EquityOptions,
+}
+
+#[uniffi::export]
+impl EquityCalculator {
+    /// `iterations` is the Monte Carlo sample budget per solve; a `seed`
+    /// makes every solve reproducible. `threads` is the
+    /// [`FfiEngineConfig::threads`] of an otherwise default config.
+    #[uniffi::constructor(default(threads = 1))]
+    pub fn new(iterations: u32, seed: Option<u64>, threads: u32) -> Arc<Self> {
+        Self::with_config(FfiEngineConfig {
+            iterations,
+            time_budget_ms: None,
+            solver: FfiSolverPreference::Auto,
+            max_exact_states: None,
+            seed,
+            threads,
+        })
+    }
+
+    /// A calculator bounded by `config`: sample cap, time budget, solver
+    /// preference, and thread count.
+    #[uniffi::constructor]
+    pub fn with_config(config: FfiEngineConfig) -> Arc<Self> {
+        Arc::new(Self {
+            options: config.options(),
+        })
+    }
+
+    /// Equity for each of `players` (hero first, then villains; each exact
+    /// cards or a range) on `board`. Cancelling `handle` makes it fail with
+    /// [`SnapError::Cancelled`].
+    #[uniffi::method(default(handle = None))]
+    pub async fn solve(
+        &self,
+        players: Vec<String>,
+        board: String,
+        handle: Option<Arc<CalculationHandle>>,
+    ) -> Result<FfiEquityResult, SnapError> {
+        let options = self.options.clone();
+        background::spawn(move || {
+            let (hero, villains) = split_players(&players)?;
+            solve(&board, hero, &villains, &options, handle.as_deref())
+        })
+        .await
+    }
+
+    /// Hero's equity against each live combo of `villain` on `board`, in
+    /// range-grid order, for "which parts of the range beat me" tables.
+    /// Cancelling `handle` makes it fail with [`SnapError::Cancelled`].
+    #[uniffi::method(default(handle = None))]
+    pub async fn solve_breakdown(
+        &self,
+        hero: String,
+        villain: String,
+        board: String,
+        handle: Option<Arc<CalculationHandle>>,
+    ) -> Result<Vec<FfiComboEquity>, SnapError> {
+        let options = self.options.clone();
+        background::spawn(move || {
+            combo_equities(&hero, &villain, &board, &options, handle.as_deref())
+        })
+        .await
+    }
+}
@@ -0,0 +1,63 @@
+use std::num::NonZeroUsize;
+use std::thread;
+use std::time::Duration;
+
+use snapcall_core::{EquityEstimateMode, EquityOptions, DEFAULT_MAX_EXACT_STATES};
+
+/// Which solver an [`FfiEngineConfig`] asks for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, uniffi::Enum)]
+pub enum FfiSolverPreference {
+    /// Exact enumeration when the spot has at most `iterations` runouts,
+    /// Monte Carlo otherwise.
+    Auto,
+    /// Always enumerate; spots beyond `max_exact_states` fail with
+    /// `ResourceLimit` instead of running for minutes.
+    Exact,
+    /// Always sample, even when enumeration would be cheap.
+    MonteCarlo,
+}
+
+/// Limits a host sets on the engine, to bound CPU time and battery use.
+/// Pass it to [`EquityCalculator::with_config`](crate::EquityCalculator::with_config).
+///
+/// `threads` trades battery for speed: Monte Carlo solves split their
+/// samples over that many threads, capped at the device's core count.
+#[derive(Debug, Clone, uniffi::Record)]
+pub struct FfiEngineConfig {
+    /// Monte Carlo sample cap per solve, and the `Auto` exact threshold.
+    pub iterations: u32,
+    /// When set, Monte Carlo sampling stops after this long or after
+    /// `iterations` samples, whichever comes first.
+    #[uniffi(default = None)]
+    pub time_budget_ms: Option<u64>,
+    pub solver: FfiSolverPreference,
+    /// Largest state space `Exact` may enumerate; core's default when unset.
+    #[uniffi(default = None)]
+    pub max_exact_states: Option<u64>,
+    /// Makes every Monte Carlo solve reproducible for the same `threads`.
+    #[uniffi(default = None)]
+    pub seed: Option<u64>,
+    /// Threads each Monte Carlo solve samples on; 0 and 1 both mean one.
+    #[uniffi(default = 1)]
+    pub threads: u32,
+}
+
+impl FfiEngineConfig {
+    pub(crate) fn options(&self) -> EquityOptions {
+        EquityOptions {
+            mode: match self.solver {
+                FfiSolverPreference::Auto => None,
+                FfiSolverPreference::Exact => Some(EquityEstimateMode::ExactEnumeration),
+                FfiSolverPreference::MonteCarlo => Some(EquityEstimateMode::MonteCarlo),
+            },
+            max_exact_states: self.max_exact_states.map_or(DEFAULT_MAX_EXACT_STATES, |n| {
+                usize::try_from(n).unwrap_or(usize::MAX)
+            }),
+            time_budget: self.time_budget_ms.map(Duration::from_millis),
+            seed: self.seed,
+            threads: (self.threads as usize)
+                .min(thread::available_parallelism().map_or(1, NonZeroUsize::get)),
+            ..EquityOptions::new(self.iterations as usize)
+        }
+    }
+}
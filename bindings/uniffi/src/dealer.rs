@@ -0,0 +1,113 @@
+use std::sync::{Arc, Mutex};
+
+use snapcall_core::{Dealer, Table};
+
+use crate::hand::card_names;
+use crate::SnapError;
+
+/// A shuffled deck for play-money tables: deal hole cards, burn, and deal
+/// the board, with the same shuffle as every other snapcall front end.
+///
+/// The same `seed` always deals the same cards on every device.
+#[derive(Debug, uniffi::Object)]
+pub struct FfiDealer {
+    dealer: Mutex<Dealer>,
+}
+
+#[uniffi::export]
+impl FfiDealer {
+    #[uniffi::constructor]
+    pub fn new(seed: Option<u64>) -> Arc<Self> {
+        Arc::new(Self {
+            dealer: Mutex::new(Dealer::new(seed)),
+        })
+    }
+
+    /// Two cards for each of `seats` seats, seat 0 first, e.g.
+    /// `["AhKd", "7c7s"]`.
+    pub fn deal_hole_cards(&self, seats: u32) -> Result<Vec<String>, SnapError> {
+        let holes = self.lock().deal_hole_cards(seats as usize)?;
+        Ok(holes.iter().map(|[a, b]| format!("{a}{b}")).collect())
+    }
+
+    /// Discards the top card face down.
+    pub fn burn(&self) -> Result<(), SnapError> {
+        Ok(self.lock().burn()?)
+    }
+
+    /// `count` community cards, e.g. 3 for the flop.
+    pub fn deal_board(&self, count: u32) -> Result<Vec<String>, SnapError> {
+        Ok(card_names(&self.lock().deal_board(count as usize)?))
+    }
+
+    /// Every card dealt or burned so far, in order.
+    pub fn dealt_cards(&self) -> Vec<String> {
+        card_names(self.lock().dealt())
+    }
+
+    /// Cards still in the deck.
+    pub fn remaining(&self) -> u32 {
+        self.lock().remaining() as u32
+    }
+}
+
+impl FfiDealer {
+    fn lock(&self) -> std::sync::MutexGuard<'_, Dealer> {
+        // A panic mid-deal cannot leave the deck inconsistent
+        self.dealer.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// One hand dealt street by street: hole cards on creation, then a burn
+/// and the flop, turn, and river on each [`FfiTable::next_street`].
+///
+/// The same `seed` always deals the same hand on every device.
+#[derive(Debug, uniffi::Object)]
+pub struct FfiTable {
+    table: Mutex<Table>,
+}
+
+#[uniffi::export]
+impl FfiTable {
+    /// Fails with `InvalidHand` for fewer than 2 or more than 22 seats.
+    #[uniffi::constructor]
+    pub fn new(seats: u32, seed: Option<u64>) -> Result<Arc<Self>, SnapError> {
+        Ok(Arc::new(Self {
+            table: Mutex::new(Table::new(seats as usize, seed)?),
+        }))
+    }
+
+    /// Burns and deals the next street, returning its new cards. Fails with
+    /// `InvalidHand` once the river is out.
+    pub fn next_street(&self) -> Result<Vec<String>, SnapError> {
+        Ok(card_names(&self.lock().next_street()?))
+    }
+
+    /// Each seat's hole cards, seat 0 first, e.g. `["AhKd", "7c7s"]`.
+    pub fn hole_cards(&self) -> Vec<String> {
+        let table = self.lock();
+        table
+            .hole_cards()
+            .iter()
+            .map(|[a, b]| format!("{a}{b}"))
+            .collect()
+    }
+
+    /// Community cards dealt so far.
+    pub fn board(&self) -> Vec<String> {
+        card_names(self.lock().board())
+    }
+
+    /// Seats holding the best hand on the board so far (more than one on a
+    /// tie).
+    pub fn winners(&self) -> Vec<u32> {
+        let showdown = self.lock().showdown();
+        showdown.winners.iter().map(|&seat| seat as u32).collect()
+    }
+}
+
+impl FfiTable {
+    fn lock(&self) -> std::sync::MutexGuard<'_, Table> {
+        self.table.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
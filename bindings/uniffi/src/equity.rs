@@ -0,0 +1,225 @@
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use snapcall_core::{
+    estimate_equity_with_options, EquityChunks, EquityEstimateMode, EquityOptions, EquityResult,
+};
+
+use crate::{background, CalculationHandle, SnapError};
+
+/// Monte Carlo samples run between two progress reports.
+const CHUNK_SAMPLES: usize = 10_000;
+
+/// Receives progress from [`ffi_calculate_equity_with_progress`], on the
+/// calculation's background thread, between chunks of work.
+#[uniffi::export(callback_interface)]
+pub trait EquityProgressListener: Send + Sync {
+    /// `percent` of the sample budget is done (100 on the last call);
+    /// `equities` is the running estimate in seat order.
+    fn on_progress(&self, percent: f64, equities: Vec<f64>);
+}
+
+/// How an equity result was computed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, uniffi::Enum)]
+pub enum FfiEquityMode {
+    /// Every runout was enumerated; the numbers are exact.
+    Exact,
+    /// Runouts were sampled; the numbers are estimates.
+    MonteCarlo,
+}
+
+/// Equity for every player plus how it was computed, so apps can show
+/// accuracy next to the numbers.
+///
+/// Per-player lists are in seat order (hero first) and in percent.
+#[derive(Debug, Clone, uniffi::Record)]
+pub struct FfiEquityResult {
+    /// Share of the pot each player can expect; sums to 100.
+    pub equities: Vec<f64>,
+    /// Share of samples each player wins outright.
+    pub wins: Vec<f64>,
+    /// Share of samples each player splits the pot.
+    pub ties: Vec<f64>,
+    pub mode: FfiEquityMode,
+    /// Runouts evaluated (all of them when `mode` is `Exact`).
+    pub samples: u64,
+    /// Wall-clock time spent in the engine.
+    pub elapsed_ms: f64,
+}
+
+impl FfiEquityResult {
+    pub(crate) fn new(result: EquityResult, elapsed: Duration) -> Self {
+        Self {
+            equities: result.equities,
+            wins: result.wins,
+            ties: result.ties,
+            mode: match result.mode {
+                EquityEstimateMode::ExactEnumeration => FfiEquityMode::Exact,
+                EquityEstimateMode::MonteCarlo => FfiEquityMode::MonteCarlo,
+            },
+            samples: result.samples as u64,
+            elapsed_ms: elapsed.as_secs_f64() * 1000.0,
+        }
+    }
+}
+
+/// Like [`ffi_calculate_equity`], reporting progress to `listener` after
+/// every chunk of samples, for long multiway range calculations. Exact
+/// enumeration runs in one go and reports once.
+///
+/// A `seed` makes the whole run, progress reports included, reproducible.
+/// Cancelling `handle` makes it fail with [`SnapError::Cancelled`].
+#[uniffi::export]
+pub async fn ffi_calculate_equity_with_progress(
+    players: Vec<String>,
+    board: String,
+    iterations: u32,
+    seed: Option<u64>,
+    listener: Box<dyn EquityProgressListener>,
+    handle: Option<Arc<CalculationHandle>>,
+) -> Result<FfiEquityResult, SnapError> {
+    let options = equity_options(iterations, seed);
+    background::spawn(move || {
+        equity_with_progress(&players, &board, &options, &*listener, handle.as_deref())
+    })
+    .await
+}
+
+fn equity_with_progress(
+    players: &[String],
+    board: &str,
+    options: &EquityOptions,
+    listener: &dyn EquityProgressListener,
+    handle: Option<&CalculationHandle>,
+) -> Result<FfiEquityResult, SnapError> {
+    let check = || handle.map_or(Ok(()), CalculationHandle::check);
+    check()?;
+    let (hero, villains) = split_players(players)?;
+    let start = Instant::now();
+    let result = equity_in_chunks(board, hero, &villains, options, |done, running| {
+        check()?;
+        listener.on_progress(percent_done(done, options), running.equities.clone());
+        Ok(())
+    })?;
+    Ok(FfiEquityResult::new(result, start.elapsed()))
+}
+
+/// Equity for each of `players` (hero first, then villains; each exact cards
+/// or a range) on `board`.
+///
+/// Passing a `seed` makes Monte Carlo results identical on every call and
+/// every device; without one each call samples afresh.
+///
+/// Async so Swift and Kotlin can `await` it: the work runs on its own thread
+/// rather than the caller's. Cancelling `handle` makes it fail with
+/// [`SnapError::Cancelled`].
+#[uniffi::export(default(handle = None))]
+pub async fn ffi_calculate_equity(
+    players: Vec<String>,
+    board: String,
+    iterations: u32,
+    seed: Option<u64>,
+    handle: Option<Arc<CalculationHandle>>,
+) -> Result<FfiEquityResult, SnapError> {
+    let options = equity_options(iterations, seed);
+    background::spawn(move || {
+        let (hero, villains) = split_players(&players)?;
+        solve(&board, hero, &villains, &options, handle.as_deref())
+    })
+    .await
+}
+
+/// Equity of `hero` against each of `villains` on `board`, with the same
+/// arguments as the core and WASM `estimate_equity`. Like
+/// [`ffi_calculate_equity`] otherwise.
+#[uniffi::export(default(handle = None))]
+pub async fn ffi_estimate_equity(
+    board: String,
+    hero: String,
+    villains: Vec<String>,
+    iterations: u32,
+    seed: Option<u64>,
+    handle: Option<Arc<CalculationHandle>>,
+) -> Result<FfiEquityResult, SnapError> {
+    let options = equity_options(iterations, seed);
+    background::spawn(move || {
+        let villains: Vec<&str> = villains.iter().map(String::as_str).collect();
+        solve(&board, &hero, &villains, &options, handle.as_deref())
+    })
+    .await
+}
+
+/// One timed run of the core engine, shared by every equity export.
+pub(crate) fn solve(
+    board: &str,
+    hero: &str,
+    villains: &[&str],
+    options: &EquityOptions,
+    handle: Option<&CalculationHandle>,
+) -> Result<FfiEquityResult, SnapError> {
+    let start = Instant::now();
+    let result = estimate(board, hero, villains, options, handle)?;
+    Ok(FfiEquityResult::new(result, start.elapsed()))
+}
+
+/// Core's result for a spot. With a `handle` the run goes through
+/// [`equity_in_chunks`] and stops with [`SnapError::Cancelled`] at the first
+/// chunk boundary after a cancel.
+pub(crate) fn estimate(
+    board: &str,
+    hero: &str,
+    villains: &[&str],
+    options: &EquityOptions,
+    handle: Option<&CalculationHandle>,
+) -> Result<EquityResult, SnapError> {
+    match handle {
+        Some(handle) => {
+            handle.check()?;
+            equity_in_chunks(board, hero, villains, options, |_, _| handle.check())
+        }
+        None => Ok(estimate_equity_with_options(
+            board, hero, villains, options,
+        )?),
+    }
+}
+
+pub(crate) fn equity_options(iterations: u32, seed: Option<u64>) -> EquityOptions {
+    EquityOptions {
+        seed,
+        ..EquityOptions::new(iterations as usize)
+    }
+}
+
+/// `done` samples as a percentage of the budget in `options`.
+pub(crate) fn percent_done(done: usize, options: &EquityOptions) -> f64 {
+    if options.iterations == 0 {
+        100.0
+    } else {
+        done as f64 / options.iterations as f64 * 100.0
+    }
+}
+
+/// Hero and villains out of a `players` list.
+pub(crate) fn split_players(players: &[String]) -> Result<(&str, Vec<&str>), SnapError> {
+    let (hero, villains) = players.split_first().ok_or(SnapError::InvalidArgument {
+        message: "at least one player is required".to_string(),
+    })?;
+    Ok((hero, villains.iter().map(String::as_str).collect()))
+}
+
+/// Runs a calculation in chunks of [`CHUNK_SAMPLES`] through core's
+/// [`EquityChunks`], calling `after_chunk(done, running_result)` after each.
+/// An error from `after_chunk` stops the run and is returned as is.
+pub(crate) fn equity_in_chunks(
+    board: &str,
+    hero: &str,
+    villains: &[&str],
+    options: &EquityOptions,
+    mut after_chunk: impl FnMut(usize, &EquityResult) -> Result<(), SnapError>,
+) -> Result<EquityResult, SnapError> {
+    let mut chunks = EquityChunks::new(board, hero, villains, options, CHUNK_SAMPLES)?;
+    while let Some((done, running)) = chunks.next_chunk()? {
+        after_chunk(done, running)?;
+    }
+    Ok(chunks.into_result()?)
+}
@@ -0,0 +1,36 @@
+/// Mirror of the core `SnapError` for Swift and Kotlin, one variant per
+/// kind so callers can `switch`/`when` on it instead of parsing messages.
+#[derive(Debug, thiserror::Error, uniffi::Error)]
+pub enum SnapError {
+    /// A card string could not be parsed (e.g. `"Xz"`).
+    #[error("Invalid card string: {message}")]
+    InvalidCard { message: String },
+    /// A hand is structurally invalid (e.g. wrong number of cards, duplicates).
+    #[error("Invalid hand: {message}")]
+    InvalidHand { message: String },
+    /// A range expression could not be parsed or produced no hands.
+    #[error("Invalid range: {message}")]
+    InvalidRange { message: String },
+    /// A numeric or option argument is out of range.
+    #[error("Invalid argument: {message}")]
+    InvalidArgument { message: String },
+    /// The requested computation exceeds a configured limit.
+    #[error("Resource limit exceeded: {message}")]
+    ResourceLimit { message: String },
+    /// The caller cancelled the calculation before it finished.
+    #[error("Calculation cancelled")]
+    Cancelled,
+}
+
+impl From<snapcall_core::SnapError> for SnapError {
+    fn from(err: snapcall_core::SnapError) -> Self {
+        use snapcall_core::SnapError as Core;
+        match err {
+            Core::InvalidCard(message) => Self::InvalidCard { message },
+            Core::InvalidHand(message) => Self::InvalidHand { message },
+            Core::InvalidRange(message) => Self::InvalidRange { message },
+            Core::InvalidArgument(message) => Self::InvalidArgument { message },
+            Core::ResourceLimit(message) => Self::ResourceLimit { message },
+        }
+    }
+}
@@ -0,0 +1,50 @@
+use snapcall_core::{format_card, format_cards, parse_styled_cards, SuitStyle};
+
+use crate::hand::card_names;
+use crate::SnapError;
+
+/// How suits are drawn when cards are shown.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, uniffi::Enum)]
+pub enum FfiSuitStyle {
+    /// `Ah Ks`: plain letters, safe for screen readers.
+    Ascii,
+    /// `A♥ K♠`
+    Unicode,
+    /// `A♥️ K♠️`
+    Emoji,
+}
+
+impl From<FfiSuitStyle> for SuitStyle {
+    fn from(style: FfiSuitStyle) -> Self {
+        match style {
+            FfiSuitStyle::Ascii => Self::Ascii,
+            FfiSuitStyle::Unicode => Self::Unicode,
+            FfiSuitStyle::Emoji => Self::Emoji,
+        }
+    }
+}
+
+/// Renders one card (e.g. `"ah"`) in `style`, e.g. `"A♥"`.
+#[uniffi::export]
+pub fn ffi_format_card(card: String, style: FfiSuitStyle) -> Result<String, SnapError> {
+    match parse_styled_cards(&card)?.as_slice() {
+        [card] => Ok(format_card(*card, style.into())),
+        cards => Err(SnapError::InvalidCard {
+            message: format!("expected one card, got {}", cards.len()),
+        }),
+    }
+}
+
+/// Renders a run of cards (e.g. `"AhKd2c"`) in `style`, separated by spaces
+/// in the same order as [`crate::ffi_parse_cards`].
+#[uniffi::export]
+pub fn ffi_format_cards(cards: String, style: FfiSuitStyle) -> Result<String, SnapError> {
+    Ok(format_cards(&parse_styled_cards(&cards)?, style.into()))
+}
+
+/// Reads cards written in any style (`"A♥️ K♠"`, `"AhKs"`) back into
+/// canonical card strings, e.g. `["Ah", "Ks"]`.
+#[uniffi::export]
+pub fn ffi_parse_styled_cards(cards: String) -> Result<Vec<String>, SnapError> {
+    Ok(card_names(&parse_styled_cards(&cards)?))
+}
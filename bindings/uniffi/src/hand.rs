@@ -0,0 +1,110 @@
+use snapcall_core::{best_hand, parse_cards, BestHand, Card, HandCategory, Locale};
+
+use crate::SnapError;
+
+/// Hand category, weakest first; a later case always wins.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, uniffi::Enum)]
+pub enum FfiHandCategory {
+    HighCard,
+    OnePair,
+    TwoPair,
+    ThreeOfAKind,
+    Straight,
+    Flush,
+    FullHouse,
+    FourOfAKind,
+    StraightFlush,
+}
+
+impl From<HandCategory> for FfiHandCategory {
+    fn from(category: HandCategory) -> Self {
+        match category {
+            HandCategory::HighCard => Self::HighCard,
+            HandCategory::OnePair => Self::OnePair,
+            HandCategory::TwoPair => Self::TwoPair,
+            HandCategory::ThreeOfAKind => Self::ThreeOfAKind,
+            HandCategory::Straight => Self::Straight,
+            HandCategory::Flush => Self::Flush,
+            HandCategory::FullHouse => Self::FullHouse,
+            HandCategory::FourOfAKind => Self::FourOfAKind,
+            HandCategory::StraightFlush => Self::StraightFlush,
+        }
+    }
+}
+
+impl From<FfiHandCategory> for HandCategory {
+    fn from(category: FfiHandCategory) -> Self {
+        match category {
+            FfiHandCategory::HighCard => Self::HighCard,
+            FfiHandCategory::OnePair => Self::OnePair,
+            FfiHandCategory::TwoPair => Self::TwoPair,
+            FfiHandCategory::ThreeOfAKind => Self::ThreeOfAKind,
+            FfiHandCategory::Straight => Self::Straight,
+            FfiHandCategory::Flush => Self::Flush,
+            FfiHandCategory::FullHouse => Self::FullHouse,
+            FfiHandCategory::FourOfAKind => Self::FourOfAKind,
+            FfiHandCategory::StraightFlush => Self::StraightFlush,
+        }
+    }
+}
+
+/// The best five-card hand out of 5–7 cards, structured so apps can render
+/// localized, styled descriptions.
+#[derive(Debug, Clone, uniffi::Record)]
+pub struct FfiHandRank {
+    pub category: FfiHandCategory,
+    /// Category name, e.g. `"Full House"`.
+    pub name: String,
+    /// Description, e.g. `"Aces full of Kings"`.
+    pub description: String,
+    /// The five cards, those making the hand first, e.g.
+    /// `["Ah", "Ad", "Ks", "Kc", "9c"]`.
+    pub cards: Vec<String>,
+    /// Leading entries of `cards` that form the category itself.
+    pub made: Vec<String>,
+    /// Ranks of the remaining cards, which only break ties, e.g. `["Q", "9"]`.
+    pub kickers: Vec<String>,
+}
+
+impl FfiHandRank {
+    fn new(best: &BestHand, locale: Locale) -> Self {
+        Self {
+            category: best.category.into(),
+            name: best.category.name_in(locale).to_string(),
+            description: best.describe_in(locale),
+            cards: card_names(&best.cards),
+            made: card_names(best.made()),
+            kickers: best
+                .kickers()
+                .iter()
+                .map(|c| c.value.to_char().to_string())
+                .collect(),
+        }
+    }
+}
+
+/// Evaluates the best five-card hand among 5–7 cards, e.g.
+/// `"AhAdKcKs2h9c"`, with `name` and `description` in `locale` (a tag such
+/// as `"zh-Hans"` or `"pt-BR"`; English when unset or unsupported).
+#[uniffi::export]
+pub fn ffi_evaluate_hand(cards: String, locale: Option<String>) -> Result<FfiHandRank, SnapError> {
+    let best = best_hand(&parse_cards(&cards)?)?;
+    Ok(FfiHandRank::new(&best, locale_from(locale.as_deref())))
+}
+
+/// `category`'s name in `locale` (zh, es, pt, ru; English otherwise), e.g.
+/// `"Escalera de color"`.
+#[uniffi::export]
+pub fn ffi_hand_type_name(category: FfiHandCategory, locale: String) -> String {
+    HandCategory::from(category)
+        .name_in(Locale::from_tag(&locale))
+        .to_string()
+}
+
+fn locale_from(tag: Option<&str>) -> Locale {
+    tag.map_or(Locale::English, Locale::from_tag)
+}
+
+pub(crate) fn card_names(cards: &[Card]) -> Vec<String> {
+    cards.iter().map(ToString::to_string).collect()
+}
@@ -0,0 +1,45 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+use crate::SnapError;
+
+/// Lets an app stop a calculation it started, e.g. when the user edits the
+/// inputs. Create one, pass it to the calculation, and call [`cancel`] from
+/// any thread; the calculation then fails with [`SnapError::Cancelled`].
+///
+/// Monte Carlo runs stop at the next chunk boundary; exact enumeration runs
+/// in one go and only notices a cancel made before it starts.
+///
+/// [`cancel`]: CalculationHandle::cancel
+#[derive(Debug, Default, uniffi::Object)]
+pub struct CalculationHandle {
+    cancelled: AtomicBool,
+}
+
+#[uniffi::export]
+impl CalculationHandle {
+    #[uniffi::constructor]
+    pub fn new() -> Arc<Self> {
+        Arc::new(Self::default())
+    }
+
+    /// Asks the calculation to stop. Calling it again has no effect.
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+}
+
+impl CalculationHandle {
+    /// `Err(Cancelled)` once [`cancel`](Self::cancel) has been called.
+    pub(crate) fn check(&self) -> Result<(), SnapError> {
+        if self.is_cancelled() {
+            Err(SnapError::Cancelled)
+        } else {
+            Ok(())
+        }
+    }
+}
@@ -0,0 +1,59 @@
+use snapcall_core::{chop_deal, estimate_icm_equity, ChopMethod, DEFAULT_ICM_TRIALS};
+
+use crate::SnapError;
+
+/// Each player's prize equity under the Independent Chip Model, in the units
+/// of `payouts` (`payouts[k]` pays place `k + 1`). Players with a zero stack
+/// have already busted.
+///
+/// Exact for up to 20 players with chips; larger fields are estimated from
+/// sampled finishing orders, so repeated calls may differ slightly.
+///
+/// Fails with `InvalidArgument` for negative or non-finite payouts or when
+/// nobody has chips.
+#[uniffi::export]
+pub fn ffi_calculate_icm(stacks: Vec<u64>, payouts: Vec<f64>) -> Result<Vec<f64>, SnapError> {
+    let stacks: Vec<f64> = stacks.into_iter().map(|s| s as f64).collect();
+    Ok(estimate_icm_equity(
+        &stacks,
+        &payouts,
+        DEFAULT_ICM_TRIALS,
+        None,
+    )?)
+}
+
+/// How [`ffi_chop_deal`] splits the prizes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, uniffi::Enum)]
+pub enum FfiChopMethod {
+    /// Each player's ICM prize equity.
+    Icm,
+    /// Everyone locks up the lowest prize still in play; the rest goes out
+    /// by chips.
+    ChipChip,
+}
+
+impl From<FfiChopMethod> for ChopMethod {
+    fn from(method: FfiChopMethod) -> Self {
+        match method {
+            FfiChopMethod::Icm => Self::Icm,
+            FfiChopMethod::ChipChip => Self::ChipChip,
+        }
+    }
+}
+
+/// Money each player takes in a final-table deal, in the units of
+/// `payouts`. `play_for` comes off first prize and is left to play for;
+/// pass 0 for a full chop.
+///
+/// Fails with `InvalidArgument` for a `play_for` outside 0 to the first
+/// prize, plus the errors of [`ffi_calculate_icm`].
+#[uniffi::export]
+pub fn ffi_chop_deal(
+    stacks: Vec<u64>,
+    payouts: Vec<f64>,
+    method: FfiChopMethod,
+    play_for: f64,
+) -> Result<Vec<f64>, SnapError> {
+    let stacks: Vec<f64> = stacks.into_iter().map(|s| s as f64).collect();
+    Ok(chop_deal(&stacks, &payouts, method.into(), play_for)?)
+}
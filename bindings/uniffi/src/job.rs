@@ -0,0 +1,138 @@
+use std::collections::HashMap;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, LazyLock, Mutex};
+use std::thread;
+use std::time::Instant;
+
+use snapcall_core::{parse_cards, plan_equity, EquityOptions};
+
+use crate::equity::{equity_in_chunks, percent_done, FfiEquityResult};
+use crate::{CalculationHandle, FfiEquityRequest, SnapError};
+
+/// Jobs started and not yet finished-and-polled or cancelled, by id.
+static JOBS: LazyLock<Mutex<HashMap<u64, Arc<Job>>>> = LazyLock::new(Default::default);
+
+static NEXT_ID: AtomicU64 = AtomicU64::new(1);
+
+struct Job {
+    handle: CalculationHandle,
+    state: Mutex<JobState>,
+}
+
+#[derive(Default)]
+struct JobState {
+    progress: f64,
+    partial: Vec<f64>,
+    outcome: Option<thread::Result<Result<FfiEquityResult, SnapError>>>,
+}
+
+/// Where a job started with [`ffi_start_equity_job`] stands.
+#[derive(Debug, Clone, uniffi::Record)]
+pub struct FfiJobStatus {
+    /// Percent of the sample budget done, 0 to 100.
+    pub progress: f64,
+    /// Running equity estimate in seat order; empty until the first chunk
+    /// of samples is in.
+    pub partial: Vec<f64>,
+    pub done: bool,
+    /// The final result once `done`.
+    pub result: Option<FfiEquityResult>,
+}
+
+/// Starts an equity calculation on its own thread and returns its job id,
+/// for hosts that cannot receive foreign callbacks (e.g. some game engines).
+/// Follow it with [`ffi_poll_job`] until `done`, or [`ffi_cancel_job`].
+///
+/// Input errors are reported here rather than by the first poll.
+#[uniffi::export]
+pub fn ffi_start_equity_job(request: FfiEquityRequest) -> Result<u64, SnapError> {
+    let options = EquityOptions {
+        seed: request.seed,
+        dead_cards: parse_cards(&request.dead)?,
+        ..EquityOptions::new(request.iterations as usize)
+    };
+    let villains: Vec<&str> = request.villains.iter().map(String::as_str).collect();
+    plan_equity(&request.board, &request.hero, &villains, &options)?;
+
+    let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+    let job = Arc::new(Job {
+        handle: CalculationHandle::default(),
+        state: Mutex::default(),
+    });
+    lock(&JOBS).insert(id, Arc::clone(&job));
+    thread::spawn(move || {
+        let outcome = panic::catch_unwind(AssertUnwindSafe(|| run(&job, &request, &options)));
+        lock(&job.state).outcome = Some(outcome);
+    });
+    Ok(id)
+}
+
+/// Progress of job `id`, with the result once it is done. The job is
+/// forgotten after reporting `done` (or failing), so later polls fail with
+/// [`SnapError::InvalidArgument`].
+#[uniffi::export]
+pub fn ffi_poll_job(id: u64) -> Result<FfiJobStatus, SnapError> {
+    let job = lock(&JOBS).get(&id).cloned().ok_or_else(|| unknown(id))?;
+    let mut state = lock(&job.state);
+    let Some(outcome) = state.outcome.take() else {
+        return Ok(FfiJobStatus {
+            progress: state.progress,
+            partial: state.partial.clone(),
+            done: false,
+            result: None,
+        });
+    };
+    lock(&JOBS).remove(&id);
+    // A panic resumes here, where UniFFI reports it
+    let result = outcome.unwrap_or_else(|panic| panic::resume_unwind(panic))?;
+    Ok(FfiJobStatus {
+        progress: 100.0,
+        partial: result.equities.clone(),
+        done: true,
+        result: Some(result),
+    })
+}
+
+/// Stops job `id` at its next chunk boundary and forgets it. Cancelling a
+/// job that is already finished and polled fails with
+/// [`SnapError::InvalidArgument`].
+#[uniffi::export]
+pub fn ffi_cancel_job(id: u64) -> Result<(), SnapError> {
+    let job = lock(&JOBS).remove(&id).ok_or_else(|| unknown(id))?;
+    job.handle.cancel();
+    Ok(())
+}
+
+fn run(
+    job: &Job,
+    request: &FfiEquityRequest,
+    options: &EquityOptions,
+) -> Result<FfiEquityResult, SnapError> {
+    let villains: Vec<&str> = request.villains.iter().map(String::as_str).collect();
+    let start = Instant::now();
+    let result = equity_in_chunks(
+        &request.board,
+        &request.hero,
+        &villains,
+        options,
+        |done, running| {
+            job.handle.check()?;
+            let mut state = lock(&job.state);
+            state.progress = percent_done(done, options);
+            state.partial.clone_from(&running.equities);
+            Ok(())
+        },
+    )?;
+    Ok(FfiEquityResult::new(result, start.elapsed()))
+}
+
+fn unknown(id: u64) -> SnapError {
+    SnapError::InvalidArgument {
+        message: format!("no running job with id {id}"),
+    }
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> std::sync::MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(|e| e.into_inner())
+}
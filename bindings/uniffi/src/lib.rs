@@ -1 +1,66 @@
+//! Swift/Kotlin bindings. Arguments and results are strings and plain
+//! records; every export returns [`SnapError`] instead of panicking.
+
 uniffi::setup_scaffolding!();
+
+mod background;
+mod batch;
+mod board;
+mod breakdown;
+mod calculator;
+mod config;
+mod dealer;
+mod equity;
+mod error;
+mod format;
+mod hand;
+mod handle;
+mod icm;
+mod job;
+mod odds;
+mod outs;
+mod range;
+
+use snapcall_core::parse_cards;
+
+pub use batch::{ffi_calculate_equity_batch, FfiEquityRequest};
+pub use board::{ffi_analyze_board, FfiBoardTexture, FfiHighCard, FfiSuitPattern};
+pub use breakdown::{ffi_combo_breakdown, ffi_equity_when_called, FfiCalledEquity, FfiComboEquity};
+pub use calculator::EquityCalculator;
+pub use config::{FfiEngineConfig, FfiSolverPreference};
+pub use dealer::{FfiDealer, FfiTable};
+pub use equity::{
+    ffi_calculate_equity, ffi_calculate_equity_with_progress, ffi_estimate_equity,
+    EquityProgressListener, FfiEquityMode, FfiEquityResult,
+};
+pub use error::SnapError;
+pub use format::{ffi_format_card, ffi_format_cards, ffi_parse_styled_cards, FfiSuitStyle};
+pub use hand::{ffi_evaluate_hand, ffi_hand_type_name, FfiHandCategory, FfiHandRank};
+pub use handle::CalculationHandle;
+pub use icm::{ffi_calculate_icm, ffi_chop_deal, FfiChopMethod};
+pub use job::{ffi_cancel_job, ffi_poll_job, ffi_start_equity_job, FfiJobStatus};
+pub use odds::{
+    ffi_call_ev, ffi_decision_ev, ffi_pot_odds, FfiDecision, FfiDecisionEv, FfiRakeModel,
+};
+pub use outs::{ffi_calculate_outs, ffi_grade_outs, FfiOutQuality, FfiOuts, FfiStraightDraw};
+pub use range::{ffi_expand_range, FfiCombo};
+
+/// Parses one card (e.g. `"ah"`) into its canonical form, `"Ah"`.
+#[uniffi::export]
+pub fn ffi_parse_card(card: String) -> Result<String, SnapError> {
+    match parse_cards(&card)?.as_slice() {
+        [card] => Ok(card.to_string()),
+        cards => Err(SnapError::InvalidCard {
+            message: format!("expected one card, got {}", cards.len()),
+        }),
+    }
+}
+
+/// Parses a run of cards (e.g. `"AhKd 2c"`) into canonical card strings.
+#[uniffi::export]
+pub fn ffi_parse_cards(cards: String) -> Result<Vec<String>, SnapError> {
+    Ok(parse_cards(&cards)?
+        .iter()
+        .map(ToString::to_string)
+        .collect())
+}
@@ -0,0 +1,129 @@
+use snapcall_core::{call_ev, decision_ev_with_rake, raked_pot_odds_percent, Decision, RakeModel};
+
+use crate::SnapError;
+
+/// Equity (in percent) needed to break even on calling `call_amount` into
+/// `pot` (the pot before the call, including the bet being faced), after an
+/// optional `rake_percent` of the final pot capped at `rake_cap`.
+#[uniffi::export(default(rake_percent = 0.0, rake_cap = None))]
+pub fn ffi_pot_odds(
+    pot: f64,
+    call_amount: f64,
+    rake_percent: f64,
+    rake_cap: Option<f64>,
+) -> Result<f64, SnapError> {
+    check_chips(&[pot, call_amount, rake_percent])?;
+    if call_amount == 0.0 {
+        return Err(SnapError::InvalidArgument {
+            message: "call amount must be positive".to_string(),
+        });
+    }
+    Ok(raked_pot_odds_percent(
+        pot,
+        call_amount,
+        rake_percent,
+        rake_cap,
+    ))
+}
+
+/// Chips won or lost on average by calling `call_amount` into `pot` (as for
+/// [`ffi_pot_odds`]) with `equity_percent`, relative to folding.
+#[uniffi::export]
+pub fn ffi_call_ev(pot: f64, call_amount: f64, equity_percent: f64) -> Result<f64, SnapError> {
+    check_chips(&[pot, call_amount])?;
+    if !(0.0..=100.0).contains(&equity_percent) {
+        return Err(SnapError::InvalidArgument {
+            message: format!("equity must be between 0 and 100, got {equity_percent}"),
+        });
+    }
+    Ok(call_ev(pot, call_amount, equity_percent))
+}
+
+/// One of hero's options in [`FfiDecisionEv`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, uniffi::Enum)]
+pub enum FfiDecision {
+    Fold,
+    Call,
+    Shove,
+}
+
+impl From<Decision> for FfiDecision {
+    fn from(decision: Decision) -> Self {
+        match decision {
+            Decision::Fold => Self::Fold,
+            Decision::Call => Self::Call,
+            Decision::Shove => Self::Shove,
+        }
+    }
+}
+
+/// EVs of hero's options in chips relative to folding.
+#[derive(Debug, Clone, uniffi::Record)]
+pub struct FfiDecisionEv {
+    /// `None` when there is no bet to call.
+    pub call: Option<f64>,
+    pub shove: f64,
+    /// The shove's EV when villain calls.
+    pub shove_called: f64,
+    /// Fold equity (percent) the shove needs to break even; `None` when it
+    /// profits even when called.
+    pub break_even_fold_percent: Option<f64>,
+    pub best: FfiDecision,
+}
+
+/// How the house takes its cut of a pot.
+#[derive(Debug, Clone, Copy, PartialEq, uniffi::Enum)]
+pub enum FfiRakeModel {
+    /// `percent` of every pot, limited to `cap` when one applies.
+    Percent { percent: f64, cap: Option<f64> },
+    /// A fixed `amount` from every pot.
+    Drop { amount: f64 },
+    /// Time-charged games: nothing comes out of the pot.
+    TimeCharge,
+}
+
+impl From<FfiRakeModel> for RakeModel {
+    fn from(model: FfiRakeModel) -> Self {
+        match model {
+            FfiRakeModel::Percent { percent, cap } => Self::Percent { percent, cap },
+            FfiRakeModel::Drop { amount } => Self::Drop { amount },
+            FfiRakeModel::TimeCharge => Self::TimeCharge,
+        }
+    }
+}
+
+/// EVs of folding, calling, and shoving facing `bet` into `pot` (the pot
+/// before the bet) with `stack` behind, given hero's equity against the
+/// continuing range and villain's fold percentage to a shove. An optional
+/// `rake` comes out of every pot hero wins.
+///
+/// Fails with `InvalidArgument` for a non-positive pot or stack, a negative
+/// bet, a percentage outside 0 to 100, or bad rake parameters.
+#[uniffi::export(default(rake = None))]
+pub fn ffi_decision_ev(
+    pot: f64,
+    bet: f64,
+    stack: f64,
+    equity_percent: f64,
+    fold_percent: f64,
+    rake: Option<FfiRakeModel>,
+) -> Result<FfiDecisionEv, SnapError> {
+    let rake = rake.map_or(RakeModel::TimeCharge, Into::into);
+    let ev = decision_ev_with_rake(pot, bet, stack, equity_percent, fold_percent, &rake)?;
+    Ok(FfiDecisionEv {
+        call: ev.call,
+        shove: ev.shove,
+        shove_called: ev.shove_called,
+        break_even_fold_percent: ev.break_even_fold_percent,
+        best: ev.best().into(),
+    })
+}
+
+fn check_chips(amounts: &[f64]) -> Result<(), SnapError> {
+    match amounts.iter().find(|a| !a.is_finite() || **a < 0.0) {
+        Some(bad) => Err(SnapError::InvalidArgument {
+            message: format!("amounts must be finite and not negative, got {bad}"),
+        }),
+        None => Ok(()),
+    }
+}
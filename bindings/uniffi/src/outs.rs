@@ -0,0 +1,88 @@
+use snapcall_core::{find_draws, grade_outs, parse_cards, StraightDraw};
+
+use crate::hand::card_names;
+use crate::SnapError;
+
+/// Kind of straight draw, by the number of ranks that complete it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, uniffi::Enum)]
+pub enum FfiStraightDraw {
+    /// One rank completes it (usually 4 outs).
+    Gutshot,
+    /// Two ranks complete it without four connected cards.
+    DoubleGutshot,
+    /// Four connected ranks with both ends open (usually 8 outs).
+    OpenEnded,
+}
+
+impl From<StraightDraw> for FfiStraightDraw {
+    fn from(draw: StraightDraw) -> Self {
+        match draw {
+            StraightDraw::Gutshot => Self::Gutshot,
+            StraightDraw::DoubleGutshot => Self::DoubleGutshot,
+            StraightDraw::OpenEnded => Self::OpenEnded,
+        }
+    }
+}
+
+/// Hero's draws on a flop or turn and the cards that complete them.
+#[derive(Debug, Clone, uniffi::Record)]
+pub struct FfiOuts {
+    pub flush_draw: bool,
+    /// `None` when there is no straight draw.
+    pub straight_draw: Option<FfiStraightDraw>,
+    /// A flush draw plus any straight draw.
+    pub combo_draw: bool,
+    /// Three to a flush on the flop, with a hole card.
+    pub backdoor_flush: bool,
+    /// A straight two more ranks complete on the flop, with no straight
+    /// draw already.
+    pub backdoor_straight: bool,
+    /// Unseen cards that give hero a straight or flush, e.g. `["5c", "Th"]`.
+    pub outs: Vec<String>,
+}
+
+/// Hero's flush and straight draws and their outs on a flop or turn (e.g.
+/// `hero = "AhKh"`, `board = "Qh7h2c"`). Draws the board makes on its own
+/// are not counted.
+#[uniffi::export]
+pub fn ffi_calculate_outs(hero: String, board: String) -> Result<FfiOuts, SnapError> {
+    let draws = find_draws(&parse_cards(&hero)?, &parse_cards(&board)?)?;
+    Ok(FfiOuts {
+        flush_draw: draws.flush_draw,
+        straight_draw: draws.straight_draw.map(Into::into),
+        combo_draw: draws.is_combo_draw(),
+        backdoor_flush: draws.backdoor_flush,
+        backdoor_straight: draws.backdoor_straight,
+        outs: card_names(&draws.outs),
+    })
+}
+
+/// One of hero's outs graded against a villain range.
+#[derive(Debug, Clone, uniffi::Record)]
+pub struct FfiOutQuality {
+    /// The out, e.g. `"Th"`.
+    pub card: String,
+    /// Share of the villain range still ahead of hero once it falls.
+    pub beaten_by: f64,
+    /// No villain combo is ahead after it.
+    pub clean: bool,
+}
+
+/// Hero's outs (as [`ffi_calculate_outs`]), each marked clean or not
+/// against `villain_range`, e.g. `"QQ, 77, AK"`.
+#[uniffi::export]
+pub fn ffi_grade_outs(
+    hero: String,
+    board: String,
+    villain_range: String,
+) -> Result<Vec<FfiOutQuality>, SnapError> {
+    let graded = grade_outs(&parse_cards(&hero)?, &parse_cards(&board)?, &villain_range)?;
+    Ok(graded
+        .into_iter()
+        .map(|out| FfiOutQuality {
+            card: out.card.to_string(),
+            beaten_by: out.beaten_by,
+            clean: out.is_clean(),
+        })
+        .collect())
+}
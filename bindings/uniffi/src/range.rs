@@ -0,0 +1,33 @@
+use snapcall_core::{expand_weighted_range, Card, HandClass};
+
+use crate::SnapError;
+
+/// One concrete two-card combo of a range.
+#[derive(Debug, Clone, uniffi::Record)]
+pub struct FfiCombo {
+    /// The two cards, higher first, e.g. `"AhKh"`.
+    pub combo: String,
+    /// Its starting-hand class, e.g. `"AKs"`, for grouping in a grid.
+    pub class: String,
+    /// The `:weight` the range gives the combo (e.g. `"AKs:0.5"`), or 1.
+    pub weight: f64,
+}
+
+/// Expands a range (e.g. `"TT+, AKs:0.5, !AhKh"`) into its concrete combos,
+/// grouped by class in grid order, using the same parser as the engine.
+#[uniffi::export]
+pub fn ffi_expand_range(range: String) -> Result<Vec<FfiCombo>, SnapError> {
+    Ok(expand_weighted_range(&range)?
+        .into_iter()
+        .map(|(combo, weight)| FfiCombo {
+            combo: combo_name(&combo),
+            class: HandClass::from_cards(combo[0], combo[1]).to_string(),
+            weight,
+        })
+        .collect())
+}
+
+/// Both cards of a combo as one string, e.g. `"AhKd"`.
+pub(crate) fn combo_name(combo: &[Card; 2]) -> String {
+    format!("{}{}", combo[0], combo[1])
+}
@@ -0,0 +1,108 @@
+use js_sys::{Array, Object};
+use snapcall_core::{board_texture, find_draws as core_find_draws, parse_cards};
+use wasm_bindgen::prelude::*;
+
+use crate::error;
+use crate::js::set;
+
+#[wasm_bindgen(typescript_custom_section)]
+const DRAW_INFO: &'static str = r#"
+/** Hero's draws on a flop or turn, from `find_draws`. */
+export interface DrawInfo {
+  flushDraw: boolean;
+  /** `open_ended` is an OESD; `null` when there is no straight draw. */
+  straightDraw: "open_ended" | "double_gutshot" | "gutshot" | null;
+  /** A flush draw plus any straight draw. */
+  comboDraw: boolean;
+  /** Three to a flush on the flop, with a hole card. */
+  backdoorFlush: boolean;
+  /** A straight two more ranks complete on the flop, with no straight draw already. */
+  backdoorStraight: boolean;
+  /** Unseen cards that give hero a straight or flush, e.g. `["5c", "Th"]`. */
+  outs: string[];
+}
+
+/** Board texture from `analyze_board`. */
+export interface BoardTexture {
+  /** Two or more cards share a rank. */
+  paired: boolean;
+  /** `monotone` means three or more of one suit: a flush is possible. */
+  suits: "rainbow" | "two_tone" | "monotone";
+  /** Most board ranks in one five-rank straight window (1-5). */
+  connectedness: number;
+  /** Some two hole cards make a straight. */
+  straightPossible: boolean;
+  /** Distinct straights (by top card) some two hole cards make. */
+  straights: number;
+  /** Suits with three or more board cards. */
+  flushes: number;
+  /** Suits with exactly two board cards before the river. */
+  flushDraws: number;
+  /** Top card ten or higher, seven to nine, or six and below. */
+  highCard: "high" | "medium" | "low";
+  /** Rank of the top card, e.g. `"A"`. */
+  topRank: string;
+  /** 0 for a static board (e.g. `"Kc7d2s"`) up to 8 for a dynamic one (e.g. `"9s8s7s"`). */
+  wetness: number;
+}
+"#;
+
+/// Hero's flush and straight draws and the cards that complete them, for a
+/// flop or turn. Draws the board makes on its own are not counted.
+///
+/// Throws a `SnapError` (see `estimate_equity`) unless hero is two cards and
+/// the board three or four.
+#[wasm_bindgen(unchecked_return_type = "DrawInfo")]
+pub fn find_draws(hero: &str, board: &str) -> Result<Object, JsValue> {
+    let hero = parse_cards(hero).map_err(error::to_js)?;
+    let board = parse_cards(board).map_err(error::to_js)?;
+    let draws = core_find_draws(&hero, &board).map_err(error::to_js)?;
+
+    let outs: Array = draws
+        .outs
+        .iter()
+        .map(|c| JsValue::from(c.to_string()))
+        .collect();
+    let straight = draws
+        .straight_draw
+        .map_or(JsValue::NULL, |kind| kind.to_string().into());
+    let object = Object::new();
+    set(&object, "flushDraw", &draws.flush_draw.into());
+    set(&object, "straightDraw", &straight);
+    set(&object, "comboDraw", &draws.is_combo_draw().into());
+    set(&object, "backdoorFlush", &draws.backdoor_flush.into());
+    set(&object, "backdoorStraight", &draws.backdoor_straight.into());
+    set(&object, "outs", &outs);
+    Ok(object)
+}
+
+/// Classifies a flop, turn, or river, e.g. `analyze_board("Ah7h2c")`.
+///
+/// Throws a `SnapError` (see `estimate_equity`) unless the board is three to
+/// five distinct cards.
+#[wasm_bindgen(unchecked_return_type = "BoardTexture")]
+pub fn analyze_board(board: &str) -> Result<Object, JsValue> {
+    let board = parse_cards(board).map_err(error::to_js)?;
+    let texture = board_texture(&board).map_err(error::to_js)?;
+
+    let object = Object::new();
+    set(&object, "paired", &texture.paired.into());
+    set(&object, "suits", &texture.suits.to_string().into());
+    set(&object, "connectedness", &texture.connectedness.into());
+    set(
+        &object,
+        "straightPossible",
+        &texture.straight_possible().into(),
+    );
+    set(&object, "straights", &texture.straights.into());
+    set(&object, "flushes", &texture.flushes.into());
+    set(&object, "flushDraws", &texture.flush_draws.into());
+    set(&object, "highCard", &texture.high_card.to_string().into());
+    set(
+        &object,
+        "topRank",
+        &texture.top_rank.to_char().to_string().into(),
+    );
+    set(&object, "wetness", &texture.wetness().into());
+    Ok(object)
+}
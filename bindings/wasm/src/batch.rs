@@ -0,0 +1,107 @@
+use serde::{Deserialize, Serialize};
+use snapcall_core::{estimate_equity_with_options, parse_cards, EquityOptions, SnapError};
+use wasm_bindgen::prelude::*;
+
+use crate::error;
+
+#[wasm_bindgen(typescript_custom_section)]
+const BATCH_TYPES: &'static str = r#"
+/** One entry of the JSON array passed to `estimate_equity_batch`. */
+export interface BatchScenario {
+  board?: string;
+  hero: string;
+  villains: string[];
+  /** Defaults to 100 000. */
+  iterations?: number;
+  seed?: number;
+  /** Dead cards, e.g. `"2c7d"`. */
+  dead?: string;
+}
+
+/** Result for the scenario at the same index; failures do not stop the batch. */
+export type BatchResult =
+  | { equities: number[]; wins: number[]; ties: number[]; mode: "exact" | "monte_carlo"; samples: number }
+  | { error: { code: SnapErrorCode; message: string } };
+"#;
+
+/// Sample budget for scenarios that do not set `iterations`.
+const DEFAULT_ITERATIONS: usize = 100_000;
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct BatchScenario {
+    #[serde(default)]
+    board: String,
+    hero: String,
+    villains: Vec<String>,
+    iterations: Option<usize>,
+    seed: Option<u64>,
+    dead: Option<String>,
+}
+
+#[derive(Serialize)]
+#[serde(untagged)]
+enum BatchResult {
+    Equity {
+        equities: Vec<f64>,
+        wins: Vec<f64>,
+        ties: Vec<f64>,
+        mode: String,
+        samples: usize,
+    },
+    Failed {
+        error: BatchError,
+    },
+}
+
+#[derive(Serialize)]
+struct BatchError {
+    code: &'static str,
+    message: String,
+}
+
+/// Runs many equity scenarios in one call: `scenarios` is a JSON array of
+/// `BatchScenario` and the return value a JSON array of `BatchResult`, one
+/// per scenario in order.
+///
+/// A bad scenario yields an `error` entry instead of failing the batch; only
+/// JSON that is not an array of scenarios throws a `SnapError` with code
+/// `invalid_argument`.
+#[wasm_bindgen]
+pub fn estimate_equity_batch(scenarios: &str) -> Result<String, JsValue> {
+    let scenarios: Vec<BatchScenario> = serde_json::from_str(scenarios).map_err(|e| {
+        error::to_js(SnapError::InvalidArgument(format!(
+            "Batch must be a JSON array of scenarios: {e}"
+        )))
+    })?;
+    let results: Vec<BatchResult> = scenarios
+        .iter()
+        .map(|scenario| {
+            run(scenario).unwrap_or_else(|err| BatchResult::Failed {
+                error: BatchError {
+                    code: err.code(),
+                    message: err.to_string(),
+                },
+            })
+        })
+        .collect();
+    serde_json::to_string(&results).map_err(|e| JsValue::from_str(&e.to_string()))
+}
+
+fn run(scenario: &BatchScenario) -> Result<BatchResult, SnapError> {
+    let options = EquityOptions {
+        seed: scenario.seed,
+        dead_cards: parse_cards(scenario.dead.as_deref().unwrap_or_default())?,
+        ..EquityOptions::new(scenario.iterations.unwrap_or(DEFAULT_ITERATIONS))
+    };
+    let villains: Vec<&str> = scenario.villains.iter().map(|s| s.as_str()).collect();
+    let result =
+        estimate_equity_with_options(&scenario.board, &scenario.hero, &villains, &options)?;
+    Ok(BatchResult::Equity {
+        equities: result.equities,
+        wins: result.wins,
+        ties: result.ties,
+        mode: result.mode.to_string(),
+        samples: result.samples,
+    })
+}
@@ -0,0 +1,47 @@
+use js_sys::{Array, Object};
+use snapcall_core::combo_breakdown as core_combo_breakdown;
+use wasm_bindgen::prelude::*;
+
+use crate::js::set;
+use crate::{equity_options, parse};
+
+#[wasm_bindgen(typescript_custom_section)]
+const COMBO_EQUITY: &'static str = r#"
+/** Hero's equity against one villain combo, from `combo_breakdown`. */
+export interface ComboEquity {
+  /** Villain's cards, higher first, e.g. `"QhQd"`. */
+  combo: string;
+  /** Hero's equity against this combo in percent; below 50 means it beats hero. */
+  equity: number;
+  /** Share of the live villain range, from 0 to 1; all weights sum to 1. */
+  weight: number;
+}
+"#;
+
+/// Hero's equity against each combo of a villain range, for "what beats
+/// me" tables. Each combo is solved with `iterations` (and `seed`) as in
+/// `estimate_equity`, so wide preflop ranges take proportionally longer.
+///
+/// Throws a `SnapError` (see `estimate_equity`) for invalid input, with an
+/// `issue` when an argument does not parse.
+#[wasm_bindgen(unchecked_return_type = "ComboEquity[]")]
+pub fn combo_breakdown(
+    board: &str,
+    hero: &str,
+    villain: &str,
+    iterations: u32,
+    seed: Option<u32>,
+) -> Result<Array, JsValue> {
+    let combos = core_combo_breakdown(board, hero, villain, &equity_options(iterations, seed))
+        .map_err(|e| parse::equity_error(e, board, hero, &[villain]))?;
+    let rows = Array::new();
+    for entry in combos {
+        let object = Object::new();
+        let combo = format!("{}{}", entry.combo[0], entry.combo[1]);
+        set(&object, "combo", &combo.into());
+        set(&object, "equity", &entry.equity.into());
+        set(&object, "weight", &entry.weight.into());
+        rows.push(&object);
+    }
+    Ok(rows)
+}
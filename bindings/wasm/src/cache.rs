@@ -0,0 +1,154 @@
+use std::cell::RefCell;
+
+use js_sys::{Array, Function, Object, Promise};
+use snapcall_core::{EquityEstimateMode, EquityMath, EquityOptions, EquityResult};
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+use wasm_bindgen_futures::{spawn_local, JsFuture};
+
+use crate::js::{field, set};
+
+#[wasm_bindgen(typescript_custom_section)]
+const EQUITY_CACHE: &'static str = r#"
+/** A result as handed to `cachePut`; structured-cloneable for IndexedDB. */
+export interface CachedEquity {
+  equities: number[];
+  wins: number[];
+  ties: number[];
+  mode: "exact" | "monte_carlo";
+  samples: number;
+}
+
+/** Looks up a result stored under `key`; `undefined` (or any error) is a miss. */
+export type EquityCacheGet = (key: string) => CachedEquity | undefined | Promise<CachedEquity | undefined>;
+
+/** Stores a freshly computed result; errors are ignored. */
+export type EquityCachePut = (key: string, value: CachedEquity) => void | Promise<void>;
+"#;
+
+thread_local! {
+    static HOOKS: RefCell<Hooks> = const {
+        RefCell::new(Hooks {
+            get: None,
+            put: None,
+        })
+    };
+}
+
+struct Hooks {
+    get: Option<Function>,
+    put: Option<Function>,
+}
+
+/// Installs host callbacks that let chunked calculations
+/// (`calculate_equity_async`, `stream_equity`, the equity worker) reuse
+/// results, e.g. from IndexedDB across sessions. Call with no arguments to
+/// remove them.
+///
+/// Keys identify the spot, iteration budget, seed, and engine version, so an
+/// upgrade never serves stale results. Unseeded Monte Carlo results are
+/// cached too: a hit replays the stored estimate instead of sampling again.
+/// Each JS realm has its own hooks, so a worker must install them itself.
+#[wasm_bindgen(js_name = setEquityCache)]
+pub fn set_equity_cache(
+    #[wasm_bindgen(unchecked_param_type = "EquityCacheGet | null | undefined")] cache_get: Option<
+        Function,
+    >,
+    #[wasm_bindgen(unchecked_param_type = "EquityCachePut | null | undefined")] cache_put: Option<
+        Function,
+    >,
+) {
+    HOOKS.with_borrow_mut(|hooks| {
+        hooks.get = cache_get;
+        hooks.put = cache_put;
+    });
+}
+
+/// Cache key for one calculation, e.g.
+/// `snapcall/0.1.0/equity?board=AhKd2c&hero=AsQs&villains=JJ;TT+&iterations=100000&seed=-`.
+///
+/// The chunk size is not part of the key: core's `EquityChunks` draws every
+/// chunk from one seeded generator, so a seeded `stream_equity` run and a
+/// seeded `calculate_equity_async` run sample the same runouts.
+pub(crate) fn key(board: &str, hero: &str, villains: &[&str], options: &EquityOptions) -> String {
+    let villains: Vec<&str> = villains.iter().map(|v| v.trim()).collect();
+    let seed = options.seed.map_or("-".to_string(), |s| s.to_string());
+    format!(
+        "snapcall/{}/equity?board={}&hero={}&villains={}&iterations={}&seed={}",
+        env!("CARGO_PKG_VERSION"),
+        board.trim(),
+        hero.trim(),
+        villains.join(";"),
+        options.iterations,
+        seed
+    )
+}
+
+/// The result stored under `key` for `players` seats, if the host has one.
+/// `math` is the current plan's, as it is not stored.
+pub(crate) async fn get(key: &str, players: usize, math: EquityMath) -> Option<EquityResult> {
+    let get = HOOKS.with_borrow(|hooks| hooks.get.clone())?;
+    let value = get.call1(&JsValue::NULL, &key.into()).ok()?;
+    let value = match value.dyn_into::<Promise>() {
+        Ok(promise) => JsFuture::from(promise).await.ok()?,
+        Err(value) => value,
+    };
+    from_js(&value, players, math)
+}
+
+/// Hands a fresh result to the host's `cachePut`, if any.
+pub(crate) fn put(key: &str, result: &EquityResult) {
+    let Some(put) = HOOKS.with_borrow(|hooks| hooks.put.clone()) else {
+        return;
+    };
+    let Ok(returned) = put.call2(&JsValue::NULL, &key.into(), &to_js(result)) else {
+        return;
+    };
+    // Swallow async failures instead of leaving an unhandled rejection
+    if let Ok(promise) = returned.dyn_into::<Promise>() {
+        spawn_local(async move {
+            let _ = JsFuture::from(promise).await;
+        });
+    }
+}
+
+fn to_js(result: &EquityResult) -> Object {
+    let numbers = |values: &[f64]| -> Array { values.iter().map(|&v| JsValue::from(v)).collect() };
+    let object = Object::new();
+    set(&object, "equities", &numbers(&result.equities));
+    set(&object, "wins", &numbers(&result.wins));
+    set(&object, "ties", &numbers(&result.ties));
+    set(&object, "mode", &result.mode.to_string().into());
+    set(&object, "samples", &(result.samples as f64).into());
+    object
+}
+
+/// Reads a `CachedEquity`, rejecting anything malformed or sized for a
+/// different number of players.
+fn from_js(value: &JsValue, players: usize, math: EquityMath) -> Option<EquityResult> {
+    let numbers = |key: &str| -> Option<Vec<f64>> {
+        let values: Vec<f64> = field(value, key)
+            .dyn_into::<Array>()
+            .ok()?
+            .iter()
+            .map(|v| v.as_f64())
+            .collect::<Option<_>>()?;
+        (values.len() == players).then_some(values)
+    };
+    let mode = match field(value, "mode").as_string()?.as_str() {
+        "exact" => EquityEstimateMode::ExactEnumeration,
+        "monte_carlo" => EquityEstimateMode::MonteCarlo,
+        _ => return None,
+    };
+    let samples = field(value, "samples")
+        .as_f64()
+        .filter(|n| n.fract() == 0.0 && *n >= 0.0)?;
+    Some(EquityResult {
+        equities: numbers("equities")?,
+        wins: numbers("wins")?,
+        ties: numbers("ties")?,
+        mode,
+        samples: samples as usize,
+        math,
+    })
+}
@@ -0,0 +1,53 @@
+use snapcall_core::{format_card, parse_cards, Card, SnapError, SuitStyle};
+use wasm_bindgen::prelude::*;
+
+use crate::error;
+
+/// A single playing card.
+#[wasm_bindgen]
+pub struct JsCard {
+    card: Card,
+}
+
+#[wasm_bindgen]
+impl JsCard {
+    /// Parses one card such as `"Ah"` or `"td"` (case-insensitive).
+    ///
+    /// Throws a `SnapError` with code `invalid_card` for anything else,
+    /// including more than one card.
+    pub fn parse(text: &str) -> Result<JsCard, JsValue> {
+        let cards = parse_cards(text).map_err(error::to_js)?;
+        match cards.as_slice() {
+            [card] => Ok(JsCard { card: *card }),
+            _ => Err(error::to_js(SnapError::InvalidCard(format!(
+                "Expected exactly one card, got {} in '{}'",
+                cards.len(),
+                text
+            )))),
+        }
+    }
+
+    /// Rank character: `"A"`, `"K"`, …, `"T"`, `"9"`, …, `"2"`.
+    #[wasm_bindgen(getter)]
+    pub fn rank(&self) -> String {
+        self.card.value.to_char().to_string()
+    }
+
+    /// Suit letter: `"s"`, `"h"`, `"d"`, or `"c"`.
+    #[wasm_bindgen(getter)]
+    pub fn suit(&self) -> String {
+        self.card.suit.to_char().to_string()
+    }
+
+    /// Rank plus suit symbol, e.g. `"A♥"`.
+    #[wasm_bindgen(js_name = toUnicode)]
+    pub fn to_unicode(&self) -> String {
+        format_card(self.card, SuitStyle::Unicode)
+    }
+
+    /// Two-character form accepted everywhere else in the API, e.g. `"Ah"`.
+    #[wasm_bindgen(js_name = toCompact)]
+    pub fn to_compact(&self) -> String {
+        self.card.to_string()
+    }
+}
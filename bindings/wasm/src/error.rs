@@ -0,0 +1,50 @@
+use snapcall_core::SnapError;
+use wasm_bindgen::prelude::*;
+
+// Keep in sync with `SnapError::code` and `cancelled` below.
+#[wasm_bindgen(typescript_custom_section)]
+const ERROR_TYPES: &'static str = r#"
+/** `code` of a `SnapError`, one per core error variant. */
+export type SnapErrorCode =
+  | "invalid_card"
+  | "invalid_hand"
+  | "invalid_range"
+  | "invalid_argument"
+  | "resource_limit";
+
+/**
+ * What exports throw (or reject with) for bad input. Equity exports set
+ * `issue` when an argument does not parse.
+ */
+export interface SnapError extends Error {
+  name: "SnapError";
+  code: SnapErrorCode;
+  issue?: InputIssue;
+}
+
+/** What async exports reject with when their `AbortSignal` fires. */
+export interface AbortError extends Error {
+  name: "AbortError";
+  code: "cancelled";
+}
+"#;
+
+/// Converts a core error into a JS `Error` named `SnapError` whose `code`
+/// property is [`SnapError::code`], so callers can branch without parsing the
+/// message.
+pub(crate) fn to_js(err: SnapError) -> JsValue {
+    let js = js_sys::Error::new(&err.to_string());
+    js.set_name("SnapError");
+    // Setting a property on a fresh Error object cannot fail
+    let _ = js_sys::Reflect::set(&js, &"code".into(), &err.code().into());
+    js.into()
+}
+
+/// The `Error` a cancelled calculation rejects with: named `AbortError`
+/// (like an aborted `fetch`) with `code` `"cancelled"`.
+pub(crate) fn cancelled() -> JsValue {
+    let js = js_sys::Error::new("Calculation cancelled");
+    js.set_name("AbortError");
+    let _ = js_sys::Reflect::set(&js, &"code".into(), &"cancelled".into());
+    js.into()
+}
@@ -0,0 +1,138 @@
+use js_sys::{Array, Object};
+use snapcall_core::{
+    best_hand, deal_random_hand, parse_cards, BestHand, Card, HandCategory, Street,
+};
+use wasm_bindgen::prelude::*;
+
+use crate::error;
+use crate::js::set;
+
+#[wasm_bindgen(typescript_custom_section)]
+const HAND_RANK: &'static str = r#"
+/** The best five-card hand out of 5-7 cards, from `evaluate_hand`. */
+export interface HandRank {
+  /** Stable id to localize or style by. */
+  category:
+    | "high_card"
+    | "one_pair"
+    | "two_pair"
+    | "three_of_a_kind"
+    | "straight"
+    | "flush"
+    | "full_house"
+    | "four_of_a_kind"
+    | "straight_flush";
+  /** 0 (high card) to 8 (straight flush); a higher index always wins. */
+  categoryIndex: number;
+  /** English category name, e.g. `"Full House"`. */
+  name: string;
+  /** English description, e.g. `"Aces full of Kings"`. */
+  description: string;
+  /** The five cards, those making the hand first, e.g. `["Ah", "Ad", "Ks", "Kc", "9c"]`. */
+  cards: string[];
+  /** Leading entries of `cards` that form the category itself. */
+  made: string[];
+  /** Ranks of the remaining cards, which only break ties, e.g. `["Q", "9"]`. */
+  kickers: string[];
+}
+
+/** A random hand from `deal`. */
+export interface DealtHand {
+  /** Each seat's two hole cards, e.g. `[["Ah", "Kd"], ["7c", "7s"]]`. */
+  holeCards: string[][];
+  /** Board cards showing on the requested street (none preflop). */
+  board: string[];
+  /** Each seat's best hand with the board; all `null` preflop. */
+  hands: (HandRank | null)[];
+  /** Seats holding the best hand, more than one on a tie (preflop: hole cards only). */
+  winners: number[];
+}
+"#;
+
+/// Evaluates the best five-card hand among 5–7 cards, e.g.
+/// `evaluate_hand("AhAdKcKs2h9c")`.
+///
+/// Throws a `SnapError` (see `estimate_equity`) unless the input is five to
+/// seven distinct cards.
+#[wasm_bindgen(unchecked_return_type = "HandRank")]
+pub fn evaluate_hand(cards: &str) -> Result<Object, JsValue> {
+    let cards = parse_cards(cards).map_err(error::to_js)?;
+    let best = best_hand(&cards).map_err(error::to_js)?;
+    Ok(hand_rank(&best))
+}
+
+/// Deals `num_players` random hands and the board up to `street`, with each
+/// seat's made hand and the winners, for demo tables and tutorials. The same
+/// `seed` always deals the same hand.
+///
+/// Throws a `SnapError` (see `estimate_equity`) for an unknown street or a
+/// player count outside 2–22.
+#[wasm_bindgen(unchecked_return_type = "DealtHand")]
+pub fn deal(
+    num_players: u32,
+    #[wasm_bindgen(unchecked_param_type = "\"preflop\" | \"flop\" | \"turn\" | \"river\"")]
+    street: &str,
+    seed: Option<u32>,
+) -> Result<Object, JsValue> {
+    let street: Street = street.parse().map_err(error::to_js)?;
+    let dealt = deal_random_hand(num_players as usize, street, seed.map(u64::from))
+        .map_err(error::to_js)?;
+
+    let holes = Array::new();
+    let hands = Array::new();
+    for hole in &dealt.hole_cards {
+        holes.push(&card_names(hole));
+        let mut cards = hole.to_vec();
+        cards.extend_from_slice(&dealt.board);
+        // Preflop there are only two cards, too few for a five-card hand
+        let hand = best_hand(&cards).map_or(JsValue::NULL, |best| hand_rank(&best).into());
+        hands.push(&hand);
+    }
+    let winners: Array = dealt
+        .winners
+        .iter()
+        .map(|&seat| JsValue::from(seat as u32))
+        .collect();
+    let object = Object::new();
+    set(&object, "holeCards", &holes);
+    set(&object, "board", &card_names(&dealt.board));
+    set(&object, "hands", &hands);
+    set(&object, "winners", &winners);
+    Ok(object)
+}
+
+/// A `HandRank` object for `best`.
+fn hand_rank(best: &BestHand) -> Object {
+    let kickers: Array = best
+        .kickers()
+        .iter()
+        .map(|c| JsValue::from(c.value.to_char().to_string()))
+        .collect();
+    let object = Object::new();
+    set(&object, "category", &category_id(best.category).into());
+    set(&object, "categoryIndex", &(best.category as u8).into());
+    set(&object, "name", &best.category.name().into());
+    set(&object, "description", &best.describe().into());
+    set(&object, "cards", &card_names(&best.cards));
+    set(&object, "made", &card_names(best.made()));
+    set(&object, "kickers", &kickers);
+    object
+}
+
+fn category_id(category: HandCategory) -> &'static str {
+    match category {
+        HandCategory::HighCard => "high_card",
+        HandCategory::OnePair => "one_pair",
+        HandCategory::TwoPair => "two_pair",
+        HandCategory::ThreeOfAKind => "three_of_a_kind",
+        HandCategory::Straight => "straight",
+        HandCategory::Flush => "flush",
+        HandCategory::FullHouse => "full_house",
+        HandCategory::FourOfAKind => "four_of_a_kind",
+        HandCategory::StraightFlush => "straight_flush",
+    }
+}
+
+fn card_names(cards: &[Card]) -> Array {
+    cards.iter().map(|c| JsValue::from(c.to_string())).collect()
+}
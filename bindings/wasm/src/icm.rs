@@ -0,0 +1,18 @@
+use snapcall_core::{estimate_icm_equity, DEFAULT_ICM_TRIALS};
+use wasm_bindgen::prelude::*;
+
+use crate::error;
+
+/// Prize equity of each player under the Independent Chip Model, in the
+/// units of `payouts` and in the order of `stacks`.
+///
+/// `payouts[k]` pays place `k + 1`; players with an empty stack have busted
+/// and share the bottom places. Exact for up to 20 players with chips;
+/// larger fields are estimated from sampled finishing orders.
+///
+/// Throws a `SnapError` with code `invalid_argument` for negative stacks or
+/// payouts.
+#[wasm_bindgen]
+pub fn calculate_icm(stacks: Vec<f64>, payouts: Vec<f64>) -> Result<Vec<f64>, JsValue> {
+    estimate_icm_equity(&stacks, &payouts, DEFAULT_ICM_TRIALS, None).map_err(error::to_js)
+}
@@ -0,0 +1,15 @@
+//! Helpers for reading and building plain JS objects.
+
+use js_sys::{Object, Reflect};
+use wasm_bindgen::JsValue;
+
+/// `object[key]`, or `undefined` when the lookup throws.
+#[cfg(any(feature = "worker", feature = "cache"))]
+pub(crate) fn field(object: &JsValue, key: &str) -> JsValue {
+    Reflect::get(object, &key.into()).unwrap_or(JsValue::UNDEFINED)
+}
+
+pub(crate) fn set(object: &Object, key: &str, value: &JsValue) {
+    // Defining a property on a plain object cannot fail
+    let _ = Reflect::set(object, &key.into(), value);
+}
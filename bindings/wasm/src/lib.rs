@@ -1,44 +1,274 @@
-use snapcall_core::estimate_equity as core_estimate_equity;
+use snapcall_core::{
+    equity_vs_random as core_equity_vs_random, estimate_equity_with_options, EquityChunks,
+    EquityOptions, EquityResult,
+};
 use wasm_bindgen::prelude::*;
+use wasm_bindgen_futures::JsFuture;
 
+#[cfg(feature = "analysis")]
+mod analysis;
+#[cfg(feature = "batch")]
+mod batch;
+#[cfg(feature = "breakdown")]
+mod breakdown;
+#[cfg(feature = "cache")]
+mod cache;
+mod card;
+mod error;
+#[cfg(feature = "hand")]
+mod hand;
+#[cfg(feature = "icm")]
+mod icm;
+mod js;
+mod parse;
+#[cfg(feature = "grid")]
+mod range;
+#[cfg(feature = "stream")]
+mod stream;
+#[cfg(feature = "worker")]
+mod worker;
+
+/// Equity for every player plus how it was computed.
+///
+/// All per-player arrays are in seat order (hero first) and in percent.
 #[wasm_bindgen]
 pub struct EstimateResult {
     equities: Vec<f64>,
+    wins: Vec<f64>,
+    ties: Vec<f64>,
     mode: String,
     samples: usize,
+    elapsed_ms: f64,
+}
+
+impl EstimateResult {
+    fn new(result: EquityResult, elapsed_ms: f64) -> Self {
+        Self {
+            equities: result.equities,
+            wins: result.wins,
+            ties: result.ties,
+            mode: result.mode.to_string(),
+            samples: result.samples,
+            elapsed_ms,
+        }
+    }
 }
 
 #[wasm_bindgen]
 impl EstimateResult {
+    /// Share of the pot each player can expect; sums to 100.
     #[wasm_bindgen(getter)]
     pub fn equities(&self) -> Vec<f64> {
         self.equities.clone()
     }
 
+    /// Share of samples each player wins outright.
+    #[wasm_bindgen(getter)]
+    pub fn wins(&self) -> Vec<f64> {
+        self.wins.clone()
+    }
+
+    /// Share of samples each player splits the pot.
     #[wasm_bindgen(getter)]
+    pub fn ties(&self) -> Vec<f64> {
+        self.ties.clone()
+    }
+
+    /// `"exact"` or `"monte_carlo"`.
+    #[wasm_bindgen(getter, unchecked_return_type = "\"exact\" | \"monte_carlo\"")]
     pub fn mode(&self) -> String {
         self.mode.clone()
     }
 
+    /// Whether `equities` are exact rather than a Monte Carlo estimate, for
+    /// labelling results "exact" vs "estimated".
+    #[wasm_bindgen(getter, js_name = isExact)]
+    pub fn is_exact(&self) -> bool {
+        self.mode == "exact"
+    }
+
     #[wasm_bindgen(getter)]
     pub fn samples(&self) -> usize {
         self.samples
     }
+
+    /// Wall-clock time spent in the engine, in milliseconds.
+    #[wasm_bindgen(getter, js_name = elapsedMs)]
+    pub fn elapsed_ms(&self) -> f64 {
+        self.elapsed_ms
+    }
 }
 
+/// Estimates equity for hero against one or more villains.
+///
+/// Passing a `seed` makes Monte Carlo results identical on every call and
+/// every load; without one each call samples afresh.
+///
+/// Throws an `Error` named `SnapError` with a `code` of `invalid_card`,
+/// `invalid_hand`, `invalid_range`, `invalid_argument`, or `resource_limit`.
+/// When an argument does not parse, its `issue` says which token is wrong.
 #[wasm_bindgen]
 pub fn estimate_equity(
     board: &str,
     hero: &str,
     villains: Vec<String>,
     iterations: u32,
-) -> Result<EstimateResult, JsError> {
+    seed: Option<u32>,
+) -> Result<EstimateResult, JsValue> {
+    let v_refs: Vec<&str> = villains.iter().map(|s| s.as_str()).collect();
+    let options = equity_options(iterations, seed);
+    // `std::time::Instant` is unavailable on wasm32-unknown-unknown
+    let start = js_sys::Date::now();
+    let result = estimate_equity_with_options(board, hero, &v_refs, &options)
+        .map_err(|e| parse::equity_error(e, board, hero, &v_refs))?;
+    Ok(EstimateResult::new(result, js_sys::Date::now() - start))
+}
+
+/// Hero's equity against `num_opponents` random hands, for simple "vs N
+/// players" widgets. Faster than `estimate_equity` with empty villain
+/// strings; `equities[0]` is hero and the opponents follow.
+///
+/// Throws a `SnapError` (see `estimate_equity`) for bad cards or when
+/// `num_opponents` is 0 or too many for the deck.
+#[wasm_bindgen]
+pub fn equity_vs_random(
+    hero: &str,
+    board: &str,
+    num_opponents: u32,
+    iterations: u32,
+    seed: Option<u32>,
+) -> Result<EstimateResult, JsValue> {
+    let options = equity_options(iterations, seed);
+    let start = js_sys::Date::now();
+    let result = core_equity_vs_random(board, hero, num_opponents as usize, &options)
+        .map_err(|e| parse::equity_error(e, board, hero, &[]))?;
+    Ok(EstimateResult::new(result, js_sys::Date::now() - start))
+}
+
+/// Automatic mode selection with an optional seed from JS (a `u32`, since
+/// `u64` would surface as a `BigInt`).
+pub(crate) fn equity_options(iterations: u32, seed: Option<u32>) -> EquityOptions {
+    EquityOptions {
+        seed: seed.map(u64::from),
+        ..EquityOptions::new(iterations as usize)
+    }
+}
+
+/// Monte Carlo samples run between two yields to the event loop.
+pub(crate) const ASYNC_CHUNK_SAMPLES: u32 = 10_000;
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(js_name = setTimeout)]
+    fn set_timeout(callback: &js_sys::Function, ms: i32) -> JsValue;
+
+    /// The DOM `AbortSignal` of an `AbortController`.
+    #[wasm_bindgen(typescript_type = "AbortSignal")]
+    pub type AbortSignal;
+
+    #[wasm_bindgen(method, getter)]
+    fn aborted(this: &AbortSignal) -> bool;
+}
+
+/// Fails with [`error::cancelled`] once `signal` has fired.
+fn check_aborted(signal: Option<&AbortSignal>) -> Result<(), JsValue> {
+    match signal {
+        Some(signal) if signal.aborted() => Err(error::cancelled()),
+        _ => Ok(()),
+    }
+}
+
+/// Resolves on the next macrotask so the page can render and handle input.
+async fn yield_to_event_loop() -> Result<(), JsValue> {
+    let promise = js_sys::Promise::new(&mut |resolve, _| {
+        set_timeout(&resolve, 0);
+    });
+    JsFuture::from(promise).await.map(|_| ())
+}
+
+/// Like `estimate_equity`, but samples in chunks of 10 000 and yields to the
+/// event loop between them so the page stays responsive.
+///
+/// `on_progress(done, total)` is called after every chunk with sample
+/// counts. Spots small enough for exact enumeration are solved in one step.
+///
+/// Aborting `signal` stops the run at the next chunk and rejects with an
+/// `Error` named `AbortError` whose `code` is `cancelled`. A `seed` gives
+/// reproducible results as in `estimate_equity`.
+#[wasm_bindgen]
+pub async fn calculate_equity_async(
+    board: String,
+    hero: String,
+    villains: Vec<String>,
+    iterations: u32,
+    #[wasm_bindgen(
+        unchecked_param_type = "((done: number, total: number) => void) | null | undefined"
+    )]
+    on_progress: Option<js_sys::Function>,
+    signal: Option<AbortSignal>,
+    seed: Option<u32>,
+) -> Result<EstimateResult, JsValue> {
     let v_refs: Vec<&str> = villains.iter().map(|s| s.as_str()).collect();
-    let result = core_estimate_equity(board, hero, &v_refs, iterations as usize)
-        .map_err(|e| JsError::new(&e.to_string()))?;
-    Ok(EstimateResult {
-        equities: result.equities,
-        mode: result.mode.to_string(),
-        samples: result.samples,
-    })
+    let start = js_sys::Date::now();
+    let result = equity_in_chunks(
+        &board,
+        &hero,
+        &v_refs,
+        &equity_options(iterations, seed),
+        ASYNC_CHUNK_SAMPLES,
+        signal.as_ref(),
+        |done, _| {
+            if let Some(callback) = &on_progress {
+                callback.call2(&JsValue::NULL, &done.into(), &iterations.into())?;
+            }
+            Ok(())
+        },
+    )
+    .await?;
+    Ok(EstimateResult::new(result, js_sys::Date::now() - start))
+}
+
+/// Runs a calculation in pieces of `chunk_samples` through core's
+/// [`EquityChunks`], calling `after_chunk(done, running_result)` after each
+/// and yielding in between. An error from `after_chunk` stops the run and is
+/// returned as is, as does aborting `signal`.
+///
+/// With the `cache` feature, a result from the host's `setEquityCache` hooks
+/// is returned after a single `after_chunk` call, and fresh results are
+/// handed back to them.
+pub(crate) async fn equity_in_chunks(
+    board: &str,
+    hero: &str,
+    villains: &[&str],
+    options: &EquityOptions,
+    chunk_samples: u32,
+    signal: Option<&AbortSignal>,
+    mut after_chunk: impl FnMut(u32, &EquityResult) -> Result<(), JsValue>,
+) -> Result<EquityResult, JsValue> {
+    check_aborted(signal)?;
+    let input_error = |e| parse::equity_error(e, board, hero, villains);
+    let mut chunks = EquityChunks::new(board, hero, villains, options, chunk_samples as usize)
+        .map_err(input_error)?;
+
+    #[cfg(feature = "cache")]
+    let key = cache::key(board, hero, villains, options);
+    #[cfg(feature = "cache")]
+    if let Some(hit) = cache::get(&key, 1 + villains.len(), chunks.math()).await {
+        check_aborted(signal)?;
+        let iterations = u32::try_from(options.iterations).unwrap_or(u32::MAX);
+        after_chunk(iterations, &hit)?;
+        return Ok(hit);
+    }
+
+    while let Some((done, running)) = chunks.next_chunk().map_err(input_error)? {
+        after_chunk(u32::try_from(done).unwrap_or(u32::MAX), running)?;
+        if done < options.iterations {
+            yield_to_event_loop().await?;
+            check_aborted(signal)?;
+        }
+    }
+    let result = chunks.into_result().map_err(input_error)?;
+    #[cfg(feature = "cache")]
+    cache::put(&key, &result);
+    Ok(result)
 }
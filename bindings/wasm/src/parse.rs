@@ -0,0 +1,133 @@
+use js_sys::Object;
+use snapcall_core::{
+    card_suggestions, diagnose_cards as core_diagnose_cards, diagnose_range as core_diagnose_range,
+    looks_like_cards, range_suggestions, CardIssue, SnapError,
+};
+use wasm_bindgen::prelude::*;
+
+use crate::error;
+use crate::js::set;
+
+#[wasm_bindgen(typescript_custom_section)]
+const PARSE_ISSUE: &'static str = r#"
+/** The first token of a card or range input that does not parse. */
+export interface ParseIssue {
+  /** A bad rank or suit, a rank with no suit, or a bad range token. */
+  kind: "rank" | "suit" | "missing_suit" | "range";
+  /** The token as typed, e.g. `"Kx"` or `"AKQ"`. */
+  token: string;
+  /** Where `token` starts in the input, in UTF-16 units like `indexOf`. */
+  position: number;
+  /** A drop-in replacement for `token`, or `null` when none is clear. */
+  suggestion: string | null;
+}
+
+/** A `ParseIssue` plus which argument it was found in. */
+export interface InputIssue extends ParseIssue {
+  input: "board" | "hero" | `villains[${number}]`;
+}
+"#;
+
+/// The first card in `input` that does not parse (e.g. a board or dead
+/// cards), or `undefined` when every card parses.
+#[wasm_bindgen(unchecked_return_type = "ParseIssue | undefined")]
+pub fn diagnose_cards(input: &str) -> JsValue {
+    cards_issue(input).map_or(JsValue::UNDEFINED, Into::into)
+}
+
+/// The first token of a range expression that does not parse, or
+/// `undefined` when the range is valid.
+#[wasm_bindgen(unchecked_return_type = "ParseIssue | undefined")]
+pub fn diagnose_range(input: &str) -> JsValue {
+    range_issue(input).map_or(JsValue::UNDEFINED, Into::into)
+}
+
+/// Like `diagnose_cards` for input that reads as cards (`"AhKd"`) and like
+/// `diagnose_range` otherwise, for a hero or villain field.
+#[wasm_bindgen(unchecked_return_type = "ParseIssue | undefined")]
+pub fn diagnose_hand(input: &str) -> JsValue {
+    hand_issue(input).map_or(JsValue::UNDEFINED, Into::into)
+}
+
+/// Converts `err` like [`error::to_js`], adding the first malformed token of
+/// the board, hero, or a villain as an `InputIssue` named `issue`.
+pub(crate) fn equity_error(err: SnapError, board: &str, hero: &str, villains: &[&str]) -> JsValue {
+    let js = error::to_js(err);
+    let villains = villains
+        .iter()
+        .enumerate()
+        .map(|(i, v)| (hand_issue(v), format!("villains[{i}]")));
+    let found = [
+        (cards_issue(board), "board".to_string()),
+        (hand_issue(hero), "hero".to_string()),
+    ]
+    .into_iter()
+    .chain(villains)
+    .find_map(|(issue, input)| Some((issue?, input)));
+    if let Some((issue, input)) = found {
+        set(&issue, "input", &input.into());
+        // Setting a property on a fresh Error object cannot fail
+        let _ = js_sys::Reflect::set(&js, &"issue".into(), &issue);
+    }
+    js
+}
+
+fn cards_issue(input: &str) -> Option<Object> {
+    let diagnostic = core_diagnose_cards(input)?;
+    let kind = match diagnostic.issue {
+        CardIssue::Rank(_) => "rank",
+        CardIssue::Suit(_) => "suit",
+        CardIssue::MissingSuit => "missing_suit",
+    };
+    Some(issue(
+        input,
+        kind,
+        &diagnostic.token,
+        diagnostic.position,
+        card_suggestions(&diagnostic),
+    ))
+}
+
+fn range_issue(input: &str) -> Option<Object> {
+    let diagnostic = core_diagnose_range(input)?;
+    Some(issue(
+        input,
+        "range",
+        &diagnostic.token,
+        diagnostic.position,
+        range_suggestions(&diagnostic.token),
+    ))
+}
+
+/// Empty input is an unknown hand, not an error.
+fn hand_issue(input: &str) -> Option<Object> {
+    if input.trim().is_empty() {
+        None
+    } else if looks_like_cards(input) {
+        cards_issue(input)
+    } else {
+        range_issue(input)
+    }
+}
+
+/// Builds a `ParseIssue`. `position` is a byte offset into `input`; only an
+/// unambiguous suggestion is kept.
+fn issue(
+    input: &str,
+    kind: &str,
+    token: &str,
+    position: usize,
+    suggestions: Vec<String>,
+) -> Object {
+    let utf16_position = input[..position].encode_utf16().count();
+    let suggestion = match suggestions.as_slice() {
+        [only] => JsValue::from(only.as_str()),
+        _ => JsValue::NULL,
+    };
+    let object = Object::new();
+    set(&object, "kind", &kind.into());
+    set(&object, "token", &token.into());
+    set(&object, "position", &(utf16_position as f64).into());
+    set(&object, "suggestion", &suggestion);
+    object
+}
@@ -0,0 +1,54 @@
+use js_sys::{Array, Object};
+use snapcall_core::{expand_weighted_range, range_grid, HandClass};
+use wasm_bindgen::prelude::*;
+
+use crate::error;
+use crate::js::set;
+
+#[wasm_bindgen(typescript_custom_section)]
+const RANGE_GRID_CELL: &'static str = r#"
+/** One cell of the 13×13 grid returned by `range_grid`. */
+export interface RangeGridCell {
+  /** Class label such as `"AA"`, `"AKs"`, or `"T9o"`. */
+  class: string;
+  /** Combos of this class in the range. */
+  combos: number;
+  /** Combos of this class in a full deck (6, 4, or 12). */
+  total: number;
+  /** Share of the class in the range, from 0 to 1, counting combo weights (`"AKs:0.5"`). */
+  weight: number;
+  /** Whether any combo of the class is in the range. */
+  included: boolean;
+}
+"#;
+
+/// Expands a range (or exact hand) into the conventional 13×13 grid: rows
+/// and columns run from ace to deuce, pairs on the diagonal, suited hands
+/// above it and offsuit hands below.
+///
+/// Throws a `SnapError` (see `estimate_equity`) for an invalid range.
+#[wasm_bindgen(js_name = range_grid, unchecked_return_type = "RangeGridCell[][]")]
+pub fn range_grid_cells(range: &str) -> Result<Array, JsValue> {
+    let weighted = expand_weighted_range(range).map_err(error::to_js)?;
+    let combos: Vec<_> = weighted.iter().map(|(combo, _)| *combo).collect();
+    let mut weights = [[0.0; 13]; 13];
+    for (combo, weight) in &weighted {
+        let (row, col) = HandClass::from_cards(combo[0], combo[1]).grid_position();
+        weights[row][col] += weight;
+    }
+    let rows = Array::new();
+    for (grid_row, weight_row) in range_grid(&combos).into_iter().zip(weights) {
+        let cells = Array::new();
+        for (cell, weight) in grid_row.into_iter().zip(weight_row) {
+            let object = Object::new();
+            set(&object, "class", &cell.class.to_string().into());
+            set(&object, "combos", &(cell.combos as f64).into());
+            set(&object, "total", &(cell.total as f64).into());
+            set(&object, "weight", &(weight / cell.total as f64).into());
+            set(&object, "included", &(cell.combos > 0).into());
+            cells.push(&object);
+        }
+        rows.push(&cells);
+    }
+    Ok(rows)
+}
@@ -0,0 +1,86 @@
+use js_sys::{Function, Object};
+use snapcall_core::{EquityResult, SnapError};
+use wasm_bindgen::prelude::*;
+
+use crate::js::set;
+use crate::{equity_in_chunks, equity_options, error, AbortSignal, EstimateResult};
+
+/// Normal quantile of the 95% confidence interval in streamed updates.
+const CONFIDENCE_Z: f64 = 1.96;
+
+#[wasm_bindgen(typescript_custom_section)]
+const EQUITY_UPDATE: &'static str = r#"
+/** Running estimate passed to the `stream_equity` callback. */
+export interface EquityUpdate {
+  /** Samples run so far and the requested total. */
+  samples: number;
+  total: number;
+  /** Per-player equity in percent, hero first; sums to 100. */
+  equities: number[];
+  /** 95% confidence bounds around each equity, in percent. */
+  low: number[];
+  high: number[];
+}
+"#;
+
+/// Like `calculate_equity_async`, but calls `on_update` with the running
+/// estimate and its 95% confidence interval every `every` samples, for
+/// displays that refine while the calculation runs.
+///
+/// Exact spots report once, with zero-width intervals. Throws a `SnapError`
+/// (see `estimate_equity`) for invalid input or when `every` is 0; aborting
+/// `signal` cancels and `seed` fixes the results as in
+/// `calculate_equity_async`.
+#[wasm_bindgen]
+#[allow(clippy::too_many_arguments)] // flat positional arguments mirror the other exports
+pub async fn stream_equity(
+    board: String,
+    hero: String,
+    villains: Vec<String>,
+    iterations: u32,
+    every: u32,
+    #[wasm_bindgen(unchecked_param_type = "(update: EquityUpdate) => void")] on_update: Function,
+    signal: Option<AbortSignal>,
+    seed: Option<u32>,
+) -> Result<EstimateResult, JsValue> {
+    if every == 0 {
+        return Err(error::to_js(SnapError::InvalidArgument(
+            "Update interval must be at least 1 sample".to_string(),
+        )));
+    }
+    let v_refs: Vec<&str> = villains.iter().map(|s| s.as_str()).collect();
+    let start = js_sys::Date::now();
+    let result = equity_in_chunks(
+        &board,
+        &hero,
+        &v_refs,
+        &equity_options(iterations, seed),
+        every,
+        signal.as_ref(),
+        |done, running| {
+            on_update.call1(&JsValue::NULL, &update(done, iterations, running))?;
+            Ok(())
+        },
+    )
+    .await?;
+    Ok(EstimateResult::new(result, js_sys::Date::now() - start))
+}
+
+fn update(done: u32, total: u32, running: &EquityResult) -> JsValue {
+    let margins = running.margin_of_error(CONFIDENCE_Z);
+    let bound = |sign: f64| -> Vec<f64> {
+        running
+            .equities
+            .iter()
+            .zip(&margins)
+            .map(|(e, m)| (e + sign * m).clamp(0.0, 100.0))
+            .collect()
+    };
+    let object = Object::new();
+    set(&object, "samples", &done.into());
+    set(&object, "total", &total.into());
+    set(&object, "equities", &running.equities.clone().into());
+    set(&object, "low", &bound(-1.0).into());
+    set(&object, "high", &bound(1.0).into());
+    object.into()
+}
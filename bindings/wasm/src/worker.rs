@@ -0,0 +1,220 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+use js_sys::{Array, Float64Array, Function, Object, Reflect};
+use snapcall_core::EquityEstimateMode;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+use wasm_bindgen_futures::spawn_local;
+
+use crate::js::{field, set};
+use crate::{equity_in_chunks, equity_options, error, ASYNC_CHUNK_SAMPLES};
+
+#[wasm_bindgen(typescript_custom_section)]
+const WORKER_PROTOCOL: &'static str = r#"
+/** Message posted to a worker running `installEquityWorker`. */
+export type EquityWorkerRequest =
+  | { type: "equity"; id: number; board: string; hero: string; villains: string[]; iterations: number; seed?: number }
+  | { type: "cancel"; id: number };
+
+/** Arrays are transferred, not copied; per-player values are in percent. */
+export interface EquityWorkerResult {
+  equities: Float64Array;
+  wins: Float64Array;
+  ties: Float64Array;
+  mode: "exact" | "monte_carlo";
+  /** `mode === "exact"`: label the result exact rather than estimated. */
+  isExact: boolean;
+  samples: number;
+  elapsedMs: number;
+}
+
+/**
+ * Message posted back by the worker. Every `equity` request ends with exactly
+ * one `result` or `error` carrying its `id`; `internal` marks an unexpected
+ * failure.
+ */
+export type EquityWorkerResponse =
+  | { type: "progress"; id: number; done: number; total: number }
+  | ({ type: "result"; id: number } & EquityWorkerResult)
+  | { type: "error"; id: number; message: string; code: SnapErrorCode | "cancelled" | "internal"; issue?: InputIssue };
+"#;
+
+thread_local! {
+    /// Running request ids and whether each has been asked to cancel.
+    static JOBS: RefCell<HashMap<u32, bool>> = RefCell::new(HashMap::new());
+}
+
+/// Turns the current dedicated worker into an equity server: sets
+/// `onmessage` to handle `EquityWorkerRequest`s and answers with
+/// `EquityWorkerResponse`s.
+///
+/// Requests run concurrently in chunks, so a `cancel` for a running id takes
+/// effect at the next chunk and ends it with an `error` whose code is
+/// `cancelled`. Call once after `init()` inside the worker.
+#[wasm_bindgen(js_name = installEquityWorker)]
+pub fn install_equity_worker() -> Result<(), JsValue> {
+    let handler = Closure::<dyn FnMut(JsValue)>::new(|event: JsValue| {
+        handle(field(&event, "data"));
+    });
+    Reflect::set(&js_sys::global(), &"onmessage".into(), handler.as_ref())?;
+    // The handler lives as long as the worker
+    handler.forget();
+    Ok(())
+}
+
+fn handle(message: JsValue) {
+    let raw_id = field(&message, "id");
+    let Some(id) = whole_number(&raw_id) else {
+        post_error(
+            &raw_id,
+            "Request id must be a non-negative integer",
+            "invalid_argument",
+        );
+        return;
+    };
+    match field(&message, "type").as_string().as_deref() {
+        Some("equity") => {
+            let started = JOBS.with_borrow_mut(|jobs| jobs.insert(id, false).is_none());
+            if !started {
+                post_error(&raw_id, "Request id is already running", "invalid_argument");
+                return;
+            }
+            spawn_local(async move {
+                let outcome = run_equity(id, &message).await;
+                JOBS.with_borrow_mut(|jobs| jobs.remove(&id));
+                if let Err(err) = outcome {
+                    let text = field(&err, "message")
+                        .as_string()
+                        .unwrap_or_else(|| format!("{err:?}"));
+                    let code = field(&err, "code").as_string();
+                    let reply =
+                        error_response(&id.into(), &text, code.as_deref().unwrap_or("internal"));
+                    let issue = field(&err, "issue");
+                    if !issue.is_undefined() {
+                        set(&reply, "issue", &issue);
+                    }
+                    // Nothing left to report to if posting itself fails
+                    let _ = post(&reply, &Array::new());
+                }
+            });
+        }
+        Some("cancel") => JOBS.with_borrow_mut(|jobs| {
+            if let Some(cancelled) = jobs.get_mut(&id) {
+                *cancelled = true;
+            }
+        }),
+        _ => post_error(
+            &raw_id,
+            "Request type must be \"equity\" or \"cancel\"",
+            "invalid_argument",
+        ),
+    }
+}
+
+async fn run_equity(id: u32, message: &JsValue) -> Result<(), JsValue> {
+    let board = string_field(message, "board")?;
+    let hero = string_field(message, "hero")?;
+    let villains = field(message, "villains");
+    if !Array::is_array(&villains) {
+        return Err(invalid("villains must be an array of strings"));
+    }
+    let villains = Array::from(&villains)
+        .iter()
+        .map(|v| {
+            v.as_string()
+                .ok_or_else(|| invalid("villains must be an array of strings"))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    let iterations = whole_number(&field(message, "iterations"))
+        .ok_or_else(|| invalid("iterations must be a non-negative integer"))?;
+    let seed = match field(message, "seed") {
+        absent if absent.is_undefined() || absent.is_null() => None,
+        value => Some(
+            whole_number(&value).ok_or_else(|| invalid("seed must be a non-negative integer"))?,
+        ),
+    };
+
+    let v_refs: Vec<&str> = villains.iter().map(|s| s.as_str()).collect();
+    let start = js_sys::Date::now();
+    let result = equity_in_chunks(
+        &board,
+        &hero,
+        &v_refs,
+        &equity_options(iterations, seed),
+        ASYNC_CHUNK_SAMPLES,
+        None,
+        |done, _| {
+            if JOBS.with_borrow(|jobs| jobs.get(&id).copied().unwrap_or(false)) {
+                return Err(error::cancelled());
+            }
+            let progress = response("progress", &id.into());
+            set(&progress, "done", &done.into());
+            set(&progress, "total", &iterations.into());
+            post(&progress, &Array::new())
+        },
+    )
+    .await?;
+
+    let equities = Float64Array::from(result.equities.as_slice());
+    let wins = Float64Array::from(result.wins.as_slice());
+    let ties = Float64Array::from(result.ties.as_slice());
+    let reply = response("result", &id.into());
+    set(&reply, "equities", &equities);
+    set(&reply, "wins", &wins);
+    set(&reply, "ties", &ties);
+    set(&reply, "mode", &result.mode.to_string().into());
+    let exact = result.mode == EquityEstimateMode::ExactEnumeration;
+    set(&reply, "isExact", &exact.into());
+    set(&reply, "samples", &(result.samples as f64).into());
+    set(&reply, "elapsedMs", &(js_sys::Date::now() - start).into());
+    let transfer = Array::of3(&equities.buffer(), &wins.buffer(), &ties.buffer());
+    post(&reply, &transfer)
+}
+
+/// A JS number that fits a `u32` exactly.
+fn whole_number(value: &JsValue) -> Option<u32> {
+    value
+        .as_f64()
+        .filter(|n| n.fract() == 0.0 && (0.0..=u32::MAX as f64).contains(n))
+        .map(|n| n as u32)
+}
+
+fn string_field(object: &JsValue, key: &str) -> Result<String, JsValue> {
+    field(object, key)
+        .as_string()
+        .ok_or_else(|| invalid(&format!("{key} must be a string")))
+}
+
+/// A malformed request, reported with the same shape as core errors.
+fn invalid(message: &str) -> JsValue {
+    error::to_js(snapcall_core::SnapError::InvalidArgument(
+        message.to_string(),
+    ))
+}
+
+fn response(kind: &str, id: &JsValue) -> Object {
+    let object = Object::new();
+    set(&object, "type", &kind.into());
+    set(&object, "id", id);
+    object
+}
+
+fn post(message: &Object, transfer: &Array) -> Result<(), JsValue> {
+    let scope = js_sys::global();
+    let post_message: Function = Reflect::get(&scope, &"postMessage".into())?.dyn_into()?;
+    post_message.call2(&scope, message, transfer)?;
+    Ok(())
+}
+
+fn error_response(id: &JsValue, message: &str, code: &str) -> Object {
+    let reply = response("error", id);
+    set(&reply, "message", &message.into());
+    set(&reply, "code", &code.into());
+    reply
+}
+
+fn post_error(id: &JsValue, message: &str, code: &str) {
+    // Nothing left to report to if posting itself fails
+    let _ = post(&error_response(id, message, code), &Array::new());
+}
@@ -0,0 +1,75 @@
+use std::io::IsTerminal;
+use std::sync::OnceLock;
+
+use clap::ValueEnum;
+use rs_poker::core::{Card, Suit};
+use serde::Deserialize;
+use snapcall_core::format_card;
+
+/// How suits are drawn when cards are printed.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, ValueEnum, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SuitStyle {
+    /// `Ah Ks` (plain letters; safe for any terminal and screen readers)
+    #[default]
+    Ascii,
+    /// `A♥ K♠`
+    Unicode,
+    /// `A♥️ K♠️`
+    Emoji,
+}
+
+impl From<SuitStyle> for snapcall_core::SuitStyle {
+    fn from(style: SuitStyle) -> Self {
+        match style {
+            SuitStyle::Ascii => Self::Ascii,
+            SuitStyle::Unicode => Self::Unicode,
+            SuitStyle::Emoji => Self::Emoji,
+        }
+    }
+}
+
+/// Card rendering chosen once per run from flags and config.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct CardStyle {
+    pub suits: SuitStyle,
+    /// Colors each suit differently (spades default, hearts red, diamonds blue, clubs green)
+    pub four_color: bool,
+}
+
+/// Whether ANSI colors may be written: `allowed` (no `--no-color` or
+/// `color = false`), stdout is a terminal, and `NO_COLOR` is unset.
+pub fn color_enabled(allowed: bool) -> bool {
+    allowed && std::env::var_os("NO_COLOR").is_none() && std::io::stdout().is_terminal()
+}
+
+static STYLE: OnceLock<CardStyle> = OnceLock::new();
+
+/// Sets the style for the rest of the process; later calls are ignored.
+pub fn init(style: CardStyle) {
+    let _ = STYLE.set(style);
+}
+
+/// Renders one card in the configured style.
+pub fn card(card: Card) -> String {
+    let style = STYLE.get().copied().unwrap_or_default();
+    let text = format_card(card, style.suits.into());
+    if !style.four_color {
+        return text;
+    }
+    let color = match card.suit {
+        Suit::Spade => return text,
+        Suit::Heart => "31",
+        Suit::Diamond => "34",
+        Suit::Club => "32",
+    };
+    format!("\x1b[{color}m{text}\x1b[0m")
+}
+
+/// Renders cards separated by spaces, or `-` when there are none.
+pub fn cards(cards: &[Card]) -> String {
+    if cards.is_empty() {
+        return "-".to_string();
+    }
+    cards.iter().map(|&c| card(c)).collect::<Vec<_>>().join(" ")
+}
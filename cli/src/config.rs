@@ -0,0 +1,113 @@
+use std::num::NonZeroUsize;
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+use snapcall_schema::DEFAULT_ITERATIONS;
+
+use crate::cards::{CardStyle, SuitStyle};
+use crate::error::{CliError, ErrorFormat};
+
+/// User defaults loaded from `config.toml`.
+///
+/// Command-line flags always win over values set here.
+#[derive(Debug, Default, Deserialize)]
+pub struct Config {
+    /// Default sample budget for equity commands
+    pub iterations: Option<u32>,
+
+    /// Default suit rendering: `ascii`, `unicode`, or `emoji`
+    pub suit_style: Option<SuitStyle>,
+
+    /// Color suits differently by default
+    pub four_color: Option<bool>,
+
+    /// `false` turns ANSI colors off, like `--no-color`
+    pub color: Option<bool>,
+
+    /// Default error format: `text` or `json`
+    pub format: Option<ErrorFormat>,
+
+    /// Default Monte Carlo thread count for `equity`
+    pub threads: Option<NonZeroUsize>,
+
+    #[serde(flatten)]
+    other: toml::Table,
+}
+
+impl Config {
+    /// Loads the config from `path`, or from the default location when `None`.
+    ///
+    /// A missing default file yields an empty config; a missing explicit
+    /// `--config` file is an error.
+    pub fn load(path: Option<&Path>) -> Result<Self, CliError> {
+        let (path, explicit) = match path {
+            Some(p) => (p.to_path_buf(), true),
+            None => match default_path() {
+                Some(p) => (p, false),
+                None => return Ok(Self::default()),
+            },
+        };
+
+        let text = match std::fs::read_to_string(&path) {
+            Ok(text) => text,
+            Err(e) if !explicit && e.kind() == std::io::ErrorKind::NotFound => {
+                return Ok(Self::default());
+            }
+            Err(e) => {
+                return Err(CliError::io(format!(
+                    "reading config '{}': {e}",
+                    path.display()
+                )))
+            }
+        };
+
+        let config: Self = toml::from_str(&text)
+            .map_err(|e| CliError::input(format!("parsing config '{}': {e}", path.display())))?;
+        for key in config.other.keys() {
+            eprintln!(
+                "Warning: unknown config key '{key}' in '{}'",
+                path.display()
+            );
+        }
+        Ok(config)
+    }
+
+    /// Resolves a sample budget: flag, then config, then the built-in default.
+    pub fn iterations(&self, flag: Option<u32>) -> u32 {
+        flag.or(self.iterations).unwrap_or(DEFAULT_ITERATIONS)
+    }
+
+    /// Resolves the error format: flag, then config, then text.
+    pub fn error_format(&self, flag: Option<ErrorFormat>) -> ErrorFormat {
+        flag.or(self.format).unwrap_or_default()
+    }
+
+    /// Resolves a thread count: flag, then config, then one thread.
+    pub fn threads(&self, flag: Option<NonZeroUsize>) -> usize {
+        flag.or(self.threads).map_or(1, NonZeroUsize::get)
+    }
+
+    /// Whether ANSI colors are allowed at all: `--no-color` wins, then
+    /// `color` in the config.
+    pub fn color(&self, no_color: bool) -> bool {
+        !no_color && self.color.unwrap_or(true)
+    }
+
+    /// Resolves card rendering: flags, then config, then plain ASCII.
+    pub fn card_style(&self, suit_style: Option<SuitStyle>, four_color: bool) -> CardStyle {
+        CardStyle {
+            suits: suit_style.or(self.suit_style).unwrap_or_default(),
+            four_color: four_color || self.four_color.unwrap_or(false),
+        }
+    }
+}
+
+/// `$XDG_CONFIG_HOME/snapcall/config.toml`, falling back to
+/// `~/.config/snapcall/config.toml`.
+fn default_path() -> Option<PathBuf> {
+    let base = match std::env::var_os("XDG_CONFIG_HOME") {
+        Some(dir) if !dir.is_empty() => PathBuf::from(dir),
+        _ => PathBuf::from(std::env::var_os("HOME")?).join(".config"),
+    };
+    Some(base.join("snapcall").join("config.toml"))
+}
@@ -0,0 +1,104 @@
+use std::fmt;
+use std::process::ExitCode;
+
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+use snapcall_core::SnapError;
+use snapcall_schema::{ErrorBody, ErrorKind, ErrorResponse};
+
+/// How errors are reported on stderr.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, ValueEnum, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ErrorFormat {
+    /// `Error: <message>`
+    #[default]
+    Text,
+    /// `{"error":{"kind":"input","code":2,"message":"...","hint":"..."}}`
+    Json,
+}
+
+/// A command failure: its kind plus a human-readable message.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct CliError {
+    pub kind: ErrorKind,
+    pub message: String,
+    /// A likely correction for mistyped input, shown after the message
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hint: Option<String>,
+}
+
+impl CliError {
+    pub fn input(message: impl Into<String>) -> Self {
+        Self {
+            kind: ErrorKind::Input,
+            message: message.into(),
+            hint: None,
+        }
+    }
+
+    pub fn io(message: impl Into<String>) -> Self {
+        Self {
+            kind: ErrorKind::Io,
+            message: message.into(),
+            hint: None,
+        }
+    }
+
+    /// Wraps a core error, prefixing `context` to its message.
+    pub fn snap(context: &str, err: SnapError) -> Self {
+        Self {
+            kind: ErrorKind::of(&err),
+            message: format!("{context}: {err}"),
+            hint: None,
+        }
+    }
+
+    /// Attaches a correction hint to an input error; other kinds are unchanged.
+    pub fn with_hint(mut self, hint: Option<String>) -> Self {
+        if self.kind == ErrorKind::Input {
+            self.hint = hint;
+        }
+        self
+    }
+
+    /// The shared `{"kind", "code", "message", "hint"}` error object.
+    pub fn body(&self) -> ErrorBody {
+        ErrorBody {
+            hint: self.hint.clone(),
+            ..ErrorBody::new(self.kind, self.message.as_str())
+        }
+    }
+
+    /// Prints the error to stderr in `format` and returns the matching exit code.
+    pub fn report(&self, format: ErrorFormat) -> ExitCode {
+        match format {
+            ErrorFormat::Text => {
+                eprintln!("Error: {}", self.message);
+                if let Some(hint) = &self.hint {
+                    eprintln!("Hint: {hint}");
+                }
+            }
+            ErrorFormat::Json => {
+                let body = ErrorResponse { error: self.body() };
+                match serde_json::to_string(&body) {
+                    Ok(json) => eprintln!("{json}"),
+                    Err(_) => eprintln!("Error: {}", self.message),
+                }
+            }
+        }
+        ExitCode::from(self.kind.code())
+    }
+}
+
+impl fmt::Display for CliError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+/// Argument validation messages are input errors.
+impl From<String> for CliError {
+    fn from(message: String) -> Self {
+        Self::input(message)
+    }
+}
@@ -1,13 +1,61 @@
+use std::num::NonZeroUsize;
+use std::path::{Path, PathBuf};
 use std::process::ExitCode;
+use std::time::{Duration, Instant};
 
-use clap::{Parser, Subcommand};
-use rs_poker::core::{FlatHand, Rankable};
-use snapcall_core::estimate_equity;
+use clap::{ArgAction, Args, Parser, Subcommand, ValueEnum};
+use rs_poker::core::{Card, FlatHand, Rankable};
+use snapcall_core::{
+    best_hand, bluff_break_even_percent, bounty_call_equity_percent, bounty_call_ev,
+    bubble_call_equity_percent, bubble_factor, call_ev, chop_deal, deal_random_hand,
+    decision_ev_with_rake, estimate_equity, estimate_equity_with_options,
+    estimate_multi_board_equity, estimate_range_equity, expand_range, geometric_bet_fraction,
+    geometric_plan, hand_potential, hand_strength, icm_equity, minimum_defense_frequency_percent,
+    next_card_strengths, outs_rule_of_thumb, outs_to_percent, parse_cards, percent_to_odds_against,
+    percent_to_outs, plan_equity, pot_fraction_bet, pot_odds_percent, preflop_chart, range_grid,
+    simulate_bankroll, solve_blind_vs_blind, stack_off_after_bet_percent, stack_off_equity_percent,
+    stack_to_pot_ratio, BountyOptions, ChartKind, ChartOptions, ChopMethod, EquityCache,
+    EquityEstimateMode, EquityOptions, HandCategory, HandClass, HandStrength, Position, RakeModel,
+    RealizationModel, SnapError, Street, VarianceConfig, DEFAULT_MAX_EXACT_STATES, TOTAL_COMBOS,
+};
+use snapcall_schema::{EquityOutcome, EquityScenario, Record};
+
+mod cards;
+mod config;
+mod error;
+mod output;
+mod serve;
+mod suggest;
+
+use cards::SuitStyle;
+use config::Config;
+use error::{CliError, ErrorFormat};
+use output::write_record;
 
 #[derive(Parser)]
 #[command(name = "snapcall")]
 #[command(about = "Texas Hold'em Equity Calculator")]
 struct Cli {
+    /// Config file with defaults (default: ~/.config/snapcall/config.toml)
+    #[arg(long, global = true)]
+    config: Option<PathBuf>,
+
+    /// How to print errors on stderr (exit codes: 2 input, 3 compute, 4 I/O) [default: text]
+    #[arg(long = "error-format", global = true, value_enum)]
+    error_format: Option<ErrorFormat>,
+
+    /// How to draw suits when printing cards
+    #[arg(long = "suit-style", global = true, value_enum)]
+    suit_style: Option<SuitStyle>,
+
+    /// Color each suit differently (hearts red, diamonds blue, clubs green)
+    #[arg(long = "four-color", global = true)]
+    four_color: bool,
+
+    /// Never write ANSI colors (they are also off when stdout is not a terminal)
+    #[arg(long = "no-color", global = true)]
+    no_color: bool,
+
     #[command(subcommand)]
     command: Commands,
 }
@@ -21,28 +69,375 @@ enum Commands {
         hand: String,
     },
     /// Calculate equity for multiple players
-    Equity {
+    Equity(EquityArgs),
+
+    /// Print a pairwise equity matrix between hands or ranges
+    Matrix {
+        /// Hands or ranges to compare, two or more (e.g., -p AA -p KK -p AKs)
+        #[arg(short = 'p', long = "player", required = true, num_args = 1..)]
+        players: Vec<String>,
+
+        /// Community cards string with 0/3/4/5 known cards
+        #[arg(short = 'b', long = "board")]
+        board: Option<String>,
+
+        /// Number of Monte Carlo iterations per pair [default: 100000]
+        #[arg(short = 'i', long)]
+        iterations: Option<u32>,
+    },
+
+    /// Inspect a range: combo count, share of all hands, combos, grid
+    Range {
+        /// Range expression (e.g., "ATs+, 77+")
+        range: String,
+
+        /// List every concrete combo in the range
+        #[arg(short = 'l', long)]
+        list: bool,
+
+        /// Render the range as a 13×13 grid
+        #[arg(short = 'g', long)]
+        grid: bool,
+    },
+
+    /// Convert outs to hit percentages and odds (or a percentage back to outs)
+    Odds {
+        /// Number of outs to convert
+        #[arg(short = 'o', long, conflicts_with = "percent")]
+        outs: Option<usize>,
+
+        /// Target hit percentage to convert back into outs
+        #[arg(short = 'P', long)]
+        percent: Option<f64>,
+
+        /// Street the draw is on
+        #[arg(short = 's', long, value_enum, default_value = "flop")]
+        street: OddsStreet,
+    },
+
+    /// Deal a random hand and show the winner
+    Deal {
+        /// Number of players at the table
+        #[arg(short = 'n', long, default_value = "6")]
+        players: usize,
+
+        /// Street to deal up to: preflop / flop / turn / river
+        #[arg(short = 's', long, default_value = "river")]
+        street: Street,
+
+        /// Seed for a reproducible deal
+        #[arg(long)]
+        seed: Option<u64>,
+    },
+
+    /// Compare hero equity with the pot odds of a call and print a verdict
+    Decide {
+        /// Hero hand first, then one or more villain hands/ranges (e.g., -p AhKh -p TT+)
+        #[arg(short = 'p', long = "player", required = true, num_args = 1..)]
+        players: Vec<String>,
+
+        /// Community cards string with 0/3/4/5 known cards
+        #[arg(short = 'b', long = "board")]
+        board: Option<String>,
+
+        /// Pot size before the bet you are facing
+        #[arg(long, required = true)]
+        pot: f64,
+
+        /// Bet you are facing (the amount to call)
+        #[arg(long, required = true)]
+        bet: f64,
+
+        /// Number of Monte Carlo iterations [default: 100000]
+        #[arg(short = 'i', long)]
+        iterations: Option<u32>,
+    },
+
+    /// Expected value of folding, calling, and shoving against a villain range
+    Ev {
+        /// Hero hand as 1 or 2 known cards (e.g., "AhKh")
+        #[arg(short = 'H', long = "hero", required = true)]
+        hero: String,
+
+        /// Villain hand or range that continues (e.g., "TT+, AK")
+        #[arg(short = 'V', long = "villain", default_value = "")]
+        villain: String,
+
         /// Community cards string with 0/3/4/5 known cards
         #[arg(short = 'b', long = "board")]
         board: Option<String>,
 
-        /// Hero hand as 1 or 2 known cards (e.g., "Ah" or "AhAd")
+        /// Pot size before the bet you are facing
+        #[arg(long, required = true)]
+        pot: f64,
+
+        /// Bet you are facing (0 when first to act)
+        #[arg(long, default_value = "0")]
+        bet: f64,
+
+        /// Effective stack behind (hero's remaining chips, capped by villain's)
+        #[arg(long, required = true)]
+        stack: f64,
+
+        /// Chance in percent that villain folds to a shove
+        #[arg(short = 'f', long = "fold-equity", default_value = "0")]
+        fold_equity: f64,
+
+        /// Hero's position after calling; scales the call's equity by a realization estimate
+        #[arg(long, value_enum)]
+        position: Option<CliPosition>,
+
+        #[command(flatten)]
+        rake: RakeArgs,
+
+        /// Number of Monte Carlo iterations [default: 100000]
+        #[arg(short = 'i', long)]
+        iterations: Option<u32>,
+    },
+
+    /// Calling an all-in in a knockout tournament: bounty-adjusted price and EV
+    Pko {
+        /// Hero hand as 1 or 2 known cards (e.g., "AhKh")
         #[arg(short = 'H', long = "hero", required = true)]
         hero: String,
 
-        /// One or more villains: unknown / partial / exact / range (e.g., "", "Kh", "KhKd", "TT+")
-        #[arg(short = 'V', long = "villain", num_args = 1..)]
-        villains: Vec<String>,
+        /// Shover's hand or range (e.g., "22+, A2s+")
+        #[arg(short = 'V', long = "villain", default_value = "")]
+        villain: String,
 
-        /// Total number of villains (fills missing villains as unknown hands)
-        #[arg(short = 'n', long = "villain-count")]
-        villain_count: Option<usize>,
+        /// Community cards string with 0/3/4/5 known cards
+        #[arg(short = 'b', long = "board")]
+        board: Option<String>,
+
+        /// Pot including the all-in being faced
+        #[arg(long, required = true)]
+        pot: f64,
+
+        /// Chips hero must put in to call
+        #[arg(long, required = true)]
+        call: f64,
+
+        /// Money on the shover's head (e.g., 50 for a $50 bounty)
+        #[arg(long, required = true)]
+        bounty: f64,
+
+        /// Chips in a starting stack
+        #[arg(long = "starting-stack", required = true)]
+        starting_stack: f64,
+
+        /// Part of the buy-in that goes to the regular prize pool
+        #[arg(long = "prize-buy-in", required = true)]
+        prize_buy_in: f64,
 
-        /// Number of Monte Carlo iterations
-        #[arg(short = 'i', long, default_value = "100000")]
+        /// Share of a won bounty paid at once (1 for a regular knockout)
+        #[arg(long = "cash-share", default_value = "0.5")]
+        cash_share: f64,
+
+        /// Worth of the bounty added to hero's head, as a share of its face value
+        #[arg(long = "head-value", default_value = "0.5")]
+        head_value: f64,
+
+        /// Hero has the shover covered, so calling can win the bounty
+        #[arg(long)]
+        covers: bool,
+
+        /// Number of Monte Carlo iterations [default: 100000]
+        #[arg(short = 'i', long)]
+        iterations: Option<u32>,
+    },
+
+    /// Run standard scenarios and report evaluation and sample throughput
+    Bench {
+        /// Sample budget for each Monte Carlo scenario
+        #[arg(short = 'i', long, default_value = "200000")]
         iterations: u32,
     },
 
+    /// Compare one hand against several villain ranges, one at a time
+    Compare {
+        /// Hero hand as 1 or 2 known cards (e.g., "JhTh")
+        #[arg(short = 'p', long = "player", required = true)]
+        hero: String,
+
+        /// Villain range to compare against (repeatable, e.g., --vs "22+" --vs "top 20%")
+        #[arg(long = "vs", required = true, num_args = 1..)]
+        ranges: Vec<String>,
+
+        /// Community cards string with 0/3/4/5 known cards
+        #[arg(short = 'b', long = "board")]
+        board: Option<String>,
+
+        /// Number of Monte Carlo iterations per range [default: 100000]
+        #[arg(short = 'i', long)]
+        iterations: Option<u32>,
+    },
+
+    /// Where hero's made hand ranks against every opponent holding
+    Strength {
+        /// Hero hole cards (e.g. "KhQh")
+        #[arg(short = 'p', long = "hero", required = true)]
+        hero: String,
+
+        /// Board with 3-5 cards (e.g. "Qs7d2c")
+        #[arg(short = 'b', long, required = true)]
+        board: String,
+
+        /// Villain range for hand potential and EHS (e.g. "TT+, AQs+")
+        #[arg(short = 'r', long)]
+        range: Option<String>,
+    },
+
+    /// Classic preflop confrontations with exact all-in equities
+    Matchups,
+
+    /// Heads-up push/fold equilibrium: small blind shoving and big blind calling ranges
+    Shove {
+        /// Effective stack in big blinds (e.g., 12 or 12bb)
+        #[arg(short = 's', long, value_parser = parse_big_blinds)]
+        stack: f64,
+
+        /// Seat doing the shoving; only the small blind (folded to, heads-up vs BB) is supported
+        #[arg(long, value_enum, default_value = "sb")]
+        position: ShovePosition,
+
+        /// Ante per player in big blinds (e.g., 0.125)
+        #[arg(short = 'a', long, default_value = "0", value_parser = parse_big_blinds)]
+        ante: f64,
+
+        /// Players dealt in; everyone before the small blind folded, leaving their antes as dead money
+        #[arg(short = 'p', long, default_value = "2")]
+        players: usize,
+
+        /// Render both ranges as 13×13 grids instead of class lists
+        #[arg(short = 'g', long)]
+        grid: bool,
+    },
+
+    /// Generate a preflop open, call, or 3-bet chart from a simple EV model
+    Chart {
+        /// Which chart to build
+        #[arg(value_enum)]
+        kind: CliChartKind,
+
+        /// Effective stack in big blinds (e.g., 100 or 100bb)
+        #[arg(short = 's', long, default_value = "100", value_parser = parse_big_blinds)]
+        stack: f64,
+
+        /// Hero's position after the flop
+        #[arg(long, value_enum, default_value = "ip")]
+        position: CliPosition,
+
+        /// Players left to act behind an open
+        #[arg(long = "behind", default_value = "3")]
+        players_behind: u32,
+
+        /// Opener's range facing an open, as a top percentage of hands
+        #[arg(long = "opener-percent", default_value = "25")]
+        opener_percent: f64,
+
+        /// Render the chart as a 13×13 grid instead of a class list
+        #[arg(short = 'g', long)]
+        grid: bool,
+    },
+
+    /// Stay resident and answer one equity scenario per input line
+    Serve {
+        /// Read scenarios from stdin and write JSON results to stdout
+        #[arg(long, required = true)]
+        stdio: bool,
+
+        /// Default Monte Carlo iterations for lines that omit one [default: 100000]
+        #[arg(short = 'i', long)]
+        iterations: Option<u32>,
+
+        /// Also append each scenario and its result as a JSON line to this file
+        #[arg(short = 'o', long = "output")]
+        output: Option<PathBuf>,
+    },
+
+    /// Stack-to-pot ratio, commitment zone, and equity needed to stack off
+    Spr {
+        /// Effective stack behind (the smaller of hero's and villain's)
+        #[arg(short = 's', long, required = true)]
+        stack: f64,
+
+        /// Current pot size
+        #[arg(short = 'p', long, required = true)]
+        pot: f64,
+    },
+
+    /// Bet sizes: pot fractions, how committed each leaves you, and geometric bets to get all-in
+    Sizing {
+        /// Effective stack behind (the smaller of hero's and villain's)
+        #[arg(short = 's', long, required = true)]
+        stack: f64,
+
+        /// Current pot size
+        #[arg(short = 'p', long, required = true)]
+        pot: f64,
+
+        /// Streets left to get the stack in with equal pot-fraction bets
+        #[arg(short = 'n', long, default_value = "3")]
+        streets: u32,
+    },
+
+    /// ICM bubble factors and the equity needed to call all-ins
+    Bubble {
+        /// Chip stacks, one per player (e.g. "4000,3000,2000,1000")
+        #[arg(short = 's', long, required = true, value_delimiter = ',')]
+        stacks: Vec<f64>,
+
+        /// Prizes by finishing place (e.g. "50,30,20")
+        #[arg(short = 'p', long, required = true, value_delimiter = ',')]
+        payouts: Vec<f64>,
+    },
+
+    /// Final-table deal: ICM and chip-chop amounts for each player
+    Chop {
+        /// Chip stacks, one per player (e.g. "4000,3000,2000,1000")
+        #[arg(short = 's', long, required = true, value_delimiter = ',')]
+        stacks: Vec<f64>,
+
+        /// Prizes still to be paid by finishing place (e.g. "500,300,200")
+        #[arg(short = 'p', long, required = true, value_delimiter = ',')]
+        payouts: Vec<f64>,
+
+        /// Amount taken off first prize and left to play for
+        #[arg(long = "play-for", default_value = "0")]
+        play_for: f64,
+    },
+
+    /// Simulate bankroll swings: risk of ruin and downswing percentiles
+    Variance {
+        /// Win rate in big blinds per 100 hands
+        #[arg(short = 'w', long, required = true, allow_negative_numbers = true)]
+        winrate: f64,
+
+        /// Standard deviation in big blinds per 100 hands
+        #[arg(short = 's', long, required = true)]
+        stddev: f64,
+
+        /// Number of hands per trajectory
+        #[arg(short = 'n', long, default_value = "100000")]
+        hands: u64,
+
+        /// Starting bankroll in buy-ins
+        #[arg(short = 'b', long, required = true)]
+        bankroll: f64,
+
+        /// Big blinds per buy-in
+        #[arg(long = "buy-in", default_value = "100")]
+        buy_in: f64,
+
+        /// Number of simulated trajectories
+        #[arg(short = 't', long, default_value = "2000")]
+        trials: usize,
+
+        /// Seed for a reproducible simulation
+        #[arg(long)]
+        seed: Option<u64>,
+    },
+
     /// Calculate pot odds
     PotOdds {
         /// Current pot size before your call (includes opponent action)
@@ -52,63 +447,436 @@ enum Commands {
         /// Amount you need to call
         #[arg(short = 'c', long = "call-amount", required = true)]
         call_amount: f64,
+
+        #[command(flatten)]
+        rake: RakeArgs,
     },
 }
 
+/// Arguments for the `equity` command.
+#[derive(Args)]
+struct EquityArgs {
+    /// Community cards string with 0/3/4/5 known cards
+    #[arg(short = 'b', long = "board")]
+    board: Option<String>,
+
+    /// Second board for double-board bomb pots (0/3/4/5 known cards, "" for none)
+    #[arg(long = "board2", conflicts_with = "exact")]
+    board2: Option<String>,
+
+    /// Hero hand as 1 or 2 known cards (e.g., "Ah" or "AhAd")
+    #[arg(short = 'H', long = "hero", required = true)]
+    hero: String,
+
+    /// One or more villains: unknown / partial / exact / range (e.g., "", "Kh", "KhKd", "TT+")
+    #[arg(short = 'V', long = "villain", num_args = 1..)]
+    villains: Vec<String>,
+
+    /// Total number of villains (fills missing villains as unknown hands)
+    #[arg(short = 'n', long = "villain-count")]
+    villain_count: Option<usize>,
+
+    /// Exposed or mucked cards removed from the deck (e.g., "Ah 7c")
+    #[arg(short = 'd', long = "dead", conflicts_with = "board2")]
+    dead: Option<String>,
+
+    /// Number of Monte Carlo iterations [default: 100000]
+    #[arg(short = 'i', long)]
+    iterations: Option<u32>,
+
+    /// Stop sampling after a wall-clock budget (e.g., 500ms, 2s); with -i, after
+    /// -i samples if that comes first
+    #[arg(short = 't', long = "time", value_parser = parse_duration, conflicts_with = "exact")]
+    time: Option<Duration>,
+
+    /// Force exact enumeration (fails if the state space exceeds --max-states)
+    #[arg(long, conflicts_with = "monte_carlo")]
+    exact: bool,
+
+    /// Force Monte Carlo sampling even when enumeration is affordable
+    #[arg(long = "monte-carlo")]
+    monte_carlo: bool,
+
+    /// State-space bound for --exact
+    #[arg(long = "max-states", default_value_t = DEFAULT_MAX_EXACT_STATES)]
+    max_states: usize,
+
+    /// Split Monte Carlo sampling over this many threads, at most one per core [default: 1]
+    #[arg(long = "threads", conflicts_with = "board2")]
+    threads: Option<NonZeroUsize>,
+
+    /// Also write the scenario and result as JSON to this file
+    #[arg(short = 'o', long = "output")]
+    output: Option<PathBuf>,
+
+    /// Reuse results from this equity cache file and store new ones in it
+    #[arg(long = "cache", conflicts_with = "board2")]
+    cache: Option<PathBuf>,
+
+    /// Print solver diagnostics (-v: state space and timing, -vv: mode decision)
+    #[arg(short = 'v', long = "verbose", action = ArgAction::Count)]
+    verbose: u8,
+}
+
+/// Rake options shared by the `pot-odds` and `ev` commands.
+#[derive(Args)]
+struct RakeArgs {
+    /// Rake taken from the final pot, in percent
+    #[arg(long = "rake-percent", default_value = "0")]
+    rake_percent: f64,
+
+    /// Maximum rake per pot, in the same units as the pot
+    #[arg(long = "rake-cap")]
+    rake_cap: Option<f64>,
+
+    /// Fixed rake taken from every pot instead of a percentage
+    #[arg(long = "rake-drop", conflicts_with_all = ["rake_percent", "rake_cap"])]
+    rake_drop: Option<f64>,
+}
+
+impl RakeArgs {
+    /// The rake model; no rake options means a time-charged game.
+    fn model(&self) -> Result<RakeModel, CliError> {
+        let model = match self.rake_drop {
+            Some(amount) => RakeModel::Drop { amount },
+            None if self.rake_percent > 0.0 => RakeModel::Percent {
+                percent: self.rake_percent,
+                cap: self.rake_cap,
+            },
+            None => RakeModel::TimeCharge,
+        };
+        model
+            .validate()
+            .map_err(|e| CliError::snap("reading rake options", e))?;
+        Ok(model)
+    }
+}
+
+/// Seat that moves all-in in the `shove` command.
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum ShovePosition {
+    /// Small blind, folded to, against the big blind
+    #[value(name = "sb", alias = "SB")]
+    Sb,
+}
+
+/// Chart built by the `chart` command.
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum CliChartKind {
+    /// First in: raise or fold
+    Open,
+    /// Facing an open: flat call
+    Call,
+    /// Facing an open: re-raise
+    #[value(name = "3bet")]
+    ThreeBet,
+}
+
+impl From<CliChartKind> for ChartKind {
+    fn from(kind: CliChartKind) -> Self {
+        match kind {
+            CliChartKind::Open => ChartKind::Open,
+            CliChartKind::Call => ChartKind::Call,
+            CliChartKind::ThreeBet => ChartKind::ThreeBet,
+        }
+    }
+}
+
+/// Hero's position for the `ev` realization estimate and `chart`.
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum CliPosition {
+    /// In position
+    Ip,
+    /// Out of position
+    Oop,
+}
+
+impl From<CliPosition> for Position {
+    fn from(position: CliPosition) -> Self {
+        match position {
+            CliPosition::Ip => Position::InPosition,
+            CliPosition::Oop => Position::OutOfPosition,
+        }
+    }
+}
+
+/// Street a draw is evaluated from.
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum OddsStreet {
+    Flop,
+    Turn,
+}
+
+impl OddsStreet {
+    /// Cards not visible to hero (52 − 2 hole cards − board).
+    fn unseen(self) -> usize {
+        match self {
+            OddsStreet::Flop => 47,
+            OddsStreet::Turn => 46,
+        }
+    }
+
+    /// Cards still to come before showdown.
+    fn draws(self) -> usize {
+        match self {
+            OddsStreet::Flop => 2,
+            OddsStreet::Turn => 1,
+        }
+    }
+}
+
 fn main() -> ExitCode {
     let cli = Cli::parse();
 
+    let config = match Config::load(cli.config.as_deref()) {
+        Ok(config) => config,
+        Err(err) => return err.report(cli.error_format.unwrap_or_default()),
+    };
+    let error_format = config.error_format(cli.error_format);
+    let mut style = config.card_style(cli.suit_style, cli.four_color);
+    style.four_color &= cards::color_enabled(config.color(cli.no_color));
+    cards::init(style);
+
     let result = match cli.command {
         Commands::Evaluate { hand } => run_evaluate_command(&hand),
-        Commands::Equity {
+        Commands::Equity(args) => {
+            let iterations = config.iterations(args.iterations);
+            let threads = config.threads(args.threads);
+            run_equity_command(args, iterations, threads)
+        }
+        Commands::Matrix {
+            players,
+            board,
+            iterations,
+        } => run_matrix_command(players, board, config.iterations(iterations)),
+        Commands::Range { range, list, grid } => run_range_command(&range, list, grid),
+        Commands::Odds {
+            outs,
+            percent,
+            street,
+        } => run_odds_command(outs, percent, street),
+        Commands::Deal {
+            players,
+            street,
+            seed,
+        } => run_deal_command(players, street, seed),
+        Commands::Decide {
+            players,
+            board,
+            pot,
+            bet,
+            iterations,
+        } => run_decide_command(players, board, pot, bet, config.iterations(iterations)),
+        Commands::Ev {
+            hero,
+            villain,
+            board,
+            pot,
+            bet,
+            stack,
+            fold_equity,
+            position,
+            rake,
+            iterations,
+        } => rake.model().and_then(|rake| {
+            run_ev_command(
+                hero,
+                villain,
+                board,
+                pot,
+                bet,
+                stack,
+                fold_equity,
+                position.map(Into::into),
+                &rake,
+                config.iterations(iterations),
+            )
+        }),
+        Commands::Pko {
+            hero,
+            villain,
             board,
+            pot,
+            call,
+            bounty,
+            starting_stack,
+            prize_buy_in,
+            cash_share,
+            head_value,
+            covers,
+            iterations,
+        } => {
+            let options = BountyOptions {
+                cash_share,
+                head_value,
+                ..BountyOptions::new(starting_stack, prize_buy_in)
+            };
+            run_pko_command(
+                &hero,
+                &villain,
+                board,
+                pot,
+                call,
+                bounty,
+                &options,
+                covers,
+                config.iterations(iterations),
+            )
+        }
+        Commands::Bench { iterations } => run_bench_command(iterations),
+        Commands::Compare {
             hero,
-            villains,
-            villain_count,
+            ranges,
+            board,
             iterations,
-        } => run_equity_command(board, hero, villains, villain_count, iterations),
+        } => run_compare_command(hero, ranges, board, config.iterations(iterations)),
+        Commands::Shove {
+            stack,
+            position: ShovePosition::Sb,
+            ante,
+            players,
+            grid,
+        } => run_shove_command(stack, ante, players, grid),
+        Commands::Chart {
+            kind,
+            stack,
+            position,
+            players_behind,
+            opener_percent,
+            grid,
+        } => {
+            let options = ChartOptions {
+                stack_bb: stack,
+                position: position.into(),
+                players_behind,
+                opener_percent,
+                ..ChartOptions::default()
+            };
+            run_chart_command(kind.into(), &options, grid)
+        }
+        Commands::Serve {
+            stdio: _,
+            iterations,
+            output,
+        } => serve::run_stdio(config.iterations(iterations), output.as_deref()),
+        Commands::Spr { stack, pot } => run_spr_command(stack, pot),
+        Commands::Sizing {
+            stack,
+            pot,
+            streets,
+        } => run_sizing_command(stack, pot, streets),
+        Commands::Strength { hero, board, range } => {
+            run_strength_command(&hero, &board, range.as_deref())
+        }
+        Commands::Matchups => run_matchups_command(),
+        Commands::Bubble { stacks, payouts } => run_bubble_command(&stacks, &payouts),
+        Commands::Chop {
+            stacks,
+            payouts,
+            play_for,
+        } => run_chop_command(&stacks, &payouts, play_for),
+        Commands::Variance {
+            winrate,
+            stddev,
+            hands,
+            bankroll,
+            buy_in,
+            trials,
+            seed,
+        } => {
+            if buy_in <= 0.0 {
+                Err(CliError::input(format!(
+                    "buy-in must be positive, got {}",
+                    buy_in
+                )))
+            } else {
+                run_variance_command(
+                    VarianceConfig {
+                        winrate,
+                        stddev,
+                        hands,
+                        bankroll: bankroll * buy_in,
+                        trials,
+                        seed,
+                    },
+                    buy_in,
+                )
+            }
+        }
         Commands::PotOdds {
             pot_size,
             call_amount,
-        } => run_pot_odds_command(pot_size, call_amount),
+            rake,
+        } => rake
+            .model()
+            .and_then(|rake| run_pot_odds_command(pot_size, call_amount, &rake)),
     };
 
     match result {
         Ok(()) => ExitCode::SUCCESS,
-        Err(msg) => {
-            eprintln!("Error: {msg}");
-            ExitCode::FAILURE
-        }
+        Err(err) => err.report(error_format),
     }
 }
 
-fn run_evaluate_command(hand: &str) -> Result<(), String> {
+fn run_evaluate_command(hand: &str) -> Result<(), CliError> {
     let cleaned: String = hand
         .chars()
         .filter(|c| !c.is_whitespace() && *c != ',')
         .collect();
 
-    let fh = FlatHand::new_from_str(&cleaned)
-        .map_err(|e| format!("parsing hand '{}': {:?}", hand, e))?;
+    let fh = FlatHand::new_from_str(&cleaned).map_err(|e| {
+        CliError::input(format!("parsing hand '{}': {:?}", hand, e)).with_hint(suggest::cards(hand))
+    })?;
 
     if fh.len() < 5 || fh.len() > 7 {
-        return Err(format!("hand must have 5-7 cards, got {}", fh.len()));
+        return Err(CliError::input(format!(
+            "hand must have 5-7 cards, got {}",
+            fh.len()
+        )));
     }
 
-    println!("Rank: {:?}", fh.rank());
+    let cards: Vec<Card> = fh.iter().copied().collect();
+    let best = best_hand(&cards).map_err(|e| CliError::snap("evaluating hand", e))?;
+    let mut unused: Vec<Card> = cards
+        .iter()
+        .copied()
+        .filter(|c| !best.cards.contains(c))
+        .collect();
+    unused.sort_by(|a, b| b.cmp(a));
+
+    println!("Hand:    {} ({})", best.category, best.describe());
+    println!("Best 5:  {}", cards::cards(&best.cards));
+    println!("Made:    {}", cards::cards(best.made()));
+    println!("Kickers: {}", cards::cards(best.kickers()));
+    if !unused.is_empty() {
+        println!("Unused:  {}", cards::cards(&unused));
+    }
     Ok(())
 }
 
-fn run_equity_command(
-    board: Option<String>,
-    hero: String,
-    villains: Vec<String>,
-    villain_count: Option<usize>,
-    iterations: u32,
-) -> Result<(), String> {
+fn run_equity_command(args: EquityArgs, iterations: u32, threads: usize) -> Result<(), CliError> {
+    let EquityArgs {
+        board,
+        board2,
+        hero,
+        villains,
+        villain_count,
+        dead,
+        iterations: sample_cap,
+        time,
+        exact,
+        monte_carlo,
+        max_states,
+        output,
+        cache,
+        verbose,
+        ..
+    } = args;
     let count = villain_count.unwrap_or(villains.len());
     if count == 0 {
-        return Err("provide at least one opponent via --villain or --villain-count".to_string());
+        return Err(CliError::input(
+            "provide at least one opponent via --villain or --villain-count".to_string(),
+        ));
     }
 
     let board_str = board.unwrap_or_default();
@@ -117,12 +885,124 @@ fn run_equity_command(
         villains_str.push("");
     }
 
-    let result = estimate_equity(&board_str, &hero, &villains_str, iterations as usize)
-        .map_err(|e| format!("calculating equity: {e}"))?;
+    let scenario = EquityScenario {
+        board: board_str.clone(),
+        board2: board2.clone(),
+        hero: hero.clone(),
+        villains: villains_str.iter().map(|v| v.to_string()).collect(),
+        dead: dead.clone(),
+        iterations,
+        time_ms: time.map(|t| u64::try_from(t.as_millis()).unwrap_or(u64::MAX)),
+        seed: None,
+    };
+
+    if board2.is_some() {
+        return run_double_board(&scenario, output.as_deref());
+    }
+
+    let options = EquityOptions {
+        mode: if exact {
+            Some(EquityEstimateMode::ExactEnumeration)
+        } else if monte_carlo {
+            Some(EquityEstimateMode::MonteCarlo)
+        } else {
+            None
+        },
+        max_exact_states: max_states,
+        dead_cards: parse_cards(dead.as_deref().unwrap_or_default()).map_err(|e| {
+            CliError::snap("parsing dead cards", e)
+                .with_hint(suggest::cards(dead.as_deref().unwrap_or_default()))
+        })?,
+        time_budget: time,
+        threads,
+        ..EquityOptions::new(iterations as usize)
+    };
+    let forced = options.mode.is_some();
+    let options = if time.is_some() && sample_cap.is_none() {
+        sample_until_deadline(&board_str, &hero, &villains_str, options).map_err(|e| {
+            CliError::snap("calculating equity", e).with_hint(equity_input_hint(
+                &board_str,
+                &hero,
+                &villains_str,
+            ))
+        })?
+    } else {
+        options
+    };
+
+    let mut cache = cache
+        .map(|path| {
+            EquityCache::open(&path)
+                .map_err(|e| CliError::io(format!("opening cache '{}': {e}", path.display())))
+                .map(|cache| (path, cache))
+        })
+        .transpose()?;
+
+    let start = Instant::now();
+    let result = match cache.as_mut() {
+        Some((_, cache)) => cache.estimate_equity(&board_str, &hero, &villains_str, &options),
+        None => estimate_equity_with_options(&board_str, &hero, &villains_str, &options),
+    }
+    .map_err(|e| {
+        CliError::snap("calculating equity", e).with_hint(equity_input_hint(
+            &board_str,
+            &hero,
+            &villains_str,
+        ))
+    })?;
+    let elapsed = start.elapsed();
+    if let Some((path, cache)) = cache.as_mut() {
+        cache
+            .save()
+            .map_err(|e| CliError::io(format!("writing cache '{}': {e}", path.display())))?;
+    }
 
     println!("Computation:");
     println!("  Mode: {}", result.mode);
     println!("  Samples: {}", result.samples);
+    if let Some((_, cache)) = &cache {
+        let hit = if cache.hits() > 0 { "hit" } else { "miss" };
+        println!("  Cache: {}", hit);
+    }
+    if verbose >= 1 {
+        let math = &result.math;
+        println!("  Time: {:.3} ms", elapsed.as_secs_f64() * 1000.0);
+        println!(
+            "  Assignment Combinations: {}",
+            math.assignment_combinations
+        );
+        println!(
+            "  Runout Combinations: {} (C({}, {}))",
+            math.runout_combinations, math.available_cards, math.runout_slots
+        );
+        match math.total_states {
+            Some(total) => println!("  Total States: {}", total),
+            None => println!("  Total States: overflow"),
+        }
+    }
+    if verbose >= 2 {
+        let reason = match (forced, result.math.total_states) {
+            (true, _) => "forced by flag".to_string(),
+            (false, total) => match total {
+                Some(total) if total > 0 && total <= iterations as usize => {
+                    format!("{} states fit the budget of {}", total, iterations)
+                }
+                Some(total) => match time {
+                    Some(budget) => format!(
+                        "{} states exceed the threshold of {}; sampling for {:?}",
+                        total, iterations, budget
+                    ),
+                    None => format!("{} states exceed the budget of {}", total, iterations),
+                },
+                None => "state space overflows".to_string(),
+            },
+        };
+        println!("  Decision: {} ({})", result.mode, reason);
+        println!(
+            "  Throughput: {:.0} samples/s",
+            result.samples as f64 / elapsed.as_secs_f64().max(f64::EPSILON)
+        );
+    }
     println!();
 
     println!("Equity Results:");
@@ -130,30 +1010,1242 @@ fn run_equity_command(
     for (i, eq) in result.equities[1..].iter().enumerate() {
         println!("  Villain {}: {:.2}%", i + 1, eq);
     }
+
+    if let Some(path) = output {
+        let outcome = EquityOutcome {
+            mode: result.mode.to_string(),
+            samples: result.samples,
+            equities: result.equities,
+            board_equities: None,
+        };
+        let record = Record {
+            scenario: &scenario,
+            result: &outcome,
+        };
+        write_record(&path, &record, false)?;
+    }
     Ok(())
 }
 
-fn run_pot_odds_command(pot_size: f64, call_amount: f64) -> Result<(), String> {
-    if pot_size <= 0.0 {
-        return Err(format!("pot size must be positive, got {}", pot_size));
-    }
-    if call_amount <= 0.0 {
-        return Err(format!("call amount must be positive, got {}", call_amount));
+/// Lifts the Monte Carlo sample cap for `--time` without `-i`, so only the
+/// time budget ends sampling. `options.iterations` still decides whether the
+/// spot is small enough to enumerate exactly, and exact spots are unchanged.
+fn sample_until_deadline(
+    board: &str,
+    hero: &str,
+    villains: &[&str],
+    options: EquityOptions,
+) -> Result<EquityOptions, SnapError> {
+    let (mode, _) = plan_equity(board, hero, villains, &options)?;
+    if mode == EquityEstimateMode::ExactEnumeration {
+        return Ok(options);
     }
+    Ok(EquityOptions {
+        iterations: usize::MAX,
+        mode: Some(EquityEstimateMode::MonteCarlo),
+        ..options
+    })
+}
 
-    let total_pot_after_call = pot_size + call_amount;
-    let pot_odds_pct = (call_amount / total_pot_after_call) * 100.0;
+/// Correction hint for the first mistyped board or hand among equity inputs.
+fn equity_input_hint(board: &str, hero: &str, villains: &[&str]) -> Option<String> {
+    suggest::first(
+        [suggest::cards(board), suggest::hand(hero)]
+            .into_iter()
+            .chain(villains.iter().map(|v| suggest::hand(v))),
+    )
+}
 
-    println!("Pot Odds Calculation:");
-    println!("  Pot Size (Before Call): {:.0}", pot_size);
-    println!("  Amount to Call: {:.0}", call_amount);
-    println!("  Total Pot After Call: {:.0}", total_pot_after_call);
+/// Equity for a double-board bomb pot: per-board equities and overall pot share.
+fn run_double_board(scenario: &EquityScenario, output: Option<&Path>) -> Result<(), CliError> {
+    let boards = [
+        scenario.board.as_str(),
+        scenario.board2.as_deref().unwrap_or_default(),
+    ];
+    let villains: Vec<&str> = scenario.villains.iter().map(String::as_str).collect();
+    let result = estimate_multi_board_equity(
+        &boards,
+        &scenario.hero,
+        &villains,
+        scenario.iterations as usize,
+    )
+    .map_err(|e| {
+        CliError::snap("calculating equity", e).with_hint(suggest::first([
+            suggest::cards(boards[0]),
+            suggest::cards(boards[1]),
+            equity_input_hint("", &scenario.hero, &villains),
+        ]))
+    })?;
+
+    println!("Computation:");
+    println!("  Mode: {}", EquityEstimateMode::MonteCarlo);
+    println!("  Samples: {}", result.samples);
     println!();
-    println!("  Pot Odds: {:.2}%", pot_odds_pct);
+
+    println!(
+        "{:<12} {:>9} {:>9} {:>10}",
+        "Player", "Board 1", "Board 2", "Pot Share"
+    );
+    for (i, share) in result.pot_share.iter().enumerate() {
+        let name = if i == 0 {
+            "Hero".to_string()
+        } else {
+            format!("Villain {}", i)
+        };
+        println!(
+            "{:<12} {:>8.2}% {:>8.2}% {:>9.2}%",
+            name, result.board_equities[0][i], result.board_equities[1][i], share
+        );
+    }
+
+    if let Some(path) = output {
+        let outcome = EquityOutcome {
+            mode: EquityEstimateMode::MonteCarlo.to_string(),
+            samples: result.samples,
+            equities: result.pot_share,
+            board_equities: Some(result.board_equities),
+        };
+        let record = Record {
+            scenario,
+            result: &outcome,
+        };
+        write_record(path, &record, false)?;
+    }
+    Ok(())
+}
+
+fn run_matrix_command(
+    players: Vec<String>,
+    board: Option<String>,
+    iterations: u32,
+) -> Result<(), CliError> {
+    if players.len() < 2 {
+        return Err(CliError::input(
+            "provide at least two hands or ranges via --player".to_string(),
+        ));
+    }
+
+    let board_str = board.unwrap_or_default();
+    let n = players.len();
+    // cells[row][col] is row's equity against col; the diagonal stays empty
+    let mut cells: Vec<Vec<Option<f64>>> = vec![vec![None; n]; n];
+    for row in 0..n {
+        for col in (row + 1)..n {
+            let result = estimate_range_equity(
+                &board_str,
+                &[players[row].as_str(), players[col].as_str()],
+                iterations as usize,
+            )
+            .map_err(|e| {
+                CliError::snap(&format!("{} vs {}", players[row], players[col]), e).with_hint(
+                    suggest::first([
+                        suggest::cards(&board_str),
+                        suggest::hand(&players[row]),
+                        suggest::hand(&players[col]),
+                    ]),
+                )
+            })?;
+            cells[row][col] = Some(result.equities[0]);
+            cells[col][row] = Some(result.equities[1]);
+        }
+    }
+
+    let width = players.iter().map(|p| p.len()).max().unwrap_or(0).max(7);
+    println!("Equity Matrix (row vs column):");
+    print!("  {:<width$}", "");
+    for p in &players {
+        print!(" {:>width$}", p);
+    }
     println!();
+    for (row, p) in players.iter().enumerate() {
+        print!("  {:<width$}", p);
+        for cell in &cells[row] {
+            match cell {
+                Some(eq) => print!(" {:>width$}", format!("{:.2}%", eq)),
+                None => print!(" {:>width$}", "-"),
+            }
+        }
+        println!();
+    }
+    Ok(())
+}
+
+fn run_range_command(range: &str, list: bool, grid: bool) -> Result<(), CliError> {
+    let combos = expand_range(range)
+        .map_err(|e| CliError::snap("parsing range", e).with_hint(suggest::hand(range)))?;
+
+    println!("Range: {}", range.trim());
+    println!("  Combos: {}", combos.len());
     println!(
-        "  You need at least {:.2}% equity to break even",
-        pot_odds_pct
+        "  Share of all hands: {:.2}%",
+        combos.len() as f64 / TOTAL_COMBOS as f64 * 100.0
     );
+
+    if list {
+        println!();
+        println!("Combos:");
+        for line in combos.chunks(8) {
+            let items: Vec<String> = line
+                .iter()
+                .map(|c| format!("{}{}", cards::card(c[0]), cards::card(c[1])))
+                .collect();
+            println!("  {}", items.join(" "));
+        }
+    }
+
+    if grid {
+        println!();
+        println!("Grid (* = partially included):");
+        print_range_grid(&combos);
+    }
     Ok(())
 }
+
+/// Prints a 13×13 grid of `combos`, marking partially included classes with `*`.
+fn print_range_grid(combos: &[[Card; 2]]) {
+    for row in range_grid(combos) {
+        let cells: Vec<String> = row
+            .iter()
+            .map(|cell| {
+                let label = cell.class.to_string();
+                if cell.combos == cell.total {
+                    format!("{:<4}", label)
+                } else if cell.combos > 0 {
+                    format!("{:<4}", format!("{}*", label))
+                } else {
+                    format!("{:<4}", ".")
+                }
+            })
+            .collect();
+        println!("  {}", cells.join(" ").trim_end());
+    }
+}
+
+/// Prints starting-hand classes one grid row per line, or as a 13×13 grid.
+fn print_classes(classes: &[HandClass], grid: bool) {
+    if grid {
+        let combos: Vec<[Card; 2]> = classes.iter().flat_map(|c| c.cards()).collect();
+        print_range_grid(&combos);
+        return;
+    }
+    for row in 0..13 {
+        let labels: Vec<String> = classes
+            .iter()
+            .filter(|c| c.grid_position().0 == row)
+            .map(|c| c.to_string())
+            .collect();
+        if !labels.is_empty() {
+            println!("  {}", labels.join(" "));
+        }
+    }
+}
+
+fn run_odds_command(
+    outs: Option<usize>,
+    percent: Option<f64>,
+    street: OddsStreet,
+) -> Result<(), CliError> {
+    let unseen = street.unseen();
+    let draws = street.draws();
+
+    if let Some(pct) = percent {
+        if !(0.0..=100.0).contains(&pct) {
+            return Err(CliError::input(format!(
+                "percent must be between 0 and 100, got {}",
+                pct
+            )));
+        }
+        let needed = percent_to_outs(pct, unseen, draws)
+            .ok_or_else(|| format!("{:.2}% is not reachable", pct))?;
+        println!("Outs Needed:");
+        println!("  Target: {:.2}%", pct);
+        println!("  Outs: {}", needed);
+        println!(
+            "  Exact: {:.2}% (rule of thumb {:.0}%)",
+            outs_to_percent(needed, unseen, draws),
+            outs_rule_of_thumb(needed, draws)
+        );
+        return Ok(());
+    }
+
+    let Some(outs) = outs else {
+        println!("Odds Chart (exact %, odds against):");
+        println!(
+            "  {:>4}  {:>16}  {:>16}  {:>16}",
+            "Outs", "Flop→Turn", "Flop→River", "Turn→River"
+        );
+        for outs in 1..=20 {
+            let cells = [(47, 1), (47, 2), (46, 1)].map(|(unseen, draws)| {
+                let pct = outs_to_percent(outs, unseen, draws);
+                format!("{:.1}% {:.1}:1", pct, percent_to_odds_against(pct))
+            });
+            println!(
+                "  {:>4}  {:>16}  {:>16}  {:>16}",
+                outs, cells[0], cells[1], cells[2]
+            );
+        }
+        return Ok(());
+    };
+
+    if outs > unseen {
+        return Err(CliError::input(format!(
+            "outs must be at most {}, got {}",
+            unseen, outs
+        )));
+    }
+
+    println!("Outs Conversion:");
+    println!("  Outs: {}", outs);
+    println!("  Unseen Cards: {}", unseen);
+    let mut horizons = vec![("Next card", 1)];
+    if draws == 2 {
+        horizons.push(("By the river", 2));
+    }
+    for (label, n) in horizons {
+        let pct = outs_to_percent(outs, unseen, n);
+        println!();
+        println!("  {}:", label);
+        println!("    Exact: {:.2}%", pct);
+        println!("    Rule of {}: {:.0}%", 2 * n, outs_rule_of_thumb(outs, n));
+        println!("    Odds Against: {:.2} : 1", percent_to_odds_against(pct));
+    }
+    Ok(())
+}
+
+fn run_deal_command(players: usize, street: Street, seed: Option<u64>) -> Result<(), CliError> {
+    let hand = deal_random_hand(players, street, seed).map_err(|e| CliError::snap("dealing", e))?;
+
+    println!("Board: {}", cards::cards(&hand.board));
+    println!();
+    println!("Players:");
+    for (i, hole) in hand.hole_cards.iter().enumerate() {
+        let marker = if hand.winners.contains(&i) { " *" } else { "" };
+        println!(
+            "  Seat {}: {}  {}{}",
+            i + 1,
+            cards::cards(hole),
+            HandCategory::from_rank(&hand.ranks[i]),
+            marker
+        );
+    }
+    println!();
+    let seats: Vec<String> = hand
+        .winners
+        .iter()
+        .map(|i| format!("Seat {}", i + 1))
+        .collect();
+    if hand.winners.len() > 1 {
+        println!("Split pot: {}", seats.join(", "));
+    } else {
+        println!("Winner: {}", seats.join(", "));
+    }
+    Ok(())
+}
+
+/// Parses an amount in big blinds such as `12`, `12bb`, or `0.125`.
+fn parse_big_blinds(s: &str) -> Result<f64, String> {
+    let s = s.trim();
+    let number = s
+        .strip_suffix("bb")
+        .or_else(|| s.strip_suffix("BB"))
+        .unwrap_or(s);
+    number
+        .trim()
+        .parse()
+        .map_err(|_| format!("'{s}' is not an amount in big blinds, e.g. 12bb"))
+}
+
+/// Parses a wall-clock budget such as `500ms`, `2s`, or `1.5s`.
+fn parse_duration(s: &str) -> Result<Duration, String> {
+    let s = s.trim();
+    let (number, scale) = if let Some(ms) = s.strip_suffix("ms") {
+        (ms, 0.001)
+    } else if let Some(secs) = s.strip_suffix('s') {
+        (secs, 1.0)
+    } else {
+        return Err(format!("'{s}' needs a unit, e.g. 500ms or 2s"));
+    };
+    let value: f64 = number
+        .trim()
+        .parse()
+        .map_err(|_| format!("'{s}' is not a duration, e.g. 500ms or 2s"))?;
+    if !value.is_finite() || value <= 0.0 {
+        return Err(format!("'{s}' must be a positive duration"));
+    }
+    Ok(Duration::from_secs_f64(value * scale))
+}
+
+fn run_decide_command(
+    players: Vec<String>,
+    board: Option<String>,
+    pot: f64,
+    bet: f64,
+    iterations: u32,
+) -> Result<(), CliError> {
+    if players.len() < 2 {
+        return Err(CliError::input(
+            "provide hero and at least one villain via --player".to_string(),
+        ));
+    }
+    if pot <= 0.0 {
+        return Err(CliError::input(format!(
+            "pot must be positive, got {}",
+            pot
+        )));
+    }
+    if bet <= 0.0 {
+        return Err(CliError::input(format!(
+            "bet must be positive, got {}",
+            bet
+        )));
+    }
+
+    let board_str = board.unwrap_or_default();
+    let villains: Vec<&str> = players[1..].iter().map(|s| s.as_str()).collect();
+    let result =
+        estimate_equity(&board_str, &players[0], &villains, iterations as usize).map_err(|e| {
+            CliError::snap("calculating equity", e).with_hint(equity_input_hint(
+                &board_str,
+                &players[0],
+                &villains,
+            ))
+        })?;
+
+    let equity = result.equities[0];
+    let required = pot_odds_percent(pot + bet, bet);
+    let margin = equity - required;
+    let ev = call_ev(pot + bet, bet, equity);
+
+    println!("Decision:");
+    println!(
+        "  Hero Equity: {:.2}% ({}, {} samples)",
+        equity, result.mode, result.samples
+    );
+    println!("  Required Equity: {:.2}%", required);
+    println!("  Margin: {:+.2}%", margin);
+    println!("  Call EV: {:+.2} chips", ev);
+    println!();
+    println!("  Verdict: {}", if ev >= 0.0 { "CALL" } else { "FOLD" });
+    Ok(())
+}
+
+#[allow(clippy::too_many_arguments)]
+fn run_ev_command(
+    hero: String,
+    villain: String,
+    board: Option<String>,
+    pot: f64,
+    bet: f64,
+    stack: f64,
+    fold_equity: f64,
+    position: Option<Position>,
+    rake: &RakeModel,
+    iterations: u32,
+) -> Result<(), CliError> {
+    if pot <= 0.0 {
+        return Err(CliError::input(format!(
+            "pot must be positive, got {}",
+            pot
+        )));
+    }
+    if bet < 0.0 {
+        return Err(CliError::input(format!(
+            "bet must not be negative, got {}",
+            bet
+        )));
+    }
+    if stack <= 0.0 {
+        return Err(CliError::input(format!(
+            "stack must be positive, got {}",
+            stack
+        )));
+    }
+    if !(0.0..=100.0).contains(&fold_equity) {
+        return Err(CliError::input(format!(
+            "fold equity must be between 0 and 100, got {}",
+            fold_equity
+        )));
+    }
+
+    let hero_class = match position {
+        Some(_) => match parse_cards(&hero).as_deref() {
+            Ok(&[a, b]) => Some(HandClass::from_cards(a, b)),
+            _ => {
+                return Err(CliError::input(format!(
+                    "--position needs hero's two hole cards, got '{}'",
+                    hero
+                )))
+            }
+        },
+        None => None,
+    };
+
+    let board_str = board.unwrap_or_default();
+    let result = estimate_equity(&board_str, &hero, &[villain.as_str()], iterations as usize)
+        .map_err(|e| {
+            CliError::snap("calculating equity", e).with_hint(equity_input_hint(
+                &board_str,
+                &hero,
+                &[villain.as_str()],
+            ))
+        })?;
+    let equity = result.equities[0];
+    let ev = decision_ev_with_rake(pot, bet, stack, equity, fold_equity, rake)
+        .map_err(|e| CliError::snap("computing EV", e))?;
+
+    // Only the call leaves chips behind to realize equity with
+    let call_amount = bet.min(stack);
+    let realized = position.zip(hero_class).map(|(position, class)| {
+        let spr = stack_to_pot_ratio(stack - call_amount, pot + 2.0 * call_amount);
+        let percent =
+            RealizationModel::default().realized_equity_percent(equity, class, position, spr);
+        (position, spr, percent)
+    });
+    let call = match realized {
+        Some((_, _, percent)) if bet > 0.0 => {
+            Some(rake.call_ev(pot + call_amount, call_amount, percent))
+        }
+        _ => ev.call,
+    };
+
+    println!("Expected Value (chips, relative to folding):");
+    println!(
+        "  Hero Equity When Called: {:.2}% ({}, {} samples)",
+        equity, result.mode, result.samples
+    );
+    if let Some((position, spr, percent)) = realized {
+        println!(
+            "  Realized When Calling: {:.2}% ({}, SPR {:.2})",
+            percent, position, spr
+        );
+    }
+    if *rake != RakeModel::TimeCharge {
+        println!("  Rake: {}", rake);
+    }
+    println!();
+    println!("  Fold:  {:+.2}", 0.0);
+    if let Some(call) = call {
+        println!("  Call:  {:+.2}", call);
+    }
+    println!("  Shove: {:+.2}", ev.shove);
+    if let Some(break_even) = ev.break_even_fold_percent {
+        println!();
+        println!("  Shove breaks even at {:.2}% fold equity", break_even);
+    }
+    Ok(())
+}
+
+#[allow(clippy::too_many_arguments)]
+fn run_pko_command(
+    hero: &str,
+    villain: &str,
+    board: Option<String>,
+    pot: f64,
+    call: f64,
+    bounty: f64,
+    options: &BountyOptions,
+    covers: bool,
+    iterations: u32,
+) -> Result<(), CliError> {
+    if call <= 0.0 || pot < call {
+        return Err(CliError::input(format!(
+            "call must be positive and no more than the pot, got call {} into {}",
+            call, pot
+        )));
+    }
+    let bounty_chips = options
+        .bounty_chips(bounty)
+        .map_err(|e| CliError::snap("valuing the bounty", e))?;
+
+    let board_str = board.unwrap_or_default();
+    let result =
+        estimate_equity(&board_str, hero, &[villain], iterations as usize).map_err(|e| {
+            CliError::snap("calculating equity", e).with_hint(equity_input_hint(
+                &board_str,
+                hero,
+                &[villain],
+            ))
+        })?;
+    let equity = result.equities[0];
+
+    println!("Knockout Call:");
+    println!(
+        "  Hero Equity: {:.2}% ({}, {} samples)",
+        equity, result.mode, result.samples
+    );
+    println!(
+        "  Bounty: {} = {:.0} chips{}",
+        bounty,
+        bounty_chips,
+        if covers {
+            ""
+        } else {
+            " (not covered, can't win it)"
+        }
+    );
+    println!();
+    println!(
+        "  Required Equity: {:.2}% without bounty, {:.2}% with",
+        pot_odds_percent(pot, call),
+        bounty_call_equity_percent(pot, call, bounty_chips, covers)
+    );
+    println!(
+        "  Call EV: {:+.0} chips",
+        bounty_call_ev(pot, call, equity, bounty_chips, covers)
+    );
+    Ok(())
+}
+
+/// Benchmark scenario: `(name, board, hero, villains)`.
+const BENCH_SCENARIOS: &[(&str, &str, &str, &[&str])] = &[
+    ("Heads-up preflop", "", "AhAd", &["KhKd"]),
+    ("Heads-up vs random", "", "AhKh", &[""]),
+    ("3-way preflop ranges", "", "AhKh", &["TT+", "AQs+"]),
+    ("4-way range flop", "Ts9s2d", "AhKh", &["TT+", "AQs+", ""]),
+    ("Heads-up turn range", "2h5h9cTd", "AhKh", &["22+"]),
+];
+
+fn run_bench_command(iterations: u32) -> Result<(), CliError> {
+    let budget = iterations as usize;
+
+    // Raw hand evaluation over a fixed set of seeded 7-card hands
+    let mut hands: Vec<Vec<Card>> = Vec::with_capacity(1024);
+    for seed in 0..1024u64 {
+        let dealt = deal_random_hand(2, Street::River, Some(seed))
+            .map_err(|e| CliError::snap("dealing", e))?;
+        let mut cards = dealt.hole_cards[0].to_vec();
+        cards.extend_from_slice(&dealt.board);
+        hands.push(cards);
+    }
+    let eval_count = 2_000_000usize;
+    let start = Instant::now();
+    let mut checksum = 0usize;
+    for i in 0..eval_count {
+        if let rs_poker::core::Rank::OnePair(_) = hands[i % hands.len()].rank() {
+            checksum += 1;
+        }
+    }
+    let elapsed = start.elapsed().as_secs_f64();
+    println!("Hand Evaluation:");
+    println!(
+        "  {} evaluations in {:.3}s ({:.1}M evals/s, checksum {})",
+        eval_count,
+        elapsed,
+        eval_count as f64 / elapsed / 1e6,
+        checksum
+    );
+    println!();
+
+    println!("Equity Scenarios:");
+    println!(
+        "  {:<24} {:>12} {:>10} {:>9} {:>14} {:>14}",
+        "Scenario", "Mode", "Samples", "Time", "Samples/s", "Evals/s"
+    );
+    for (name, board, hero, villains) in BENCH_SCENARIOS {
+        let start = Instant::now();
+        let result =
+            estimate_equity(board, hero, villains, budget).map_err(|e| CliError::snap(name, e))?;
+        let elapsed = start.elapsed().as_secs_f64();
+        let evals = result.samples * (villains.len() + 1);
+        println!(
+            "  {:<24} {:>12} {:>10} {:>8.3}s {:>14.0} {:>14.0}",
+            name,
+            result.mode.to_string(),
+            result.samples,
+            elapsed,
+            result.samples as f64 / elapsed,
+            evals as f64 / elapsed
+        );
+    }
+    println!();
+
+    // Exact vs Monte Carlo on the same spot: a flop with C(45, 2) = 990 runouts.
+    // A budget of 989 is one short of exhaustive, forcing Monte Carlo.
+    println!("Exact vs Monte Carlo (AhKh vs QsQc on 2h5h9c):");
+    for budget in [990usize, 989] {
+        let start = Instant::now();
+        let result = estimate_equity("2h5h9c", "AhKh", &["QsQc"], budget)
+            .map_err(|e| CliError::snap("exact vs MC", e))?;
+        let elapsed = start.elapsed().as_secs_f64();
+        println!(
+            "  {:<12} hero {:>6.2}%  {:>6} samples  {:.4}s  {:.0} samples/s",
+            result.mode.to_string(),
+            result.equities[0],
+            result.samples,
+            elapsed,
+            result.samples as f64 / elapsed
+        );
+    }
+    Ok(())
+}
+
+fn run_compare_command(
+    hero: String,
+    ranges: Vec<String>,
+    board: Option<String>,
+    iterations: u32,
+) -> Result<(), CliError> {
+    let board_str = board.unwrap_or_default();
+    let width = ranges.iter().map(|r| r.len()).max().unwrap_or(0).max(12);
+
+    println!("Hero {} vs each range:", hero.trim());
+    println!(
+        "  {:<width$} {:>7} {:>9} {:>12} {:>10}",
+        "Range", "Combos", "Hero", "Mode", "Samples"
+    );
+    for range in &ranges {
+        let combos = expand_range(range).map_err(|e| {
+            CliError::snap(&format!("parsing range '{range}'"), e).with_hint(suggest::hand(range))
+        })?;
+        let result = estimate_equity(&board_str, &hero, &[range.as_str()], iterations as usize)
+            .map_err(|e| {
+                CliError::snap(&format!("vs '{range}'"), e).with_hint(equity_input_hint(
+                    &board_str,
+                    &hero,
+                    &[],
+                ))
+            })?;
+        println!(
+            "  {:<width$} {:>7} {:>8.2}% {:>12} {:>10}",
+            range,
+            combos.len(),
+            result.equities[0],
+            result.mode.to_string(),
+            result.samples
+        );
+    }
+    Ok(())
+}
+
+fn run_shove_command(stack: f64, ante: f64, players: usize, grid: bool) -> Result<(), CliError> {
+    let solution = solve_blind_vs_blind(stack, ante, players)
+        .map_err(|e| CliError::snap("solving push/fold", e))?;
+
+    println!(
+        "Push/fold: SB vs BB, {} bb effective, ante {} bb",
+        stack, ante
+    );
+    if solution.dead_bb > 0.0 {
+        println!(
+            "  Folded to SB at {} players: {:.2} bb of dead antes",
+            players, solution.dead_bb
+        );
+    }
+    println!();
+
+    let ranges = [
+        ("SB shove", solution.shove_range(), solution.shove_percent()),
+        ("BB call", solution.call_range(), solution.call_percent()),
+    ];
+    for (name, classes, percent) in ranges {
+        println!("{} ({:.1}% of hands):", name, percent);
+        print_classes(&classes, grid);
+        println!();
+    }
+    Ok(())
+}
+
+fn run_chart_command(kind: ChartKind, options: &ChartOptions, grid: bool) -> Result<(), CliError> {
+    let chart = preflop_chart(kind, options).map_err(|e| CliError::snap("building chart", e))?;
+
+    println!(
+        "Preflop {} chart: {} bb, {}",
+        kind, options.stack_bb, options.position
+    );
+    match kind {
+        ChartKind::Open => println!(
+            "  Open to {} bb with {} behind, each defending {}% (3-betting {}%)",
+            options.open_bb,
+            options.players_behind,
+            options.defend_percent,
+            options.three_bet_percent
+        ),
+        ChartKind::Call | ChartKind::ThreeBet => println!(
+            "  Facing a {} bb open from {}%; 3-bet to {} bb, opener continues {}% (shoves {}%)",
+            options.open_bb,
+            options.opener_percent,
+            options.three_bet_bb,
+            options.continue_percent,
+            options.four_bet_percent
+        ),
+    }
+    println!();
+    println!("{} ({:.1}% of hands):", kind, chart.percent());
+    print_classes(&chart.range(), grid);
+    Ok(())
+}
+
+/// SPR commitment zones: upper bound, label, and hands worth stacking off with.
+const SPR_ZONES: [(f64, &str, &str); 3] = [
+    (4.0, "low", "top pair / overpair and better"),
+    (13.0, "medium", "two pair, sets, strong draws"),
+    (f64::INFINITY, "high", "sets, straights, flushes and better"),
+];
+
+fn run_spr_command(stack: f64, pot: f64) -> Result<(), CliError> {
+    if pot <= 0.0 {
+        return Err(CliError::input(format!(
+            "pot must be positive, got {}",
+            pot
+        )));
+    }
+    if stack <= 0.0 {
+        return Err(CliError::input(format!(
+            "stack must be positive, got {}",
+            stack
+        )));
+    }
+
+    let spr = stack_to_pot_ratio(stack, pot);
+    let zone = SPR_ZONES
+        .iter()
+        .position(|(limit, _, _)| spr <= *limit)
+        .unwrap_or(SPR_ZONES.len() - 1);
+
+    println!("Stack-to-Pot Ratio:");
+    println!("  Stack: {:.0}", stack);
+    println!("  Pot: {:.0}", pot);
+    println!("  SPR: {:.2} ({})", spr, SPR_ZONES[zone].1);
+    println!();
+
+    println!("Commitment Zones:");
+    let mut lower = 0.0;
+    for (i, (limit, name, hands)) in SPR_ZONES.iter().enumerate() {
+        let marker = if i == zone { ">" } else { " " };
+        let bounds = if limit.is_finite() {
+            format!("{:>4.0} - {:<4.0}", lower, limit)
+        } else {
+            format!("{:>4.0}+      ", lower)
+        };
+        println!("{} {} {:<7} {}", marker, bounds, name, hands);
+        lower = *limit;
+    }
+    println!();
+
+    println!(
+        "  Stacking off needs {:.2}% equity (calling or getting called all-in)",
+        stack_off_equity_percent(spr)
+    );
+    let geometric: Vec<String> = (1..=3)
+        .map(|streets| {
+            format!(
+                "{} street{} {:.0}%",
+                streets,
+                if streets == 1 { "" } else { "s" },
+                geometric_bet_fraction(spr, streets) * 100.0
+            )
+        })
+        .collect();
+    println!(
+        "  Geometric bet to get it in (pot %): {}",
+        geometric.join(", ")
+    );
+    Ok(())
+}
+
+/// Pot fractions listed by `sizing`.
+const SIZING_FRACTIONS: [f64; 6] = [0.25, 0.33, 0.5, 0.75, 1.0, 1.5];
+
+fn run_sizing_command(stack: f64, pot: f64, streets: u32) -> Result<(), CliError> {
+    let plan =
+        geometric_plan(pot, stack, streets).map_err(|e| CliError::snap("planning bets", e))?;
+
+    println!("Bet Sizes (pot {:.0}, stack {:.0}):", pot, stack);
+    println!("  {:<6} {:>9} {:>18}", "Size", "Bet", "Stack Off vs Jam");
+    for fraction in SIZING_FRACTIONS {
+        let bet = pot_fraction_bet(pot, fraction);
+        if bet >= stack {
+            println!("  {:>4.0}% {:>9} {:>18}", fraction * 100.0, "all-in", "-");
+            break;
+        }
+        println!(
+            "  {:>4.0}% {:>9.1} {:>17.1}%",
+            fraction * 100.0,
+            bet,
+            stack_off_after_bet_percent(pot, bet, stack)
+        );
+    }
+    println!();
+
+    println!(
+        "Geometric Sizing ({} street{}, {:.0}% pot each):",
+        streets,
+        if streets == 1 { "" } else { "s" },
+        geometric_bet_fraction(stack / pot, streets) * 100.0
+    );
+    println!(
+        "  {:<7} {:>9} {:>9} {:>9}",
+        "Street", "Pot", "Bet", "Behind"
+    );
+    for (i, street) in plan.iter().enumerate() {
+        println!(
+            "  {:<7} {:>9.1} {:>9.1} {:>9.1}",
+            i + 1,
+            street.pot,
+            street.bet,
+            street.stack_behind
+        );
+    }
+    Ok(())
+}
+
+/// Next cards listed at each end of the ranking in `strength`.
+const STRENGTH_EXTREMES: usize = 5;
+
+fn run_strength_command(hero: &str, board: &str, range: Option<&str>) -> Result<(), CliError> {
+    let hero_cards = parse_cards(hero)
+        .map_err(|e| CliError::snap("parsing hero", e).with_hint(suggest::cards(hero)))?;
+    let board_cards = parse_cards(board)
+        .map_err(|e| CliError::snap("parsing board", e).with_hint(suggest::cards(board)))?;
+    let current =
+        hand_strength(&hero_cards, &board_cards).map_err(|e| CliError::snap("ranking hand", e))?;
+    let all_cards: Vec<Card> = hero_cards.iter().chain(&board_cards).copied().collect();
+    let made = best_hand(&all_cards).map_err(|e| CliError::snap("evaluating hand", e))?;
+
+    println!("Hand Strength:");
+    println!(
+        "  Hero: {}  Board: {}",
+        cards::cards(&hero_cards),
+        cards::cards(&board_cards)
+    );
+    println!("  Made Hand: {}", made.describe());
+    println!(
+        "  Ahead of {:.1}% of {} combos ({} ahead, {} tied, {} behind)",
+        current.percentile(),
+        current.combos(),
+        current.ahead,
+        current.tied,
+        current.behind
+    );
+
+    if let Some(range) = range {
+        let potential = hand_potential(&hero_cards, &board_cards, range)
+            .map_err(|e| CliError::snap("parsing villain range", e))?;
+        println!();
+        println!("Against {}:", range);
+        println!("  Hand Strength: {:.1}%", potential.hand_strength * 100.0);
+        if board_cards.len() < 5 {
+            println!(
+                "  Potential: {:.1}% positive, {:.1}% negative",
+                potential.positive_potential * 100.0,
+                potential.negative_potential * 100.0
+            );
+        }
+        println!(
+            "  EHS: {:.1}%  EHS²: {:.1}%",
+            potential.ehs() * 100.0,
+            potential.ehs2 * 100.0
+        );
+    }
+
+    if board_cards.len() == 5 {
+        return Ok(());
+    }
+    let next = next_card_strengths(&hero_cards, &board_cards)
+        .map_err(|e| CliError::snap("ranking next cards", e))?;
+    let street = if board_cards.len() == 3 {
+        "Turn"
+    } else {
+        "River"
+    };
+    let average = next.iter().map(|(_, s)| s.percentile()).sum::<f64>() / next.len() as f64;
+    let better = next
+        .iter()
+        .filter(|(_, s)| s.percentile() > current.percentile())
+        .count();
+
+    println!();
+    println!("On the {}:", street);
+    println!(
+        "  Average: {:.1}% ({:+.1} points)",
+        average,
+        average - current.percentile()
+    );
+    println!(
+        "  Improves on {} of {} cards, worsens on {}",
+        better,
+        next.len(),
+        next.iter()
+            .filter(|(_, s)| s.percentile() < current.percentile())
+            .count()
+    );
+
+    let mut ranked: Vec<&(Card, HandStrength)> = next.iter().collect();
+    ranked.sort_by(|a, b| b.1.percentile().total_cmp(&a.1.percentile()));
+    let list = |cards: &[&(Card, HandStrength)]| {
+        cards
+            .iter()
+            .map(|(c, s)| format!("{} {:.0}%", cards::card(*c), s.percentile()))
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+    let shown = STRENGTH_EXTREMES.min(ranked.len());
+    println!("  Best:  {}", list(&ranked[..shown]));
+    println!("  Worst: {}", list(&ranked[ranked.len() - shown..]));
+    Ok(())
+}
+
+/// Textbook preflop confrontations: (description, first hand, second hand).
+const PREFLOP_MATCHUPS: [(&str, &str, &str); 12] = [
+    ("Overpair vs underpair", "KsKh", "QdQc"),
+    ("Pair vs dominated hand", "AsAh", "AdKc"),
+    ("Pair vs two overcards", "QsQh", "AdKc"),
+    ("Small pair vs two overcards", "2s2h", "AdKc"),
+    ("Pair vs overcard and undercard", "TsTh", "Ad9c"),
+    ("Overpair vs suited connectors", "AsAh", "7d6d"),
+    ("Two overcards vs two undercards", "AsKh", "8d7c"),
+    ("Interleaved high cards", "AsJh", "KdTc"),
+    ("Dominated kicker, shared ace", "AsKh", "AdQc"),
+    ("Dominated kicker, shared king", "AsKh", "KdQc"),
+    ("Suited vs offsuit, same ranks", "AsKs", "AhKd"),
+    ("Suited connectors vs small pair", "JhTh", "2s2c"),
+];
+
+fn run_matchups_command() -> Result<(), CliError> {
+    let options = EquityOptions {
+        mode: Some(EquityEstimateMode::ExactEnumeration),
+        ..EquityOptions::new(0)
+    };
+
+    println!("Preflop All-in Matchups (exact enumeration):");
+    println!(
+        "  {:<34} {:<5} {:>7}   {:<5} {:>7}",
+        "Matchup", "Hand", "Equity", "Hand", "Equity"
+    );
+    for (name, first, second) in PREFLOP_MATCHUPS {
+        let result = estimate_equity_with_options("", first, &[second], &options)
+            .map_err(|e| CliError::snap(&format!("calculating {} vs {}", first, second), e))?;
+        println!(
+            "  {:<34} {:<5} {:>6.2}%   {:<5} {:>6.2}%",
+            name, first, result.equities[0], second, result.equities[1]
+        );
+    }
+    println!();
+    println!("  Ties are split, so each row sums to 100%.");
+    Ok(())
+}
+
+fn run_bubble_command(stacks: &[f64], payouts: &[f64]) -> Result<(), CliError> {
+    if stacks.len() < 2 {
+        return Err(CliError::input("need at least two stacks"));
+    }
+    let equity = icm_equity(stacks, payouts).map_err(|e| CliError::snap("computing ICM", e))?;
+    let total_chips: f64 = stacks.iter().sum();
+
+    println!("ICM Equity:");
+    println!(
+        "  {:<8} {:>10} {:>8} {:>10}",
+        "Player", "Chips", "Chips%", "Equity"
+    );
+    for (i, (stack, value)) in stacks.iter().zip(&equity).enumerate() {
+        println!(
+            "  P{:<7} {:>10.0} {:>7.2}% {:>10.2}",
+            i + 1,
+            stack,
+            stack / total_chips * 100.0,
+            value
+        );
+    }
+    println!();
+
+    // factors[hero][villain], `None` where no all-in is possible
+    let mut factors = vec![vec![None; stacks.len()]; stacks.len()];
+    for (hero, row) in factors.iter_mut().enumerate() {
+        for (villain, cell) in row.iter_mut().enumerate() {
+            if hero != villain && stacks[hero] > 0.0 && stacks[villain] > 0.0 {
+                let bf = bubble_factor(stacks, payouts, hero, villain)
+                    .map_err(|e| CliError::snap("computing bubble factor", e))?;
+                *cell = Some(bf);
+            }
+        }
+    }
+
+    let print_matrix = |title: &str, format: &dyn Fn(f64) -> String| {
+        println!("{}", title);
+        print!("  {:<8}", "Caller");
+        for villain in 0..stacks.len() {
+            print!(" {:>8}", format!("vs P{}", villain + 1));
+        }
+        println!();
+        for (hero, row) in factors.iter().enumerate() {
+            print!("  P{:<7}", hero + 1);
+            for cell in row {
+                match cell {
+                    Some(bf) => print!(" {:>8}", format(*bf)),
+                    None => print!(" {:>8}", "-"),
+                }
+            }
+            println!();
+        }
+        println!();
+    };
+    print_matrix("Bubble Factors (row calls an all-in from column):", &|bf| {
+        format!("{:.2}", bf)
+    });
+    print_matrix(
+        "Equity Needed to Call (no dead money, chip EV 50%):",
+        &|bf| format!("{:.1}%", bubble_call_equity_percent(bf)),
+    );
+    Ok(())
+}
+
+fn run_chop_command(stacks: &[f64], payouts: &[f64], play_for: f64) -> Result<(), CliError> {
+    if stacks.len() < 2 {
+        return Err(CliError::input("need at least two stacks"));
+    }
+    let deal = |method| {
+        chop_deal(stacks, payouts, method, play_for)
+            .map_err(|e| CliError::snap("computing the deal", e))
+    };
+    let icm = deal(ChopMethod::Icm)?;
+    let chip = deal(ChopMethod::ChipChip)?;
+    let total_chips: f64 = stacks.iter().sum();
+
+    println!("Deal:");
+    println!(
+        "  {:<8} {:>10} {:>8} {:>10} {:>10}",
+        "Player", "Chips", "Chips%", "ICM", "Chip Chop"
+    );
+    for (i, stack) in stacks.iter().enumerate() {
+        println!(
+            "  P{:<7} {:>10.0} {:>7.2}% {:>10.2} {:>10.2}",
+            i + 1,
+            stack,
+            stack / total_chips * 100.0,
+            icm[i],
+            chip[i]
+        );
+    }
+    if play_for > 0.0 {
+        println!();
+        println!("  Left to play for: {:.2} (winner takes all)", play_for);
+    }
+    Ok(())
+}
+
+fn run_variance_command(config: VarianceConfig, buy_in: f64) -> Result<(), CliError> {
+    let report =
+        simulate_bankroll(&config).map_err(|e| CliError::snap("simulating bankroll", e))?;
+    let bb = |amount: f64| format!("{:+.0} bb ({:+.1} BI)", amount, amount / buy_in);
+
+    println!("Bankroll Simulation:");
+    println!(
+        "  Win Rate: {} bb/100, Std Dev: {} bb/100",
+        config.winrate, config.stddev
+    );
+    println!(
+        "  Hands: {}, Bankroll: {:.0} bb ({:.1} BI), Trials: {}",
+        config.hands,
+        config.bankroll,
+        config.bankroll / buy_in,
+        config.trials
+    );
+    println!();
+    println!("  Expected Winnings: {}", bb(report.expected_winnings));
+    println!("  Std Dev of Result: {:.0} bb", report.expected_stddev);
+    println!(
+        "  Risk of Ruin: {:.2}% simulated, {:.2}% long-run (closed form)",
+        report.risk_of_ruin * 100.0,
+        report.analytic_risk_of_ruin * 100.0
+    );
+    println!();
+
+    println!("Final Result Percentiles:");
+    for (p, value) in &report.final_percentiles {
+        println!("  {:>3.0}%: {}", p, bb(*value));
+    }
+    println!();
+    println!("Deepest Downswing Percentiles:");
+    for (p, value) in &report.downswing_percentiles {
+        println!("  {:>3.0}%: {:.0} bb ({:.1} BI)", p, value, value / buy_in);
+    }
+    Ok(())
+}
+
+fn run_pot_odds_command(pot_size: f64, call_amount: f64, rake: &RakeModel) -> Result<(), CliError> {
+    if pot_size <= 0.0 {
+        return Err(CliError::input(format!(
+            "pot size must be positive, got {}",
+            pot_size
+        )));
+    }
+    if call_amount <= 0.0 {
+        return Err(CliError::input(format!(
+            "call amount must be positive, got {}",
+            call_amount
+        )));
+    }
+
+    let raked = *rake != RakeModel::TimeCharge;
+    let total_pot_after_call = pot_size + call_amount;
+    let pot_odds_pct = rake.pot_odds_percent(pot_size, call_amount);
+
+    println!("Pot Odds Calculation:");
+    println!("  Pot Size (Before Call): {:.0}", pot_size);
+    println!("  Amount to Call: {:.0}", call_amount);
+    println!("  Total Pot After Call: {:.0}", total_pot_after_call);
+    if raked {
+        println!("  Rake: {:.2} ({})", rake.rake(total_pot_after_call), rake);
+        println!(
+            "  Pot After Rake: {:.2}",
+            rake.raked_pot(total_pot_after_call)
+        );
+    }
+    println!();
+    println!("  Pot Odds: {:.2}%", pot_odds_pct);
+    if raked {
+        println!(
+            "  (Unraked: {:.2}%)",
+            pot_odds_percent(pot_size, call_amount)
+        );
+    }
+    println!();
+    println!(
+        "  You need at least {:.2}% equity to break even",
+        pot_odds_pct
+    );
+
+    // The bet being called is the last thing that went into the pot
+    let before_bet = pot_size - call_amount;
+    if before_bet > 0.0 {
+        println!();
+        println!(
+            "  Minimum Defense Frequency: {:.2}%",
+            minimum_defense_frequency_percent(before_bet, call_amount)
+        );
+        println!(
+            "  A pure bluff of this size needs {:.2}% folds",
+            bluff_break_even_percent(before_bet, call_amount)
+        );
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn time_only_runs_sample_past_the_iteration_default() {
+        let options = EquityOptions {
+            time_budget: Some(Duration::from_millis(200)),
+            ..EquityOptions::new(1_000)
+        };
+        let options = sample_until_deadline("2h7c9d", "AhKh", &[""], options).unwrap();
+        let result = estimate_equity_with_options("2h7c9d", "AhKh", &[""], &options).unwrap();
+        assert_eq!(result.mode, EquityEstimateMode::MonteCarlo);
+        assert!(result.samples > 1_000, "got {}", result.samples);
+    }
+
+    #[test]
+    fn time_only_runs_keep_exact_spots_exact() {
+        let options = EquityOptions {
+            time_budget: Some(Duration::from_millis(200)),
+            ..EquityOptions::new(1_000)
+        };
+        let options = sample_until_deadline("2h7c9dTs", "AhKh", &["QsQc"], options).unwrap();
+        assert_eq!(options.iterations, 1_000);
+        let result = estimate_equity_with_options("2h7c9dTs", "AhKh", &["QsQc"], &options).unwrap();
+        assert_eq!(result.mode, EquityEstimateMode::ExactEnumeration);
+    }
+}
@@ -0,0 +1,28 @@
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::Path;
+
+use serde::Serialize;
+use snapcall_schema::Record;
+
+use crate::error::CliError;
+
+/// Writes `record` to `path` as a single JSON line.
+///
+/// With `append` the line is added to the end of the file (batch mode);
+/// otherwise the file is replaced.
+pub fn write_record<S: Serialize, R: Serialize>(
+    path: &Path,
+    record: &Record<S, R>,
+    append: bool,
+) -> Result<(), CliError> {
+    let json = serde_json::to_string(record).map_err(|e| CliError::io(e.to_string()))?;
+    let mut file = OpenOptions::new()
+        .create(true)
+        .write(true)
+        .append(append)
+        .truncate(!append)
+        .open(path)
+        .map_err(|e| CliError::io(format!("opening '{}': {e}", path.display())))?;
+    writeln!(file, "{json}").map_err(|e| CliError::io(format!("writing '{}': {e}", path.display())))
+}
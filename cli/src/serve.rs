@@ -0,0 +1,191 @@
+use std::io::{BufRead, Write};
+use std::path::Path;
+use std::time::Duration;
+
+use serde::Serialize;
+use serde_json::Value;
+use snapcall_core::{
+    estimate_equity_with_options, estimate_multi_board_equity, parse_cards, EquityEstimateMode,
+    EquityOptions,
+};
+use snapcall_schema::{EquityOutcome, EquityScenario, ErrorBody, Record};
+
+use crate::error::CliError;
+use crate::output::write_record;
+use crate::{equity_input_hint, suggest};
+
+/// One result line written back to the caller: the scenario with either its
+/// `result` or its `error`, like a `snapcall-server` batch entry.
+#[derive(Serialize)]
+struct Response {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    id: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    scenario: Option<EquityScenario>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    result: Option<EquityOutcome>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<ErrorBody>,
+}
+
+/// Reads one scenario per line from stdin and writes one JSON result per line
+/// to stdout until EOF.
+///
+/// Each line is either a JSON [`EquityScenario`] plus an optional `id`
+/// (`{"hero": "AhKh", "villains": ["TT+"], "board": "", "iterations": 10000,
+/// "id": 1}`) or compact text (`AhKh vs TT+ vs AKs on 2h7c9d`). Blank lines
+/// are ignored. Errors are reported per line as a coded `error` object and
+/// never end the session. With `output`, every solved scenario is also
+/// appended to that file as the same record `snapcall equity -o` writes.
+pub fn run_stdio(default_iterations: u32, output: Option<&Path>) -> Result<(), CliError> {
+    let stdin = std::io::stdin();
+    let mut stdout = std::io::stdout().lock();
+
+    for line in stdin.lock().lines() {
+        let line = line.map_err(|e| CliError::io(format!("reading stdin: {e}")))?;
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        let (id, scenario) = parse_line(line, default_iterations);
+        let response = match scenario {
+            Ok(scenario) => match solve(&scenario) {
+                Ok(outcome) => {
+                    if let Some(path) = output {
+                        let record = Record {
+                            scenario: &scenario,
+                            result: &outcome,
+                        };
+                        write_record(path, &record, true)?;
+                    }
+                    Response {
+                        id,
+                        scenario: Some(scenario),
+                        result: Some(outcome),
+                        error: None,
+                    }
+                }
+                Err(err) => Response {
+                    id,
+                    scenario: Some(scenario),
+                    result: None,
+                    error: Some(err.body()),
+                },
+            },
+            Err(err) => Response {
+                id,
+                scenario: None,
+                result: None,
+                error: Some(err.body()),
+            },
+        };
+        let json = serde_json::to_string(&response).map_err(|e| CliError::io(e.to_string()))?;
+        writeln!(stdout, "{json}").map_err(|e| CliError::io(format!("writing stdout: {e}")))?;
+        stdout
+            .flush()
+            .map_err(|e| CliError::io(format!("writing stdout: {e}")))?;
+    }
+    Ok(())
+}
+
+/// Parses a line into its scenario, keeping a JSON `id` even when the rest
+/// of the line is invalid.
+fn parse_line(
+    line: &str,
+    default_iterations: u32,
+) -> (Option<Value>, Result<EquityScenario, CliError>) {
+    if line.starts_with('{') {
+        let mut value: Value = match serde_json::from_str(line) {
+            Ok(value) => value,
+            Err(e) => return (None, Err(CliError::input(format!("invalid JSON: {e}")))),
+        };
+        let id = value.as_object_mut().and_then(|fields| {
+            fields
+                .entry("iterations")
+                .or_insert(default_iterations.into());
+            fields.remove("id")
+        });
+        let scenario = serde_json::from_value(value)
+            .map_err(|e| CliError::input(format!("invalid scenario: {e}")));
+        return (id, scenario);
+    }
+
+    // Compact form: HERO vs VILLAIN [vs VILLAIN ...] [on BOARD]
+    let (players, board) = match line.split_once(" on ") {
+        Some((players, board)) => (players, board.trim().to_string()),
+        None => (line, String::new()),
+    };
+    let mut parts = players.split(" vs ").map(|p| p.trim().to_string());
+    let hero = parts.next().unwrap_or_default();
+    let villains: Vec<String> = parts.collect();
+    if villains.is_empty() {
+        return (
+            None,
+            Err(CliError::input(
+                "expected 'HERO vs VILLAIN [vs ...] [on BOARD]'",
+            )),
+        );
+    }
+    let scenario = EquityScenario {
+        board,
+        board2: None,
+        hero,
+        villains,
+        dead: None,
+        iterations: default_iterations,
+        time_ms: None,
+        seed: None,
+    };
+    (None, Ok(scenario))
+}
+
+/// Solves one scenario the way `snapcall equity` does.
+fn solve(scenario: &EquityScenario) -> Result<EquityOutcome, CliError> {
+    let villains: Vec<&str> = scenario.villains.iter().map(String::as_str).collect();
+
+    if let Some(board2) = &scenario.board2 {
+        if scenario.dead.is_some() || scenario.time_ms.is_some() || scenario.seed.is_some() {
+            return Err(CliError::input(
+                "board2 cannot be combined with dead, time_ms, or seed",
+            ));
+        }
+        let boards = [scenario.board.as_str(), board2.as_str()];
+        let result = estimate_multi_board_equity(
+            &boards,
+            &scenario.hero,
+            &villains,
+            scenario.iterations as usize,
+        )
+        .map_err(|e| CliError::snap("calculating equity", e))?;
+        return Ok(EquityOutcome {
+            mode: EquityEstimateMode::MonteCarlo.to_string(),
+            samples: result.samples,
+            equities: result.pot_share,
+            board_equities: Some(result.board_equities),
+        });
+    }
+
+    let dead = scenario.dead.as_deref().unwrap_or_default();
+    let options = EquityOptions {
+        dead_cards: parse_cards(dead)
+            .map_err(|e| CliError::snap("parsing dead cards", e).with_hint(suggest::cards(dead)))?,
+        time_budget: scenario.time_ms.map(Duration::from_millis),
+        seed: scenario.seed,
+        ..EquityOptions::new(scenario.iterations as usize)
+    };
+    let result = estimate_equity_with_options(&scenario.board, &scenario.hero, &villains, &options)
+        .map_err(|e| {
+            CliError::snap("calculating equity", e).with_hint(equity_input_hint(
+                &scenario.board,
+                &scenario.hero,
+                &villains,
+            ))
+        })?;
+    Ok(EquityOutcome {
+        mode: result.mode.to_string(),
+        samples: result.samples,
+        equities: result.equities,
+        board_equities: None,
+    })
+}
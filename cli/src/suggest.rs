@@ -0,0 +1,51 @@
+use snapcall_core::{
+    card_suggestions, diagnose_cards, diagnose_range, looks_like_cards, range_suggestions,
+    CardIssue,
+};
+
+/// More equally close candidates than this means the guess is too vague to show.
+const MAX_SUGGESTIONS: usize = 4;
+
+/// A correction hint for a card string such as a board or dead cards.
+pub fn cards(input: &str) -> Option<String> {
+    let diagnostic = diagnose_cards(input)?;
+    let token = &diagnostic.token;
+    let problem = match diagnostic.issue {
+        CardIssue::Rank('1') if token.starts_with("10") => "'10' is not a rank".to_string(),
+        CardIssue::Rank(rank) => format!("'{rank}' is not a rank"),
+        CardIssue::Suit(symbol) => format!("'{symbol}' is not a suit"),
+        CardIssue::MissingSuit => format!("'{token}' needs a suit"),
+    };
+    did_you_mean(&card_suggestions(&diagnostic)).map(|hint| format!("{problem} — {hint}"))
+}
+
+/// A correction hint for a range expression such as `"TT+, AKs"`.
+pub fn range(input: &str) -> Option<String> {
+    let token = diagnose_range(input)?.token;
+    did_you_mean(&range_suggestions(&token))
+        .map(|hint| format!("'{}' is not a range — {}", token, hint))
+}
+
+/// A correction hint for a hand that may be cards (`"AhKd"`) or a range.
+pub fn hand(input: &str) -> Option<String> {
+    if looks_like_cards(input) {
+        cards(input).or_else(|| range(input))
+    } else {
+        range(input)
+    }
+}
+
+/// The first hint among several inputs, checked in order.
+pub fn first(hints: impl IntoIterator<Item = Option<String>>) -> Option<String> {
+    hints.into_iter().flatten().next()
+}
+
+fn did_you_mean(candidates: &[String]) -> Option<String> {
+    let quoted: Vec<String> = candidates.iter().map(|c| format!("'{c}'")).collect();
+    match quoted.as_slice() {
+        [] => None,
+        many if many.len() > MAX_SUGGESTIONS => None,
+        [one] => Some(format!("did you mean {one}?")),
+        [rest @ .., last] => Some(format!("did you mean {} or {}?", rest.join(", "), last)),
+    }
+}
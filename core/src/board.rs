@@ -0,0 +1,220 @@
+use std::collections::HashSet;
+
+use rs_poker::core::{Card, Suit, Value};
+
+use crate::types::SnapError;
+
+/// How the board's suits are spread.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SuitPattern {
+    /// No two cards share a suit.
+    Rainbow,
+    /// At most two cards of any suit: flush draws are possible.
+    TwoTone,
+    /// Three or more cards of one suit (every card, on a flop): a flush is
+    /// possible.
+    Monotone,
+}
+
+/// Where the board's top card falls.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HighCard {
+    /// Ten or higher.
+    High,
+    /// Seven to nine.
+    Medium,
+    /// Six or lower.
+    Low,
+}
+
+impl std::fmt::Display for SuitPattern {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SuitPattern::Rainbow => write!(f, "rainbow"),
+            SuitPattern::TwoTone => write!(f, "two_tone"),
+            SuitPattern::Monotone => write!(f, "monotone"),
+        }
+    }
+}
+
+impl std::fmt::Display for HighCard {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            HighCard::High => write!(f, "high"),
+            HighCard::Medium => write!(f, "medium"),
+            HighCard::Low => write!(f, "low"),
+        }
+    }
+}
+
+/// Texture of a flop, turn, or river.
+///
+/// - `paired` is true when two or more cards share a rank.
+/// - `connectedness` is the most distinct board ranks inside any five-rank
+///   straight window (ace high or low): 3 or more means a straight is
+///   possible, 2 allows straight draws.
+/// - `straights` counts the straights (by top card) some two hole cards
+///   make, e.g. 3 on `9s8s7s`: J-high, T-high, and 9-high.
+/// - `flushes` counts suits with three or more board cards, and
+///   `flush_draws` suits with exactly two while cards are still to come.
+/// - `top_rank` is the highest card's rank.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BoardTexture {
+    pub paired: bool,
+    pub suits: SuitPattern,
+    pub connectedness: u8,
+    pub straights: u8,
+    pub flushes: u8,
+    pub flush_draws: u8,
+    pub high_card: HighCard,
+    pub top_rank: Value,
+}
+
+impl BoardTexture {
+    /// Whether some two hole cards make a straight on this board.
+    pub fn straight_possible(&self) -> bool {
+        self.connectedness >= 3
+    }
+
+    /// How much the board lets hands draw and improve, from 0 (static, e.g.
+    /// `Kc7d2s`) to 8 (dynamic, e.g. `9s8s7s`): up to 4 each for flush and
+    /// straight potential, one less when paired.
+    pub fn wetness(&self) -> u8 {
+        let flush: u8 = match self.suits {
+            SuitPattern::Rainbow => 0,
+            SuitPattern::TwoTone => 2,
+            SuitPattern::Monotone => 4,
+        };
+        let straight = match self.connectedness {
+            0..=1 => 0,
+            2 => 1,
+            _ => 4,
+        };
+        (flush + straight).saturating_sub(u8::from(self.paired))
+    }
+}
+
+/// Classifies a 3–5 card board.
+///
+/// # Errors
+/// Returns [`SnapError::InvalidHand`] for fewer than 3 or more than 5 cards,
+/// or duplicates.
+pub fn board_texture(board: &[Card]) -> Result<BoardTexture, SnapError> {
+    if !(3..=5).contains(&board.len()) {
+        return Err(SnapError::InvalidHand(format!(
+            "Board needs 3-5 cards, got {}",
+            board.len()
+        )));
+    }
+    if board.iter().collect::<HashSet<_>>().len() != board.len() {
+        return Err(SnapError::InvalidHand(
+            "Duplicate card on board".to_string(),
+        ));
+    }
+
+    let ranks: u16 = board.iter().fold(0, |mask, c| mask | 1 << c.value as u8);
+    let paired = (ranks.count_ones() as usize) < board.len();
+
+    let suit_counts: Vec<usize> = Suit::suits()
+        .iter()
+        .map(|&suit| board.iter().filter(|c| c.suit == suit).count())
+        .collect();
+    let most_of_one_suit = suit_counts.iter().copied().max().unwrap_or(0);
+    let flushes = suit_counts.iter().filter(|&&n| n >= 3).count() as u8;
+    let flush_draws = if board.len() < 5 {
+        suit_counts.iter().filter(|&&n| n == 2).count() as u8
+    } else {
+        0
+    };
+    let suits = match most_of_one_suit {
+        0 | 1 => SuitPattern::Rainbow,
+        2 => SuitPattern::TwoTone,
+        _ => SuitPattern::Monotone,
+    };
+
+    // Ace also counts below the deuce
+    let with_low_ace = (ranks << 1) | (ranks >> 12 & 1);
+    let windows: Vec<u8> = (0..=9)
+        .map(|low| (with_low_ace >> low & 0b11111).count_ones() as u8)
+        .collect();
+    let connectedness = windows.iter().copied().max().unwrap_or(0);
+    let straights = windows.iter().filter(|&&n| n >= 3).count() as u8;
+
+    let top_rank = board.iter().map(|c| c.value).max().unwrap_or(Value::Two);
+    let high_card = if top_rank >= Value::Ten {
+        HighCard::High
+    } else if top_rank >= Value::Seven {
+        HighCard::Medium
+    } else {
+        HighCard::Low
+    };
+
+    Ok(BoardTexture {
+        paired,
+        suits,
+        connectedness,
+        straights,
+        flushes,
+        flush_draws,
+        high_card,
+        top_rank,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::input::parse_cards;
+
+    fn texture(board: &str) -> BoardTexture {
+        board_texture(&parse_cards(board).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn dry_ace_high_flop() {
+        let t = texture("Ah7h2c");
+        assert!(!t.paired);
+        assert_eq!(t.suits, SuitPattern::TwoTone);
+        assert_eq!(t.high_card, HighCard::High);
+        assert_eq!(t.top_rank, Value::Ace);
+        // A and 2 share the wheel window
+        assert_eq!(t.connectedness, 2);
+        assert!(!t.straight_possible());
+        assert_eq!(t.straights, 0);
+        assert_eq!((t.flushes, t.flush_draws), (0, 1));
+        assert_eq!(t.wetness(), 3);
+    }
+
+    #[test]
+    fn wet_connected_monotone_flop() {
+        let t = texture("9s8s7s");
+        assert_eq!(t.suits, SuitPattern::Monotone);
+        assert_eq!(t.connectedness, 3);
+        assert!(t.straight_possible());
+        // JT, T6, and 65 make J-high, T-high, and 9-high straights
+        assert_eq!(t.straights, 3);
+        assert_eq!((t.flushes, t.flush_draws), (1, 0));
+        assert_eq!(t.high_card, HighCard::Medium);
+        assert_eq!(t.wetness(), 8);
+    }
+
+    #[test]
+    fn paired_low_rainbow_turn() {
+        let t = texture("5c5d3h2s");
+        assert!(t.paired);
+        assert_eq!(t.suits, SuitPattern::Rainbow);
+        assert_eq!(t.high_card, HighCard::Low);
+        assert!(t.straight_possible());
+        // The wheel and six-high
+        assert_eq!(t.straights, 2);
+        assert_eq!(t.wetness(), 3);
+    }
+
+    #[test]
+    fn rejects_bad_boards() {
+        assert!(board_texture(&parse_cards("AhKh").unwrap()).is_err());
+        let ace = Card::try_from("Ah").unwrap();
+        let king = Card::try_from("Kd").unwrap();
+        assert!(board_texture(&[ace, ace, king]).is_err());
+    }
+}
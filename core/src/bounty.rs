@@ -0,0 +1,133 @@
+use crate::ev::call_ev;
+use crate::odds::pot_odds_percent;
+use crate::types::SnapError;
+
+/// How a knockout tournament converts bounty money into chips.
+///
+/// - `starting_stack` chips were bought with `prize_buy_in` of the regular
+///   prize pool (the buy-in minus the bounty part and the fee), which sets
+///   the exchange rate.
+/// - `cash_share` of a won bounty is paid at once: 0.5 in a progressive
+///   knockout (PKO), 1 when the whole bounty is paid out.
+/// - `head_value` is how much of its face value the rest is worth once it
+///   sits on hero's own head, since hero only collects it by winning the
+///   tournament or through a later bounty; 0.5 by default.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BountyOptions {
+    pub starting_stack: f64,
+    pub prize_buy_in: f64,
+    pub cash_share: f64,
+    pub head_value: f64,
+}
+
+impl BountyOptions {
+    /// A progressive knockout: half paid at once, the other half on hero's
+    /// head at half its face value.
+    pub fn new(starting_stack: f64, prize_buy_in: f64) -> Self {
+        Self {
+            starting_stack,
+            prize_buy_in,
+            cash_share: 0.5,
+            head_value: 0.5,
+        }
+    }
+
+    /// Chips a bounty of `bounty` (in money) is worth to the player who
+    /// wins it.
+    ///
+    /// # Errors
+    /// Returns [`SnapError::InvalidArgument`] for a non-positive starting
+    /// stack or prize buy-in, a negative bounty, or shares outside 0 to 1.
+    pub fn bounty_chips(&self, bounty: f64) -> Result<f64, SnapError> {
+        if [self.starting_stack, self.prize_buy_in]
+            .iter()
+            .any(|v| !v.is_finite() || *v <= 0.0)
+        {
+            return Err(SnapError::InvalidArgument(
+                "Starting stack and prize buy-in must be positive".to_string(),
+            ));
+        }
+        if [self.cash_share, self.head_value]
+            .iter()
+            .any(|v| !(0.0..=1.0).contains(v))
+        {
+            return Err(SnapError::InvalidArgument(
+                "Cash share and head value must be between 0 and 1".to_string(),
+            ));
+        }
+        if !bounty.is_finite() || bounty < 0.0 {
+            return Err(SnapError::InvalidArgument(format!(
+                "Bounty must not be negative, got {}",
+                bounty
+            )));
+        }
+        let worth = self.cash_share + (1.0 - self.cash_share) * self.head_value;
+        Ok(bounty * worth * self.starting_stack / self.prize_buy_in)
+    }
+}
+
+/// Equity (in percent) needed to call an all-in when knocking the shover
+/// out also wins `bounty_chips` (see [`BountyOptions::bounty_chips`]).
+///
+/// `pot` includes the bet being faced, as for [`pot_odds_percent`]. The
+/// bounty only counts when hero `covers` the shover; otherwise this is the
+/// plain pot odds.
+pub fn bounty_call_equity_percent(
+    pot: f64,
+    call_amount: f64,
+    bounty_chips: f64,
+    covers: bool,
+) -> f64 {
+    let bounty = if covers { bounty_chips } else { 0.0 };
+    pot_odds_percent(pot + bounty, call_amount)
+}
+
+/// Chips won or lost by calling an all-in relative to folding, counting
+/// `bounty_chips` as part of what hero wins when hero `covers` the shover.
+pub fn bounty_call_ev(
+    pot: f64,
+    call_amount: f64,
+    equity_percent: f64,
+    bounty_chips: f64,
+    covers: bool,
+) -> f64 {
+    let bounty = if covers { bounty_chips } else { 0.0 };
+    call_ev(pot + bounty, call_amount, equity_percent)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(a: f64, b: f64) {
+        assert!((a - b).abs() < 1e-9, "{a} vs {b}");
+    }
+
+    #[test]
+    fn pko_bounties_are_worth_three_quarters_of_face() {
+        // $50 of a $100 buy-in goes to prizes for 10,000 chips
+        let options = BountyOptions::new(10_000.0, 50.0);
+        assert_close(options.bounty_chips(50.0).unwrap(), 7_500.0);
+        let regular = BountyOptions {
+            cash_share: 1.0,
+            ..options.clone()
+        };
+        assert_close(regular.bounty_chips(50.0).unwrap(), 10_000.0);
+        assert!(options.bounty_chips(-1.0).is_err());
+        assert!(BountyOptions::new(0.0, 50.0).bounty_chips(50.0).is_err());
+    }
+
+    #[test]
+    fn covering_the_shover_lowers_the_price() {
+        // 10,000 shove into 1,500 with a 7,500-chip bounty
+        let plain = bounty_call_equity_percent(11_500.0, 10_000.0, 7_500.0, false);
+        let covered = bounty_call_equity_percent(11_500.0, 10_000.0, 7_500.0, true);
+        assert_close(plain, 10_000.0 / 21_500.0 * 100.0);
+        assert_close(covered, 10_000.0 / 29_000.0 * 100.0);
+        assert_close(
+            bounty_call_ev(11_500.0, 10_000.0, covered, 7_500.0, true),
+            0.0,
+        );
+        assert!(bounty_call_ev(11_500.0, 10_000.0, covered, 7_500.0, false) < 0.0);
+    }
+}
@@ -0,0 +1,153 @@
+use rs_poker::core::Card;
+
+use crate::estimate::{estimate_equity_with_options, plan_equity};
+use crate::input::parse_cards;
+use crate::range::expand_weighted_range;
+use crate::types::{EquityOptions, EquityResult, SnapError};
+
+/// Hero's equity against one combo of a villain range.
+///
+/// - `equity` is hero's share of the pot against `combo`, in percent; below
+///   50 means the combo is ahead of hero.
+/// - `weight` is the combo's share of the villain range once combos blocked by
+///   hero, the board, or dead cards are removed, counting any range weights
+///   (`"AKs:0.5"`); weights sum to 1.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ComboEquity {
+    pub combo: [Card; 2],
+    pub equity: f64,
+    pub weight: f64,
+}
+
+/// Splits hero's equity against a villain range into one entry per live
+/// villain combo, in grid order (see [`expand_range`](crate::expand_range)).
+///
+/// Every combo is solved on its own with `options`, so the weighted sum of
+/// the equities is hero's equity against the whole range. Expect roughly
+/// `options.iterations` samples per combo when Monte Carlo is needed.
+///
+/// # Errors
+/// The same validation errors as [`estimate_equity_with_options`] for hero
+/// against `villain`.
+pub fn combo_breakdown(
+    board: &str,
+    hero: &str,
+    villain: &str,
+    options: &EquityOptions,
+) -> Result<Vec<ComboEquity>, SnapError> {
+    combo_breakdown_with(board, hero, villain, options, |combo| {
+        estimate_equity_with_options(board, hero, &[combo], options)
+    })
+}
+
+/// Like [`combo_breakdown`], solving each combo with `solve_combo` (given
+/// the combo as text, e.g. `"AhKd"`) instead of
+/// [`estimate_equity_with_options`], so hosts can sample in chunks or stop
+/// between combos. The first error from `solve_combo` ends the breakdown;
+/// its error type only has to hold a [`SnapError`], so a host can return its
+/// own (e.g. a cancellation).
+///
+/// # Errors
+/// Same as [`combo_breakdown`], plus any error from `solve_combo`.
+pub fn combo_breakdown_with<E: From<SnapError>>(
+    board: &str,
+    hero: &str,
+    villain: &str,
+    options: &EquityOptions,
+    mut solve_combo: impl FnMut(&str) -> Result<EquityResult, E>,
+) -> Result<Vec<ComboEquity>, E> {
+    plan_equity(board, hero, &[villain], options)?;
+
+    let mut blocked = parse_cards(board)?;
+    blocked.extend(parse_cards(hero)?);
+    blocked.extend(&options.dead_cards);
+    let live: Vec<([Card; 2], f64)> = expand_weighted_range(villain)?
+        .into_iter()
+        .filter(|(combo, _)| !combo.iter().any(|c| blocked.contains(c)))
+        .collect();
+
+    let total_weight: f64 = live.iter().map(|(_, w)| w).sum();
+    live.into_iter()
+        .map(|(combo, weight)| {
+            let text = format!("{}{}", combo[0], combo[1]);
+            let result = solve_combo(&text)?;
+            Ok(ComboEquity {
+                combo,
+                equity: result.equities[0],
+                weight: weight / total_weight,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::estimate::estimate_equity;
+
+    #[test]
+    fn weighted_sum_matches_range_equity() {
+        let board = "Ah7d2c9s3h";
+        let options = EquityOptions::new(10_000);
+        let breakdown = combo_breakdown(board, "AsKs", "QQ+, A7s", &options).unwrap();
+        // QQ 6, KK 3 (hero's Ks), AA 1 (Ah, As gone), A7s only Ac7c
+        assert_eq!(breakdown.len(), 6 + 3 + 1 + 1);
+        let total_weight: f64 = breakdown.iter().map(|c| c.weight).sum();
+        assert!((total_weight - 1.0).abs() < 1e-9);
+
+        let weighted: f64 = breakdown.iter().map(|c| c.weight * c.equity).sum();
+        let whole = estimate_equity(board, "AsKs", &["QQ+, A7s"], 10_000).unwrap();
+        assert!(
+            (weighted - whole.equities[0]).abs() < 1e-9,
+            "{weighted} vs {}",
+            whole.equities[0]
+        );
+    }
+
+    #[test]
+    fn flags_combos_that_beat_hero() {
+        let breakdown =
+            combo_breakdown("Ah7d2c9s3h", "AsKs", "77, QQ", &EquityOptions::new(1)).unwrap();
+        for entry in &breakdown {
+            let expected = if entry.combo[0].value == rs_poker::core::Value::Seven {
+                0.0
+            } else {
+                100.0
+            };
+            assert_eq!(entry.equity, expected);
+        }
+    }
+
+    #[test]
+    fn weights_follow_the_range() {
+        let breakdown =
+            combo_breakdown("2h5h9cTdJs", "QhQd", "AA:0.25, 33", &EquityOptions::new(1)).unwrap();
+        assert_eq!(breakdown.len(), 12);
+        for entry in &breakdown {
+            let expected = if entry.equity == 0.0 { 0.25 } else { 1.0 } / 7.5;
+            assert!((entry.weight - expected).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn custom_solvers_can_stop_the_breakdown() {
+        let options = EquityOptions::new(100);
+        let mut solved = 0;
+        let result = combo_breakdown_with("Ah7d2c9s3h", "AsKs", "QQ+", &options, |combo| {
+            solved += 1;
+            if solved == 3 {
+                return Err(SnapError::InvalidArgument("stop".to_string()));
+            }
+            estimate_equity_with_options("Ah7d2c9s3h", "AsKs", &[combo], &options)
+        });
+        assert!(matches!(result, Err(SnapError::InvalidArgument(_))));
+        assert_eq!(solved, 3);
+    }
+
+    #[test]
+    fn rejects_what_equity_rejects() {
+        let options = EquityOptions::new(100);
+        assert!(combo_breakdown("", "AsKs", "AsKs", &options).is_err());
+        assert!(combo_breakdown("", "AK", "QQ", &options).is_err());
+    }
+}
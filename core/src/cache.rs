@@ -0,0 +1,415 @@
+use std::collections::HashMap;
+use std::fs;
+use std::io::{self, BufRead, BufReader, BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+use rs_poker::core::Card;
+
+use crate::estimate::{estimate_equity_with_options, estimate_range_equity_with_options};
+use crate::input::{BoardCardsInput, HoleCardsInput};
+use crate::types::{EquityEstimateMode, EquityMath, EquityOptions, EquityResult, SnapError};
+
+/// First line of a cache file; bump the version when the line format changes.
+const HEADER: &str = "snapcall-equity-cache 1";
+
+/// Equity results keyed by canonicalized scenario, optionally backed by a
+/// file.
+///
+/// The key is the board, every seat, and the dead cards with card order and
+/// range notation normalized away (`"KhAh"` and `"AhKh"`, or `"AK"` and
+/// `"AKs, AKo"`, share an entry). Seat order is kept, since it orders the
+/// result.
+///
+/// A cached result answers a request when it is exact, or when it is a Monte
+/// Carlo result with at least `options.iterations` samples and exact
+/// enumeration was not forced. Anything else is computed and replaces the
+/// entry if it is more precise. Seeds and time budgets are not part of the
+/// key, so a hit returns whatever sample sequence was stored.
+///
+/// Changes stay in memory until [`save`](Self::save).
+#[derive(Debug, Default)]
+pub struct EquityCache {
+    path: Option<PathBuf>,
+    entries: HashMap<String, EquityResult>,
+    hits: usize,
+    misses: usize,
+    dirty: bool,
+}
+
+impl EquityCache {
+    /// An empty cache with no file behind it.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Loads the cache stored at `path`; a missing file starts empty and is
+    /// created by the first [`save`](Self::save).
+    ///
+    /// # Errors
+    /// Returns the I/O error for an unreadable file, or
+    /// [`io::ErrorKind::InvalidData`] for one that is not a cache file.
+    pub fn open(path: impl AsRef<Path>) -> io::Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let mut cache = Self {
+            path: Some(path.clone()),
+            ..Self::default()
+        };
+        let file = match fs::File::open(&path) {
+            Ok(file) => file,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(cache),
+            Err(e) => return Err(e),
+        };
+        let mut lines = BufReader::new(file).lines();
+        match lines.next().transpose()? {
+            Some(header) if header == HEADER => {}
+            None => return Ok(cache),
+            Some(_) => return Err(invalid_data(&path, 1)),
+        }
+        for (idx, line) in lines.enumerate() {
+            let line = line?;
+            let (key, result) = parse_entry(&line).ok_or_else(|| invalid_data(&path, idx + 2))?;
+            cache.entries.insert(key, result);
+        }
+        Ok(cache)
+    }
+
+    /// [`estimate_equity_with_options`] through the cache.
+    ///
+    /// # Errors
+    /// Same as [`estimate_equity_with_options`].
+    pub fn estimate_equity(
+        &mut self,
+        board: &str,
+        hero: &str,
+        villains: &[&str],
+        options: &EquityOptions,
+    ) -> Result<EquityResult, SnapError> {
+        let mut seats = Vec::with_capacity(1 + villains.len());
+        seats.push(hero);
+        seats.extend_from_slice(villains);
+        let key = scenario_key(board, &seats, &options.dead_cards)?;
+        self.lookup(key, options, || {
+            estimate_equity_with_options(board, hero, villains, options)
+        })
+    }
+
+    /// [`estimate_range_equity_with_options`] through the cache.
+    ///
+    /// # Errors
+    /// Same as [`estimate_range_equity_with_options`].
+    pub fn estimate_range_equity(
+        &mut self,
+        board: &str,
+        players: &[&str],
+        options: &EquityOptions,
+    ) -> Result<EquityResult, SnapError> {
+        let key = scenario_key(board, players, &options.dead_cards)?;
+        self.lookup(key, options, || {
+            estimate_range_equity_with_options(board, players, options)
+        })
+    }
+
+    /// Number of cached scenarios.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Requests answered from the cache since it was created or opened.
+    pub fn hits(&self) -> usize {
+        self.hits
+    }
+
+    /// Requests that had to be computed.
+    pub fn misses(&self) -> usize {
+        self.misses
+    }
+
+    /// Writes every entry to the file the cache was opened from, replacing
+    /// it. Does nothing for an in-memory cache or when nothing changed.
+    ///
+    /// # Errors
+    /// Returns the I/O error from writing or renaming the file.
+    pub fn save(&mut self) -> io::Result<()> {
+        let Some(path) = self.path.as_ref().filter(|_| self.dirty) else {
+            return Ok(());
+        };
+        // Write beside the target and rename, so a crash never leaves half a file
+        let tmp = path.with_extension("tmp");
+        let mut out = BufWriter::new(fs::File::create(&tmp)?);
+        writeln!(out, "{}", HEADER)?;
+        let mut keys: Vec<&String> = self.entries.keys().collect();
+        keys.sort();
+        for key in keys {
+            writeln!(out, "{}", format_entry(key, &self.entries[key]))?;
+        }
+        out.into_inner().map_err(|e| e.into_error())?.sync_all()?;
+        fs::rename(&tmp, path)?;
+        self.dirty = false;
+        Ok(())
+    }
+
+    fn lookup(
+        &mut self,
+        key: String,
+        options: &EquityOptions,
+        compute: impl FnOnce() -> Result<EquityResult, SnapError>,
+    ) -> Result<EquityResult, SnapError> {
+        if let Some(cached) = self.entries.get(&key).filter(|r| answers(r, options)) {
+            self.hits += 1;
+            return Ok(cached.clone());
+        }
+        self.misses += 1;
+        let result = compute()?;
+        let better = self
+            .entries
+            .get(&key)
+            .is_none_or(|old| precision(&result) > precision(old));
+        if better {
+            self.entries.insert(key, result.clone());
+            self.dirty = true;
+        }
+        Ok(result)
+    }
+}
+
+/// Whether a cached result is at least as precise as `options` asks for.
+fn answers(result: &EquityResult, options: &EquityOptions) -> bool {
+    match result.mode {
+        EquityEstimateMode::ExactEnumeration => true,
+        EquityEstimateMode::MonteCarlo => {
+            options.mode != Some(EquityEstimateMode::ExactEnumeration)
+                && result.samples >= options.iterations
+        }
+    }
+}
+
+/// Orders results by precision: exact beats any sample count.
+fn precision(result: &EquityResult) -> (bool, usize) {
+    (
+        result.mode == EquityEstimateMode::ExactEnumeration,
+        result.samples,
+    )
+}
+
+/// Canonical text for a scenario: `board|seat|...|dead`, cards high first.
+fn scenario_key(board: &str, seats: &[&str], dead: &[Card]) -> Result<String, SnapError> {
+    let board: BoardCardsInput = board.parse()?;
+    let mut parts = vec![cards_key(board.cards())];
+    for seat in seats {
+        parts.push(match seat.parse()? {
+            HoleCardsInput::Exact(hand) => cards_key(hand.iter().copied().collect()),
+            HoleCardsInput::Partial(card) => cards_key(vec![card]),
+            HoleCardsInput::Unknown => "?".to_string(),
+            HoleCardsInput::Range(hands) => {
+                let weights = vec![1.0; hands.len()];
+                range_key(&hands, &weights)
+            }
+            HoleCardsInput::WeightedRange(hands, weights) => range_key(&hands, &weights),
+        });
+    }
+    parts.push(cards_key(dead.to_vec()));
+    Ok(parts.join("|"))
+}
+
+fn cards_key(mut cards: Vec<Card>) -> String {
+    cards.sort_by(|a, b| b.cmp(a));
+    cards.iter().map(Card::to_string).collect()
+}
+
+fn range_key(hands: &[rs_poker::core::FlatHand], weights: &[f64]) -> String {
+    let mut combos: Vec<String> = hands
+        .iter()
+        .zip(weights)
+        .map(|(hand, &weight)| {
+            let cards = cards_key(hand.iter().copied().collect());
+            if weight < 1.0 {
+                format!("{}:{}", cards, weight)
+            } else {
+                cards
+            }
+        })
+        .collect();
+    combos.sort();
+    combos.join(",")
+}
+
+fn invalid_data(path: &Path, line: usize) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!(
+            "{}:{}: not a snapcall equity cache entry",
+            path.display(),
+            line
+        ),
+    )
+}
+
+/// One tab-separated line: key, mode, samples, equities, wins, ties, math.
+fn format_entry(key: &str, result: &EquityResult) -> String {
+    let list = |values: &[f64]| {
+        values
+            .iter()
+            .map(f64::to_string)
+            .collect::<Vec<_>>()
+            .join(",")
+    };
+    let math = &result.math;
+    let total = math
+        .total_states
+        .map_or_else(|| "-".to_string(), |t| t.to_string());
+    format!(
+        "{}\t{}\t{}\t{}\t{}\t{}\t{},{},{},{},{}",
+        key,
+        result.mode,
+        result.samples,
+        list(&result.equities),
+        list(&result.wins),
+        list(&result.ties),
+        math.assignment_combinations,
+        math.available_cards,
+        math.runout_slots,
+        math.runout_combinations,
+        total
+    )
+}
+
+fn parse_entry(line: &str) -> Option<(String, EquityResult)> {
+    let fields: Vec<&str> = line.split('\t').collect();
+    let [key, mode, samples, equities, wins, ties, math] = fields[..] else {
+        return None;
+    };
+    let list = |text: &str| {
+        text.split(',')
+            .map(|v| v.parse::<f64>().ok())
+            .collect::<Option<Vec<f64>>>()
+    };
+    let mode = match mode {
+        "exact" => EquityEstimateMode::ExactEnumeration,
+        "monte_carlo" => EquityEstimateMode::MonteCarlo,
+        _ => return None,
+    };
+    let math: Vec<&str> = math.split(',').collect();
+    let [assignment, available, slots, runouts, total] = math[..] else {
+        return None;
+    };
+    let math = EquityMath {
+        assignment_combinations: assignment.parse().ok()?,
+        available_cards: available.parse().ok()?,
+        runout_slots: slots.parse().ok()?,
+        runout_combinations: runouts.parse().ok()?,
+        total_states: match total {
+            "-" => None,
+            t => Some(t.parse().ok()?),
+        },
+    };
+    let result = EquityResult {
+        equities: list(equities)?,
+        wins: list(wins)?,
+        ties: list(ties)?,
+        mode,
+        samples: samples.parse().ok()?,
+        math,
+    };
+    Some((key.to_string(), result))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn monte_carlo(iterations: usize) -> EquityOptions {
+        EquityOptions {
+            mode: Some(EquityEstimateMode::MonteCarlo),
+            seed: Some(1),
+            ..EquityOptions::new(iterations)
+        }
+    }
+
+    #[test]
+    fn equivalent_scenarios_share_an_entry() {
+        let mut cache = EquityCache::new();
+        let options = EquityOptions::new(10_000);
+        let first = cache
+            .estimate_equity("Ts9s2d", "AhKh", &["QQ, AK"], &options)
+            .unwrap();
+        let second = cache
+            .estimate_equity("2dTs9s", "KhAh", &["AKo, AKs, QQ"], &options)
+            .unwrap();
+        assert_eq!(first, second);
+        assert_eq!((cache.hits(), cache.misses(), cache.len()), (1, 1, 1));
+
+        // Seat order is part of the scenario
+        cache
+            .estimate_range_equity("Ts9s2d", &["QQ, AK", "AhKh"], &options)
+            .unwrap();
+        assert_eq!(cache.len(), 2);
+    }
+
+    #[test]
+    fn more_samples_replace_a_less_precise_entry() {
+        let mut cache = EquityCache::new();
+        let rough = cache
+            .estimate_equity("", "AhKh", &["QQ+"], &monte_carlo(1_000))
+            .unwrap();
+        // Fewer samples than stored: a hit
+        let again = cache
+            .estimate_equity("", "AhKh", &["QQ+"], &monte_carlo(500))
+            .unwrap();
+        assert_eq!(rough, again);
+        // More samples than stored: computed and kept
+        let fine = cache
+            .estimate_equity("", "AhKh", &["QQ+"], &monte_carlo(4_000))
+            .unwrap();
+        assert_eq!(fine.samples, 4_000);
+        assert_eq!((cache.hits(), cache.misses()), (1, 2));
+        let hit = cache
+            .estimate_equity("", "AhKh", &["QQ+"], &monte_carlo(2_000))
+            .unwrap();
+        assert_eq!(hit, fine);
+    }
+
+    #[test]
+    fn entries_survive_a_save_and_reopen() {
+        let path =
+            std::env::temp_dir().join(format!("snapcall-equity-cache-{}.txt", std::process::id()));
+        let _ = fs::remove_file(&path);
+
+        let options = EquityOptions {
+            dead_cards: vec![Card::try_from("2c").unwrap()],
+            ..EquityOptions::new(100_000)
+        };
+        let mut cache = EquityCache::open(&path).unwrap();
+        let exact = cache
+            .estimate_equity("Ts9s2d", "AhKh", &["QQ:0.5, AK", ""], &options)
+            .unwrap();
+        let sampled = cache
+            .estimate_equity("", "AhKh", &["QQ+"], &monte_carlo(1_000))
+            .unwrap();
+        cache.save().unwrap();
+
+        let mut reopened = EquityCache::open(&path).unwrap();
+        assert_eq!(reopened.len(), 2);
+        assert_eq!(
+            reopened
+                .estimate_equity("Ts9s2d", "AhKh", &["QQ:0.5, AK", ""], &options)
+                .unwrap(),
+            exact
+        );
+        assert_eq!(
+            reopened
+                .estimate_equity("", "AhKh", &["QQ+"], &monte_carlo(1_000))
+                .unwrap(),
+            sampled
+        );
+        assert_eq!(reopened.hits(), 2);
+
+        fs::write(&path, "something else\n").unwrap();
+        let err = EquityCache::open(&path).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        fs::remove_file(&path).unwrap();
+    }
+}
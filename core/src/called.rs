@@ -0,0 +1,171 @@
+use rs_poker::core::Card;
+
+use crate::estimate::estimate_range_equity_with_options;
+use crate::input::{BoardCardsInput, HoleCardsInput};
+use crate::range::expand_weighted_range;
+use crate::types::{EquityEstimateMode, EquityOptions, SnapError};
+
+/// The part of villain's range that calls a bet, for [`equity_when_called`].
+#[derive(Clone, Copy)]
+pub enum ContinueRange<'a> {
+    /// Range notation for the hands that continue (`"TT+, AK"`). Combos
+    /// outside villain's full range are ignored, and weights multiply, so
+    /// `"AKo:0.5"` keeps half of however much AKo villain has.
+    Range(&'a str),
+    /// Keeps the combos the function returns `true` for.
+    Filter(&'a dyn Fn([Card; 2]) -> bool),
+}
+
+/// Hero's equity against the continuing part of villain's range.
+///
+/// - `equity` is hero's equity in percent against the `continuing` combos
+///   (with their weights), as from [`estimate_range_equity`](crate::estimate_range_equity).
+/// - `continue_percent` is the share of villain's live range (after hero's
+///   cards, the board, and dead cards) that continues; the rest folds.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CalledEquity {
+    pub equity: f64,
+    pub continue_percent: f64,
+    pub continuing: Vec<([Card; 2], f64)>,
+    pub mode: EquityEstimateMode,
+    pub samples: usize,
+}
+
+/// Hero's equity when a bet gets called: against only the combos of
+/// `villain` that `continuing` keeps, rather than the whole range.
+///
+/// `hero` may be exact cards or a range, as for
+/// [`estimate_range_equity`](crate::estimate_range_equity).
+///
+/// # Errors
+/// Returns [`SnapError::InvalidRange`] when no live combo of `villain`
+/// continues, plus the [`estimate_range_equity_with_options`] errors.
+pub fn equity_when_called(
+    board: &str,
+    hero: &str,
+    villain: &str,
+    continuing: ContinueRange<'_>,
+    options: &EquityOptions,
+) -> Result<CalledEquity, SnapError> {
+    let board_input: BoardCardsInput = board.parse()?;
+    let mut blocked = board_input.cards();
+    match hero.parse()? {
+        HoleCardsInput::Exact(hand) => blocked.extend(hand.iter()),
+        HoleCardsInput::Partial(card) => blocked.push(card),
+        _ => {}
+    }
+    blocked.extend(&options.dead_cards);
+
+    let live: Vec<([Card; 2], f64)> = expand_weighted_range(villain)?
+        .into_iter()
+        .filter(|(combo, _)| !combo.iter().any(|c| blocked.contains(c)))
+        .collect();
+    let calls: Vec<([Card; 2], f64)> = match continuing {
+        ContinueRange::Range(range) => {
+            let keep = expand_weighted_range(range)?;
+            live.iter()
+                .filter_map(|&(combo, weight)| {
+                    keep.iter()
+                        .find(|(kept, _)| same_combo(kept, &combo))
+                        .map(|&(_, w)| (combo, weight * w))
+                })
+                .collect()
+        }
+        ContinueRange::Filter(filter) => live
+            .iter()
+            .copied()
+            .filter(|&(combo, _)| filter(combo))
+            .collect(),
+    };
+    if calls.is_empty() {
+        return Err(SnapError::InvalidRange(format!(
+            "No combo of '{}' continues",
+            villain
+        )));
+    }
+
+    let total: f64 = live.iter().map(|(_, w)| w).sum();
+    let continued: f64 = calls.iter().map(|(_, w)| w).sum();
+    let text = calls
+        .iter()
+        .map(|(combo, weight)| format!("{}{}:{}", combo[0], combo[1], weight))
+        .collect::<Vec<_>>()
+        .join(",");
+    let result = estimate_range_equity_with_options(board, &[hero, text.as_str()], options)?;
+    Ok(CalledEquity {
+        equity: result.equities[0],
+        continue_percent: continued / total * 100.0,
+        continuing: calls,
+        mode: result.mode,
+        samples: result.samples,
+    })
+}
+
+fn same_combo(a: &[Card; 2], b: &[Card; 2]) -> bool {
+    (a[0] == b[0] && a[1] == b[1]) || (a[0] == b[1] && a[1] == b[0])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::estimate::estimate_equity;
+
+    #[test]
+    fn only_the_calling_range_counts() {
+        let options = EquityOptions::new(100_000);
+        let called = equity_when_called(
+            "Ts7d2c",
+            "JhJd",
+            "QQ+, AK, 88-99",
+            ContinueRange::Range("QQ+, AK"),
+            &options,
+        )
+        .unwrap();
+        // QQ+ 18 and AK 16 call, 12 small pairs fold
+        assert_eq!(called.continuing.len(), 34);
+        assert!((called.continue_percent - 34.0 / 46.0 * 100.0).abs() < 1e-9);
+        let direct = estimate_equity("Ts7d2c", "JhJd", &["QQ+, AK"], 100_000).unwrap();
+        assert!((called.equity - direct.equities[0]).abs() < 1e-9);
+
+        let whole = estimate_equity("Ts7d2c", "JhJd", &["QQ+, AK, 88-99"], 100_000).unwrap();
+        assert!(called.equity < whole.equities[0]);
+    }
+
+    #[test]
+    fn filters_and_weights_shape_the_continuing_range() {
+        let options = EquityOptions::new(20_000);
+        let pairs = |combo: [Card; 2]| combo[0].value == combo[1].value;
+        let filtered = equity_when_called(
+            "Ts7d2c",
+            "JhJd",
+            "QQ+, AK",
+            ContinueRange::Filter(&pairs),
+            &options,
+        )
+        .unwrap();
+        let explicit = equity_when_called(
+            "Ts7d2c",
+            "JhJd",
+            "QQ+, AK",
+            ContinueRange::Range("QQ+"),
+            &options,
+        )
+        .unwrap();
+        assert_eq!(filtered, explicit);
+
+        let half = equity_when_called(
+            "",
+            "JhJd",
+            "AA:0.5, KK",
+            ContinueRange::Range("AA, KK:0.5"),
+            &options,
+        )
+        .unwrap();
+        // All 3 of AA's weight and half of KK's 6
+        assert!((half.continue_percent - 6.0 / 9.0 * 100.0).abs() < 1e-9);
+
+        assert!(
+            equity_when_called("", "JhJd", "QQ+", ContinueRange::Range("AK"), &options).is_err()
+        );
+    }
+}
@@ -0,0 +1,387 @@
+use rs_poker::core::Card;
+
+use crate::pushfold::{class_at, equity, grid_index, removal_weights, NUM_CLASSES};
+use crate::range::{range_grid, top_percent_classes, HandClass, RangeGridCell, TOTAL_COMBOS};
+use crate::realization::{Position, RealizationModel};
+use crate::types::SnapError;
+
+/// Which decision a [`PreflopChart`] covers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ChartKind {
+    /// First in: raise or fold.
+    Open,
+    /// Facing an open: the hands that flat call.
+    Call,
+    /// Facing an open: the hands that re-raise.
+    ThreeBet,
+}
+
+impl std::fmt::Display for ChartKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ChartKind::Open => write!(f, "open"),
+            ChartKind::Call => write!(f, "call"),
+            ChartKind::ThreeBet => write!(f, "3bet"),
+        }
+    }
+}
+
+/// The simple EV model behind [`preflop_chart`], in big blinds.
+///
+/// - `dead_bb` is already in the pot before anyone opens (the blinds plus
+///   antes); hero's own blind is not modelled.
+/// - Opening raises to `open_bb` with `players_behind` players left. Each
+///   continues with the top `defend_percent` of hands, re-raising to
+///   `three_bet_bb` with the top `three_bet_percent` of them; hero then
+///   shoves or folds.
+/// - Facing an open, the opener raised to `open_bb` with the top
+///   `opener_percent`. Hero calls or 3-bets to `three_bet_bb`; the opener
+///   continues with the top `continue_percent` of hands (within the opening
+///   range), shoving the top `four_bet_percent` and calling with the rest.
+///   Hero calls the shove or folds.
+/// - Called pots are checked down, with hero's equity scaled by
+///   `realization` for `position` at the SPR left. All-ins use raw equity.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChartOptions {
+    pub stack_bb: f64,
+    pub dead_bb: f64,
+    pub open_bb: f64,
+    pub three_bet_bb: f64,
+    pub players_behind: u32,
+    pub defend_percent: f64,
+    pub three_bet_percent: f64,
+    pub opener_percent: f64,
+    pub continue_percent: f64,
+    pub four_bet_percent: f64,
+    pub position: Position,
+    pub realization: RealizationModel,
+}
+
+impl Default for ChartOptions {
+    fn default() -> Self {
+        Self {
+            stack_bb: 100.0,
+            dead_bb: 1.5,
+            open_bb: 2.5,
+            three_bet_bb: 9.0,
+            players_behind: 3,
+            defend_percent: 40.0,
+            three_bet_percent: 6.0,
+            opener_percent: 25.0,
+            continue_percent: 12.0,
+            four_bet_percent: 4.0,
+            position: Position::InPosition,
+            realization: RealizationModel::default(),
+        }
+    }
+}
+
+impl ChartOptions {
+    fn validate(&self) -> Result<(), SnapError> {
+        let sizes = [self.stack_bb, self.open_bb, self.three_bet_bb];
+        if sizes.iter().any(|v| !v.is_finite() || *v <= 0.0)
+            || !self.dead_bb.is_finite()
+            || self.dead_bb < 0.0
+        {
+            return Err(SnapError::InvalidArgument(
+                "Stack and bet sizes must be positive and dead money not negative".to_string(),
+            ));
+        }
+        if !(self.open_bb < self.three_bet_bb && self.three_bet_bb <= self.stack_bb) {
+            return Err(SnapError::InvalidArgument(format!(
+                "Need open < 3-bet <= stack, got {}, {}, and {} bb",
+                self.open_bb, self.three_bet_bb, self.stack_bb
+            )));
+        }
+        if [
+            self.defend_percent,
+            self.three_bet_percent,
+            self.opener_percent,
+            self.continue_percent,
+            self.four_bet_percent,
+        ]
+        .iter()
+        .any(|p| !(0.0..=100.0).contains(p))
+        {
+            return Err(SnapError::InvalidArgument(
+                "Range percentages must be between 0 and 100".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// EV of hero's best reply to an all-in for the whole stack against
+    /// `range`, with `invested` already in: call, or fold and lose it.
+    fn versus_shove(&self, class: usize, range: &Range, invested: f64) -> f64 {
+        let pot = self.dead_bb + 2.0 * self.stack_bb;
+        (range.equity_of(class) * pot - self.stack_bb).max(-invested)
+    }
+
+    /// Realized equity (0–1) of `class` against `range` in a called pot of
+    /// `pot` with `invested` of hero's chips in it.
+    fn realized(&self, class: usize, range: &Range, pot: f64, invested: f64) -> f64 {
+        let raw = range.equity_of(class) * 100.0;
+        let spr = (self.stack_bb - invested) / pot;
+        self.realization
+            .realized_equity_percent(raw, class_at(class), self.position, spr)
+            / 100.0
+    }
+}
+
+/// One generated chart: the classes that take the chart's action, and the
+/// model's EV (in big blinds, relative to folding) of that action for
+/// every class.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PreflopChart {
+    pub kind: ChartKind,
+    evs: Vec<f64>,
+    included: Vec<bool>,
+}
+
+impl PreflopChart {
+    /// Whether `class` takes the chart's action.
+    pub fn contains(&self, class: HandClass) -> bool {
+        self.included[grid_index(class)]
+    }
+
+    /// Modelled EV of the chart's action with `class`, in big blinds.
+    pub fn ev(&self, class: HandClass) -> f64 {
+        self.evs[grid_index(class)]
+    }
+
+    /// Classes in the chart, in grid order.
+    pub fn range(&self) -> Vec<HandClass> {
+        (0..NUM_CLASSES)
+            .filter(|&i| self.included[i])
+            .map(class_at)
+            .collect()
+    }
+
+    /// The chart as range notation (`"AA, AKs, ..."`) for the equity engine.
+    pub fn range_notation(&self) -> String {
+        self.range()
+            .iter()
+            .map(|c| c.to_string())
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    /// The chart as a 13×13 grid, as from [`range_grid`].
+    pub fn grid(&self) -> Vec<Vec<RangeGridCell>> {
+        let combos: Vec<[Card; 2]> = self.range().iter().flat_map(|c| c.cards()).collect();
+        range_grid(&combos)
+    }
+
+    /// Share of all 1326 combos in the chart, in percent.
+    pub fn percent(&self) -> f64 {
+        let combos: usize = self.range().iter().map(|c| c.combos()).sum();
+        combos as f64 / TOTAL_COMBOS as f64 * 100.0
+    }
+}
+
+/// An assumed opponent range as per-class membership, with the combo
+/// weights the equity table needs.
+struct Range {
+    members: Vec<bool>,
+    weights: Vec<f64>,
+}
+
+impl Range {
+    fn top(percent: f64, weights: &[f64]) -> Self {
+        let mut members = vec![false; NUM_CLASSES];
+        for class in top_percent_classes(percent) {
+            members[grid_index(class)] = true;
+        }
+        Self {
+            members,
+            weights: weights.to_vec(),
+        }
+    }
+
+    /// Keeps only classes also in `other`.
+    fn within(mut self, other: &Range) -> Self {
+        for (member, &also) in self.members.iter_mut().zip(&other.members) {
+            *member &= also;
+        }
+        self
+    }
+
+    /// Drops classes in `other`.
+    fn without(mut self, other: &Range) -> Self {
+        for (member, &also) in self.members.iter_mut().zip(&other.members) {
+            *member &= !also;
+        }
+        self
+    }
+
+    /// Equity (0–1) of `class` against the range, with card removal; 0
+    /// when every combo is blocked.
+    fn equity_of(&self, class: usize) -> f64 {
+        let (mut total, mut weight) = (0.0, 0.0);
+        for j in (0..NUM_CLASSES).filter(|&j| self.members[j]) {
+            let w = self.weights[class * NUM_CLASSES + j];
+            total += w * equity(class, j);
+            weight += w;
+        }
+        if weight > 0.0 {
+            total / weight
+        } else {
+            0.0
+        }
+    }
+
+    /// Share (0–1) of all combos, with `class`'s cards removed, in the
+    /// range.
+    fn share(&self, class: usize) -> f64 {
+        let row = &self.weights[class * NUM_CLASSES..(class + 1) * NUM_CLASSES];
+        let live: f64 = row.iter().sum();
+        let held: f64 = (0..NUM_CLASSES)
+            .filter(|&j| self.members[j])
+            .map(|j| row[j])
+            .sum();
+        held / live
+    }
+}
+
+/// Generates a preflop chart of `kind` from the EV model in `options`.
+///
+/// Every class takes whichever action has the highest modelled EV, with
+/// folding at 0; facing an open, a class goes in the [`ChartKind::Call`] or
+/// [`ChartKind::ThreeBet`] chart but never both. Equities come from the
+/// same class-vs-class table as [`solve_push_fold`](crate::solve_push_fold),
+/// so this is instant but only as good as its assumed ranges.
+///
+/// # Errors
+/// Returns [`SnapError::InvalidArgument`] for non-positive sizes, a 3-bet
+/// no bigger than the open or bigger than the stack, or a percentage
+/// outside 0 to 100.
+pub fn preflop_chart(kind: ChartKind, options: &ChartOptions) -> Result<PreflopChart, SnapError> {
+    options.validate()?;
+    let classes: Vec<HandClass> = (0..NUM_CLASSES).map(class_at).collect();
+    let weights = removal_weights(&classes);
+    let o = options;
+
+    let mut evs = vec![0.0; NUM_CLASSES];
+    let mut included = vec![false; NUM_CLASSES];
+    match kind {
+        ChartKind::Open => {
+            let defenders = Range::top(o.defend_percent, &weights);
+            let three_bettors = Range::top(o.three_bet_percent, &weights).within(&defenders);
+            let called_pot = o.dead_bb + 2.0 * o.open_bb;
+            let n = o.players_behind as i32;
+            for i in 0..NUM_CLASSES {
+                let folds = (1.0 - defenders.share(i)).powi(n);
+                let re_raised = 1.0 - (1.0 - three_bettors.share(i)).powi(n);
+                let called = o.realized(i, &defenders, called_pot, o.open_bb) * called_pot;
+                evs[i] = folds * o.dead_bb
+                    + re_raised * o.versus_shove(i, &three_bettors, o.open_bb)
+                    + (1.0 - folds - re_raised) * (called - o.open_bb);
+                included[i] = evs[i] > 0.0;
+            }
+        }
+        ChartKind::Call | ChartKind::ThreeBet => {
+            let opener = Range::top(o.opener_percent, &weights);
+            let continuing = Range::top(o.continue_percent, &weights).within(&opener);
+            let shoves = Range::top(o.four_bet_percent, &weights).within(&continuing);
+            let calls = continuing.without(&shoves);
+            let called_pot = o.dead_bb + 2.0 * o.open_bb;
+            let three_bet_pot = o.dead_bb + 2.0 * o.three_bet_bb;
+            for i in 0..NUM_CLASSES {
+                let call = o.realized(i, &opener, called_pot, o.open_bb) * called_pot - o.open_bb;
+                let opener_share = opener.share(i);
+                let (shoved, called) = if opener_share > 0.0 {
+                    (
+                        shoves.share(i) / opener_share,
+                        calls.share(i) / opener_share,
+                    )
+                } else {
+                    (0.0, 0.0)
+                };
+                let three_bet = (1.0 - shoved - called) * (o.dead_bb + o.open_bb)
+                    + shoved * o.versus_shove(i, &shoves, o.three_bet_bb)
+                    + called
+                        * (o.realized(i, &calls, three_bet_pot, o.three_bet_bb) * three_bet_pot
+                            - o.three_bet_bb);
+                let (ev, best) = if kind == ChartKind::Call {
+                    (call, call > three_bet)
+                } else {
+                    (three_bet, three_bet >= call)
+                };
+                evs[i] = ev;
+                included[i] = best && ev > 0.0;
+            }
+        }
+    }
+    Ok(PreflopChart {
+        kind,
+        evs,
+        included,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn class(label: &str) -> HandClass {
+        label.parse().unwrap()
+    }
+
+    #[test]
+    fn opening_chart_keeps_strong_hands() {
+        let chart = preflop_chart(ChartKind::Open, &ChartOptions::default()).unwrap();
+        assert!(chart.contains(class("AA")));
+        assert!(chart.contains(class("AKo")));
+        assert!(!chart.contains(class("72o")));
+        assert!(chart.ev(class("AA")) > chart.ev(class("KQo")));
+        let percent = chart.percent();
+        assert!(percent > 5.0 && percent < 60.0, "opened {percent}%");
+
+        // More players to get through means a tighter range
+        let early = ChartOptions {
+            players_behind: 6,
+            ..ChartOptions::default()
+        };
+        let tighter = preflop_chart(ChartKind::Open, &early).unwrap();
+        assert!(tighter.percent() < percent);
+        assert_eq!(
+            tighter
+                .grid()
+                .iter()
+                .flatten()
+                .map(|c| c.combos)
+                .sum::<usize>(),
+            tighter.range().iter().map(|c| c.combos()).sum::<usize>()
+        );
+    }
+
+    #[test]
+    fn call_and_three_bet_charts_do_not_overlap() {
+        let options = ChartOptions::default();
+        let call = preflop_chart(ChartKind::Call, &options).unwrap();
+        let three_bet = preflop_chart(ChartKind::ThreeBet, &options).unwrap();
+        assert!(three_bet.contains(class("AA")));
+        assert!(!call.contains(class("32o")) && !three_bet.contains(class("32o")));
+        for c in call.range() {
+            assert!(!three_bet.contains(c), "{c} in both charts");
+        }
+        let notation = three_bet.range_notation();
+        assert!(notation.starts_with("AA"), "{notation}");
+        let combos = crate::expand_range(&notation).unwrap().len();
+        assert!((combos as f64 - three_bet.percent() / 100.0 * TOTAL_COMBOS as f64).abs() < 1e-9);
+    }
+
+    #[test]
+    fn rejects_bad_options() {
+        let small_three_bet = ChartOptions {
+            three_bet_bb: 2.0,
+            ..ChartOptions::default()
+        };
+        assert!(preflop_chart(ChartKind::ThreeBet, &small_three_bet).is_err());
+        let bad_percent = ChartOptions {
+            defend_percent: 120.0,
+            ..ChartOptions::default()
+        };
+        assert!(preflop_chart(ChartKind::Open, &bad_percent).is_err());
+    }
+}
@@ -0,0 +1,116 @@
+use crate::icm::{busted_equity, estimate_icm_equity, live_players, DEFAULT_ICM_TRIALS};
+use crate::types::SnapError;
+
+/// How [`chop_deal`] splits the prizes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ChopMethod {
+    /// Each player's ICM prize equity.
+    Icm,
+    /// Everyone locks up the lowest prize still in play, and the rest of
+    /// the money goes out in proportion to chips.
+    ChipChip,
+}
+
+impl std::fmt::Display for ChopMethod {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ChopMethod::Icm => write!(f, "icm"),
+            ChopMethod::ChipChip => write!(f, "chip"),
+        }
+    }
+}
+
+/// Money each player takes in a deal, in the order of `stacks` and the
+/// units of `payouts` (`payouts[k]` pays place `k + 1`).
+///
+/// `play_for` is a "save" left out of the deal: it comes off the first
+/// prize before the split, and the players play on for it, winner take all.
+/// Use 0 for a full chop. Players with an empty stack have already busted
+/// and keep the bottom places, as in [`icm_equity`](crate::icm_equity).
+///
+/// # Errors
+/// Returns [`SnapError::InvalidArgument`] for a `play_for` that is negative
+/// or more than the first prize, plus any stack or payout error from
+/// [`icm_equity`](crate::icm_equity).
+pub fn chop_deal(
+    stacks: &[f64],
+    payouts: &[f64],
+    method: ChopMethod,
+    play_for: f64,
+) -> Result<Vec<f64>, SnapError> {
+    let first = payouts.first().copied().unwrap_or(0.0);
+    if !play_for.is_finite() || play_for < 0.0 || play_for > first {
+        return Err(SnapError::InvalidArgument(format!(
+            "The amount played for must be between 0 and the first prize ({}), got {}",
+            first, play_for
+        )));
+    }
+    let mut dealt = payouts.to_vec();
+    if let Some(top) = dealt.first_mut() {
+        *top -= play_for;
+    }
+
+    match method {
+        ChopMethod::Icm => estimate_icm_equity(stacks, &dealt, DEFAULT_ICM_TRIALS, None),
+        ChopMethod::ChipChip => {
+            let alive = live_players(stacks, &dealt)?;
+            let prize = |place: usize| dealt.get(place).copied().unwrap_or(0.0);
+            let floor = prize(alive.len() - 1);
+            let pool: f64 = (0..alive.len()).map(prize).sum::<f64>() - floor * alive.len() as f64;
+            let chips: f64 = alive.iter().map(|&i| stacks[i]).sum();
+            let mut amounts = busted_equity(stacks, &dealt, alive.len());
+            for &i in &alive {
+                amounts[i] = floor + pool * stacks[i] / chips;
+            }
+            Ok(amounts)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::icm::icm_equity;
+
+    fn assert_close(a: f64, b: f64) {
+        assert!((a - b).abs() < 1e-9, "{a} vs {b}");
+    }
+
+    #[test]
+    fn chip_chop_guarantees_the_last_prize() {
+        let amounts = chop_deal(
+            &[6000.0, 3000.0, 1000.0],
+            &[500.0, 300.0, 200.0],
+            ChopMethod::ChipChip,
+            0.0,
+        )
+        .unwrap();
+        // 200 each, then the other 400 by chips
+        assert_close(amounts[0], 440.0);
+        assert_close(amounts[1], 320.0);
+        assert_close(amounts[2], 240.0);
+    }
+
+    #[test]
+    fn icm_chop_matches_icm_equity() {
+        let stacks = [6000.0, 3000.0, 1000.0];
+        let payouts = [500.0, 300.0, 200.0];
+        let amounts = chop_deal(&stacks, &payouts, ChopMethod::Icm, 0.0).unwrap();
+        assert_eq!(amounts, icm_equity(&stacks, &payouts).unwrap());
+    }
+
+    #[test]
+    fn saves_leave_money_to_play_for() {
+        let stacks = [5000.0, 5000.0, 0.0];
+        let payouts = [1000.0, 600.0, 300.0];
+        let amounts = chop_deal(&stacks, &payouts, ChopMethod::ChipChip, 100.0).unwrap();
+        assert_close(amounts[0], 750.0);
+        assert_close(amounts[1], 750.0);
+        // The busted player already holds third
+        assert_close(amounts[2], 300.0);
+        assert_close(amounts.iter().sum::<f64>() + 100.0, 1900.0);
+
+        assert!(chop_deal(&stacks, &payouts, ChopMethod::Icm, 1500.0).is_err());
+        assert!(chop_deal(&stacks, &payouts, ChopMethod::Icm, -1.0).is_err());
+    }
+}
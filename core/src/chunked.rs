@@ -0,0 +1,302 @@
+use std::time::{Duration, Instant};
+
+use rand::rngs::StdRng;
+use rand::RngExt;
+
+use crate::estimate::{estimate_equity_with_options, estimate_equity_with_rng, plan_equity};
+use crate::monte_carlo::seeded_rng;
+use crate::types::{EquityEstimateMode, EquityMath, EquityOptions, EquityResult, SnapError};
+
+/// An equity calculation run a chunk of Monte Carlo samples at a time, for
+/// hosts that report progress, yield, or check for cancellation in between.
+///
+/// Every chunk draws from one generator seeded with `options.seed`, so a
+/// seeded run samples the same runouts as [`estimate_equity_with_options`]
+/// whatever the chunk size, on every host. `options.iterations` is the whole
+/// budget and `options.time_budget` bounds the whole run, not each chunk.
+/// Spots small enough for exact enumeration are solved in the first chunk.
+///
+/// With `options.threads` above 1, each chunk is split over that many
+/// threads, seeded from the run's generator; a seeded result then also
+/// depends on the chunk size.
+///
+/// ```
+/// use snapcall_core::{EquityChunks, EquityOptions};
+///
+/// let options = EquityOptions::new(50_000);
+/// let mut chunks = EquityChunks::new("", "AsAd", &["KcKd"], &options, 10_000).unwrap();
+/// while let Some((done, running)) = chunks.next_chunk().unwrap() {
+///     println!("{done}: {:.1}%", running.equities[0]);
+/// }
+/// let result = chunks.into_result().unwrap();
+/// assert_eq!(result.samples, 50_000);
+/// ```
+pub struct EquityChunks<'a> {
+    board: &'a str,
+    hero: &'a str,
+    villains: &'a [&'a str],
+    options: &'a EquityOptions,
+    chunk_samples: usize,
+    mode: EquityEstimateMode,
+    math: EquityMath,
+    deadline: Option<Instant>,
+    rng: StdRng,
+    done: usize,
+    merged: Option<EquityResult>,
+}
+
+impl<'a> EquityChunks<'a> {
+    /// Validates the spot and plans it like [`estimate_equity_with_options`];
+    /// no samples are drawn until [`next_chunk`](Self::next_chunk).
+    ///
+    /// # Errors
+    /// The same validation errors as [`estimate_equity_with_options`], or
+    /// `InvalidArgument` if `chunk_samples` is 0.
+    pub fn new(
+        board: &'a str,
+        hero: &'a str,
+        villains: &'a [&'a str],
+        options: &'a EquityOptions,
+        chunk_samples: usize,
+    ) -> Result<Self, SnapError> {
+        if chunk_samples == 0 {
+            return Err(SnapError::InvalidArgument(
+                "chunk size must be at least 1 sample".to_string(),
+            ));
+        }
+        let (mode, math) = plan_equity(board, hero, villains, options)?;
+        Ok(Self {
+            board,
+            hero,
+            villains,
+            options,
+            chunk_samples,
+            mode,
+            math,
+            deadline: options.time_budget.map(|budget| Instant::now() + budget),
+            rng: seeded_rng(options.seed),
+            done: 0,
+            merged: None,
+        })
+    }
+
+    /// How the spot is solved.
+    pub fn mode(&self) -> EquityEstimateMode {
+        self.mode
+    }
+
+    /// State-space size behind [`mode`](Self::mode).
+    pub fn math(&self) -> EquityMath {
+        self.math
+    }
+
+    /// Runs the next chunk and returns the sample budget used so far with
+    /// the running result, or `None` once the run is finished. An exact
+    /// spot reports the whole budget after its only chunk.
+    ///
+    /// # Errors
+    /// Any error from sampling the chunk.
+    pub fn next_chunk(&mut self) -> Result<Option<(usize, &EquityResult)>, SnapError> {
+        // Read the clock once, so a deadline passing mid-call cannot leave a
+        // later chunk with no time to sample
+        let time_left = self
+            .deadline
+            .map(|deadline| deadline.saturating_duration_since(Instant::now()));
+        if self.merged.is_some()
+            && (self.done >= self.options.iterations || time_left == Some(Duration::ZERO))
+        {
+            return Ok(None);
+        }
+        if self.mode == EquityEstimateMode::ExactEnumeration {
+            let result =
+                estimate_equity_with_options(self.board, self.hero, self.villains, self.options)?;
+            self.done = self.options.iterations;
+            return Ok(Some((self.done, self.merged.insert(result))));
+        }
+
+        let chunk = self.chunk_samples.min(self.options.iterations - self.done);
+        let chunk_options = EquityOptions {
+            mode: Some(EquityEstimateMode::MonteCarlo),
+            dead_cards: self.options.dead_cards.clone(),
+            time_budget: time_left,
+            ..EquityOptions::new(chunk)
+        };
+        let result = if self.options.threads > 1 {
+            let threaded = EquityOptions {
+                seed: Some(self.rng.random()),
+                threads: self.options.threads,
+                ..chunk_options
+            };
+            estimate_equity_with_options(self.board, self.hero, self.villains, &threaded)?
+        } else {
+            estimate_equity_with_rng(
+                self.board,
+                self.hero,
+                self.villains,
+                &chunk_options,
+                &mut self.rng,
+            )?
+        };
+        self.done += chunk;
+        match &mut self.merged {
+            Some(total) => total.merge(&result),
+            None => self.merged = Some(result),
+        }
+        Ok(self.merged.as_ref().map(|running| (self.done, running)))
+    }
+
+    /// The merged result of every chunk run so far.
+    ///
+    /// # Errors
+    /// `InvalidArgument` if no chunk has run yet.
+    pub fn into_result(self) -> Result<EquityResult, SnapError> {
+        self.merged
+            .ok_or_else(|| SnapError::InvalidArgument("no samples".to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::input::parse_cards;
+
+    fn run(chunks: &mut EquityChunks) -> Vec<usize> {
+        let mut reports = Vec::new();
+        while let Some((done, _)) = chunks.next_chunk().unwrap() {
+            reports.push(done);
+        }
+        reports
+    }
+
+    #[test]
+    fn samples_the_whole_budget_in_chunks() {
+        let options = EquityOptions::new(25_000);
+        let mut chunks = EquityChunks::new("", "AsAd", &["KcKd"], &options, 10_000).unwrap();
+        assert_eq!(chunks.mode(), EquityEstimateMode::MonteCarlo);
+        assert_eq!(run(&mut chunks), vec![10_000, 20_000, 25_000]);
+        assert_eq!(chunks.into_result().unwrap().samples, 25_000);
+    }
+
+    #[test]
+    fn solves_exact_spots_in_one_chunk() {
+        let options = EquityOptions::new(10_000);
+        let mut chunks = EquityChunks::new("AhKdQc7s", "AsKs", &["JdJc"], &options, 100).unwrap();
+        assert_eq!(chunks.mode(), EquityEstimateMode::ExactEnumeration);
+        assert_eq!(run(&mut chunks), vec![10_000]);
+        let exact = estimate_equity_with_options("AhKdQc7s", "AsKs", &["JdJc"], &options).unwrap();
+        assert_eq!(chunks.into_result().unwrap().equities, exact.equities);
+    }
+
+    #[test]
+    fn seeded_runs_repeat() {
+        let options = EquityOptions {
+            seed: Some(7),
+            ..EquityOptions::new(30_000)
+        };
+        let result = || {
+            let mut chunks = EquityChunks::new("", "AsAd", &["KK+"], &options, 10_000).unwrap();
+            run(&mut chunks);
+            chunks.into_result().unwrap().equities
+        };
+        assert_eq!(result(), result());
+    }
+
+    #[test]
+    fn seeded_runs_do_not_depend_on_the_chunk_size() {
+        let options = EquityOptions {
+            mode: Some(EquityEstimateMode::MonteCarlo),
+            seed: Some(42),
+            ..EquityOptions::new(30_000)
+        };
+        let chunked = |chunk_samples| {
+            let mut chunks =
+                EquityChunks::new("Ah7d2c", "AsKs", &["QQ+", "JTs"], &options, chunk_samples)
+                    .unwrap();
+            run(&mut chunks);
+            chunks.into_result().unwrap()
+        };
+        let whole =
+            estimate_equity_with_options("Ah7d2c", "AsKs", &["QQ+", "JTs"], &options).unwrap();
+        for result in [chunked(10_000), chunked(7_001), chunked(30_000)] {
+            assert_eq!(result.samples, whole.samples);
+            for (a, b) in result.equities.iter().zip(&whole.equities) {
+                assert!((a - b).abs() < 1e-9, "{a} vs {b}");
+            }
+        }
+    }
+
+    #[test]
+    fn splits_chunks_over_threads() {
+        let options = EquityOptions {
+            seed: Some(9),
+            threads: 3,
+            ..EquityOptions::new(25_000)
+        };
+        let result = || {
+            let mut chunks = EquityChunks::new("", "AsAd", &["KcKd"], &options, 10_000).unwrap();
+            assert_eq!(run(&mut chunks), vec![10_000, 20_000, 25_000]);
+            chunks.into_result().unwrap()
+        };
+        let first = result();
+        assert_eq!(first.samples, 25_000);
+        assert_eq!(first.equities, result().equities);
+    }
+
+    #[test]
+    fn keeps_dead_cards_out_of_every_chunk() {
+        // With the other two kings dead, villain's kings can never make a
+        // set, so hero's aces hold up far more often.
+        let options = EquityOptions {
+            dead_cards: parse_cards("Kh Ks").unwrap(),
+            seed: Some(1),
+            ..EquityOptions::new(20_000)
+        };
+        let mut chunks = EquityChunks::new("", "AsAd", &["KcKd"], &options, 5_000).unwrap();
+        run(&mut chunks);
+        let with_dead = chunks.into_result().unwrap().equities[0];
+        let live = estimate_equity_with_options(
+            "",
+            "AsAd",
+            &["KcKd"],
+            &EquityOptions {
+                seed: Some(1),
+                ..EquityOptions::new(20_000)
+            },
+        )
+        .unwrap()
+        .equities[0];
+        assert!(with_dead > live + 2.0, "{with_dead} vs {live}");
+    }
+
+    #[test]
+    fn an_expired_deadline_still_returns_a_result() {
+        let options = EquityOptions {
+            time_budget: Some(Duration::ZERO),
+            ..EquityOptions::new(50_000)
+        };
+        let mut chunks = EquityChunks::new("", "AsAd", &["KK+"], &options, 10_000).unwrap();
+        assert_eq!(run(&mut chunks), vec![10_000]);
+        let result = chunks.into_result().unwrap();
+        assert!(
+            result.samples > 0 && result.samples < 10_000,
+            "{}",
+            result.samples
+        );
+
+        let whole = estimate_equity_with_options("", "AsAd", &["KK+"], &options).unwrap();
+        assert!(
+            whole.samples > 0 && whole.samples < 50_000,
+            "{}",
+            whole.samples
+        );
+    }
+
+    #[test]
+    fn rejects_empty_chunks() {
+        let options = EquityOptions::new(1_000);
+        assert!(matches!(
+            EquityChunks::new("", "AsAd", &["KcKd"], &options, 0),
+            Err(SnapError::InvalidArgument(_))
+        ));
+    }
+}
@@ -0,0 +1,375 @@
+use rand::seq::SliceRandom;
+use rs_poker::core::{Card, Deck, Rank, Rankable};
+
+use crate::monte_carlo::seeded_rng;
+use crate::types::SnapError;
+
+/// Betting street, identified by how many board cards are showing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Street {
+    PreFlop,
+    Flop,
+    Turn,
+    River,
+}
+
+impl Street {
+    /// Number of community cards visible on this street.
+    pub fn board_len(self) -> usize {
+        match self {
+            Self::PreFlop => 0,
+            Self::Flop => 3,
+            Self::Turn => 4,
+            Self::River => 5,
+        }
+    }
+
+    /// The street after this one, `None` on the river.
+    pub fn next(self) -> Option<Street> {
+        match self {
+            Self::PreFlop => Some(Self::Flop),
+            Self::Flop => Some(Self::Turn),
+            Self::Turn => Some(Self::River),
+            Self::River => None,
+        }
+    }
+}
+
+impl std::str::FromStr for Street {
+    type Err = SnapError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "preflop" | "pre-flop" => Ok(Self::PreFlop),
+            "flop" => Ok(Self::Flop),
+            "turn" => Ok(Self::Turn),
+            "river" => Ok(Self::River),
+            other => Err(SnapError::InvalidHand(format!(
+                "Unknown street '{}', expected preflop/flop/turn/river",
+                other
+            ))),
+        }
+    }
+}
+
+/// A randomly dealt hand.
+///
+/// - `hole_cards[i]` belongs to seat `i`.
+/// - `ranks[i]` evaluates seat `i`'s hole cards plus the visible board.
+/// - `winners` lists the seats holding the best hand (more than one on a tie).
+#[derive(Debug, Clone, PartialEq)]
+pub struct DealtHand {
+    pub hole_cards: Vec<[Card; 2]>,
+    pub board: Vec<Card>,
+    pub ranks: Vec<Rank>,
+    pub winners: Vec<usize>,
+}
+
+/// A shuffled deck dealt from the top, tracking every card that has left it.
+///
+/// The same `seed` always shuffles the same way; `None` draws a fresh seed.
+/// Burned cards count as dealt but are not returned to the caller.
+#[derive(Debug, Clone)]
+pub struct Dealer {
+    deck: Vec<Card>,
+    next: usize,
+}
+
+impl Dealer {
+    pub fn new(seed: Option<u64>) -> Self {
+        let mut rng = seeded_rng(seed);
+        let mut deck: Vec<Card> = Deck::default().into_iter().collect();
+        deck.sort();
+        deck.shuffle(&mut rng);
+        Self { deck, next: 0 }
+    }
+
+    /// Deals the top card.
+    ///
+    /// # Errors
+    /// Returns [`SnapError::InvalidHand`] once the deck is empty.
+    pub fn deal_card(&mut self) -> Result<Card, SnapError> {
+        let card = *self
+            .deck
+            .get(self.next)
+            .ok_or_else(|| SnapError::InvalidHand("Deck exhausted".to_string()))?;
+        self.next += 1;
+        Ok(card)
+    }
+
+    /// Deals two cards to each of `seats` seats, seat 0 first.
+    ///
+    /// # Errors
+    /// Returns [`SnapError::InvalidHand`] when the deck runs out; nothing is
+    /// dealt in that case.
+    pub fn deal_hole_cards(&mut self, seats: usize) -> Result<Vec<[Card; 2]>, SnapError> {
+        self.ensure(2 * seats)?;
+        (0..seats)
+            .map(|_| Ok([self.deal_card()?, self.deal_card()?]))
+            .collect()
+    }
+
+    /// Discards the top card face down.
+    ///
+    /// # Errors
+    /// Returns [`SnapError::InvalidHand`] once the deck is empty.
+    pub fn burn(&mut self) -> Result<(), SnapError> {
+        self.deal_card().map(|_| ())
+    }
+
+    /// Deals `count` community cards.
+    ///
+    /// # Errors
+    /// Returns [`SnapError::InvalidHand`] when fewer than `count` cards are
+    /// left; nothing is dealt in that case.
+    pub fn deal_board(&mut self, count: usize) -> Result<Vec<Card>, SnapError> {
+        self.ensure(count)?;
+        (0..count).map(|_| self.deal_card()).collect()
+    }
+
+    /// Every card dealt or burned so far, in order.
+    pub fn dealt(&self) -> &[Card] {
+        &self.deck[..self.next]
+    }
+
+    /// Cards still in the deck.
+    pub fn remaining(&self) -> usize {
+        self.deck.len() - self.next
+    }
+
+    fn ensure(&self, count: usize) -> Result<(), SnapError> {
+        if count > self.remaining() {
+            return Err(SnapError::InvalidHand(format!(
+                "Deck has {} cards left, need {}",
+                self.remaining(),
+                count
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Most seats a [`Table`] deals to: 22 hands plus a board and three burns
+/// fit in 52 cards.
+pub const MAX_SEATS: usize = 22;
+
+/// One hand at a table, dealt street by street the way a dealer would: hole
+/// cards to every seat first, then a burn before the flop, turn, and river.
+///
+/// The same `seed` always deals the same hand; `None` draws a fresh seed.
+#[derive(Debug, Clone)]
+pub struct Table {
+    dealer: Dealer,
+    hole_cards: Vec<[Card; 2]>,
+    board: Vec<Card>,
+    street: Street,
+}
+
+impl Table {
+    /// Shuffles and deals hole cards to `seats` seats.
+    ///
+    /// # Errors
+    /// Returns [`SnapError::InvalidHand`] for fewer than 2 or more than
+    /// [`MAX_SEATS`] seats.
+    pub fn new(seats: usize, seed: Option<u64>) -> Result<Self, SnapError> {
+        if seats < 2 {
+            return Err(SnapError::InvalidHand(
+                "Need at least 2 players".to_string(),
+            ));
+        }
+        if seats > MAX_SEATS {
+            return Err(SnapError::InvalidHand(
+                "Too many players/cards for a 52-card deck".to_string(),
+            ));
+        }
+        let mut dealer = Dealer::new(seed);
+        let hole_cards = dealer.deal_hole_cards(seats)?;
+        Ok(Self {
+            dealer,
+            hole_cards,
+            board: Vec::new(),
+            street: Street::PreFlop,
+        })
+    }
+
+    /// Burns and deals the next street, returning its new board cards.
+    ///
+    /// # Errors
+    /// Returns [`SnapError::InvalidHand`] once the river is out.
+    pub fn next_street(&mut self) -> Result<Vec<Card>, SnapError> {
+        let next = self
+            .street
+            .next()
+            .ok_or_else(|| SnapError::InvalidHand("The river is already dealt".to_string()))?;
+        self.dealer.burn()?;
+        let cards = self
+            .dealer
+            .deal_board(next.board_len() - self.street.board_len())?;
+        self.board.extend_from_slice(&cards);
+        self.street = next;
+        Ok(cards)
+    }
+
+    /// Deals streets until `street` is showing; nothing when it already is
+    /// or has passed.
+    ///
+    /// # Errors
+    /// As [`next_street`](Self::next_street).
+    pub fn deal_to(&mut self, street: Street) -> Result<(), SnapError> {
+        while self.street < street {
+            self.next_street()?;
+        }
+        Ok(())
+    }
+
+    pub fn street(&self) -> Street {
+        self.street
+    }
+
+    /// `hole_cards()[i]` belongs to seat `i`.
+    pub fn hole_cards(&self) -> &[[Card; 2]] {
+        &self.hole_cards
+    }
+
+    pub fn board(&self) -> &[Card] {
+        &self.board
+    }
+
+    /// The deck, for every card dealt or burned and the count left.
+    pub fn dealer(&self) -> &Dealer {
+        &self.dealer
+    }
+
+    /// Every seat's hand on the board so far and who is winning.
+    pub fn showdown(&self) -> DealtHand {
+        let ranks: Vec<Rank> = self
+            .hole_cards
+            .iter()
+            .map(|hole| {
+                let mut cards = hole.to_vec();
+                cards.extend_from_slice(&self.board);
+                cards.rank()
+            })
+            .collect();
+        let winners = match ranks.iter().max() {
+            Some(best) => (0..ranks.len()).filter(|&i| ranks[i] == *best).collect(),
+            None => Vec::new(),
+        };
+        DealtHand {
+            hole_cards: self.hole_cards.clone(),
+            board: self.board.clone(),
+            ranks,
+            winners,
+        }
+    }
+}
+
+/// Deals `num_players` random hands and the board up to `street` on a
+/// [`Table`], burns included.
+///
+/// The same `seed` always produces the same deal; `None` draws a fresh seed.
+///
+/// # Errors
+/// Returns [`SnapError`] when fewer than two players are requested or the
+/// deck cannot cover every seat plus the board.
+pub fn deal_random_hand(
+    num_players: usize,
+    street: Street,
+    seed: Option<u64>,
+) -> Result<DealtHand, SnapError> {
+    let mut table = Table::new(num_players, seed)?;
+    table.deal_to(street)?;
+    Ok(table.showdown())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+
+    #[test]
+    fn deals_requested_shape() {
+        let hand = deal_random_hand(6, Street::Turn, Some(7)).unwrap();
+        assert_eq!(hand.hole_cards.len(), 6);
+        assert_eq!(hand.board.len(), 4);
+        assert_eq!(hand.ranks.len(), 6);
+        assert!(!hand.winners.is_empty());
+    }
+
+    #[test]
+    fn dealt_cards_are_unique() {
+        let hand = deal_random_hand(9, Street::River, None).unwrap();
+        let mut seen = HashSet::new();
+        for c in hand.hole_cards.iter().flatten().chain(&hand.board) {
+            assert!(seen.insert(*c), "duplicate card {c}");
+        }
+    }
+
+    #[test]
+    fn same_seed_same_deal() {
+        let a = deal_random_hand(4, Street::River, Some(42)).unwrap();
+        let b = deal_random_hand(4, Street::River, Some(42)).unwrap();
+        assert_eq!(a, b);
+    }
+
+    #[test]
+    fn rejects_bad_player_counts() {
+        assert!(deal_random_hand(1, Street::Flop, None).is_err());
+        assert!(deal_random_hand(MAX_SEATS + 1, Street::Flop, None).is_err());
+        assert!(deal_random_hand(MAX_SEATS, Street::River, None).is_ok());
+    }
+
+    #[test]
+    fn dealer_tracks_dealt_cards() {
+        let mut dealer = Dealer::new(Some(3));
+        let holes = dealer.deal_hole_cards(2).unwrap();
+        dealer.burn().unwrap();
+        let flop = dealer.deal_board(3).unwrap();
+        assert_eq!(dealer.remaining(), 52 - 8);
+        assert_eq!(dealer.dealt()[..4], [holes[0], holes[1]].concat()[..]);
+        assert_eq!(dealer.dealt()[5..], flop[..]);
+
+        let mut again = Dealer::new(Some(3));
+        assert_eq!(again.deal_hole_cards(2).unwrap(), holes);
+    }
+
+    #[test]
+    fn dealer_refuses_to_overdeal() {
+        let mut dealer = Dealer::new(None);
+        dealer.deal_board(50).unwrap();
+        assert!(dealer.deal_hole_cards(2).is_err());
+        assert_eq!(dealer.remaining(), 2);
+        dealer.deal_hole_cards(1).unwrap();
+        assert!(dealer.burn().is_err());
+    }
+
+    #[test]
+    fn table_burns_before_each_street() {
+        let mut table = Table::new(3, Some(11)).unwrap();
+        assert_eq!(table.street(), Street::PreFlop);
+        let flop = table.next_street().unwrap();
+        assert_eq!(flop.len(), 3);
+        table.deal_to(Street::River).unwrap();
+        assert_eq!(table.street(), Street::River);
+        assert_eq!(table.board().len(), 5);
+        // 6 hole cards, 3 burns, 5 board cards
+        assert_eq!(table.dealer().remaining(), 52 - 14);
+        assert_eq!(table.dealer().dealt()[7..10], flop[..]);
+        assert!(table.next_street().is_err());
+
+        let showdown = table.showdown();
+        assert_eq!(showdown.board, table.board());
+        assert_eq!(
+            showdown,
+            deal_random_hand(3, Street::River, Some(11)).unwrap()
+        );
+    }
+
+    #[test]
+    fn parses_street_names() {
+        assert_eq!("River".parse::<Street>().unwrap(), Street::River);
+        assert_eq!("preflop".parse::<Street>().unwrap(), Street::PreFlop);
+        assert!("fifth".parse::<Street>().is_err());
+    }
+}
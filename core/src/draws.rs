@@ -0,0 +1,314 @@
+use std::collections::HashSet;
+
+use rs_poker::core::{Card, Deck, Rankable, Suit};
+
+use crate::range::expand_weighted_range;
+use crate::types::SnapError;
+
+/// Kind of straight draw, by the number of ranks that complete it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StraightDraw {
+    /// One rank completes it (usually 4 outs), including one-ended draws
+    /// like `JQKA`.
+    Gutshot,
+    /// Two ranks complete it without four connected cards (e.g. `5 7 8 9 J`).
+    DoubleGutshot,
+    /// Four connected ranks with both ends open (usually 8 outs).
+    OpenEnded,
+}
+
+impl std::fmt::Display for StraightDraw {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            StraightDraw::Gutshot => write!(f, "gutshot"),
+            StraightDraw::DoubleGutshot => write!(f, "double_gutshot"),
+            StraightDraw::OpenEnded => write!(f, "open_ended"),
+        }
+    }
+}
+
+/// Hero's flush and straight draws on a flop or turn.
+///
+/// Only draws that use a hole card count: a straight or flush the board
+/// makes on its own is not hero's. `outs` are the unseen cards that give
+/// hero a straight or flush, sorted.
+///
+/// The backdoor flags are flop-only runner-runner draws: three to a flush,
+/// or a straight two more ranks would complete, when hero has no draw of
+/// that kind already.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Draws {
+    pub flush_draw: bool,
+    pub straight_draw: Option<StraightDraw>,
+    pub backdoor_flush: bool,
+    pub backdoor_straight: bool,
+    pub outs: Vec<Card>,
+}
+
+impl Draws {
+    /// A flush draw together with any straight draw.
+    pub fn is_combo_draw(&self) -> bool {
+        self.flush_draw && self.straight_draw.is_some()
+    }
+}
+
+/// Finds hero's flush and straight draws and their outs.
+///
+/// A made straight or flush is not a draw to itself, so hero holding one
+/// reports no draw of that kind.
+///
+/// # Errors
+/// Returns [`SnapError::InvalidHand`] unless hero has 2 cards, the board
+/// 3 or 4, and all cards are distinct.
+pub fn find_draws(hero: &[Card], board: &[Card]) -> Result<Draws, SnapError> {
+    if hero.len() != 2 {
+        return Err(SnapError::InvalidHand(format!(
+            "Hero needs exactly 2 cards, got {}",
+            hero.len()
+        )));
+    }
+    if !(3..=4).contains(&board.len()) {
+        return Err(SnapError::InvalidHand(format!(
+            "Draws need a flop or turn (3-4 board cards), got {}",
+            board.len()
+        )));
+    }
+    let known: HashSet<Card> = hero.iter().chain(board).copied().collect();
+    if known.len() != hero.len() + board.len() {
+        return Err(SnapError::InvalidHand(
+            "Duplicate card in hero hand and board".to_string(),
+        ));
+    }
+
+    let hero_ranks = rank_mask(hero.iter().chain(board));
+    let board_ranks = rank_mask(board);
+
+    // Flush draw: four of a suit including a hole card, no flush yet
+    let suited_with_hero = |count: usize| {
+        Suit::suits().into_iter().find(|&suit| {
+            hero.iter().chain(board).filter(|c| c.suit == suit).count() == count
+                && hero.iter().any(|c| c.suit == suit)
+        })
+    };
+    let flush_suit = suited_with_hero(4);
+    let flop = board.len() == 3;
+
+    // Ranks that would give hero a straight the board alone does not have
+    let made = straight_high(hero_ranks).is_some();
+    let out_ranks: Vec<u8> = if made {
+        Vec::new()
+    } else {
+        (0..13u8)
+            .filter(|&r| {
+                let bit = 1 << r;
+                straight_high(hero_ranks | bit) > straight_high(board_ranks | bit)
+            })
+            .collect()
+    };
+    let straight_draw = match out_ranks.len() {
+        0 => None,
+        1 => Some(StraightDraw::Gutshot),
+        _ if open_ended(hero_ranks, &out_ranks) => Some(StraightDraw::OpenEnded),
+        _ => Some(StraightDraw::DoubleGutshot),
+    };
+    let backdoor_straight = flop
+        && !made
+        && straight_draw.is_none()
+        && (0..13u8).any(|a| {
+            (a + 1..13).any(|b| {
+                let bits = 1 << a | 1 << b;
+                straight_high(hero_ranks | bits) > straight_high(board_ranks | bits)
+            })
+        });
+
+    let mut outs: Vec<Card> = Deck::default()
+        .into_iter()
+        .filter(|c| !known.contains(c))
+        .filter(|c| Some(c.suit) == flush_suit || out_ranks.contains(&(c.value as u8)))
+        .collect();
+    outs.sort();
+
+    Ok(Draws {
+        flush_draw: flush_suit.is_some(),
+        straight_draw,
+        backdoor_flush: flop && suited_with_hero(3).is_some(),
+        backdoor_straight,
+        outs,
+    })
+}
+
+/// One of hero's outs graded against a villain range.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OutQuality {
+    pub card: Card,
+    /// Share of the villain range (by weight) that still beats hero once
+    /// this card falls; 0 for a clean out.
+    pub beaten_by: f64,
+}
+
+impl OutQuality {
+    /// No villain combo beats hero after this card.
+    pub fn is_clean(&self) -> bool {
+        self.beaten_by == 0.0
+    }
+}
+
+/// Grades each of hero's [`find_draws`] outs by how much of `villain_range`
+/// still beats hero when it comes, e.g. a flush card that pairs the board
+/// and fills up villain's sets. Villain combos that share a card with hero,
+/// the board, or the out are left out.
+///
+/// # Errors
+/// As [`find_draws`], plus any error parsing `villain_range`.
+pub fn grade_outs(
+    hero: &[Card],
+    board: &[Card],
+    villain_range: &str,
+) -> Result<Vec<OutQuality>, SnapError> {
+    let draws = find_draws(hero, board)?;
+    let villains = expand_weighted_range(villain_range)?;
+    let rank_with = |hole: &[Card], runout: &[Card]| {
+        let cards: Vec<Card> = hole.iter().chain(runout).copied().collect();
+        cards.as_slice().rank()
+    };
+
+    Ok(draws
+        .outs
+        .iter()
+        .map(|&out| {
+            let runout: Vec<Card> = board.iter().copied().chain([out]).collect();
+            let hero_rank = rank_with(hero, &runout);
+            let (beating, total) = villains
+                .iter()
+                .filter(|(combo, _)| !combo.iter().any(|c| hero.contains(c) || runout.contains(c)))
+                .fold((0.0, 0.0), |(beating, total), (combo, weight)| {
+                    let ahead = rank_with(combo, &runout) > hero_rank;
+                    (beating + if ahead { *weight } else { 0.0 }, total + weight)
+                });
+            OutQuality {
+                card: out,
+                beaten_by: if total > 0.0 { beating / total } else { 0.0 },
+            }
+        })
+        .collect())
+}
+
+/// Bit `r` set for each rank present, deuce = 0 through ace = 12.
+fn rank_mask<'a>(cards: impl IntoIterator<Item = &'a Card>) -> u16 {
+    cards
+        .into_iter()
+        .fold(0, |mask, c| mask | 1 << c.value as u8)
+}
+
+/// Top rank of the highest straight in `mask`, counting the wheel (`A2345`)
+/// as five-high.
+fn straight_high(mask: u16) -> Option<u8> {
+    // Shift up one and put the ace below the deuce as well
+    let ranks = (mask << 1) | (mask >> 12 & 1);
+    (0..=9u8)
+        .rev()
+        .find(|&low| ranks >> low & 0b11111 == 0b11111)
+        .map(|low| low + 3)
+}
+
+/// Four consecutive ranks whose ranks on both sides are outs (the ace sits
+/// below `2345`).
+fn open_ended(mask: u16, out_ranks: &[u8]) -> bool {
+    (0..=8u8).any(|low| {
+        let window = 0b1111 << low;
+        let below = if low == 0 { 12 } else { low - 1 };
+        mask & window == window && out_ranks.contains(&below) && out_ranks.contains(&(low + 4))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::input::parse_cards;
+    use rs_poker::core::Value;
+
+    fn draws(hero: &str, board: &str) -> Draws {
+        find_draws(&parse_cards(hero).unwrap(), &parse_cards(board).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn combo_draw_counts_fifteen_outs() {
+        let d = draws("9h8h", "7h6c2h");
+        assert!(d.flush_draw);
+        assert_eq!(d.straight_draw, Some(StraightDraw::OpenEnded));
+        assert!(d.is_combo_draw());
+        // 9 hearts plus the non-heart fives and tens
+        assert_eq!(d.outs.len(), 15);
+    }
+
+    #[test]
+    fn gutshot_and_double_gutshot() {
+        let gutshot = draws("AhKd", "QcTs3d");
+        assert_eq!(gutshot.straight_draw, Some(StraightDraw::Gutshot));
+        assert_eq!(gutshot.outs.len(), 4);
+        assert!(!gutshot.flush_draw);
+
+        let double = draws("9c7d", "Jh8s5c");
+        assert_eq!(double.straight_draw, Some(StraightDraw::DoubleGutshot));
+        assert_eq!(double.outs.len(), 8);
+    }
+
+    #[test]
+    fn wheel_draw_is_open_ended() {
+        let d = draws("3c2d", "5h4s9c");
+        assert_eq!(d.straight_draw, Some(StraightDraw::OpenEnded));
+        assert_eq!(d.outs.len(), 8);
+    }
+
+    #[test]
+    fn board_straights_are_not_hero_draws() {
+        // Any four or nine straightens the board for everyone
+        let d = draws("AdKs", "5h6c7d8s");
+        assert_eq!(d.straight_draw, None);
+        assert!(d.outs.is_empty());
+    }
+
+    #[test]
+    fn made_hands_have_no_draw_to_themselves() {
+        let d = draws("9h8d", "7c6s5h");
+        assert_eq!(d.straight_draw, None);
+    }
+
+    #[test]
+    fn backdoor_draws_on_the_flop_only() {
+        let d = draws("AhJh", "Kh7c2d");
+        assert!(d.backdoor_flush);
+        // Q and T make broadway
+        assert!(d.backdoor_straight);
+        assert!(d.outs.is_empty());
+
+        let turn = draws("AhJh", "Kh7c2d3s");
+        assert!(!turn.backdoor_flush && !turn.backdoor_straight);
+        // An open-ender is not also a backdoor straight draw
+        assert!(!draws("9h8d", "7c6s2h").backdoor_straight);
+    }
+
+    #[test]
+    fn outs_that_pair_the_board_are_dirty_against_sets() {
+        let hero = parse_cards("AhKh").unwrap();
+        let board = parse_cards("Qh7h3c2d").unwrap();
+        let graded = grade_outs(&hero, &board, "QQ").unwrap();
+        assert_eq!(graded.len(), 9);
+        // The three and deuce of hearts fill up the set
+        let trey = graded
+            .iter()
+            .find(|o| o.card.value == Value::Three)
+            .unwrap();
+        assert_eq!(trey.beaten_by, 1.0);
+        assert!(!trey.is_clean());
+        assert_eq!(graded.iter().filter(|o| o.is_clean()).count(), 7);
+    }
+
+    #[test]
+    fn rejects_bad_inputs() {
+        let hero = parse_cards("AhKh").unwrap();
+        assert!(find_draws(&hero, &parse_cards("Qh7d").unwrap()).is_err());
+        assert!(find_draws(&hero, &parse_cards("Qh7d2c3s4s").unwrap()).is_err());
+        assert!(find_draws(&hero, &parse_cards("Ah7d2c").unwrap()).is_err());
+    }
+}
@@ -3,7 +3,7 @@ use std::collections::HashSet;
 use rs_poker::core::{Card, FlatHand, Rank, Rankable, Suit, Value};
 
 use crate::input::HoleCardsInput;
-use crate::types::{EquityEstimateMode, EquityResult};
+use crate::types::{EquityEstimateMode, EquityMath, EquityResult};
 
 /// Compute the binomial coefficient C(n, k).
 pub(crate) fn n_choose_k(n: usize, k: usize) -> usize {
@@ -55,10 +55,13 @@ fn for_each_combination(cards: &[Card], k: usize, mut callback: impl FnMut(&[Car
     }
 }
 
+/// A range player's seat, combos, and weights (`None` when unweighted).
+type RangePlayer<'a> = (usize, &'a Vec<FlatHand>, Option<&'a [f64]>);
+
 /// Immutable context for exact enumeration.
 struct EnumerationContext<'a> {
     players: &'a [HoleCardsInput],
-    range_players: Vec<(usize, &'a Vec<FlatHand>)>,
+    range_players: Vec<RangePlayer<'a>>,
     available: Vec<Card>,
     board_set: &'a HashSet<Card>,
     fixed_known: &'a HashSet<Card>,
@@ -70,7 +73,11 @@ struct EnumerationContext<'a> {
 /// Mutable state for exact enumeration, including reusable buffers.
 struct EnumerationState {
     range_assignments: Vec<[Card; 2]>,
-    wins: Vec<usize>,
+    /// Product of the weights of the current range assignments.
+    assignment_weight: f64,
+    wins: Vec<f64>,
+    outright: Vec<f64>,
+    total_weight: f64,
     total_combos: usize,
     // Reusable buffers for the hot loop
     hole_cards_buf: Vec<[Card; 2]>,
@@ -89,6 +96,7 @@ pub(crate) fn estimate_equity_exact_enumeration(
     board_set: &HashSet<Card>,
     players: &[HoleCardsInput],
     fixed_known: &HashSet<Card>,
+    math: EquityMath,
 ) -> EquityResult {
     let num_players = players.len();
     let missing_board = 5 - board_cards.len();
@@ -105,13 +113,16 @@ pub(crate) fn estimate_equity_exact_enumeration(
     // unknown_count: need 2 cards each
     let mut partial_count = 0usize;
     let mut unknown_count = 0usize;
-    let mut range_players: Vec<(usize, &Vec<FlatHand>)> = Vec::new();
+    let mut range_players: Vec<RangePlayer> = Vec::new();
 
     for (idx, p) in players.iter().enumerate() {
         match p {
             HoleCardsInput::Partial(_) => partial_count += 1,
             HoleCardsInput::Unknown => unknown_count += 1,
-            HoleCardsInput::Range(hands) => range_players.push((idx, hands)),
+            HoleCardsInput::Range(hands) => range_players.push((idx, hands, None)),
+            HoleCardsInput::WeightedRange(hands, weights) => {
+                range_players.push((idx, hands, Some(weights)))
+            }
             HoleCardsInput::Exact(_) => {}
         }
     }
@@ -132,7 +143,10 @@ pub(crate) fn estimate_equity_exact_enumeration(
 
     let mut state = EnumerationState {
         range_assignments: vec![[placeholder, placeholder]; ctx.range_players.len()],
-        wins: vec![0; num_players],
+        assignment_weight: 1.0,
+        wins: vec![0.0; num_players],
+        outright: vec![0.0; num_players],
+        total_weight: 0.0,
         total_combos: 0,
         hole_cards_buf: vec![[placeholder, placeholder]; num_players],
         full_board_buf: Vec::with_capacity(5),
@@ -142,22 +156,14 @@ pub(crate) fn estimate_equity_exact_enumeration(
 
     enumerate_ranges(&ctx, 0, &mut state);
 
-    let total_wins: usize = state.wins.iter().sum();
-    let equities = if total_wins == 0 {
-        vec![100.0 / num_players as f64; num_players]
-    } else {
-        state
-            .wins
-            .iter()
-            .map(|&w| (w as f64 / total_wins as f64) * 100.0)
-            .collect()
-    };
-
-    EquityResult {
-        equities,
-        mode: EquityEstimateMode::ExactEnumeration,
-        samples: state.total_combos,
-    }
+    EquityResult::from_weights(
+        &state.wins,
+        &state.outright,
+        state.total_weight,
+        state.total_combos,
+        EquityEstimateMode::ExactEnumeration,
+        math,
+    )
 }
 
 /// Recursively enumerate the cartesian product of Range players' hands.
@@ -186,7 +192,10 @@ fn enumerate_ranges(ctx: &EnumerationContext, depth: usize, state: &mut Enumerat
         // Destructure state for disjoint field borrows in the closure
         let EnumerationState {
             ref range_assignments,
+            assignment_weight,
             ref mut wins,
+            ref mut outright,
+            ref mut total_weight,
             ref mut total_combos,
             ref mut hole_cards_buf,
             ref mut full_board_buf,
@@ -221,7 +230,7 @@ fn enumerate_ranges(ctx: &EnumerationContext, depth: usize, state: &mut Enumerat
                         hole_cards_buf[idx] = [combo[cursor], combo[cursor + 1]];
                         cursor += 2;
                     }
-                    HoleCardsInput::Range(_) => {
+                    HoleCardsInput::Range(_) | HoleCardsInput::WeightedRange(..) => {
                         hole_cards_buf[idx] = range_assignments[range_cursor];
                         range_cursor += 1;
                     }
@@ -244,13 +253,18 @@ fn enumerate_ranges(ctx: &EnumerationContext, depth: usize, state: &mut Enumerat
             }
 
             if let Some(best) = ranks_buf.iter().max() {
+                let winners = ranks_buf.iter().filter(|r| *r == best).count();
                 for (i, r) in ranks_buf.iter().enumerate() {
                     if r == best {
-                        wins[i] += 1;
+                        wins[i] += assignment_weight;
+                        if winners == 1 {
+                            outright[i] += assignment_weight;
+                        }
                     }
                 }
             }
 
+            *total_weight += assignment_weight;
             *total_combos += 1;
         });
 
@@ -258,9 +272,10 @@ fn enumerate_ranges(ctx: &EnumerationContext, depth: usize, state: &mut Enumerat
     }
 
     // Current range player
-    let (_player_idx, hands) = ctx.range_players[depth];
+    let (_player_idx, hands, weights) = ctx.range_players[depth];
+    let outer_weight = state.assignment_weight;
 
-    for hand in hands {
+    for (i, hand) in hands.iter().enumerate() {
         debug_assert!(hand.len() >= 2);
         let mut iter = hand.iter().copied();
         let (Some(c1), Some(c2)) = (iter.next(), iter.next()) else {
@@ -282,9 +297,11 @@ fn enumerate_ranges(ctx: &EnumerationContext, depth: usize, state: &mut Enumerat
         }
 
         state.range_assignments[depth] = [c1, c2];
+        state.assignment_weight = outer_weight * weights.map_or(1.0, |w| w[i]);
 
         enumerate_ranges(ctx, depth + 1, state);
     }
+    state.assignment_weight = outer_weight;
 }
 
 /// Estimate the total number of combinations for exact enumeration.
@@ -1,11 +1,20 @@
 use std::collections::HashSet;
 
-use rs_poker::core::Card;
+use rand::rngs::StdRng;
+use rand::Rng;
+use rs_poker::core::{Card, FlatHand};
 
-use crate::enumeration::{estimate_enumeration_count, estimate_equity_exact_enumeration};
+use crate::enumeration::{
+    estimate_enumeration_count, estimate_equity_exact_enumeration, n_choose_k,
+};
 use crate::input::{BoardCardsInput, HoleCardsInput};
-use crate::monte_carlo::estimate_equity_monte_carlo;
-use crate::types::{EquityResult, SnapError};
+use crate::monte_carlo::{
+    estimate_equity_monte_carlo, estimate_multi_board_monte_carlo, estimate_vs_random_monte_carlo,
+    seeded_rng, stream_seed,
+};
+use crate::types::{
+    EquityEstimateMode, EquityMath, EquityOptions, EquityResult, MultiBoardResult, SnapError,
+};
 
 /// Estimates equity for hero against one or more villains.
 ///
@@ -30,6 +39,57 @@ pub fn estimate_equity(
     hero: &str,
     villains: &[&str],
     iterations: usize,
+) -> Result<EquityResult, SnapError> {
+    estimate_equity_with_options(board, hero, villains, &EquityOptions::new(iterations))
+}
+
+/// Like [`estimate_equity`], with full control over the solver via
+/// [`EquityOptions`] (e.g. forcing exact enumeration or Monte Carlo).
+///
+/// With `options.threads` above 1, Monte Carlo samples are split evenly over
+/// that many threads and merged; exact enumeration stays on one thread.
+///
+/// # Errors
+/// In addition to the [`estimate_equity`] errors, returns
+/// [`SnapError::ResourceLimit`] when exact enumeration is forced but the state
+/// space exceeds `options.max_exact_states`.
+pub fn estimate_equity_with_options(
+    board: &str,
+    hero: &str,
+    villains: &[&str],
+    options: &EquityOptions,
+) -> Result<EquityResult, SnapError> {
+    if options.threads > 1
+        && plan_equity(board, hero, villains, options)?.0 == EquityEstimateMode::MonteCarlo
+    {
+        return sample_on_threads(options, |share, rng| {
+            estimate_equity_with_rng(board, hero, villains, share, rng)
+        });
+    }
+    estimate_equity_with_rng(
+        board,
+        hero,
+        villains,
+        options,
+        &mut seeded_rng(options.seed),
+    )
+}
+
+/// Like [`estimate_equity_with_options`], but Monte Carlo draws every random
+/// card from `rng` instead of an internal generator; `options.seed` is
+/// ignored.
+///
+/// Lets hosts supply their own (hardware, audited, or deterministic)
+/// generator. Exact enumeration never touches `rng`.
+///
+/// # Errors
+/// Same as [`estimate_equity_with_options`].
+pub fn estimate_equity_with_rng<R: Rng + ?Sized>(
+    board: &str,
+    hero: &str,
+    villains: &[&str],
+    options: &EquityOptions,
+    rng: &mut R,
 ) -> Result<EquityResult, SnapError> {
     if villains.is_empty() {
         return Err(SnapError::InvalidHand(
@@ -39,14 +99,470 @@ pub fn estimate_equity(
 
     // --- Parse inputs ---
     let board_input: BoardCardsInput = board.parse()?;
+
+    let players = parse_hero_and_villains(hero, villains)?;
+
+    estimate_players(board_input, players, options, rng)
+}
+
+/// Estimates equity for two or more players where every seat, including the
+/// first, may be exact cards, a single card, a range, or unknown (`""`).
+///
+/// Used for range-vs-range comparisons such as equity matrices. Validation and
+/// the exact-vs-Monte-Carlo decision are identical to [`estimate_equity`].
+///
+/// # Errors
+/// Returns [`SnapError`] on fewer than two players, invalid cards,
+/// conflicting/duplicate cards, or empty ranges.
+pub fn estimate_range_equity(
+    board: &str,
+    players: &[&str],
+    iterations: usize,
+) -> Result<EquityResult, SnapError> {
+    estimate_range_equity_with_options(board, players, &EquityOptions::new(iterations))
+}
+
+/// Like [`estimate_range_equity`], with full control over the solver via
+/// [`EquityOptions`].
+///
+/// # Errors
+/// Same as [`estimate_range_equity`] and [`estimate_equity_with_options`].
+pub fn estimate_range_equity_with_options(
+    board: &str,
+    players: &[&str],
+    options: &EquityOptions,
+) -> Result<EquityResult, SnapError> {
+    estimate_range_equity_with_rng(board, players, options, &mut seeded_rng(options.seed))
+}
+
+/// Like [`estimate_range_equity_with_options`], sampling from `rng` as
+/// [`estimate_equity_with_rng`] does.
+///
+/// # Errors
+/// Same as [`estimate_range_equity`] and [`estimate_equity_with_options`].
+pub fn estimate_range_equity_with_rng<R: Rng + ?Sized>(
+    board: &str,
+    players: &[&str],
+    options: &EquityOptions,
+    rng: &mut R,
+) -> Result<EquityResult, SnapError> {
+    if players.len() < 2 {
+        return Err(SnapError::InvalidHand(
+            "Need at least 2 players".to_string(),
+        ));
+    }
+
+    let board_input: BoardCardsInput = board.parse()?;
+    let parsed = players
+        .iter()
+        .map(|p| p.parse())
+        .collect::<Result<Vec<HoleCardsInput>, SnapError>>()?;
+
+    estimate_players(board_input, parsed, options, rng)
+}
+
+/// Hero's equity against `opponents` players holding random hands.
+///
+/// Gives the same answer as [`estimate_equity_with_options`] with
+/// `opponents` unknown (`""`) villains: small spots are enumerated exactly,
+/// and Monte Carlo uses a sampler specialised for random opponents.
+/// `equities[0]` is hero; every opponent has the same expected equity.
+///
+/// # Errors
+/// Returns [`SnapError::InvalidArgument`] for zero opponents, plus the
+/// [`estimate_equity_with_options`] errors.
+pub fn equity_vs_random(
+    board: &str,
+    hero: &str,
+    opponents: usize,
+    options: &EquityOptions,
+) -> Result<EquityResult, SnapError> {
+    equity_vs_random_with_rng(
+        board,
+        hero,
+        opponents,
+        options,
+        &mut seeded_rng(options.seed),
+    )
+}
+
+/// Like [`equity_vs_random`], drawing every random card from `rng` as
+/// [`estimate_equity_with_rng`] does; `options.seed` is ignored.
+///
+/// # Errors
+/// Same as [`equity_vs_random`].
+pub fn equity_vs_random_with_rng<R: Rng + ?Sized>(
+    board: &str,
+    hero: &str,
+    opponents: usize,
+    options: &EquityOptions,
+    rng: &mut R,
+) -> Result<EquityResult, SnapError> {
+    if opponents == 0 {
+        return Err(SnapError::InvalidArgument(
+            "Need at least 1 opponent".to_string(),
+        ));
+    }
+    if opponents.saturating_add(1).saturating_mul(2) > 52 {
+        return Err(SnapError::InvalidHand(
+            "Too many players/cards for a 52-card deck".to_string(),
+        ));
+    }
+
+    let board_input: BoardCardsInput = board.parse()?;
+    let players = parse_hero_and_villains(hero, &vec![""; opponents])?;
+    // Hero is exact cards or one card; the sampler deals whatever is missing
+    let hero_cards: Vec<Card> = match &players[0] {
+        HoleCardsInput::Exact(hand) => hand.iter().copied().collect(),
+        HoleCardsInput::Partial(card) => vec![*card],
+        _ => Vec::new(),
+    };
+    let plan = plan_players(board_input, players, options)?;
+    if plan.use_exact {
+        return Ok(estimate_equity_exact_enumeration(
+            &plan.board_cards,
+            &plan.board_set,
+            &plan.players,
+            &plan.fixed_known,
+            plan.math,
+        ));
+    }
+    estimate_vs_random_monte_carlo(
+        &plan.board_cards,
+        &plan.fixed_known,
+        &hero_cards,
+        opponents,
+        options.iterations,
+        options.time_budget,
+        plan.math,
+        rng,
+    )
+}
+
+/// Decides how [`estimate_equity_with_options`] would solve a spot without
+/// running it: the mode it would pick and the state-space size behind that
+/// choice.
+///
+/// Useful for callers that split a Monte Carlo run into chunks (progress
+/// reporting, cancellation) but must run exact enumeration in one go.
+///
+/// # Errors
+/// The same validation errors as [`estimate_equity_with_options`].
+pub fn plan_equity(
+    board: &str,
+    hero: &str,
+    villains: &[&str],
+    options: &EquityOptions,
+) -> Result<(EquityEstimateMode, EquityMath), SnapError> {
+    if villains.is_empty() {
+        return Err(SnapError::InvalidHand(
+            "Need at least 1 villain".to_string(),
+        ));
+    }
+    let board_input: BoardCardsInput = board.parse()?;
+    let players = parse_hero_and_villains(hero, villains)?;
+    let plan = plan_players(board_input, players, options)?;
+    let mode = if plan.use_exact {
+        EquityEstimateMode::ExactEnumeration
+    } else {
+        EquityEstimateMode::MonteCarlo
+    };
+    Ok((mode, plan.math))
+}
+
+/// Splits `options.iterations` over `options.threads` scoped threads (at most
+/// one per sample and [`std::thread::available_parallelism`]), runs `sample`
+/// on each share with Monte Carlo forced and its own generator (see
+/// [`stream_seed`]), and merges the results in thread order.
+fn sample_on_threads(
+    options: &EquityOptions,
+    sample: impl Fn(&EquityOptions, &mut StdRng) -> Result<EquityResult, SnapError> + Sync,
+) -> Result<EquityResult, SnapError> {
+    let cores = std::thread::available_parallelism().map_or(1, |n| n.get());
+    let threads = options.threads.min(cores).min(options.iterations.max(1));
+    let results: Vec<Result<EquityResult, SnapError>> = std::thread::scope(|scope| {
+        let mut handles = Vec::with_capacity(threads);
+        for k in 0..threads {
+            let share = EquityOptions {
+                iterations: options.iterations / threads
+                    + usize::from(k < options.iterations % threads),
+                mode: Some(EquityEstimateMode::MonteCarlo),
+                threads: 1,
+                ..options.clone()
+            };
+            let sample = &sample;
+            let handle = std::thread::Builder::new()
+                .spawn_scoped(scope, move || {
+                    let mut rng = seeded_rng(options.seed.map(|s| stream_seed(s, k as u64)));
+                    sample(&share, &mut rng)
+                })
+                .map_err(|err| {
+                    SnapError::ResourceLimit(format!("Could not start a sampling thread: {err}"))
+                });
+            match handle {
+                Ok(handle) => handles.push(handle),
+                // Threads already started are joined when the scope ends
+                Err(err) => return vec![Err(err)],
+            }
+        }
+        handles
+            .into_iter()
+            .map(|handle| {
+                handle
+                    .join()
+                    .unwrap_or_else(|panic| std::panic::resume_unwind(panic))
+            })
+            .collect()
+    });
+
+    let mut merged: Option<EquityResult> = None;
+    for result in results {
+        let result = result?;
+        match &mut merged {
+            Some(total) => total.merge(&result),
+            None => merged = Some(result),
+        }
+    }
+    merged.ok_or_else(|| SnapError::InvalidArgument("no samples".to_string()))
+}
+
+/// Validated inputs plus the exact-vs-Monte-Carlo decision.
+struct EquityPlan {
+    board_cards: Vec<Card>,
+    board_set: HashSet<Card>,
+    players: Vec<HoleCardsInput>,
+    fixed_known: HashSet<Card>,
+    math: EquityMath,
+    use_exact: bool,
+}
+
+/// Shared validation and dispatch for already-parsed players.
+fn estimate_players<R: Rng + ?Sized>(
+    board_input: BoardCardsInput,
+    players: Vec<HoleCardsInput>,
+    options: &EquityOptions,
+    rng: &mut R,
+) -> Result<EquityResult, SnapError> {
+    let plan = plan_players(board_input, players, options)?;
+    if plan.use_exact {
+        Ok(estimate_equity_exact_enumeration(
+            &plan.board_cards,
+            &plan.board_set,
+            &plan.players,
+            &plan.fixed_known,
+            plan.math,
+        ))
+    } else {
+        estimate_equity_monte_carlo(
+            &plan.board_cards,
+            &plan.fixed_known,
+            &plan.players,
+            options.iterations,
+            options.time_budget,
+            plan.math,
+            rng,
+        )
+    }
+}
+
+/// Validates already-parsed players and decides the solver.
+fn plan_players(
+    board_input: BoardCardsInput,
+    mut players: Vec<HoleCardsInput>,
+    options: &EquityOptions,
+) -> Result<EquityPlan, SnapError> {
     let board_cards = board_input.cards();
     let board_set: HashSet<Card> = board_cards.iter().copied().collect();
 
+    let num_players = players.len();
+    if board_cards.len() + 2 * num_players > 52 {
+        return Err(SnapError::InvalidHand(
+            "Too many players/cards for a 52-card deck".to_string(),
+        ));
+    }
+
+    let fixed_known = validate_players(&board_set, &options.dead_cards, &mut players)?;
+
+    let missing_board = 5 - board_cards.len();
+
+    // --- Decide mode: exact enumeration vs Monte Carlo ---
+    let mut partial_count = 0usize;
+    let mut unknown_count = 0usize;
+    let mut range_product: usize = 1;
+    let mut range_count = 0usize;
+
+    for p in &players {
+        match p {
+            HoleCardsInput::Partial(_) => partial_count += 1,
+            HoleCardsInput::Unknown => unknown_count += 1,
+            HoleCardsInput::Range(hands) | HoleCardsInput::WeightedRange(hands, _) => {
+                range_product = range_product.saturating_mul(hands.len());
+                range_count += 1;
+            }
+            HoleCardsInput::Exact(_) => {}
+        }
+    }
+
+    let non_range_slots = partial_count + 2 * unknown_count + missing_board;
+    // Available cards for the non-range combination: 52 − fixed_known − (approx 2 per range player)
+    let available_for_estimate = 52usize
+        .saturating_sub(fixed_known.len())
+        .saturating_sub(2 * range_count);
+
+    let total_states =
+        estimate_enumeration_count(available_for_estimate, non_range_slots, range_product);
+    let math = EquityMath {
+        assignment_combinations: range_product,
+        available_cards: available_for_estimate,
+        runout_slots: non_range_slots,
+        runout_combinations: n_choose_k(available_for_estimate, non_range_slots),
+        total_states,
+    };
+
+    let iterations = options.iterations;
+    let use_exact = match options.mode {
+        None => matches!(total_states, Some(n) if n > 0 && n <= iterations),
+        Some(EquityEstimateMode::MonteCarlo) => false,
+        Some(EquityEstimateMode::ExactEnumeration) => match total_states {
+            Some(n) if n > 0 && n <= options.max_exact_states => true,
+            Some(0) => {
+                return Err(SnapError::InvalidHand(
+                    "No cards left to enumerate".to_string(),
+                ))
+            }
+            _ => {
+                return Err(SnapError::ResourceLimit(format!(
+                    "Exact enumeration needs {} states, limit is {}",
+                    total_states.map_or("too many".to_string(), |n| n.to_string()),
+                    options.max_exact_states
+                )))
+            }
+        },
+    };
+
+    Ok(EquityPlan {
+        board_cards,
+        board_set,
+        players,
+        fixed_known,
+        math,
+        use_exact,
+    })
+}
+
+/// Equity for a hand played out on several boards at once (e.g. a
+/// double-board bomb pot): hole cards are shared and every board is completed
+/// from the same deck.
+///
+/// Always uses Monte Carlo sampling. Each board is worth an equal part of the
+/// pot; [`MultiBoardResult::pot_share`] is the resulting share of the whole pot.
+///
+/// # Errors
+/// Returns [`SnapError`] on fewer than one board, no villains, boards sharing
+/// a card, or any [`estimate_equity`] input error.
+pub fn estimate_multi_board_equity(
+    boards: &[&str],
+    hero: &str,
+    villains: &[&str],
+    iterations: usize,
+) -> Result<MultiBoardResult, SnapError> {
+    estimate_multi_board_equity_with_options(
+        boards,
+        hero,
+        villains,
+        &EquityOptions::new(iterations),
+    )
+}
+
+/// Like [`estimate_multi_board_equity`], with the sample budget, dead cards,
+/// time budget, and seed taken from `options`. The solver is always Monte
+/// Carlo on the calling thread, so `mode`, `max_exact_states`, and `threads`
+/// are ignored.
+///
+/// # Errors
+/// Same as [`estimate_multi_board_equity`], plus dead cards conflicting with
+/// a board or a hand.
+pub fn estimate_multi_board_equity_with_options(
+    boards: &[&str],
+    hero: &str,
+    villains: &[&str],
+    options: &EquityOptions,
+) -> Result<MultiBoardResult, SnapError> {
+    estimate_multi_board_equity_with_rng(
+        boards,
+        hero,
+        villains,
+        options,
+        &mut seeded_rng(options.seed),
+    )
+}
+
+/// Like [`estimate_multi_board_equity_with_options`], drawing every random
+/// card from `rng` as [`estimate_equity_with_rng`] does; `options.seed` is
+/// ignored.
+///
+/// # Errors
+/// Same as [`estimate_multi_board_equity_with_options`].
+pub fn estimate_multi_board_equity_with_rng<R: Rng + ?Sized>(
+    boards: &[&str],
+    hero: &str,
+    villains: &[&str],
+    options: &EquityOptions,
+    rng: &mut R,
+) -> Result<MultiBoardResult, SnapError> {
+    if boards.is_empty() {
+        return Err(SnapError::InvalidHand("Need at least 1 board".to_string()));
+    }
+    if villains.is_empty() {
+        return Err(SnapError::InvalidHand(
+            "Need at least 1 villain".to_string(),
+        ));
+    }
+
+    let mut players = parse_hero_and_villains(hero, villains)?;
+    let mut board_cards: Vec<Vec<Card>> = Vec::with_capacity(boards.len());
+    let mut board_set: HashSet<Card> = HashSet::new();
+    for (idx, board) in boards.iter().enumerate() {
+        let cards = board.parse::<BoardCardsInput>()?.cards();
+        for &c in &cards {
+            if !board_set.insert(c) {
+                return Err(SnapError::InvalidHand(format!(
+                    "Board {} shares card {} with another board",
+                    idx + 1,
+                    c
+                )));
+            }
+        }
+        board_cards.push(cards);
+    }
+
+    if 5 * boards.len() + 2 * players.len() + options.dead_cards.len() > 52 {
+        return Err(SnapError::InvalidHand(
+            "Too many players/cards for a 52-card deck".to_string(),
+        ));
+    }
+
+    let fixed_known = validate_players(&board_set, &options.dead_cards, &mut players)?;
+    estimate_multi_board_monte_carlo(
+        &board_cards,
+        &fixed_known,
+        &players,
+        options.iterations,
+        options.time_budget,
+        rng,
+    )
+}
+
+/// Parses hero plus villains; hero must be exact cards or a single card.
+fn parse_hero_and_villains(
+    hero: &str,
+    villains: &[&str],
+) -> Result<Vec<HoleCardsInput>, SnapError> {
     let mut players: Vec<HoleCardsInput> = Vec::with_capacity(1 + villains.len());
     let hero_input: HoleCardsInput = hero.parse()?;
     if matches!(
         hero_input,
-        HoleCardsInput::Range(_) | HoleCardsInput::Unknown
+        HoleCardsInput::Range(_) | HoleCardsInput::WeightedRange(..) | HoleCardsInput::Unknown
     ) {
         return Err(SnapError::InvalidHand(
             "Hero must be exact hole cards (e.g. \"AhKd\") or a single card (e.g. \"Ah\")"
@@ -58,15 +574,26 @@ pub fn estimate_equity(
         players.push(v.parse()?);
     }
 
-    let num_players = players.len();
-    if board_cards.len() + 2 * num_players > 52 {
-        return Err(SnapError::InvalidHand(
-            "Too many players/cards for a 52-card deck".to_string(),
-        ));
-    }
+    Ok(players)
+}
 
-    // --- Validate known cards & pre-filter ranges ---
+/// Checks known cards for conflicts and drops range combos that collide with
+/// them. Returns every fixed known card (board, dead cards, exact and partial
+/// hole cards).
+fn validate_players(
+    board_set: &HashSet<Card>,
+    dead_cards: &[Card],
+    players: &mut [HoleCardsInput],
+) -> Result<HashSet<Card>, SnapError> {
     let mut fixed_known: HashSet<Card> = board_set.clone();
+    for &c in dead_cards {
+        if !fixed_known.insert(c) {
+            return Err(SnapError::InvalidHand(format!(
+                "Dead card {} conflicts with board or another dead card",
+                c
+            )));
+        }
+    }
 
     for (idx, p) in players.iter_mut().enumerate() {
         match p {
@@ -85,6 +612,12 @@ pub fn estimate_equity(
                             idx + 1
                         )));
                     }
+                    if dead_cards.contains(&c) {
+                        return Err(SnapError::InvalidHand(format!(
+                            "Player {} hand conflicts with dead cards",
+                            idx + 1
+                        )));
+                    }
                     if !fixed_known.insert(c) {
                         return Err(SnapError::InvalidHand(format!(
                             "Duplicate known card for player {}",
@@ -100,6 +633,12 @@ pub fn estimate_equity(
                         idx + 1
                     )));
                 }
+                if dead_cards.contains(card) {
+                    return Err(SnapError::InvalidHand(format!(
+                        "Player {} card conflicts with dead cards",
+                        idx + 1
+                    )));
+                }
                 if !fixed_known.insert(*card) {
                     return Err(SnapError::InvalidHand(format!(
                         "Duplicate known card for player {}",
@@ -108,77 +647,47 @@ pub fn estimate_equity(
                 }
             }
             HoleCardsInput::Range(ref mut hands) => {
-                hands.retain(|fh| {
-                    let mut iter = fh.iter();
-                    let c1 = match iter.next() {
-                        Some(c) => *c,
-                        None => return false,
-                    };
-                    let c2 = match iter.next() {
-                        Some(c) => *c,
-                        None => return false,
-                    };
-                    c1 != c2 && !fixed_known.contains(&c1) && !fixed_known.contains(&c2)
-                });
+                hands.retain(|fh| is_live(fh, &fixed_known));
                 if hands.is_empty() {
                     return Err(SnapError::InvalidRange(
                         "Range produced no valid hands after filtering".to_string(),
                     ));
                 }
             }
-            HoleCardsInput::Unknown => {}
-        }
-    }
-
-    let missing_board = 5 - board_cards.len();
-
-    // --- Decide mode: exact enumeration vs Monte Carlo ---
-    let mut partial_count = 0usize;
-    let mut unknown_count = 0usize;
-    let mut range_product: usize = 1;
-    let mut range_count = 0usize;
-
-    for p in &players {
-        match p {
-            HoleCardsInput::Partial(_) => partial_count += 1,
-            HoleCardsInput::Unknown => unknown_count += 1,
-            HoleCardsInput::Range(hands) => {
-                range_product = range_product.saturating_mul(hands.len());
-                range_count += 1;
+            HoleCardsInput::WeightedRange(ref mut hands, ref mut weights) => {
+                let (live, live_weights): (Vec<FlatHand>, Vec<f64>) = hands
+                    .drain(..)
+                    .zip(weights.drain(..))
+                    .filter(|(fh, _)| is_live(fh, &fixed_known))
+                    .unzip();
+                if live.is_empty() {
+                    return Err(SnapError::InvalidRange(
+                        "Range produced no valid hands after filtering".to_string(),
+                    ));
+                }
+                *hands = live;
+                *weights = live_weights;
             }
-            HoleCardsInput::Exact(_) => {}
+            HoleCardsInput::Unknown => {}
         }
     }
 
-    let non_range_slots = partial_count + 2 * unknown_count + missing_board;
-    // Available cards for the non-range combination: 52 − fixed_known − (approx 2 per range player)
-    let available_for_estimate = 52usize
-        .saturating_sub(fixed_known.len())
-        .saturating_sub(2 * range_count);
-
-    let use_exact = if let Some(enum_count) =
-        estimate_enumeration_count(available_for_estimate, non_range_slots, range_product)
-    {
-        enum_count > 0 && enum_count <= iterations
-    } else {
-        false
-    };
+    Ok(fixed_known)
+}
 
-    if use_exact {
-        Ok(estimate_equity_exact_enumeration(
-            &board_cards,
-            &board_set,
-            &players,
-            &fixed_known,
-        ))
-    } else {
-        estimate_equity_monte_carlo(&board_cards, &board_set, &players, iterations)
+/// Whether a range combo is two distinct cards, neither of them known.
+fn is_live(hand: &FlatHand, fixed_known: &HashSet<Card>) -> bool {
+    let mut iter = hand.iter();
+    match (iter.next(), iter.next()) {
+        (Some(c1), Some(c2)) => c1 != c2 && !fixed_known.contains(c1) && !fixed_known.contains(c2),
+        _ => false,
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::input::parse_cards;
     use crate::types::EquityEstimateMode;
 
     // ── Validation tests ──────────────────────────────────────────────
@@ -296,9 +805,451 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn range_equity_requires_two_players() {
+        assert!(estimate_range_equity("", &["AA"], 100).is_err());
+    }
+
+    #[test]
+    fn range_equity_accepts_range_in_first_seat() {
+        let result = estimate_range_equity("", &["AA", "KK"], 5_000).unwrap();
+        assert_eq!(result.equities.len(), 2);
+        assert!(result.equities[0] > 70.0, "AA should dominate KK");
+    }
+
+    #[test]
+    fn range_equity_matches_estimate_equity_on_river() {
+        let a = estimate_equity("2h5h9cTdJs", "AhKh", &["QsQc"], 100).unwrap();
+        let b = estimate_range_equity("2h5h9cTdJs", &["AhKh", "QsQc"], 100).unwrap();
+        assert_eq!(a, b);
+    }
+
+    #[test]
+    fn equity_math_describes_state_space() {
+        // Turn, exact hands: no range assignments, C(44, 1) runouts
+        let result = estimate_equity("2h5h9cTd", "AhKh", &["QsQc"], 10_000).unwrap();
+        assert_eq!(result.math.assignment_combinations, 1);
+        assert_eq!(result.math.available_cards, 44);
+        assert_eq!(result.math.runout_slots, 1);
+        assert_eq!(result.math.runout_combinations, 44);
+        assert_eq!(result.math.total_states, Some(44));
+    }
+
+    #[test]
+    fn equity_math_counts_range_assignments() {
+        let result = estimate_equity("", "AhAd", &["KK"], 100).unwrap();
+        assert_eq!(result.math.assignment_combinations, 6);
+        assert_eq!(result.mode, EquityEstimateMode::MonteCarlo);
+    }
+
+    #[test]
+    fn forced_monte_carlo_skips_enumeration() {
+        let options = EquityOptions {
+            mode: Some(EquityEstimateMode::MonteCarlo),
+            ..EquityOptions::new(1_000)
+        };
+        let result = estimate_equity_with_options("2h5h9cTd", "AhKh", &["QsQc"], &options).unwrap();
+        assert_eq!(result.mode, EquityEstimateMode::MonteCarlo);
+        assert_eq!(result.samples, 1_000);
+    }
+
+    #[test]
+    fn seeded_monte_carlo_is_reproducible() {
+        let options = EquityOptions {
+            mode: Some(EquityEstimateMode::MonteCarlo),
+            seed: Some(42),
+            ..EquityOptions::new(2_000)
+        };
+        let first = estimate_equity_with_options("", "AhKh", &["QQ+", "22-55"], &options).unwrap();
+        let second = estimate_equity_with_options("", "AhKh", &["QQ+", "22-55"], &options).unwrap();
+        assert_eq!(first, second);
+
+        let other_seed = EquityOptions {
+            seed: Some(43),
+            ..options
+        };
+        let third =
+            estimate_equity_with_options("", "AhKh", &["QQ+", "22-55"], &other_seed).unwrap();
+        assert_ne!(first.equities, third.equities);
+    }
+
+    /// Counts the words drawn from the wrapped generator.
+    struct CountingRng {
+        inner: rand::rngs::StdRng,
+        draws: usize,
+    }
+
+    impl rand::TryRng for CountingRng {
+        type Error = std::convert::Infallible;
+
+        fn try_next_u32(&mut self) -> Result<u32, Self::Error> {
+            self.draws += 1;
+            Ok(self.inner.next_u32())
+        }
+
+        fn try_next_u64(&mut self) -> Result<u64, Self::Error> {
+            self.draws += 1;
+            Ok(self.inner.next_u64())
+        }
+
+        fn try_fill_bytes(&mut self, dst: &mut [u8]) -> Result<(), Self::Error> {
+            self.draws += 1;
+            self.inner.fill_bytes(dst);
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn caller_supplied_rng_drives_sampling() {
+        use rand::SeedableRng;
+
+        let options = EquityOptions {
+            mode: Some(EquityEstimateMode::MonteCarlo),
+            seed: Some(42),
+            ..EquityOptions::new(2_000)
+        };
+        let seeded = estimate_equity_with_options("", "AhKh", &["QQ+", "22-55"], &options).unwrap();
+        let mut rng = rand::rngs::StdRng::seed_from_u64(42);
+        let injected =
+            estimate_equity_with_rng("", "AhKh", &["QQ+", "22-55"], &options, &mut rng).unwrap();
+        assert_eq!(seeded, injected);
+
+        let mut counting = CountingRng {
+            inner: rand::rngs::StdRng::seed_from_u64(1),
+            draws: 0,
+        };
+        let sampled =
+            estimate_range_equity_with_rng("", &["AhKh", "QQ+"], &options, &mut counting).unwrap();
+        assert_eq!(sampled.samples, 2_000);
+        assert!(counting.draws >= 2_000, "{}", counting.draws);
+
+        // Exact enumeration never draws
+        let river = EquityOptions::new(10_000);
+        let before = counting.draws;
+        estimate_equity_with_rng("2h5h9cTdJs", "AhKh", &["QQ"], &river, &mut counting).unwrap();
+        assert_eq!(counting.draws, before);
+    }
+
+    #[test]
+    fn multi_board_accepts_a_caller_rng() {
+        use rand::SeedableRng;
+
+        let run = |seed| {
+            let mut rng = rand::rngs::StdRng::seed_from_u64(seed);
+            estimate_multi_board_equity_with_rng(
+                &["2h7c9d", "KsKd3c"],
+                "AhKh",
+                &["TT+"],
+                &EquityOptions::new(2_000),
+                &mut rng,
+            )
+            .unwrap()
+        };
+        assert_eq!(run(5), run(5));
+        assert_ne!(run(5).pot_share, run(6).pot_share);
+
+        let seeded = EquityOptions {
+            seed: Some(5),
+            ..EquityOptions::new(2_000)
+        };
+        let with_options = estimate_multi_board_equity_with_options(
+            &["2h7c9d", "KsKd3c"],
+            "AhKh",
+            &["TT+"],
+            &seeded,
+        )
+        .unwrap();
+        assert_eq!(with_options, run(5));
+    }
+
+    #[test]
+    fn multi_board_honours_time_budget_and_dead_cards() {
+        let options = EquityOptions {
+            time_budget: Some(std::time::Duration::ZERO),
+            ..EquityOptions::new(1_000_000)
+        };
+        let result =
+            estimate_multi_board_equity_with_options(&["", ""], "AhKh", &["QQ"], &options).unwrap();
+        assert!(
+            result.samples > 0 && result.samples < 1_000_000,
+            "{}",
+            result.samples
+        );
+
+        let dead = EquityOptions {
+            dead_cards: crate::input::parse_cards("Qs").unwrap(),
+            ..EquityOptions::new(100)
+        };
+        assert!(
+            estimate_multi_board_equity_with_options(&["", ""], "AhKh", &["QsQd"], &dead).is_err()
+        );
+        assert!(estimate_multi_board_equity_with_options(
+            &["2h7c9d", "Qs3c4d"],
+            "AhKh",
+            &["TT"],
+            &dead
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn weighted_villain_range() {
+        // QQ loses to the six AA combos and beats the six 33 combos; AA at a
+        // quarter weight leaves hero ahead 6 to 1.5
+        let board = "2h5h9cTdJs";
+        let exact = estimate_equity(board, "QhQd", &["AA:0.25, 33"], 10_000).unwrap();
+        assert_eq!(exact.mode, EquityEstimateMode::ExactEnumeration);
+        assert!(
+            (exact.equities[0] - 80.0).abs() < 1e-9,
+            "{}",
+            exact.equities[0]
+        );
+
+        let options = EquityOptions {
+            mode: Some(EquityEstimateMode::MonteCarlo),
+            seed: Some(3),
+            ..EquityOptions::new(20_000)
+        };
+        let sampled =
+            estimate_equity_with_options(board, "QhQd", &["AA:0.25, 33"], &options).unwrap();
+        assert!(
+            (sampled.equities[0] - 80.0).abs() < 1.5,
+            "{}",
+            sampled.equities[0]
+        );
+    }
+
+    #[test]
+    fn vs_random_matches_unknown_villains() {
+        // River vs one random hand: C(45, 2) = 990 states, solved exactly
+        let board = "Ah7d2c9s3h";
+        let fast = equity_vs_random(board, "AsKs", 1, &EquityOptions::new(10_000)).unwrap();
+        let slow = estimate_equity(board, "AsKs", &[""], 10_000).unwrap();
+        assert_eq!(fast, slow);
+
+        // Preflop AA vs two random hands is about 73%
+        let options = EquityOptions {
+            seed: Some(7),
+            ..EquityOptions::new(40_000)
+        };
+        let sampled = equity_vs_random("", "AhAd", 2, &options).unwrap();
+        assert_eq!(sampled.mode, EquityEstimateMode::MonteCarlo);
+        assert_eq!(sampled.equities.len(), 3);
+        assert!(
+            (sampled.equities[0] - 73.4).abs() < 1.5,
+            "{}",
+            sampled.equities[0]
+        );
+        assert_eq!(sampled, equity_vs_random("", "AhAd", 2, &options).unwrap());
+    }
+
+    #[test]
+    fn vs_random_accepts_a_caller_rng() {
+        use rand::SeedableRng;
+
+        let options = EquityOptions {
+            seed: Some(11),
+            ..EquityOptions::new(5_000)
+        };
+        let seeded = equity_vs_random("", "AhAd", 2, &options).unwrap();
+        let mut rng = rand::rngs::StdRng::seed_from_u64(11);
+        let injected = equity_vs_random_with_rng("", "AhAd", 2, &options, &mut rng).unwrap();
+        assert_eq!(seeded, injected);
+
+        let mut counting = CountingRng {
+            inner: rand::rngs::StdRng::seed_from_u64(1),
+            draws: 0,
+        };
+        equity_vs_random_with_rng("", "AhAd", 2, &options, &mut counting).unwrap();
+        assert!(counting.draws >= 5_000, "{}", counting.draws);
+    }
+
+    #[test]
+    fn vs_random_rejects_bad_counts() {
+        let options = EquityOptions::new(100);
+        assert!(equity_vs_random("", "AhAd", 0, &options).is_err());
+        assert!(equity_vs_random("", "AhAd", 25, &options).is_err());
+        assert!(equity_vs_random("", "AK", 1, &options).is_err());
+    }
+
+    #[test]
+    fn forced_exact_ignores_iteration_budget() {
+        let options = EquityOptions {
+            mode: Some(EquityEstimateMode::ExactEnumeration),
+            ..EquityOptions::new(1)
+        };
+        let result = estimate_equity_with_options("2h5h9cTd", "AhKh", &["QsQc"], &options).unwrap();
+        assert_eq!(result.mode, EquityEstimateMode::ExactEnumeration);
+        assert_eq!(result.samples, 44);
+    }
+
+    #[test]
+    fn forced_exact_respects_state_limit() {
+        let options = EquityOptions {
+            mode: Some(EquityEstimateMode::ExactEnumeration),
+            max_exact_states: 10,
+            ..EquityOptions::new(100)
+        };
+        let result = estimate_equity_with_options("2h5h9cTd", "AhKh", &["QsQc"], &options);
+        assert!(matches!(result, Err(SnapError::ResourceLimit(_))));
+    }
+
+    #[test]
+    fn time_budget_and_iterations_stop_at_whichever_comes_first() {
+        let short = EquityOptions {
+            mode: Some(EquityEstimateMode::MonteCarlo),
+            time_budget: Some(std::time::Duration::from_millis(20)),
+            ..EquityOptions::new(1_000_000_000)
+        };
+        let result = estimate_equity_with_options("", "AhAd", &["KhKd"], &short).unwrap();
+        assert!(
+            result.samples < 1_000_000_000,
+            "got {} samples",
+            result.samples
+        );
+
+        let capped = EquityOptions {
+            time_budget: Some(std::time::Duration::from_secs(60)),
+            ..EquityOptions::new(10)
+        };
+        let result = estimate_equity_with_options("", "AhAd", &["KhKd"], &capped).unwrap();
+        assert_eq!(result.samples, 10);
+    }
+
+    #[test]
+    fn time_budget_keeps_exact_for_small_spaces() {
+        let options = EquityOptions {
+            time_budget: Some(std::time::Duration::from_millis(20)),
+            ..EquityOptions::new(100)
+        };
+        let result = estimate_equity_with_options("2h5h9cTd", "AhKh", &["QsQc"], &options).unwrap();
+        assert_eq!(result.mode, EquityEstimateMode::ExactEnumeration);
+        assert_eq!(result.samples, 44);
+    }
+
+    #[test]
+    fn threads_split_monte_carlo_samples() {
+        let options = |threads| EquityOptions {
+            seed: Some(3),
+            threads,
+            ..EquityOptions::new(40_001)
+        };
+        let run = |threads| {
+            estimate_equity_with_options("", "AsAd", &["KK+"], &options(threads)).unwrap()
+        };
+        let single = run(1);
+        let split = run(4);
+        assert_eq!(split.mode, EquityEstimateMode::MonteCarlo);
+        assert_eq!(split.samples, 40_001);
+        assert_eq!(split.equities, run(4).equities);
+        assert!((split.equities[0] - single.equities[0]).abs() < 2.0);
+    }
+
+    #[test]
+    fn threads_are_capped_at_the_available_cores() {
+        let options = EquityOptions {
+            mode: Some(EquityEstimateMode::MonteCarlo),
+            threads: 100_000,
+            ..EquityOptions::new(200_000)
+        };
+        let result = estimate_equity_with_options("", "AsAd", &["KcKd"], &options).unwrap();
+        assert_eq!(result.samples, 200_000);
+    }
+
+    #[test]
+    fn thread_streams_do_not_overlap_neighbouring_seeds() {
+        assert_ne!(stream_seed(5, 1), stream_seed(6, 0));
+        assert_ne!(stream_seed(5, 0), stream_seed(5, 1));
+        assert_eq!(stream_seed(5, 1), stream_seed(5, 1));
+    }
+
+    #[test]
+    fn threads_leave_exact_enumeration_alone() {
+        let options = EquityOptions {
+            threads: 4,
+            ..EquityOptions::new(100)
+        };
+        let result = estimate_equity_with_options("2h5h9cTd", "AhKh", &["QsQc"], &options).unwrap();
+        assert_eq!(result.mode, EquityEstimateMode::ExactEnumeration);
+        assert_eq!(result.samples, 44);
+    }
+
+    #[test]
+    fn dead_cards_shrink_the_deck() {
+        let options = EquityOptions {
+            dead_cards: parse_cards("2c 3c").unwrap(),
+            ..EquityOptions::new(10_000)
+        };
+        let result = estimate_equity_with_options("2h5h9cTd", "AhKh", &["QsQc"], &options).unwrap();
+        assert_eq!(result.math.available_cards, 42);
+        assert_eq!(result.samples, 42);
+    }
+
+    #[test]
+    fn dead_cards_remove_outs() {
+        // Killing the remaining hearts takes hero's flush outs away
+        let base = estimate_equity("2h5h9cTd", "AhKh", &["QsQc"], 10_000).unwrap();
+        let dead = parse_cards("3h 4h 6h 7h 8h Jh Qh").unwrap();
+        let options = EquityOptions {
+            dead_cards: dead,
+            ..EquityOptions::new(10_000)
+        };
+        let result = estimate_equity_with_options("2h5h9cTd", "AhKh", &["QsQc"], &options).unwrap();
+        assert!(result.equities[0] < base.equities[0] - 10.0);
+        assert_eq!(result.samples, 37);
+    }
+
+    #[test]
+    fn dead_card_conflicting_with_hand_is_error() {
+        let options = EquityOptions {
+            dead_cards: parse_cards("Ah").unwrap(),
+            ..EquityOptions::new(100)
+        };
+        assert!(estimate_equity_with_options("", "AhKh", &["QsQc"], &options).is_err());
+    }
+
+    #[test]
+    fn multi_board_shares_pot_between_boards() {
+        // Hero has the nuts on board 1 and drawing dead on board 2
+        let result =
+            estimate_multi_board_equity(&["AhKhQhJh2c", "2s3s4s5s9d"], "ThTd", &["6s7d"], 500)
+                .unwrap();
+        assert_eq!(result.board_equities.len(), 2);
+        assert!((result.board_equities[0][0] - 100.0).abs() < 1e-9);
+        assert!(result.board_equities[1][0].abs() < 1e-9);
+        assert!((result.pot_share[0] - 50.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn multi_board_rejects_shared_cards() {
+        let result = estimate_multi_board_equity(&["AhKdQc", "AhJs2d"], "9s9c", &[""], 100);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn multi_board_sums_to_100() {
+        let result = estimate_multi_board_equity(&["", ""], "AhAd", &["KK"], 2_000).unwrap();
+        let sum: f64 = result.pot_share.iter().sum();
+        assert!((sum - 100.0).abs() < 0.01, "got {sum}");
+    }
+
     #[test]
     fn equity_mode_exact_on_river() {
         let result = estimate_equity("2h5h9cTdJs", "AhKh", &["QsQc"], 100).unwrap();
         assert_eq!(result.mode, EquityEstimateMode::ExactEnumeration);
     }
+
+    #[test]
+    fn wins_and_ties_split_showdowns() {
+        // Board plays on this river: every runout is a chop
+        let chop = estimate_equity("AsKsQsJsTs", "2c3d", &["4h5h"], 100).unwrap();
+        assert_eq!(chop.wins, vec![0.0, 0.0]);
+        assert_eq!(chop.ties, vec![100.0, 100.0]);
+
+        let result = estimate_equity("2h5h9c", "AhKh", &["QsQc"], 10_000).unwrap();
+        assert_eq!(result.mode, EquityEstimateMode::ExactEnumeration);
+        let decided = result.wins[0] + result.wins[1] + result.ties[0];
+        assert!((decided - 100.0).abs() < 1e-9, "got {decided}");
+        assert_eq!(result.ties[0], result.ties[1]);
+    }
 }
@@ -0,0 +1,221 @@
+use crate::rake::RakeModel;
+use crate::types::SnapError;
+
+/// Expected value of calling, in chips relative to folding: win the pot
+/// plus the call `equity_percent` of the time, lose the call otherwise.
+///
+/// `pot` is the pot before the call, including any bet being faced, as for
+/// [`pot_odds_percent`](crate::pot_odds_percent). The result is zero at
+/// exactly the pot odds.
+pub fn call_ev(pot: f64, call_amount: f64, equity_percent: f64) -> f64 {
+    equity_percent / 100.0 * (pot + call_amount) - call_amount
+}
+
+/// One of hero's options in [`decision_ev`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Decision {
+    Fold,
+    Call,
+    Shove,
+}
+
+impl std::fmt::Display for Decision {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Decision::Fold => write!(f, "fold"),
+            Decision::Call => write!(f, "call"),
+            Decision::Shove => write!(f, "shove"),
+        }
+    }
+}
+
+/// EVs from [`decision_ev`], in chips relative to folding (so folding is
+/// always 0).
+///
+/// - `call` is `None` when there is no bet to call.
+/// - `shove_called` is the shove's EV when villain calls, and
+///   `break_even_fold_percent` how often villain must fold for the shove to
+///   break even; `None` when it profits even when called.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DecisionEv {
+    pub call: Option<f64>,
+    pub shove: f64,
+    pub shove_called: f64,
+    pub break_even_fold_percent: Option<f64>,
+}
+
+impl DecisionEv {
+    /// The option with the highest EV, preferring the more passive one on
+    /// a tie.
+    pub fn best(&self) -> Decision {
+        let mut best = (Decision::Fold, 0.0);
+        for (decision, ev) in [
+            (Decision::Call, self.call),
+            (Decision::Shove, Some(self.shove)),
+        ] {
+            if let Some(ev) = ev.filter(|&ev| ev > best.1) {
+                best = (decision, ev);
+            }
+        }
+        best.0
+    }
+}
+
+/// EVs of folding, calling, and shoving all-in.
+///
+/// - `pot` is the pot before the `bet` hero faces (0 when first to act).
+/// - `stack` is the effective stack behind, before calling; a bet larger
+///   than it is called for `stack`.
+/// - `equity_percent` is hero's equity against the range that continues:
+///   the range that calls a shove, and the betting range for a call.
+/// - `fold_percent` is how often villain folds to the shove.
+///
+/// # Errors
+/// Returns [`SnapError::InvalidArgument`] for a non-positive pot or stack,
+/// a negative bet, or a percentage outside 0 to 100.
+pub fn decision_ev(
+    pot: f64,
+    bet: f64,
+    stack: f64,
+    equity_percent: f64,
+    fold_percent: f64,
+) -> Result<DecisionEv, SnapError> {
+    decision_ev_with_rake(
+        pot,
+        bet,
+        stack,
+        equity_percent,
+        fold_percent,
+        &RakeModel::TimeCharge,
+    )
+}
+
+/// Like [`decision_ev`], with `rake` taken from every pot hero wins,
+/// including one villain gives up by folding to the shove.
+///
+/// # Errors
+/// In addition to the [`decision_ev`] errors, returns
+/// [`SnapError::InvalidArgument`] when [`RakeModel::validate`] fails.
+pub fn decision_ev_with_rake(
+    pot: f64,
+    bet: f64,
+    stack: f64,
+    equity_percent: f64,
+    fold_percent: f64,
+    rake: &RakeModel,
+) -> Result<DecisionEv, SnapError> {
+    rake.validate()?;
+    if [pot, stack].iter().any(|v| !v.is_finite() || *v <= 0.0) {
+        return Err(SnapError::InvalidArgument(format!(
+            "Pot and stack must be positive, got {} and {}",
+            pot, stack
+        )));
+    }
+    if !bet.is_finite() || bet < 0.0 {
+        return Err(SnapError::InvalidArgument(format!(
+            "Bet must not be negative, got {}",
+            bet
+        )));
+    }
+    if [equity_percent, fold_percent]
+        .iter()
+        .any(|p| !(0.0..=100.0).contains(p))
+    {
+        return Err(SnapError::InvalidArgument(format!(
+            "Equity and fold equity must be between 0 and 100, got {} and {}",
+            equity_percent, fold_percent
+        )));
+    }
+
+    let call_amount = bet.min(stack);
+    let call = (bet > 0.0).then(|| rake.call_ev(pot + call_amount, call_amount, equity_percent));
+    let equity = equity_percent / 100.0;
+    let fold = fold_percent / 100.0;
+    let shove_called = equity * rake.raked_pot(pot + 2.0 * stack) - stack;
+    let uncontested = rake.raked_pot(pot + bet);
+    let shove = fold * uncontested + (1.0 - fold) * shove_called;
+    let break_even_fold_percent =
+        (shove_called < 0.0).then(|| -shove_called / (uncontested - shove_called) * 100.0);
+    Ok(DecisionEv {
+        call,
+        shove,
+        shove_called,
+        break_even_fold_percent,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::odds::pot_odds_percent;
+
+    fn assert_close(a: f64, b: f64) {
+        assert!((a - b).abs() < 1e-9, "{a} vs {b}");
+    }
+
+    #[test]
+    fn call_ev_breaks_even_at_pot_odds() {
+        // Calling 50 into 150 needs 25%
+        assert!(call_ev(150.0, 50.0, pot_odds_percent(150.0, 50.0)).abs() < 1e-9);
+        assert!((call_ev(150.0, 50.0, 50.0) - 50.0).abs() < 1e-9);
+        assert_eq!(call_ev(150.0, 50.0, 0.0), -50.0);
+    }
+
+    #[test]
+    fn shove_needs_enough_fold_equity() {
+        // 30 in the pot, facing 10, 200 behind, 30% when called
+        let ev = decision_ev(30.0, 10.0, 200.0, 30.0, 40.0).unwrap();
+        assert_close(ev.call.unwrap(), 0.3 * 50.0 - 10.0);
+        assert_close(ev.shove_called, 0.3 * 430.0 - 200.0);
+        assert_close(ev.shove, 0.4 * 40.0 + 0.6 * ev.shove_called);
+        let needed = ev.break_even_fold_percent.unwrap();
+        let at_break_even = decision_ev(30.0, 10.0, 200.0, 30.0, needed).unwrap();
+        assert!(at_break_even.shove.abs() < 1e-9);
+        assert_eq!(ev.best(), Decision::Call);
+    }
+
+    #[test]
+    fn first_to_act_has_no_call() {
+        let ev = decision_ev(10.0, 0.0, 20.0, 60.0, 0.0).unwrap();
+        assert_eq!(ev.call, None);
+        assert!(ev.break_even_fold_percent.is_none());
+        assert_eq!(ev.best(), Decision::Shove);
+        assert_eq!(
+            decision_ev(10.0, 0.0, 20.0, 10.0, 0.0).unwrap().best(),
+            Decision::Fold
+        );
+    }
+
+    #[test]
+    fn rake_comes_out_of_every_pot_won() {
+        let rake = RakeModel::Percent {
+            percent: 5.0,
+            cap: Some(3.0),
+        };
+        let plain = decision_ev(30.0, 10.0, 200.0, 30.0, 40.0).unwrap();
+        let raked = decision_ev_with_rake(30.0, 10.0, 200.0, 30.0, 40.0, &rake).unwrap();
+        assert_close(raked.call.unwrap(), 0.3 * 47.5 - 10.0);
+        assert_close(raked.shove_called, 0.3 * 427.0 - 200.0);
+        assert_close(raked.shove, 0.4 * 38.0 + 0.6 * raked.shove_called);
+        assert!(raked.break_even_fold_percent > plain.break_even_fold_percent);
+        let unraked =
+            decision_ev_with_rake(30.0, 10.0, 200.0, 30.0, 40.0, &RakeModel::TimeCharge).unwrap();
+        assert_eq!(unraked, plain);
+        assert!(decision_ev_with_rake(
+            30.0,
+            10.0,
+            200.0,
+            30.0,
+            40.0,
+            &RakeModel::Drop { amount: -1.0 }
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn rejects_bad_inputs() {
+        assert!(decision_ev(0.0, 10.0, 100.0, 50.0, 0.0).is_err());
+        assert!(decision_ev(10.0, -1.0, 100.0, 50.0, 0.0).is_err());
+        assert!(decision_ev(10.0, 10.0, 100.0, 50.0, 101.0).is_err());
+    }
+}
@@ -0,0 +1,127 @@
+use rs_poker::core::{Card, Suit};
+
+use crate::input::parse_cards;
+use crate::types::SnapError;
+
+/// Variation selector that asks for the emoji rendering of a suit symbol.
+const EMOJI_PRESENTATION: char = '\u{fe0f}';
+
+/// How suits are drawn when cards are shown.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum SuitStyle {
+    /// `Ah Ks`: plain letters, safe for any terminal and screen readers.
+    #[default]
+    Ascii,
+    /// `A♥ K♠`
+    Unicode,
+    /// `A♥️ K♠️`
+    Emoji,
+}
+
+impl std::fmt::Display for SuitStyle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SuitStyle::Ascii => write!(f, "ascii"),
+            SuitStyle::Unicode => write!(f, "unicode"),
+            SuitStyle::Emoji => write!(f, "emoji"),
+        }
+    }
+}
+
+impl std::str::FromStr for SuitStyle {
+    type Err = SnapError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "ascii" => Ok(Self::Ascii),
+            "unicode" => Ok(Self::Unicode),
+            "emoji" => Ok(Self::Emoji),
+            other => Err(SnapError::InvalidArgument(format!(
+                "Unknown suit style '{}', expected ascii/unicode/emoji",
+                other
+            ))),
+        }
+    }
+}
+
+/// Renders one card in `style`, e.g. `"A♥"` for the ace of hearts in
+/// [`SuitStyle::Unicode`].
+pub fn format_card(card: Card, style: SuitStyle) -> String {
+    let rank = card.value.to_char();
+    match style {
+        SuitStyle::Ascii => format!("{}{}", rank, card.suit.to_char()),
+        SuitStyle::Unicode => format!("{}{}", rank, suit_symbol(card.suit)),
+        SuitStyle::Emoji => format!("{}{}{}", rank, suit_symbol(card.suit), EMOJI_PRESENTATION),
+    }
+}
+
+/// Renders cards in `style` separated by spaces.
+pub fn format_cards(cards: &[Card], style: SuitStyle) -> String {
+    cards
+        .iter()
+        .map(|&c| format_card(c, style))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Parses cards written in any [`SuitStyle`] (`"A♥️ K♠"`, `"AhKs"`), the
+/// reverse of [`format_cards`].
+///
+/// # Errors
+/// As [`parse_cards`], once suit symbols are read as letters.
+pub fn parse_styled_cards(s: &str) -> Result<Vec<Card>, SnapError> {
+    let letters: String = s
+        .chars()
+        .filter(|&c| c != EMOJI_PRESENTATION)
+        .map(|c| symbol_suit(c).unwrap_or(c))
+        .collect();
+    parse_cards(&letters)
+}
+
+fn suit_symbol(suit: Suit) -> char {
+    match suit {
+        Suit::Spade => '♠',
+        Suit::Heart => '♥',
+        Suit::Diamond => '♦',
+        Suit::Club => '♣',
+    }
+}
+
+/// Maps suit symbols, filled or outlined, back to letters.
+pub(crate) fn symbol_suit(symbol: char) -> Option<char> {
+    match symbol {
+        '♠' | '♤' => Some('s'),
+        '♥' | '♡' => Some('h'),
+        '♦' | '♢' => Some('d'),
+        '♣' | '♧' => Some('c'),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn formats_each_style() {
+        let cards = [Card::try_from("Ah").unwrap(), Card::try_from("Ts").unwrap()];
+        assert_eq!(format_cards(&cards, SuitStyle::Ascii), "Ah Ts");
+        assert_eq!(format_cards(&cards, SuitStyle::Unicode), "A♥ T♠");
+        assert_eq!(
+            format_cards(&cards, SuitStyle::Emoji),
+            "A♥\u{fe0f} T♠\u{fe0f}"
+        );
+        assert_eq!("Emoji".parse::<SuitStyle>().unwrap(), SuitStyle::Emoji);
+        assert!("color".parse::<SuitStyle>().is_err());
+    }
+
+    #[test]
+    fn parses_formatted_cards_back() {
+        let cards = parse_cards("AhTs2d").unwrap();
+        for style in [SuitStyle::Ascii, SuitStyle::Unicode, SuitStyle::Emoji] {
+            let text = format_cards(&cards, style);
+            assert_eq!(parse_styled_cards(&text).unwrap(), cards);
+        }
+        assert!(parse_styled_cards("A♥ A♡").is_err());
+    }
+}
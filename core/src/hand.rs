@@ -0,0 +1,254 @@
+use rs_poker::core::{Card, Rank, Rankable, Value};
+
+use crate::types::SnapError;
+
+/// Hand category of an evaluated [`Rank`], weakest first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum HandCategory {
+    HighCard,
+    OnePair,
+    TwoPair,
+    ThreeOfAKind,
+    Straight,
+    Flush,
+    FullHouse,
+    FourOfAKind,
+    StraightFlush,
+}
+
+impl HandCategory {
+    /// Category of an evaluated rank.
+    pub fn from_rank(rank: &Rank) -> Self {
+        match rank {
+            Rank::HighCard(_) => Self::HighCard,
+            Rank::OnePair(_) => Self::OnePair,
+            Rank::TwoPair(_) => Self::TwoPair,
+            Rank::ThreeOfAKind(_) => Self::ThreeOfAKind,
+            Rank::Straight(_) => Self::Straight,
+            Rank::Flush(_) => Self::Flush,
+            Rank::FullHouse(_) => Self::FullHouse,
+            Rank::FourOfAKind(_) => Self::FourOfAKind,
+            Rank::StraightFlush(_) => Self::StraightFlush,
+        }
+    }
+
+    /// Human-readable English name (e.g. `"Two Pair"`).
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::HighCard => "High Card",
+            Self::OnePair => "One Pair",
+            Self::TwoPair => "Two Pair",
+            Self::ThreeOfAKind => "Three of a Kind",
+            Self::Straight => "Straight",
+            Self::Flush => "Flush",
+            Self::FullHouse => "Full House",
+            Self::FourOfAKind => "Four of a Kind",
+            Self::StraightFlush => "Straight Flush",
+        }
+    }
+}
+
+impl std::fmt::Display for HandCategory {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+/// The best five-card hand out of 5–7 cards.
+///
+/// `cards` is ordered for display: the cards that make the hand first
+/// (grouped by rank, highest first; a wheel lists its ace last), then kickers.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BestHand {
+    pub category: HandCategory,
+    pub rank: Rank,
+    pub cards: [Card; 5],
+    made_len: usize,
+}
+
+impl BestHand {
+    /// Cards that form the hand class itself (e.g. the pair in one pair).
+    pub fn made(&self) -> &[Card] {
+        &self.cards[..self.made_len]
+    }
+
+    /// Remaining cards of the five that only break ties.
+    pub fn kickers(&self) -> &[Card] {
+        &self.cards[self.made_len..]
+    }
+
+    /// Short English description, e.g. `"Aces full of Kings"`.
+    pub fn describe(&self) -> String {
+        let made = self.made();
+        let first = made[0].value;
+        match self.category {
+            HandCategory::HighCard => format!("{} high", value_name(first)),
+            HandCategory::OnePair => format!("Pair of {}", value_plural(first)),
+            HandCategory::TwoPair => format!(
+                "{} and {}",
+                value_plural(first),
+                value_plural(made[2].value)
+            ),
+            HandCategory::ThreeOfAKind => format!("Three {}", value_plural(first)),
+            HandCategory::Straight => format!("{}-high Straight", value_name(first)),
+            HandCategory::Flush => format!("{}-high Flush", value_name(first)),
+            HandCategory::FullHouse => format!(
+                "{} full of {}",
+                value_plural(first),
+                value_plural(made[3].value)
+            ),
+            HandCategory::FourOfAKind => format!("Four {}", value_plural(first)),
+            HandCategory::StraightFlush if first == Value::Ace => "Royal Flush".to_string(),
+            HandCategory::StraightFlush => format!("{}-high Straight Flush", value_name(first)),
+        }
+    }
+}
+
+/// Finds the strongest five-card hand among 5–7 cards.
+pub fn best_hand(cards: &[Card]) -> Result<BestHand, SnapError> {
+    if !(5..=7).contains(&cards.len()) {
+        return Err(SnapError::InvalidHand(format!(
+            "Hand must have 5-7 cards, got {}",
+            cards.len()
+        )));
+    }
+
+    let n = cards.len();
+    let mut best: Option<([Card; 5], Rank)> = None;
+    for mask in 0u32..(1 << n) {
+        if mask.count_ones() != 5 {
+            continue;
+        }
+        let mut five = [cards[0]; 5];
+        let mut j = 0;
+        for (i, card) in cards.iter().enumerate() {
+            if mask & (1 << i) != 0 {
+                five[j] = *card;
+                j += 1;
+            }
+        }
+        let rank = five.as_slice().rank();
+        if best.as_ref().is_none_or(|(_, r)| rank > *r) {
+            best = Some((five, rank));
+        }
+    }
+    let (mut five, rank) = best.ok_or(SnapError::InvalidHand("empty hand".to_string()))?;
+    let category = HandCategory::from_rank(&rank);
+
+    let snapshot = five;
+    let count = |v: Value| snapshot.iter().filter(|c| c.value == v).count();
+    five.sort_by_key(|c| std::cmp::Reverse((count(c.value), c.value)));
+
+    let is_wheel = matches!(
+        category,
+        HandCategory::Straight | HandCategory::StraightFlush
+    ) && five[0].value == Value::Ace
+        && five[1].value == Value::Five;
+    if is_wheel {
+        five.rotate_left(1);
+    }
+
+    let made_len = match category {
+        HandCategory::HighCard => 1,
+        HandCategory::OnePair => 2,
+        HandCategory::ThreeOfAKind => 3,
+        HandCategory::TwoPair | HandCategory::FourOfAKind => 4,
+        HandCategory::Straight
+        | HandCategory::Flush
+        | HandCategory::FullHouse
+        | HandCategory::StraightFlush => 5,
+    };
+
+    Ok(BestHand {
+        category,
+        rank,
+        cards: five,
+        made_len,
+    })
+}
+
+pub(crate) fn value_name(value: Value) -> &'static str {
+    match value {
+        Value::Two => "Two",
+        Value::Three => "Three",
+        Value::Four => "Four",
+        Value::Five => "Five",
+        Value::Six => "Six",
+        Value::Seven => "Seven",
+        Value::Eight => "Eight",
+        Value::Nine => "Nine",
+        Value::Ten => "Ten",
+        Value::Jack => "Jack",
+        Value::Queen => "Queen",
+        Value::King => "King",
+        Value::Ace => "Ace",
+    }
+}
+
+pub(crate) fn value_plural(value: Value) -> String {
+    match value {
+        Value::Six => "Sixes".to_string(),
+        v => format!("{}s", value_name(v)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rs_poker::core::FlatHand;
+
+    use super::*;
+
+    fn category(hand: &str) -> HandCategory {
+        HandCategory::from_rank(&FlatHand::new_from_str(hand).unwrap().rank())
+    }
+
+    #[test]
+    fn categorizes_common_hands() {
+        assert_eq!(category("AsKsQsJsTs"), HandCategory::StraightFlush);
+        assert_eq!(category("AhAdKcKd2s"), HandCategory::TwoPair);
+        assert_eq!(category("2h3d4c5s7h"), HandCategory::HighCard);
+    }
+
+    #[test]
+    fn best_hand_picks_five_and_kickers() {
+        let cards: Vec<Card> = FlatHand::new_from_str("AhAdKcQdJs2c2d")
+            .unwrap()
+            .iter()
+            .copied()
+            .collect();
+        let best = best_hand(&cards).unwrap();
+        assert_eq!(best.category, HandCategory::TwoPair);
+        assert_eq!(best.made().len(), 4);
+        assert_eq!(best.made()[0].value, Value::Ace);
+        assert_eq!(best.made()[2].value, Value::Two);
+        assert_eq!(best.kickers().len(), 1);
+        assert_eq!(best.kickers()[0].value, Value::King);
+        assert_eq!(best.describe(), "Aces and Twos");
+    }
+
+    #[test]
+    fn best_hand_orders_wheel_ace_last() {
+        let cards: Vec<Card> = FlatHand::new_from_str("Ah2d3c4s5hKd")
+            .unwrap()
+            .iter()
+            .copied()
+            .collect();
+        let best = best_hand(&cards).unwrap();
+        assert_eq!(best.category, HandCategory::Straight);
+        assert_eq!(best.cards[0].value, Value::Five);
+        assert_eq!(best.cards[4].value, Value::Ace);
+        assert!(best.kickers().is_empty());
+        assert_eq!(best.describe(), "Five-high Straight");
+    }
+
+    #[test]
+    fn best_hand_rejects_bad_length() {
+        assert!(best_hand(&[]).is_err());
+    }
+
+    #[test]
+    fn categories_order_by_strength() {
+        assert!(HandCategory::Flush > HandCategory::Straight);
+        assert_eq!(HandCategory::FullHouse.to_string(), "Full House");
+    }
+}
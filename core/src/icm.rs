@@ -0,0 +1,532 @@
+use rand::rngs::StdRng;
+use rand::RngExt;
+
+use crate::monte_carlo::seeded_rng;
+use crate::types::SnapError;
+
+/// Most players with chips that [`icm_equity`] accepts; the calculation is
+/// exponential in the number of live stacks.
+pub const MAX_ICM_PLAYERS: usize = 20;
+
+/// Finishing orders for [`estimate_icm_equity`] to sample when callers have
+/// no budget of their own; about ±0.1% of the prize pool per player.
+pub const DEFAULT_ICM_TRIALS: usize = 100_000;
+
+/// Prize equity of each player under the Independent Chip Model
+/// (Malmuth–Harville): a player finishes first with probability equal to
+/// their share of the chips, and the remaining places follow recursively
+/// among the others.
+///
+/// - `payouts[k]` is the prize for place `k + 1`; places beyond the list pay nothing.
+/// - Players with an empty stack have already busted and take the bottom
+///   paid places, split evenly among them.
+///
+/// Returned values are in the units of `payouts` and sum to the prizes
+/// awarded to `stacks.len()` players.
+///
+/// # Errors
+/// Returns [`SnapError::InvalidArgument`] for negative or non-finite stacks
+/// or payouts, or when no player has chips, and [`SnapError::ResourceLimit`]
+/// with more than [`MAX_ICM_PLAYERS`] live stacks.
+pub fn icm_equity(stacks: &[f64], payouts: &[f64]) -> Result<Vec<f64>, SnapError> {
+    let alive = live_players(stacks, payouts)?;
+    if alive.len() > MAX_ICM_PLAYERS {
+        return Err(SnapError::ResourceLimit(format!(
+            "ICM supports at most {} players with chips, got {}",
+            MAX_ICM_PLAYERS,
+            alive.len()
+        )));
+    }
+
+    let prize = |place: usize| payouts.get(place).copied().unwrap_or(0.0);
+    let mut equity = busted_equity(stacks, payouts, alive.len());
+
+    // `reach[mask]`: probability that exactly the live players in `mask`
+    // fill the top `mask.count_ones()` places, in some order.
+    let n = alive.len();
+    let chips: Vec<f64> = alive.iter().map(|&i| stacks[i]).collect();
+    let total: f64 = chips.iter().sum();
+    let paid = payouts.len().min(n);
+    let mut reach = vec![0.0; 1 << n];
+    let mut taken_chips = vec![0.0; 1 << n];
+    reach[0] = 1.0;
+    for mask in 0usize..1 << n {
+        if mask != 0 {
+            let low = mask.trailing_zeros() as usize;
+            taken_chips[mask] = taken_chips[mask & (mask - 1)] + chips[low];
+        }
+        let place = mask.count_ones() as usize;
+        if reach[mask] == 0.0 || place >= paid {
+            continue;
+        }
+        let left = total - taken_chips[mask];
+        for (k, &player) in alive.iter().enumerate() {
+            if mask & (1 << k) != 0 {
+                continue;
+            }
+            let p = reach[mask] * chips[k] / left;
+            equity[player] += p * prize(place);
+            reach[mask | (1 << k)] += p;
+        }
+    }
+
+    Ok(equity)
+}
+
+/// Like [`icm_equity`], estimated from `trials` sampled finishing orders,
+/// for fields too large to solve exactly (e.g. a final table plus the
+/// players still waiting to bust). Orders follow the same model: each
+/// place goes to a remaining player in proportion to their chips.
+///
+/// The same `seed` always gives the same estimate; `None` draws a fresh one.
+///
+/// # Errors
+/// As [`icm_equity`], without the player limit, and
+/// [`SnapError::InvalidArgument`] for zero `trials`.
+pub fn icm_equity_monte_carlo(
+    stacks: &[f64],
+    payouts: &[f64],
+    trials: usize,
+    seed: Option<u64>,
+) -> Result<Vec<f64>, SnapError> {
+    let alive = live_players(stacks, payouts)?;
+    if trials == 0 {
+        return Err(SnapError::InvalidArgument(
+            "ICM needs at least one trial".to_string(),
+        ));
+    }
+    let mut rng = seeded_rng(seed);
+
+    let paid = payouts.len().min(alive.len());
+    let mut totals = vec![0.0; stacks.len()];
+    let mut order: Vec<(f64, usize)> = Vec::with_capacity(alive.len());
+    for _ in 0..trials {
+        // Sorting exponential draws scaled by 1/stack yields a finishing
+        // order with exactly the Malmuth–Harville place probabilities
+        order.clear();
+        order.extend(alive.iter().map(|&player| {
+            let uniform = 1.0 - rng.random::<f64>();
+            (-uniform.ln() / stacks[player], player)
+        }));
+        if paid < order.len() {
+            order.select_nth_unstable_by(paid, |a, b| a.0.total_cmp(&b.0));
+        }
+        order[..paid].sort_unstable_by(|a, b| a.0.total_cmp(&b.0));
+        for (place, &(_, player)) in order[..paid].iter().enumerate() {
+            totals[player] += payouts[place];
+        }
+    }
+
+    let mut equity = busted_equity(stacks, payouts, alive.len());
+    for &player in &alive {
+        equity[player] = totals[player] / trials as f64;
+    }
+    Ok(equity)
+}
+
+/// Prize equity solved exactly with [`icm_equity`] for up to
+/// [`MAX_ICM_PLAYERS`] players with chips, and estimated with
+/// [`icm_equity_monte_carlo`] from `trials` orders beyond that.
+///
+/// # Errors
+/// As [`icm_equity_monte_carlo`].
+pub fn estimate_icm_equity(
+    stacks: &[f64],
+    payouts: &[f64],
+    trials: usize,
+    seed: Option<u64>,
+) -> Result<Vec<f64>, SnapError> {
+    let alive = live_players(stacks, payouts)?;
+    if alive.len() <= MAX_ICM_PLAYERS {
+        icm_equity(stacks, payouts)
+    } else {
+        icm_equity_monte_carlo(stacks, payouts, trials, seed)
+    }
+}
+
+/// Future hands simulated by [`fgs_equity`] and the forced bets in them.
+///
+/// - `button` is the seat on the button for the first simulated hand; it
+///   moves to the next seat with chips every hand.
+/// - `ante` is posted by every player with chips, blinds by the two seats
+///   after the button (heads-up, the button posts the small blind).
+/// - The same `seed` always gives the same estimate; `None` draws a fresh
+///   one.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FgsOptions {
+    pub hands: usize,
+    pub small_blind: f64,
+    pub big_blind: f64,
+    pub ante: f64,
+    pub button: usize,
+    pub trials: usize,
+    pub seed: Option<u64>,
+}
+
+impl FgsOptions {
+    /// `hands` future hands at the given blinds, no ante, button on seat 0,
+    /// and [`DEFAULT_FGS_TRIALS`] runs.
+    pub fn new(hands: usize, small_blind: f64, big_blind: f64) -> Self {
+        Self {
+            hands,
+            small_blind,
+            big_blind,
+            ante: 0.0,
+            button: 0,
+            trials: DEFAULT_FGS_TRIALS,
+            seed: None,
+        }
+    }
+}
+
+/// Simulated futures for [`FgsOptions::new`].
+pub const DEFAULT_FGS_TRIALS: usize = 10_000;
+
+/// Prize equity by Future Game Simulation: plays `options.hands` more hands
+/// of forced bets before applying [`icm_equity`] to the stacks left, so a
+/// short stack about to be blinded out is worth less than its chips alone
+/// suggest.
+///
+/// Each simulated hand's pot goes to a uniformly random player still in the
+/// tournament, modelling equally skilled opponents. A winner collects from
+/// each opponent at most its own stack at the start of the hand, and a
+/// player left without chips busts, taking the lowest open place (players busting in the
+/// same hand split their places). `hands = 0` is plain [`icm_equity`].
+///
+/// # Errors
+/// As [`icm_equity`], plus [`SnapError::InvalidArgument`] for negative or
+/// non-finite blinds or ante, a `button` seat outside `stacks`, or zero
+/// `trials`.
+pub fn fgs_equity(
+    stacks: &[f64],
+    payouts: &[f64],
+    options: &FgsOptions,
+) -> Result<Vec<f64>, SnapError> {
+    let alive = live_players(stacks, payouts)?;
+    let bets = [options.small_blind, options.big_blind, options.ante];
+    if bets.iter().any(|b| !b.is_finite() || *b < 0.0) {
+        return Err(SnapError::InvalidArgument(
+            "Blinds and ante must be finite and not negative".to_string(),
+        ));
+    }
+    if options.button >= stacks.len() {
+        return Err(SnapError::InvalidArgument(format!(
+            "Button seat {} is outside {} stacks",
+            options.button,
+            stacks.len()
+        )));
+    }
+    if options.trials == 0 {
+        return Err(SnapError::InvalidArgument(
+            "FGS needs at least one trial".to_string(),
+        ));
+    }
+    // Fails fast on a field too large for the exact ICM at the end of a run
+    icm_equity(stacks, payouts)?;
+
+    let prize = |place: usize| payouts.get(place).copied().unwrap_or(0.0);
+    let mut rng = seeded_rng(options.seed);
+    let mut totals = busted_equity(stacks, payouts, alive.len());
+    for total in totals.iter_mut() {
+        *total *= options.trials as f64;
+    }
+
+    for _ in 0..options.trials {
+        let mut chips = stacks.to_vec();
+        let mut live = alive.clone();
+        let mut button = options.button;
+        for _ in 0..options.hands {
+            if live.len() < 2 {
+                break;
+            }
+            play_hand(&mut chips, &live, button, options, &mut rng);
+            let busted: Vec<usize> = live.iter().copied().filter(|&p| chips[p] <= 0.0).collect();
+            if !busted.is_empty() {
+                let places = live.len() - busted.len()..live.len();
+                let share = places.map(prize).sum::<f64>() / busted.len() as f64;
+                for &player in &busted {
+                    totals[player] += share;
+                }
+                live.retain(|&p| chips[p] > 0.0);
+            }
+            button = next_seat(&live, button, stacks.len());
+        }
+        let left: Vec<f64> = live.iter().map(|&p| chips[p]).collect();
+        let equity = icm_equity(&left, payouts)?;
+        for (&player, value) in live.iter().zip(equity) {
+            totals[player] += value;
+        }
+    }
+
+    Ok(totals
+        .into_iter()
+        .map(|total| total / options.trials as f64)
+        .collect())
+}
+
+/// Plays one hand of forced bets among `live` (in seat order) and awards the
+/// pot.
+fn play_hand(
+    chips: &mut [f64],
+    live: &[usize],
+    button: usize,
+    options: &FgsOptions,
+    rng: &mut StdRng,
+) {
+    let seats = chips.len();
+    let mut bets = vec![0.0; seats];
+    let mut post = |player: usize, amount: f64| {
+        let amount = amount.min(chips[player]);
+        chips[player] -= amount;
+        bets[player] += amount;
+    };
+    for &player in live {
+        post(player, options.ante);
+    }
+    let small = if live.len() == 2 && live.contains(&button) {
+        button
+    } else {
+        next_seat(live, button, seats)
+    };
+    post(small, options.small_blind);
+    post(next_seat(live, small, seats), options.big_blind);
+
+    // The winner calls what it takes, so each opponent pays at most the
+    // winner's starting stack
+    let winner = live[rng.random_range(0..live.len())];
+    let cover = chips[winner] + bets[winner];
+    for &player in live {
+        let won = bets[player].min(cover);
+        chips[winner] += won;
+        chips[player] += bets[player] - won;
+    }
+}
+
+/// The first seat after `seat` that is in `live`, wrapping around.
+fn next_seat(live: &[usize], seat: usize, seats: usize) -> usize {
+    (1..=seats)
+        .map(|step| (seat + step) % seats)
+        .find(|s| live.contains(s))
+        .unwrap_or(seat)
+}
+
+/// Seats with chips, after checking every stack and payout.
+pub(crate) fn live_players(stacks: &[f64], payouts: &[f64]) -> Result<Vec<usize>, SnapError> {
+    if stacks.iter().any(|s| !s.is_finite() || *s < 0.0) {
+        return Err(SnapError::InvalidArgument(
+            "Stacks must be finite and not negative".to_string(),
+        ));
+    }
+    if payouts.iter().any(|p| !p.is_finite() || *p < 0.0) {
+        return Err(SnapError::InvalidArgument(
+            "Payouts must be finite and not negative".to_string(),
+        ));
+    }
+    let alive: Vec<usize> = (0..stacks.len()).filter(|&i| stacks[i] > 0.0).collect();
+    if alive.is_empty() {
+        return Err(SnapError::InvalidArgument(
+            "At least one player needs chips".to_string(),
+        ));
+    }
+    Ok(alive)
+}
+
+/// Equity with only the busted players filled in: they share the places
+/// below the `alive` live ones evenly.
+pub(crate) fn busted_equity(stacks: &[f64], payouts: &[f64], alive: usize) -> Vec<f64> {
+    let mut equity = vec![0.0; stacks.len()];
+    let busted = stacks.len() - alive;
+    if busted > 0 {
+        let prize = |place: usize| payouts.get(place).copied().unwrap_or(0.0);
+        let share = (alive..stacks.len()).map(prize).sum::<f64>() / busted as f64;
+        for (i, stack) in stacks.iter().enumerate() {
+            if *stack == 0.0 {
+                equity[i] = share;
+            }
+        }
+    }
+    equity
+}
+
+/// Bubble factor of `hero` calling an all-in from `villain`: the prize
+/// equity hero stands to lose divided by what hero stands to gain when the
+/// effective stack changes hands.
+///
+/// A value of 1 means chips and prize money move together (chip EV);
+/// above 1 losing hurts more than winning helps.
+///
+/// # Errors
+/// Returns [`SnapError::InvalidArgument`] for out-of-range or equal seats,
+/// or a seat without chips, plus any error from [`icm_equity`].
+pub fn bubble_factor(
+    stacks: &[f64],
+    payouts: &[f64],
+    hero: usize,
+    villain: usize,
+) -> Result<f64, SnapError> {
+    if hero >= stacks.len() || villain >= stacks.len() || hero == villain {
+        return Err(SnapError::InvalidArgument(format!(
+            "Need two different seats below {}, got {} and {}",
+            stacks.len(),
+            hero,
+            villain
+        )));
+    }
+    let at_risk = stacks[hero].min(stacks[villain]);
+    if at_risk <= 0.0 {
+        return Err(SnapError::InvalidArgument(
+            "Both players need chips for an all-in".to_string(),
+        ));
+    }
+
+    let now = icm_equity(stacks, payouts)?[hero];
+    let mut after = stacks.to_vec();
+    after[hero] += at_risk;
+    after[villain] -= at_risk;
+    let win = icm_equity(&after, payouts)?[hero];
+    after[hero] -= 2.0 * at_risk;
+    after[villain] += 2.0 * at_risk;
+    let lose = icm_equity(&after, payouts)?[hero];
+
+    let gain = win - now;
+    if gain <= 0.0 {
+        return Ok(f64::INFINITY);
+    }
+    Ok((now - lose) / gain)
+}
+
+/// Equity (in percent) needed to call an all-in with no dead money at a
+/// given bubble factor: `bf / (1 + bf)`, i.e. 50% at chip EV.
+pub fn bubble_call_equity_percent(bubble_factor: f64) -> f64 {
+    if bubble_factor.is_infinite() {
+        return 100.0;
+    }
+    bubble_factor / (1.0 + bubble_factor) * 100.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(a: f64, b: f64) {
+        assert!((a - b).abs() < 1e-9, "{a} vs {b}");
+    }
+
+    #[test]
+    fn heads_up_equity_is_linear_in_chips() {
+        let equity = icm_equity(&[3000.0, 1000.0], &[70.0, 30.0]).unwrap();
+        assert_close(equity[0], 30.0 + 40.0 * 0.75);
+        assert_close(equity[1], 30.0 + 40.0 * 0.25);
+    }
+
+    #[test]
+    fn three_handed_matches_hand_calculation() {
+        // P(A 1st) = .5; A 2nd: B first (.3) · 5/7 + C first (.2) · 5/8
+        let equity = icm_equity(&[5000.0, 3000.0, 2000.0], &[50.0, 30.0, 20.0]).unwrap();
+        let a_second = 0.3 * 5.0 / 7.0 + 0.2 * 5.0 / 8.0;
+        let a_third = 1.0 - 0.5 - a_second;
+        assert_close(equity[0], 0.5 * 50.0 + a_second * 30.0 + a_third * 20.0);
+        assert_close(equity.iter().sum::<f64>(), 100.0);
+        assert!(equity[0] > equity[1] && equity[1] > equity[2]);
+    }
+
+    #[test]
+    fn busted_players_take_the_bottom_places() {
+        let equity = icm_equity(&[5000.0, 0.0, 5000.0], &[50.0, 30.0, 20.0]).unwrap();
+        assert_close(equity[1], 20.0);
+        assert_close(equity[0], 40.0);
+    }
+
+    #[test]
+    fn winner_take_all_has_no_bubble_pressure() {
+        let bf = bubble_factor(&[4000.0, 2000.0, 1000.0], &[100.0], 0, 1).unwrap();
+        assert_close(bf, 1.0);
+        assert_close(bubble_call_equity_percent(bf), 50.0);
+    }
+
+    #[test]
+    fn bubble_factor_exceeds_one_on_the_bubble() {
+        let stacks = [4000.0, 3000.0, 2000.0, 1000.0];
+        let payouts = [50.0, 30.0, 20.0];
+        let bf = bubble_factor(&stacks, &payouts, 1, 0).unwrap();
+        assert!(bf > 1.0, "got {bf}");
+        assert!(bubble_call_equity_percent(bf) > 50.0);
+    }
+
+    #[test]
+    fn monte_carlo_converges_on_exact() {
+        let stacks = [5000.0, 3000.0, 2000.0, 0.0];
+        let payouts = [50.0, 30.0, 20.0];
+        let exact = icm_equity(&stacks, &payouts).unwrap();
+        let sampled = icm_equity_monte_carlo(&stacks, &payouts, 200_000, Some(3)).unwrap();
+        for (e, s) in exact.iter().zip(&sampled) {
+            assert!((e - s).abs() < 0.3, "{e} vs {s}");
+        }
+        assert_close(sampled[3], exact[3]);
+        let again = icm_equity_monte_carlo(&stacks, &payouts, 200_000, Some(3)).unwrap();
+        assert_eq!(sampled, again);
+    }
+
+    #[test]
+    fn large_fields_fall_back_to_sampling() {
+        let stacks = [1000.0; MAX_ICM_PLAYERS + 10];
+        let payouts = [40.0, 25.0, 15.0, 10.0, 10.0];
+        let equity = estimate_icm_equity(&stacks, &payouts, 20_000, Some(1)).unwrap();
+        assert_close(equity.iter().sum::<f64>(), 100.0);
+        assert!(equity.iter().all(|e| (e - 100.0 / 30.0).abs() < 0.5));
+    }
+
+    #[test]
+    fn fgs_without_hands_is_plain_icm() {
+        let stacks = [5000.0, 3000.0, 2000.0];
+        let payouts = [50.0, 30.0, 20.0];
+        let options = FgsOptions {
+            trials: 10,
+            ..FgsOptions::new(0, 100.0, 200.0)
+        };
+        let fgs = fgs_equity(&stacks, &payouts, &options).unwrap();
+        for (a, b) in fgs.iter().zip(icm_equity(&stacks, &payouts).unwrap()) {
+            assert_close(*a, b);
+        }
+    }
+
+    #[test]
+    fn fgs_discounts_a_stack_about_to_post_the_big_blind() {
+        // Seat 3's last 300 go in the big blind with the button on seat 1,
+        // while with the button on seat 3 it posts nothing next hand
+        let stacks = [5000.0, 5000.0, 5000.0, 300.0];
+        let payouts = [50.0, 30.0, 20.0];
+        let fgs = |button| {
+            let options = FgsOptions {
+                button,
+                seed: Some(9),
+                ..FgsOptions::new(1, 200.0, 400.0)
+            };
+            fgs_equity(&stacks, &payouts, &options).unwrap()
+        };
+        let big_blind = fgs(1);
+        let on_button = fgs(3);
+        assert!(
+            big_blind[3] + 1.0 < on_button[3],
+            "{} vs {}",
+            big_blind[3],
+            on_button[3]
+        );
+        assert_close(big_blind.iter().sum::<f64>(), 100.0);
+    }
+
+    #[test]
+    fn rejects_invalid_inputs() {
+        assert!(icm_equity(&[0.0, 0.0], &[1.0]).is_err());
+        assert!(icm_equity(&[-1.0, 2.0], &[1.0]).is_err());
+        assert!(icm_equity(&[1.0; MAX_ICM_PLAYERS + 1], &[1.0]).is_err());
+        assert!(bubble_factor(&[1.0, 2.0], &[1.0], 0, 0).is_err());
+        assert!(bubble_factor(&[1.0, 2.0], &[1.0], 0, 2).is_err());
+        assert!(icm_equity_monte_carlo(&[1.0, 2.0], &[1.0], 0, None).is_err());
+        let options = FgsOptions {
+            button: 2,
+            ..FgsOptions::new(5, 1.0, 2.0)
+        };
+        assert!(fgs_equity(&[1.0, 2.0], &[1.0], &options).is_err());
+    }
+}
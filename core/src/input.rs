@@ -1,5 +1,8 @@
-use rs_poker::core::{Card, FlatHand, Rankable};
+use std::collections::HashSet;
 
+use rs_poker::core::{Card, FlatHand, Rankable, Suit, Value};
+
+use crate::range::top_percent_classes;
 use crate::types::SnapError;
 
 /// Hero or villain hole-cards input.
@@ -9,10 +12,18 @@ use crate::types::SnapError;
 /// - `"Ah"` → `Partial` (one known card, second dealt randomly)
 /// - `"AhKd"` → `Exact` (both cards known)
 /// - `"TT+"` / `"AKs"` → `Range` (expanded via `rs_poker::holdem::RangeParser`)
+/// - `"top 20%"` → `Range` (strongest classes covering 20% of combos)
+/// - `"QQ+, AKs:0.5"` → `WeightedRange` (a `:weight` from 0 to 1 keeps that
+///   share of each combo; unweighted tokens count fully)
+///
+/// A token starting with `!` removes its combos from the rest of the range,
+/// e.g. `"top 20%, !A2o"`.
 #[derive(Clone)]
 pub enum HoleCardsInput {
     Exact(FlatHand),
     Range(Vec<FlatHand>),
+    /// Range combos with a weight in `(0, 1]` for each, in the same order.
+    WeightedRange(Vec<FlatHand>, Vec<f64>),
     Partial(Card),
     Unknown,
 }
@@ -73,9 +84,7 @@ impl std::str::FromStr for HoleCardsInput {
             }
         }
 
-        let range_hands = rs_poker::holdem::RangeParser::parse_many(trimmed).map_err(|e| {
-            SnapError::InvalidRange(format!("Failed to parse range '{}': {:?}", trimmed, e))
-        })?;
+        let (range_hands, weights) = parse_range_tokens(trimmed)?;
 
         if range_hands.is_empty() {
             return Err(SnapError::InvalidRange(format!(
@@ -91,7 +100,11 @@ impl std::str::FromStr for HoleCardsInput {
             )));
         }
 
-        Ok(Self::Range(range_hands))
+        if weights.iter().all(|&w| w == 1.0) {
+            Ok(Self::Range(range_hands))
+        } else {
+            Ok(Self::WeightedRange(range_hands, weights))
+        }
     }
 }
 
@@ -105,8 +118,11 @@ impl std::str::FromStr for BoardCardsInput {
         }
 
         let cleaned = normalize_cards_str(trimmed);
-        let hand = FlatHand::new_from_str(&cleaned).map_err(|e| {
-            SnapError::InvalidHand(format!("Failed to parse board '{}': {:?}", trimmed, e))
+        let hand = FlatHand::new_from_str(&cleaned).map_err(|e| match diagnose_cards(trimmed) {
+            Some(diagnostic) => {
+                SnapError::InvalidCard(format!("{} in board '{}'", diagnostic, trimmed))
+            }
+            None => SnapError::InvalidHand(format!("Failed to parse board '{}': {:?}", trimmed, e)),
         })?;
 
         match hand.len() {
@@ -121,6 +137,271 @@ impl std::str::FromStr for BoardCardsInput {
     }
 }
 
+/// Parses a comma-separated range where each token is either `rs_poker` range
+/// syntax (`"AKs"`, `"TT+"`) or a percentile (`"top 20%"` / `"20%"`), with an
+/// optional `:weight` suffix or `!` exclusion prefix. Returns the combos and
+/// their weights.
+///
+/// Duplicate combos across tokens are removed (the first weight wins), and
+/// excluded or zero-weight combos are dropped.
+fn parse_range_tokens(s: &str) -> Result<(Vec<FlatHand>, Vec<f64>), SnapError> {
+    let mut hands: Vec<(FlatHand, f64)> = Vec::new();
+    let mut excluded: HashSet<(Card, Card)> = HashSet::new();
+    for token in s.split(',').map(str::trim) {
+        let (exclude, body, weight) = split_range_token(token)?;
+        let parsed = range_token_hands(body, s)?;
+        if exclude {
+            excluded.extend(parsed.iter().filter_map(combo_key));
+        } else {
+            hands.extend(parsed.into_iter().map(|hand| (hand, weight)));
+        }
+    }
+
+    let mut seen: HashSet<(Card, Card)> = HashSet::with_capacity(hands.len());
+    hands.retain(|(hand, weight)| match combo_key(hand) {
+        Some(key) => seen.insert(key) && !excluded.contains(&key) && *weight > 0.0,
+        None => true,
+    });
+    Ok(hands.into_iter().unzip())
+}
+
+/// Splits `"!AKs"` / `"AKs:0.5"` into (excluded, body, weight).
+fn split_range_token(token: &str) -> Result<(bool, &str, f64), SnapError> {
+    let (exclude, rest) = match token.strip_prefix('!') {
+        Some(rest) => (true, rest.trim_start()),
+        None => (false, token),
+    };
+    let Some((body, weight)) = rest.rsplit_once(':') else {
+        return Ok((exclude, rest, 1.0));
+    };
+    match weight.trim().parse::<f64>() {
+        Ok(w) if !exclude && (0.0..=1.0).contains(&w) => Ok((exclude, body.trim_end(), w)),
+        _ => Err(SnapError::InvalidRange(format!(
+            "Invalid weight in '{}', expected e.g. \"AKs:0.5\" (0 to 1, not on exclusions)",
+            token
+        ))),
+    }
+}
+
+/// Combos of one range token body (a percentile or `rs_poker` syntax) from
+/// the whole range `s`.
+fn range_token_hands(body: &str, s: &str) -> Result<Vec<FlatHand>, SnapError> {
+    match parse_percent_token(body) {
+        Some(pct) => Ok(top_percent_classes(pct?)
+            .iter()
+            .flat_map(|class| class.cards())
+            .map(|[a, b]| FlatHand::new_with_cards(vec![a, b]))
+            .collect()),
+        None => parse_range_token(body)
+            .map_err(|e| SnapError::InvalidRange(format!("{} in range '{}'", e, s))),
+    }
+}
+
+/// A two-card hand as an order-independent key.
+fn combo_key(hand: &FlatHand) -> Option<(Card, Card)> {
+    let mut iter = hand.iter().copied();
+    let (a, b) = (iter.next()?, iter.next()?);
+    Some((a.min(b), a.max(b)))
+}
+
+/// Parses one `rs_poker` range token, rejecting trailing characters the
+/// parser would otherwise ignore (`"AKQ"` is not `"AK"`).
+fn parse_range_token(token: &str) -> Result<Vec<FlatHand>, String> {
+    let parsed = rs_poker::holdem::RangeParser::parse_one(token)
+        .map_err(|e| format!("'{}' is not a range ({:?})", token, e))?;
+    let used = range_token_len(token);
+    if used < token.len() {
+        return Err(format!(
+            "'{}' is not a range: unexpected '{}' after '{}'",
+            token,
+            &token[used..],
+            &token[..used]
+        ));
+    }
+    Ok(parsed)
+}
+
+/// Number of leading bytes of `token` that `RangeParser::parse_one` reads:
+/// two ranks with optional suits, then any `+`, `s`, `o`, or `-XY` modifiers.
+fn range_token_len(token: &str) -> usize {
+    let chars: Vec<char> = token.chars().collect();
+    let is_value = |i: usize| chars.get(i).is_some_and(|&c| Value::from_char(c).is_some());
+    let is_suit = |i: usize| chars.get(i).is_some_and(|&c| Suit::from_char(c).is_some());
+
+    let mut i = 0;
+    if !is_value(i) {
+        return 0;
+    }
+    i += 1;
+    let first_suit = is_suit(i);
+    if first_suit {
+        i += 1;
+    }
+    if !is_value(i) {
+        return i;
+    }
+    i += 1;
+    if first_suit && is_suit(i) {
+        i += 1;
+    }
+    loop {
+        match chars.get(i) {
+            Some('+' | 's' | 'o') => i += 1,
+            Some('-') if is_value(i + 1) && is_value(i + 2) => i += 3,
+            _ => break,
+        }
+    }
+    chars[..i].iter().map(|c| c.len_utf8()).sum()
+}
+
+/// Recognises `"top N%"` or `"N%"`; returns `None` for any other token.
+fn parse_percent_token(token: &str) -> Option<Result<f64, SnapError>> {
+    let lower = token.to_ascii_lowercase();
+    let body = lower.strip_prefix("top").unwrap_or(&lower).trim();
+    let number = body.strip_suffix('%')?.trim();
+    Some(match number.parse::<f64>() {
+        Ok(pct) if pct > 0.0 && pct <= 100.0 => Ok(pct),
+        _ => Err(SnapError::InvalidRange(format!(
+            "Invalid percentile '{}', expected e.g. \"top 20%\"",
+            token
+        ))),
+    })
+}
+
+/// Parses any number of distinct cards (e.g. `"Ah 7c"`); `""` yields none.
+///
+/// # Errors
+/// Returns [`SnapError::InvalidCard`] on unparseable input and
+/// [`SnapError::InvalidHand`] on a repeated card.
+pub fn parse_cards(s: &str) -> Result<Vec<Card>, SnapError> {
+    let cleaned = normalize_cards_str(s);
+    if cleaned.is_empty() {
+        return Ok(Vec::new());
+    }
+    let hand = FlatHand::new_from_str(&cleaned).map_err(|e| match diagnose_cards(s) {
+        Some(diagnostic) => SnapError::InvalidCard(diagnostic.to_string()),
+        None => SnapError::InvalidCard(format!("'{}': {:?}", s.trim(), e)),
+    })?;
+    let cards: Vec<Card> = hand.iter().copied().collect();
+    let unique: HashSet<Card> = cards.iter().copied().collect();
+    if unique.len() != cards.len() {
+        return Err(SnapError::InvalidHand(format!(
+            "Duplicate card in '{}'",
+            s.trim()
+        )));
+    }
+    Ok(cards)
+}
+
+/// Why a card token failed to parse.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CardIssue {
+    /// The rank character is not one of `23456789TJQKA`.
+    Rank(char),
+    /// The suit character is not one of `shdc`.
+    Suit(char),
+    /// The token ends after the rank.
+    MissingSuit,
+}
+
+/// The first unparseable card in a card string, for error messages and
+/// correction hints.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CardDiagnostic {
+    /// The offending token as typed, e.g. `"Kx"` or `"10h"`.
+    pub token: String,
+    pub issue: CardIssue,
+    /// Byte offset of the token in the input.
+    pub position: usize,
+}
+
+impl std::fmt::Display for CardDiagnostic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.issue {
+            CardIssue::Rank(c) => write!(
+                f,
+                "'{}' is not a card: '{}' is not a rank (2-9, T, J, Q, K, A)",
+                self.token, c
+            ),
+            CardIssue::Suit(c) => write!(
+                f,
+                "'{}' is not a card: '{}' is not a suit (s, h, d, c)",
+                self.token, c
+            ),
+            CardIssue::MissingSuit => write!(f, "'{}' is missing a suit", self.token),
+        }
+    }
+}
+
+/// Finds the first card in `s` that does not parse, reading it as
+/// rank-suit pairs the way [`parse_cards`] does (`"10"` counts as one rank
+/// so `"10h"` is reported whole). Returns `None` when every card parses.
+pub fn diagnose_cards(s: &str) -> Option<CardDiagnostic> {
+    // Same characters as `normalize_cards_str`, keeping their offsets
+    let chars: Vec<(usize, char)> = s
+        .char_indices()
+        .filter(|(_, c)| !c.is_whitespace() && *c != ',')
+        .collect();
+    let char_at = |i: usize| chars.get(i).map(|&(_, c)| c);
+    let mut i = 0;
+    while i < chars.len() {
+        let (position, first) = chars[i];
+        let rank_len = if first == '1' && char_at(i + 1) == Some('0') {
+            2
+        } else {
+            1
+        };
+        let end = (i + rank_len + 1).min(chars.len());
+        let token: String = chars[i..end].iter().map(|&(_, c)| c).collect();
+        let issue = if rank_len == 2 || Value::from_char(first).is_none() {
+            Some(CardIssue::Rank(first))
+        } else {
+            match char_at(i + 1) {
+                None => Some(CardIssue::MissingSuit),
+                Some(c) if Suit::from_char(c).is_none() => Some(CardIssue::Suit(c)),
+                Some(_) => None,
+            }
+        };
+        if let Some(issue) = issue {
+            return Some(CardDiagnostic {
+                token,
+                issue,
+                position,
+            });
+        }
+        i = end;
+    }
+    None
+}
+
+/// The first range token that does not parse.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RangeDiagnostic {
+    /// The offending comma-separated token, trimmed, e.g. `"AKQ"`.
+    pub token: String,
+    /// Byte offset of the token in the input.
+    pub position: usize,
+}
+
+/// Returns the first comma-separated token of a range expression that does
+/// not parse, or `None` when the whole range is valid.
+pub fn diagnose_range(s: &str) -> Option<RangeDiagnostic> {
+    let mut offset = 0;
+    for part in s.split(',') {
+        let token = part.trim();
+        let valid =
+            split_range_token(token).is_ok_and(|(_, body, _)| range_token_hands(body, s).is_ok());
+        if !valid {
+            return Some(RangeDiagnostic {
+                token: token.to_string(),
+                position: offset + part.len() - part.trim_start().len(),
+            });
+        }
+        offset += part.len() + 1;
+    }
+    None
+}
+
 /// Strip whitespace and commas from a card string (e.g. `"Ah, Kd"` → `"AhKd"`).
 pub(crate) fn normalize_cards_str(s: &str) -> String {
     s.chars()
@@ -182,12 +463,103 @@ mod tests {
         assert!(matches!(hand, HoleCardsInput::Range(_)));
     }
 
+    #[test]
+    fn hole_cards_top_percent_range() {
+        let HoleCardsInput::Range(hands) = "top 20%".parse().unwrap() else {
+            panic!("expected range");
+        };
+        let pct = hands.len() as f64 / 1326.0 * 100.0;
+        assert!((19.0..=21.0).contains(&pct), "got {pct}%");
+    }
+
+    #[test]
+    fn hole_cards_percent_mixed_with_classes_dedupes() {
+        let HoleCardsInput::Range(hands) = "5%, AA".parse().unwrap() else {
+            panic!("expected range");
+        };
+        let HoleCardsInput::Range(top) = "5%".parse().unwrap() else {
+            panic!("expected range");
+        };
+        assert_eq!(hands.len(), top.len(), "AA is already in the top 5%");
+    }
+
+    #[test]
+    fn hole_cards_bad_percent_is_error() {
+        assert!("top 0%".parse::<HoleCardsInput>().is_err());
+        assert!("top 120%".parse::<HoleCardsInput>().is_err());
+    }
+
+    #[test]
+    fn hole_cards_weighted_range() {
+        let HoleCardsInput::WeightedRange(hands, weights) = "QQ+, AKs:0.5".parse().unwrap() else {
+            panic!("expected weighted range");
+        };
+        assert_eq!(hands.len(), 18 + 4);
+        assert_eq!(weights.iter().filter(|&&w| w == 0.5).count(), 4);
+        // All-full weights stay a plain range
+        assert!(matches!("QQ+:1".parse().unwrap(), HoleCardsInput::Range(_)));
+    }
+
+    #[test]
+    fn hole_cards_exclusions() {
+        let HoleCardsInput::Range(hands) = "TT+, !KK, AKs:0".parse().unwrap() else {
+            panic!("expected range");
+        };
+        assert_eq!(hands.len(), 5 * 6 - 6);
+        assert!("AKs:2".parse::<HoleCardsInput>().is_err());
+        assert!("!AKs:0.5".parse::<HoleCardsInput>().is_err());
+        assert!("QQ, !QQ".parse::<HoleCardsInput>().is_err());
+        assert_eq!(diagnose_range("top 10%, !AA, KQs:0.25"), None);
+    }
+
     #[test]
     fn hole_cards_three_cards_is_error() {
         let result: Result<HoleCardsInput, _> = "AhKdQc".parse();
         assert!(result.is_err());
     }
 
+    // ── parse_cards ───────────────────────────────────────────────────
+
+    #[test]
+    fn parse_cards_any_count() {
+        assert!(parse_cards("").unwrap().is_empty());
+        assert_eq!(parse_cards("Ah 7c").unwrap().len(), 2);
+        assert_eq!(parse_cards("Ah,7c,2d,9s,Ts,Jd").unwrap().len(), 6);
+    }
+
+    #[test]
+    fn parse_cards_rejects_duplicates_and_garbage() {
+        assert!(parse_cards("Ah Ah").is_err());
+        assert!(parse_cards("Xx").is_err());
+    }
+
+    #[test]
+    fn diagnoses_the_first_bad_card() {
+        assert_eq!(diagnose_cards("Ah Kd"), None);
+        let bad_suit = diagnose_cards("Ah Kx").unwrap();
+        assert_eq!(bad_suit.token, "Kx");
+        assert_eq!(bad_suit.issue, CardIssue::Suit('x'));
+        assert_eq!(bad_suit.position, 3);
+        assert_eq!(diagnose_cards("10h").unwrap().token, "10h");
+        assert_eq!(diagnose_cards("Ah,Kd, 10h").unwrap().position, 7);
+        assert_eq!(
+            diagnose_cards("Ah K").unwrap().issue,
+            CardIssue::MissingSuit
+        );
+        let err = parse_cards("Ah Kx").unwrap_err();
+        assert!(err.to_string().contains("'Kx'"), "{err}");
+    }
+
+    #[test]
+    fn diagnoses_the_first_bad_range_token() {
+        assert_eq!(diagnose_range("TT+, AKs"), None);
+        let bad = diagnose_range("TT+, AKQ, 22").unwrap();
+        assert_eq!(bad.token, "AKQ");
+        assert_eq!(bad.position, 5);
+        assert_eq!(diagnose_range("AsKs, 22-55, KTo+, top 5%"), None);
+        assert!("AKQ".parse::<HoleCardsInput>().is_err());
+    }
+
     // ── BoardCardsInput parsing ───────────────────────────────────────
 
     #[test]
@@ -16,12 +16,102 @@ pub use rs_poker::core::{Card, Deck, Rank, Rankable, Suit, Value};
 pub use rs_poker::core::{FlatHand, Hand};
 pub use rs_poker::holdem;
 
+pub use rand::Rng;
+
+mod board;
+mod bounty;
+mod breakdown;
+#[cfg(feature = "cache")]
+mod cache;
+mod called;
+mod chart;
+#[cfg(feature = "icm")]
+mod chop;
+mod chunked;
+mod deal;
+mod draws;
 mod enumeration;
 mod estimate;
+mod ev;
+mod format;
+mod hand;
+#[cfg(feature = "icm")]
+mod icm;
 mod input;
+mod locale;
 mod monte_carlo;
+mod odds;
+mod pots;
+mod pushfold;
+mod rake;
+mod range;
+mod realization;
+mod river;
+mod sizing;
+mod solver;
+mod strength;
+mod suggest;
 mod types;
+mod variance;
 
-pub use estimate::estimate_equity;
-pub use input::{BoardCardsInput, HoleCardsInput};
-pub use types::{EquityEstimateMode, EquityResult, SnapError};
+pub use board::{board_texture, BoardTexture, HighCard, SuitPattern};
+pub use bounty::{bounty_call_equity_percent, bounty_call_ev, BountyOptions};
+pub use breakdown::{combo_breakdown, combo_breakdown_with, ComboEquity};
+#[cfg(feature = "cache")]
+pub use cache::EquityCache;
+pub use called::{equity_when_called, CalledEquity, ContinueRange};
+pub use chart::{preflop_chart, ChartKind, ChartOptions, PreflopChart};
+#[cfg(feature = "icm")]
+pub use chop::{chop_deal, ChopMethod};
+pub use chunked::EquityChunks;
+pub use deal::{deal_random_hand, Dealer, DealtHand, Street, Table, MAX_SEATS};
+pub use draws::{find_draws, grade_outs, Draws, OutQuality, StraightDraw};
+pub use estimate::{
+    equity_vs_random, equity_vs_random_with_rng, estimate_equity, estimate_equity_with_options,
+    estimate_equity_with_rng, estimate_multi_board_equity,
+    estimate_multi_board_equity_with_options, estimate_multi_board_equity_with_rng,
+    estimate_range_equity, estimate_range_equity_with_options, estimate_range_equity_with_rng,
+    plan_equity,
+};
+pub use ev::{call_ev, decision_ev, decision_ev_with_rake, Decision, DecisionEv};
+pub use format::{format_card, format_cards, parse_styled_cards, SuitStyle};
+pub use hand::{best_hand, BestHand, HandCategory};
+#[cfg(feature = "icm")]
+pub use icm::{
+    bubble_call_equity_percent, bubble_factor, estimate_icm_equity, fgs_equity, icm_equity,
+    icm_equity_monte_carlo, FgsOptions, DEFAULT_FGS_TRIALS, DEFAULT_ICM_TRIALS, MAX_ICM_PLAYERS,
+};
+pub use input::{
+    diagnose_cards, diagnose_range, parse_cards, BoardCardsInput, CardDiagnostic, CardIssue,
+    HoleCardsInput, RangeDiagnostic,
+};
+pub use locale::Locale;
+pub use odds::{
+    bluff_break_even_percent, geometric_bet_fraction, minimum_defense_frequency_percent,
+    outs_rule_of_thumb, outs_to_percent, percent_to_odds_against, percent_to_outs,
+    pot_odds_percent, pot_rake, raked_pot_odds_percent, stack_off_equity_percent,
+    stack_to_pot_ratio,
+};
+pub use pots::{GameState, Pot, PotEquity, PotShare};
+pub use pushfold::{solve_blind_vs_blind, solve_push_fold, PushFoldSolution, MAX_TABLE_PLAYERS};
+pub use rake::RakeModel;
+pub use range::{
+    expand_range, expand_weighted_range, range_grid, HandClass, RangeGridCell, TOTAL_COMBOS,
+};
+pub use realization::{Position, RealizationModel};
+pub use river::{
+    solve_river, RiverAction, RiverNode, RiverOptions, RiverSolution, DEFAULT_RIVER_ITERATIONS,
+};
+pub use sizing::{
+    geometric_plan, pot_fraction_bet, pot_size_raise, stack_off_after_bet_percent, StreetBet,
+};
+pub use solver::{solve_preflop_cfr, PreflopAction, PreflopNode, PreflopSolution};
+pub use strength::{
+    hand_potential, hand_strength, next_card_strengths, HandPotential, HandStrength,
+};
+pub use suggest::{card_suggestions, looks_like_cards, range_suggestions};
+pub use types::{
+    EquityEstimateMode, EquityMath, EquityOptions, EquityResult, MultiBoardResult, SnapError,
+    DEFAULT_MAX_EXACT_STATES,
+};
+pub use variance::{simulate_bankroll, VarianceConfig, VarianceReport};
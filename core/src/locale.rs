@@ -0,0 +1,270 @@
+use rs_poker::core::Value;
+
+use crate::hand::{value_name, value_plural, BestHand, HandCategory};
+
+/// Language for hand names and descriptions.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum Locale {
+    #[default]
+    English,
+    Chinese,
+    Spanish,
+    Portuguese,
+    Russian,
+}
+
+impl Locale {
+    /// Locale for a BCP 47 tag such as `"zh-Hans"`, `"pt_BR"`, or `"es"`,
+    /// matched on the language alone. Unsupported languages fall back to
+    /// English, so a device locale can be passed as is.
+    pub fn from_tag(tag: &str) -> Self {
+        let language = tag.trim().split(['-', '_']).next().unwrap_or("");
+        match language.to_ascii_lowercase().as_str() {
+            "zh" => Self::Chinese,
+            "es" => Self::Spanish,
+            "pt" => Self::Portuguese,
+            "ru" => Self::Russian,
+            _ => Self::English,
+        }
+    }
+}
+
+impl HandCategory {
+    /// Category name in `locale`, e.g. `"葫芦"` for a full house in Chinese.
+    pub fn name_in(&self, locale: Locale) -> &'static str {
+        let names = match locale {
+            Locale::English => return self.name(),
+            Locale::Chinese => [
+                "高牌",
+                "一对",
+                "两对",
+                "三条",
+                "顺子",
+                "同花",
+                "葫芦",
+                "四条",
+                "同花顺",
+            ],
+            Locale::Spanish => [
+                "Carta alta",
+                "Pareja",
+                "Doble pareja",
+                "Trío",
+                "Escalera",
+                "Color",
+                "Full",
+                "Póker",
+                "Escalera de color",
+            ],
+            Locale::Portuguese => [
+                "Carta alta",
+                "Par",
+                "Dois pares",
+                "Trinca",
+                "Sequência",
+                "Flush",
+                "Full house",
+                "Quadra",
+                "Straight flush",
+            ],
+            Locale::Russian => [
+                "Старшая карта",
+                "Пара",
+                "Две пары",
+                "Сет",
+                "Стрит",
+                "Флеш",
+                "Фулл-хаус",
+                "Каре",
+                "Стрит-флеш",
+            ],
+        };
+        names[*self as usize]
+    }
+}
+
+impl BestHand {
+    /// Like [`describe`](Self::describe), in `locale`, e.g. `"Full de Ases
+    /// con Reyes"` in Spanish.
+    pub fn describe_in(&self, locale: Locale) -> String {
+        let made = self.made();
+        let first = made[0].value;
+        let one = |v: Value| rank_name(locale, v, false);
+        let many = |v: Value| rank_name(locale, v, true);
+        let name = self.category.name_in(locale);
+        let royal = self.category == HandCategory::StraightFlush && first == Value::Ace;
+        match locale {
+            Locale::English => self.describe(),
+            Locale::Chinese => match self.category {
+                HandCategory::HighCard => format!("高牌{}", one(first)),
+                HandCategory::OnePair => format!("一对{}", one(first)),
+                HandCategory::TwoPair => format!("两对，{}和{}", one(first), one(made[2].value)),
+                HandCategory::ThreeOfAKind => format!("三条{}", one(first)),
+                HandCategory::FullHouse => format!("葫芦，{}带{}", one(first), one(made[3].value)),
+                HandCategory::FourOfAKind => format!("四条{}", one(first)),
+                _ if royal => "皇家同花顺".to_string(),
+                _ => format!("{}，{}高", name, one(first)),
+            },
+            Locale::Spanish => match self.category {
+                HandCategory::HighCard => format!("{}: {}", name, one(first)),
+                HandCategory::OnePair => format!("Pareja de {}", many(first)),
+                HandCategory::TwoPair => {
+                    format!("Doble pareja: {} y {}", many(first), many(made[2].value))
+                }
+                HandCategory::ThreeOfAKind => format!("Trío de {}", many(first)),
+                HandCategory::FullHouse => {
+                    format!("Full de {} con {}", many(first), many(made[3].value))
+                }
+                HandCategory::FourOfAKind => format!("Póker de {}", many(first)),
+                _ if royal => "Escalera real".to_string(),
+                _ => format!("{} al {}", name, one(first)),
+            },
+            Locale::Portuguese => match self.category {
+                HandCategory::HighCard => format!("{}: {}", name, one(first)),
+                HandCategory::OnePair => format!("Par de {}", many(first)),
+                HandCategory::TwoPair => {
+                    format!("Dois pares: {} e {}", many(first), many(made[2].value))
+                }
+                HandCategory::ThreeOfAKind => format!("Trinca de {}", many(first)),
+                HandCategory::FullHouse => {
+                    format!("Full house: {} e {}", many(first), many(made[3].value))
+                }
+                HandCategory::FourOfAKind => format!("Quadra de {}", many(first)),
+                _ if royal => "Royal flush".to_string(),
+                _ => format!("{} até {}", name, one(first)),
+            },
+            // Nominative forms after a colon sidestep Russian case endings
+            Locale::Russian => match self.category {
+                HandCategory::TwoPair | HandCategory::FullHouse => {
+                    let second = made[if self.category == HandCategory::TwoPair {
+                        2
+                    } else {
+                        3
+                    }];
+                    format!("{}: {} и {}", name, many(first), many(second.value))
+                }
+                HandCategory::OnePair | HandCategory::ThreeOfAKind | HandCategory::FourOfAKind => {
+                    format!("{}: {}", name, many(first))
+                }
+                _ if royal => "Роял-флеш".to_string(),
+                _ => format!("{}: {}", name, one(first)),
+            },
+        }
+    }
+}
+
+/// A rank's name in `locale`, as a single card or as a plural ("Ases").
+/// Chinese uses the card symbols (`"A"`, `"10"`) for both.
+fn rank_name(locale: Locale, value: Value, plural: bool) -> String {
+    // Indexed by `Value as usize`: Two first, Ace last
+    let (one, many): ([&str; 13], [&str; 13]) = match locale {
+        Locale::English if plural => return value_plural(value),
+        Locale::English => return value_name(value).to_string(),
+        Locale::Chinese => {
+            let symbols = [
+                "2", "3", "4", "5", "6", "7", "8", "9", "10", "J", "Q", "K", "A",
+            ];
+            (symbols, symbols)
+        }
+        Locale::Spanish => (
+            [
+                "Dos", "Tres", "Cuatro", "Cinco", "Seis", "Siete", "Ocho", "Nueve", "Diez", "Jota",
+                "Dama", "Rey", "As",
+            ],
+            [
+                "Doses", "Treses", "Cuatros", "Cincos", "Seises", "Sietes", "Ochos", "Nueves",
+                "Dieces", "Jotas", "Damas", "Reyes", "Ases",
+            ],
+        ),
+        Locale::Portuguese => (
+            [
+                "Dois", "Três", "Quatro", "Cinco", "Seis", "Sete", "Oito", "Nove", "Dez", "Valete",
+                "Dama", "Rei", "Ás",
+            ],
+            [
+                "Dois", "Treses", "Quatros", "Cincos", "Seis", "Setes", "Oitos", "Noves", "Dez",
+                "Valetes", "Damas", "Reis", "Ases",
+            ],
+        ),
+        Locale::Russian => (
+            [
+                "Двойка",
+                "Тройка",
+                "Четвёрка",
+                "Пятёрка",
+                "Шестёрка",
+                "Семёрка",
+                "Восьмёрка",
+                "Девятка",
+                "Десятка",
+                "Валет",
+                "Дама",
+                "Король",
+                "Туз",
+            ],
+            [
+                "Двойки",
+                "Тройки",
+                "Четвёрки",
+                "Пятёрки",
+                "Шестёрки",
+                "Семёрки",
+                "Восьмёрки",
+                "Девятки",
+                "Десятки",
+                "Валеты",
+                "Дамы",
+                "Короли",
+                "Тузы",
+            ],
+        ),
+    };
+    let index = value as usize;
+    if plural { many[index] } else { one[index] }.to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hand::best_hand;
+    use crate::input::parse_cards;
+
+    fn describe(cards: &str, locale: Locale) -> String {
+        best_hand(&parse_cards(cards).unwrap())
+            .unwrap()
+            .describe_in(locale)
+    }
+
+    #[test]
+    fn reads_language_tags() {
+        assert_eq!(Locale::from_tag("zh-Hans-CN"), Locale::Chinese);
+        assert_eq!(Locale::from_tag("pt_BR"), Locale::Portuguese);
+        assert_eq!(Locale::from_tag("RU"), Locale::Russian);
+        assert_eq!(Locale::from_tag("fr-FR"), Locale::English);
+    }
+
+    #[test]
+    fn describes_hands_per_locale() {
+        let full_house = "AhAdAcKsKd";
+        assert_eq!(describe(full_house, Locale::English), "Aces full of Kings");
+        assert_eq!(describe(full_house, Locale::Chinese), "葫芦，A带K");
+        assert_eq!(
+            describe(full_house, Locale::Spanish),
+            "Full de Ases con Reyes"
+        );
+        assert_eq!(
+            describe(full_house, Locale::Portuguese),
+            "Full house: Ases e Reis"
+        );
+        assert_eq!(
+            describe(full_house, Locale::Russian),
+            "Фулл-хаус: Тузы и Короли"
+        );
+        assert_eq!(
+            describe("9h8h7h6h5h", Locale::Spanish),
+            "Escalera de color al Nueve"
+        );
+        assert_eq!(describe("AsKsQsJsTs", Locale::Chinese), "皇家同花顺");
+        assert_eq!(HandCategory::TwoPair.name_in(Locale::Russian), "Две пары");
+    }
+}
@@ -1,43 +1,70 @@
 use std::collections::HashSet;
+use std::time::{Duration, Instant};
 
+use rand::distr::weighted::WeightedIndex;
+use rand::distr::Distribution;
 use rand::prelude::{IndexedRandom, SliceRandom};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
 use rs_poker::core::{Card, Deck, Rank, Rankable};
 
 use crate::input::HoleCardsInput;
-use crate::types::{EquityEstimateMode, EquityResult, SnapError};
+use crate::types::{EquityEstimateMode, EquityMath, EquityResult, MultiBoardResult, SnapError};
+
+/// The generator every sampler in the crate uses unless the caller passes
+/// its own: seeded when `seed` is given, otherwise from the operating system.
+pub(crate) fn seeded_rng(seed: Option<u64>) -> StdRng {
+    match seed {
+        Some(s) => StdRng::seed_from_u64(s),
+        None => rand::make_rng(),
+    }
+}
+
+/// Seed for stream `stream` of a run seeded with `seed`, for samplers that
+/// split one seeded run over several generators.
+///
+/// Both inputs go through the SplitMix64 finalizer, so stream 1 of seed `s`
+/// is unrelated to stream 0 of seed `s + 1` rather than the same generator.
+pub(crate) fn stream_seed(seed: u64, stream: u64) -> u64 {
+    fn mix(mut z: u64) -> u64 {
+        z = z.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+    mix(mix(seed) ^ stream)
+}
+
+/// Iterations between clock reads when sampling against a time budget.
+const DEADLINE_CHECK_INTERVAL: usize = 256;
 
 /// Monte Carlo equity estimation via random sampling.
 ///
 /// Deals random cards to incomplete hands, evaluates all players,
-/// and accumulates win counts over `iterations` samples.
-pub(crate) fn estimate_equity_monte_carlo(
+/// and accumulates win counts over `iterations` samples, or until
+/// `time_budget` elapses if that comes first. `fixed_known` holds every card
+/// that is never dealt: board, dead cards, and exact/partial hole cards.
+/// Every random draw comes from `rng`, so a seeded generator makes the
+/// sample sequence reproducible.
+pub(crate) fn estimate_equity_monte_carlo<R: rand::Rng + ?Sized>(
     board_cards: &[Card],
-    board_set: &HashSet<Card>,
+    fixed_known: &HashSet<Card>,
     players: &[HoleCardsInput],
     iterations: usize,
+    time_budget: Option<Duration>,
+    math: EquityMath,
+    rng: &mut R,
 ) -> Result<EquityResult, SnapError> {
     let num_players = players.len();
     let full_deck: Vec<Card> = Deck::default().into_iter().collect();
-    let mut rng = rand::rng();
+    let samplers = range_samplers(players)?;
     let mut wins: Vec<usize> = vec![0; num_players];
+    let mut outright: Vec<usize> = vec![0; num_players];
     let mut samples = 0usize;
     let missing_board = 5 - board_cards.len();
 
-    // Pre-collect fixed cards (board + exact/partial) to avoid recomputing each iteration
-    let mut fixed_cards: Vec<Card> = board_set.iter().copied().collect();
-    for p in players {
-        match p {
-            HoleCardsInput::Exact(hand) => {
-                for c in hand.iter() {
-                    fixed_cards.push(*c);
-                }
-            }
-            HoleCardsInput::Partial(c) => {
-                fixed_cards.push(*c);
-            }
-            _ => {}
-        }
-    }
+    // Pre-collect fixed cards (board + dead + exact/partial) to avoid recomputing each iteration
+    let fixed_cards: Vec<Card> = fixed_known.iter().copied().collect();
 
     // Pre-allocate reusable buffers outside the hot loop
     let mut used: HashSet<Card> = HashSet::with_capacity(fixed_cards.len() + num_players * 2);
@@ -47,77 +74,35 @@ pub(crate) fn estimate_equity_monte_carlo(
     let mut seven_cards: Vec<Card> = Vec::with_capacity(7);
     let mut ranks: Vec<Rank> = Vec::with_capacity(num_players);
 
-    'outer: for _ in 0..iterations {
-        // Reset used set and fill with fixed cards
-        used.clear();
-        used.extend(&fixed_cards);
+    // A time budget stops sampling early but never extends it past
+    // `iterations`; the clock is read every DEADLINE_CHECK_INTERVAL iterations
+    // to keep `Instant::now` off the hot path. It is only read once a sample
+    // has been drawn, so a budget that is already spent still yields a result
+    // rather than an empty one.
+    let deadline = time_budget.map(|budget| Instant::now() + budget);
 
-        // First pass: deal Range players via rejection sampling
-        for (idx, p) in players.iter().enumerate() {
-            if let HoleCardsInput::Range(hands) = p {
-                let mut found = false;
-                for _ in 0..100 {
-                    let Some(hand) = hands.choose(&mut rng) else {
-                        continue 'outer;
-                    };
-                    let mut iter = hand.iter().copied();
-                    let (Some(c1), Some(c2)) = (iter.next(), iter.next()) else {
-                        continue 'outer;
-                    };
-                    if !used.contains(&c1) && !used.contains(&c2) {
-                        used.insert(c1);
-                        used.insert(c2);
-                        hole_cards[idx] = [c1, c2];
-                        found = true;
-                        break;
-                    }
-                }
-                if !found {
-                    continue 'outer;
-                }
+    'outer: for n in 0..iterations {
+        if let Some(deadline) = deadline {
+            if n % DEADLINE_CHECK_INTERVAL == 0 && samples > 0 && Instant::now() >= deadline {
+                break;
             }
         }
 
-        // Rebuild available cards from full deck, reusing the Vec
-        available.clear();
-        available.extend(full_deck.iter().copied().filter(|c| !used.contains(c)));
-        available.shuffle(&mut rng);
-        let mut cursor = 0;
-
-        // Second pass: deal non-Range players
-        let mut valid = true;
-        for (idx, p) in players.iter().enumerate() {
-            match p {
-                HoleCardsInput::Exact(hand) => {
-                    let mut iter = hand.iter().copied();
-                    let (Some(c1), Some(c2)) = (iter.next(), iter.next()) else {
-                        valid = false;
-                        break;
-                    };
-                    hole_cards[idx] = [c1, c2];
-                }
-                HoleCardsInput::Partial(known) => {
-                    if cursor >= available.len() {
-                        valid = false;
-                        break;
-                    }
-                    hole_cards[idx] = [*known, available[cursor]];
-                    cursor += 1;
-                }
-                HoleCardsInput::Unknown => {
-                    if cursor + 1 >= available.len() {
-                        valid = false;
-                        break;
-                    }
-                    hole_cards[idx] = [available[cursor], available[cursor + 1]];
-                    cursor += 2;
-                }
-                HoleCardsInput::Range(_) => {} // already dealt
-            }
-        }
-        if !valid {
+        // Reset used set and fill with fixed cards
+        used.clear();
+        used.extend(&fixed_cards);
+
+        let Some(mut cursor) = deal_hole_cards(
+            players,
+            &samplers,
+            &full_deck,
+            rng,
+            &mut used,
+            &mut available,
+            &mut hole_cards,
+        ) else {
             continue;
-        }
+        };
 
         // Complete the board, reusing the Vec
         full_board.clear();
@@ -140,9 +125,13 @@ pub(crate) fn estimate_equity_monte_carlo(
         }
 
         if let Some(best) = ranks.iter().max() {
+            let winners = ranks.iter().filter(|r| *r == best).count();
             for (i, r) in ranks.iter().enumerate() {
                 if r == best {
                     wins[i] += 1;
+                    if winners == 1 {
+                        outright[i] += 1;
+                    }
                 }
             }
         }
@@ -156,18 +145,301 @@ pub(crate) fn estimate_equity_monte_carlo(
         ));
     }
 
-    let total: usize = wins.iter().sum();
-    let equities = if total == 0 {
-        vec![100.0 / num_players as f64; num_players]
-    } else {
-        wins.iter()
-            .map(|&w| (w as f64 / total as f64) * 100.0)
-            .collect()
-    };
-
-    Ok(EquityResult {
-        equities,
-        mode: EquityEstimateMode::MonteCarlo,
+    Ok(EquityResult::from_counts(
+        &wins,
+        &outright,
+        samples,
+        EquityEstimateMode::MonteCarlo,
+        math,
+    ))
+}
+
+/// Monte Carlo equity for hero (exact cards or one known card) against
+/// `opponents` random hands.
+///
+/// With no ranges to reject-sample, each iteration only partially shuffles
+/// the live deck for the cards it needs instead of rebuilding it, which makes
+/// this noticeably faster than [`estimate_equity_monte_carlo`] with unknown
+/// players. `hero` is player 0 in the result, opponents follow.
+#[allow(clippy::too_many_arguments)] // mirrors estimate_equity_monte_carlo plus the seat shape
+pub(crate) fn estimate_vs_random_monte_carlo<R: rand::Rng + ?Sized>(
+    board_cards: &[Card],
+    fixed_known: &HashSet<Card>,
+    hero: &[Card],
+    opponents: usize,
+    iterations: usize,
+    time_budget: Option<Duration>,
+    math: EquityMath,
+    rng: &mut R,
+) -> Result<EquityResult, SnapError> {
+    let num_players = 1 + opponents;
+    let mut live: Vec<Card> = Deck::default()
+        .into_iter()
+        .filter(|c| !fixed_known.contains(c))
+        .collect();
+    let hero_missing = 2 - hero.len();
+    let needed = hero_missing + 2 * opponents + (5 - board_cards.len());
+    if needed > live.len() {
+        return Err(SnapError::InvalidHand(
+            "Too many players/cards for a 52-card deck".to_string(),
+        ));
+    }
+
+    let mut wins: Vec<usize> = vec![0; num_players];
+    let mut outright: Vec<usize> = vec![0; num_players];
+    let mut samples = 0usize;
+    let mut seven_cards: Vec<Card> = Vec::with_capacity(7);
+    let mut ranks: Vec<Rank> = Vec::with_capacity(num_players);
+
+    let deadline = time_budget.map(|budget| Instant::now() + budget);
+
+    for n in 0..iterations {
+        if let Some(deadline) = deadline {
+            if n % DEADLINE_CHECK_INTERVAL == 0 && samples > 0 && Instant::now() >= deadline {
+                break;
+            }
+        }
+
+        // Hero's missing card, then two per opponent, then the runout
+        let (dealt, _) = live.partial_shuffle(rng, needed);
+        let (hero_extra, rest) = dealt.split_at(hero_missing);
+        let (holes, runout) = rest.split_at(2 * opponents);
+
+        ranks.clear();
+        seven_cards.clear();
+        seven_cards.extend_from_slice(hero);
+        seven_cards.extend_from_slice(hero_extra);
+        seven_cards.extend_from_slice(board_cards);
+        seven_cards.extend_from_slice(runout);
+        ranks.push(seven_cards.as_slice().rank());
+        for hole in holes.chunks_exact(2) {
+            seven_cards.clear();
+            seven_cards.extend_from_slice(hole);
+            seven_cards.extend_from_slice(board_cards);
+            seven_cards.extend_from_slice(runout);
+            ranks.push(seven_cards.as_slice().rank());
+        }
+
+        if let Some(best) = ranks.iter().max() {
+            let winners = ranks.iter().filter(|r| *r == best).count();
+            for (i, r) in ranks.iter().enumerate() {
+                if r == best {
+                    wins[i] += 1;
+                    if winners == 1 {
+                        outright[i] += 1;
+                    }
+                }
+            }
+        }
+
+        samples += 1;
+    }
+
+    if samples == 0 {
+        return Err(SnapError::InvalidRange(
+            "No valid samples generated".to_string(),
+        ));
+    }
+
+    Ok(EquityResult::from_counts(
+        &wins,
+        &outright,
+        samples,
+        EquityEstimateMode::MonteCarlo,
+        math,
+    ))
+}
+
+/// Monte Carlo equity for several boards sharing the same hole cards (e.g.
+/// double-board bomb pots).
+///
+/// Each sample deals hole cards once and then completes every board from the
+/// same remaining deck, so boards never share cards. Every board is worth an
+/// equal part of the pot and ties split it. `time_budget` stops sampling
+/// early as in [`estimate_equity_monte_carlo`].
+pub(crate) fn estimate_multi_board_monte_carlo<R: rand::Rng + ?Sized>(
+    boards: &[Vec<Card>],
+    fixed_known: &HashSet<Card>,
+    players: &[HoleCardsInput],
+    iterations: usize,
+    time_budget: Option<Duration>,
+    rng: &mut R,
+) -> Result<MultiBoardResult, SnapError> {
+    let num_players = players.len();
+    let num_boards = boards.len();
+    let full_deck: Vec<Card> = Deck::default().into_iter().collect();
+    let samplers = range_samplers(players)?;
+    let fixed_cards: Vec<Card> = fixed_known.iter().copied().collect();
+
+    // shares[b][i]: pot fraction of board b won by player i, summed over samples
+    let mut shares: Vec<Vec<f64>> = vec![vec![0.0; num_players]; num_boards];
+    let mut samples = 0usize;
+
+    // Pre-allocate reusable buffers outside the hot loop
+    let mut used: HashSet<Card> = HashSet::with_capacity(fixed_cards.len() + num_players * 2);
+    let mut available: Vec<Card> = Vec::with_capacity(full_deck.len());
+    let mut hole_cards: Vec<[Card; 2]> = vec![[full_deck[0], full_deck[0]]; num_players];
+    let mut full_board: Vec<Card> = Vec::with_capacity(5);
+    let mut seven_cards: Vec<Card> = Vec::with_capacity(7);
+    let mut ranks: Vec<Rank> = Vec::with_capacity(num_players);
+
+    let deadline = time_budget.map(|budget| Instant::now() + budget);
+
+    'outer: for n in 0..iterations {
+        if let Some(deadline) = deadline {
+            if n % DEADLINE_CHECK_INTERVAL == 0 && samples > 0 && Instant::now() >= deadline {
+                break;
+            }
+        }
+
+        used.clear();
+        used.extend(&fixed_cards);
+
+        let Some(mut cursor) = deal_hole_cards(
+            players,
+            &samplers,
+            &full_deck,
+            rng,
+            &mut used,
+            &mut available,
+            &mut hole_cards,
+        ) else {
+            continue;
+        };
+
+        for (b, board) in boards.iter().enumerate() {
+            full_board.clear();
+            full_board.extend_from_slice(board);
+            for _ in board.len()..5 {
+                if cursor >= available.len() {
+                    continue 'outer;
+                }
+                full_board.push(available[cursor]);
+                cursor += 1;
+            }
+
+            ranks.clear();
+            for hole in hole_cards.iter() {
+                seven_cards.clear();
+                seven_cards.extend_from_slice(hole);
+                seven_cards.extend_from_slice(&full_board);
+                ranks.push(seven_cards.as_slice().rank());
+            }
+
+            if let Some(best) = ranks.iter().max() {
+                let winners = ranks.iter().filter(|r| *r == best).count();
+                for (i, r) in ranks.iter().enumerate() {
+                    if r == best {
+                        shares[b][i] += 1.0 / winners as f64;
+                    }
+                }
+            }
+        }
+
+        samples += 1;
+    }
+
+    if samples == 0 {
+        return Err(SnapError::InvalidRange(
+            "No valid samples generated".to_string(),
+        ));
+    }
+
+    let board_equities: Vec<Vec<f64>> = shares
+        .iter()
+        .map(|s| s.iter().map(|&w| w / samples as f64 * 100.0).collect())
+        .collect();
+    let pot_share = (0..num_players)
+        .map(|i| board_equities.iter().map(|e| e[i]).sum::<f64>() / num_boards as f64)
+        .collect();
+
+    Ok(MultiBoardResult {
+        board_equities,
+        pot_share,
         samples,
     })
 }
+
+/// Per-player combo samplers for weighted ranges, `None` for everyone else.
+fn range_samplers(
+    players: &[HoleCardsInput],
+) -> Result<Vec<Option<WeightedIndex<f64>>>, SnapError> {
+    players
+        .iter()
+        .map(|p| match p {
+            HoleCardsInput::WeightedRange(_, weights) => WeightedIndex::new(weights)
+                .map(Some)
+                .map_err(|_| SnapError::InvalidRange("Range has no weighted combos".to_string())),
+            _ => Ok(None),
+        })
+        .collect()
+}
+
+/// Deals one sample of hole cards for every player into `hole_cards`.
+///
+/// `used` must already hold every fixed card (board, exact and partial hole
+/// cards). Range players are dealt first via rejection sampling, in
+/// proportion to their `samplers` weights when they have one; the remaining
+/// deck is then shuffled into `available` and partial/unknown players draw
+/// from its front. Returns the cursor of the first undealt card in
+/// `available`, or `None` if this sample could not be dealt.
+fn deal_hole_cards<R: rand::Rng + ?Sized>(
+    players: &[HoleCardsInput],
+    samplers: &[Option<WeightedIndex<f64>>],
+    full_deck: &[Card],
+    rng: &mut R,
+    used: &mut HashSet<Card>,
+    available: &mut Vec<Card>,
+    hole_cards: &mut [[Card; 2]],
+) -> Option<usize> {
+    // First pass: deal Range players via rejection sampling
+    for (idx, p) in players.iter().enumerate() {
+        if let HoleCardsInput::Range(hands) | HoleCardsInput::WeightedRange(hands, _) = p {
+            let mut found = false;
+            for _ in 0..100 {
+                let hand = match &samplers[idx] {
+                    Some(weights) => hands.get(weights.sample(rng))?,
+                    None => hands.choose(rng)?,
+                };
+                let mut iter = hand.iter().copied();
+                let (c1, c2) = (iter.next()?, iter.next()?);
+                if !used.contains(&c1) && !used.contains(&c2) {
+                    used.insert(c1);
+                    used.insert(c2);
+                    hole_cards[idx] = [c1, c2];
+                    found = true;
+                    break;
+                }
+            }
+            if !found {
+                return None;
+            }
+        }
+    }
+
+    // Rebuild available cards from full deck, reusing the Vec
+    available.clear();
+    available.extend(full_deck.iter().copied().filter(|c| !used.contains(c)));
+    available.shuffle(rng);
+    let mut cursor = 0;
+
+    // Second pass: deal non-Range players
+    for (idx, p) in players.iter().enumerate() {
+        match p {
+            HoleCardsInput::Exact(hand) => {
+                let mut iter = hand.iter().copied();
+                hole_cards[idx] = [iter.next()?, iter.next()?];
+            }
+            HoleCardsInput::Partial(known) => {
+                hole_cards[idx] = [*known, *available.get(cursor)?];
+                cursor += 1;
+            }
+            HoleCardsInput::Unknown => {
+                hole_cards[idx] = [*available.get(cursor)?, *available.get(cursor + 1)?];
+                cursor += 2;
+            }
+            HoleCardsInput::Range(_) | HoleCardsInput::WeightedRange(..) => {} // already dealt
+        }
+    }
+    Some(cursor)
+}
@@ -0,0 +1,208 @@
+/// Exact probability (in percent) of hitting at least one of `outs` when
+/// `draws` cards are dealt from `unseen` cards.
+///
+/// Returns `0.0` when there are no outs and `100.0` when every unseen card is
+/// an out.
+pub fn outs_to_percent(outs: usize, unseen: usize, draws: usize) -> f64 {
+    if outs == 0 || draws == 0 || unseen == 0 {
+        return 0.0;
+    }
+    if outs >= unseen || draws > unseen - outs {
+        return 100.0;
+    }
+    let mut miss = 1.0;
+    for i in 0..draws {
+        miss *= (unseen - outs - i) as f64 / (unseen - i) as f64;
+    }
+    (1.0 - miss) * 100.0
+}
+
+/// "Rule of 2 and 4" approximation (in percent): outs × 2 per card to come,
+/// capped at 100.
+pub fn outs_rule_of_thumb(outs: usize, draws: usize) -> f64 {
+    ((outs * 2 * draws) as f64).min(100.0)
+}
+
+/// Smallest number of outs whose exact hit probability reaches `percent`.
+///
+/// Returns `None` if `percent` is not reachable (e.g. above 100).
+pub fn percent_to_outs(percent: f64, unseen: usize, draws: usize) -> Option<usize> {
+    (0..=unseen).find(|&outs| outs_to_percent(outs, unseen, draws) >= percent)
+}
+
+/// Converts a probability (in percent) into "odds against" as `x` in `x : 1`.
+///
+/// Returns `f64::INFINITY` for a zero probability.
+pub fn percent_to_odds_against(percent: f64) -> f64 {
+    if percent <= 0.0 {
+        f64::INFINITY
+    } else {
+        (100.0 - percent) / percent
+    }
+}
+
+/// Pot odds (in percent): the equity needed to break even on a call.
+///
+/// `pot` is the pot before the call, including any bet being faced.
+pub fn pot_odds_percent(pot: f64, call_amount: f64) -> f64 {
+    call_amount / (pot + call_amount) * 100.0
+}
+
+/// Minimum defense frequency (in percent): how much of their range a player
+/// facing `bet` into `pot` (the pot before the bet) must continue with so
+/// that a pure bluff does not profit.
+pub fn minimum_defense_frequency_percent(pot: f64, bet: f64) -> f64 {
+    pot / (pot + bet) * 100.0
+}
+
+/// Alpha (in percent): how often a bluff of `bet` into `pot` (the pot
+/// before the bet) must work to break even, i.e. 100 minus
+/// [`minimum_defense_frequency_percent`].
+pub fn bluff_break_even_percent(pot: f64, bet: f64) -> f64 {
+    bet / (pot + bet) * 100.0
+}
+
+/// Bet size as a fraction of the pot that, made on each of `streets`
+/// streets and called every time, gets a stack-to-pot ratio of `spr` all
+/// in by the last bet: `((1 + 2·spr)^(1/streets) − 1) / 2`.
+///
+/// Returns 0 for `streets == 0` or a non-positive `spr`.
+pub fn geometric_bet_fraction(spr: f64, streets: u32) -> f64 {
+    if streets == 0 || spr <= 0.0 {
+        return 0.0;
+    }
+    ((1.0 + 2.0 * spr).powf(1.0 / streets as f64) - 1.0) / 2.0
+}
+
+/// Rake taken from a final pot of `pot`: `rake_percent` of it, limited to
+/// `rake_cap` when one applies.
+pub fn pot_rake(pot: f64, rake_percent: f64, rake_cap: Option<f64>) -> f64 {
+    let rake = pot * rake_percent / 100.0;
+    match rake_cap {
+        Some(cap) => rake.min(cap),
+        None => rake,
+    }
+}
+
+/// Pot odds (in percent) once the pot is raked: the call has to win back
+/// the call itself from what is left after [`pot_rake`] of the final pot.
+///
+/// With `rake_percent == 0.0` this equals [`pot_odds_percent`].
+pub fn raked_pot_odds_percent(
+    pot: f64,
+    call_amount: f64,
+    rake_percent: f64,
+    rake_cap: Option<f64>,
+) -> f64 {
+    let final_pot = pot + call_amount;
+    call_amount / (final_pot - pot_rake(final_pot, rake_percent, rake_cap)) * 100.0
+}
+
+/// Stack-to-pot ratio: effective stack behind divided by the current pot.
+pub fn stack_to_pot_ratio(stack: f64, pot: f64) -> f64 {
+    stack / pot
+}
+
+/// Equity (in percent) needed to get the whole stack in without losing
+/// chips at a given SPR, i.e. the pot odds of calling an all-in of
+/// `spr` pots into the pot.
+pub fn stack_off_equity_percent(spr: f64) -> f64 {
+    pot_odds_percent(1.0 + spr, spr)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rake_raises_the_required_equity() {
+        // 100 into 100: 150 vs 200 total; 5% rake leaves 190
+        assert_eq!(
+            raked_pot_odds_percent(150.0, 50.0, 0.0, None),
+            pot_odds_percent(150.0, 50.0)
+        );
+        let raked = raked_pot_odds_percent(150.0, 50.0, 5.0, None);
+        assert!((raked - 50.0 / 190.0 * 100.0).abs() < 1e-9, "got {raked}");
+        // A cap of 3 leaves 197
+        let capped = raked_pot_odds_percent(150.0, 50.0, 5.0, Some(3.0));
+        assert!((capped - 50.0 / 197.0 * 100.0).abs() < 1e-9, "got {capped}");
+        assert_eq!(pot_rake(40.0, 5.0, Some(3.0)), 2.0);
+    }
+
+    #[test]
+    fn mdf_and_alpha_split_the_range() {
+        // A pot-sized bet: defend half, bluffs need to work half the time
+        assert_eq!(minimum_defense_frequency_percent(100.0, 100.0), 50.0);
+        assert_eq!(bluff_break_even_percent(100.0, 50.0), 50.0 / 150.0 * 100.0);
+        assert!(
+            (minimum_defense_frequency_percent(100.0, 75.0)
+                + bluff_break_even_percent(100.0, 75.0)
+                - 100.0)
+                .abs()
+                < 1e-9
+        );
+    }
+
+    #[test]
+    fn geometric_bets_get_the_stack_in() {
+        // SPR 13 over three streets: pot-sized bets (1 → 3 → 9 → 27)
+        assert!((geometric_bet_fraction(13.0, 3) - 1.0).abs() < 1e-12);
+        assert!((geometric_bet_fraction(4.0, 1) - 4.0).abs() < 1e-12);
+        let f = geometric_bet_fraction(6.5, 2);
+        let (mut pot, mut stack) = (1.0, 6.5);
+        for _ in 0..2 {
+            let bet = f * pot;
+            stack -= bet;
+            pot += 2.0 * bet;
+        }
+        assert!(stack.abs() < 1e-9);
+        assert_eq!(geometric_bet_fraction(5.0, 0), 0.0);
+    }
+
+    #[test]
+    fn flush_draw_on_flop() {
+        // 9 outs, 47 unseen, two cards to come ≈ 34.97%
+        let pct = outs_to_percent(9, 47, 2);
+        assert!((pct - 34.97).abs() < 0.01, "got {pct}");
+        assert_eq!(outs_rule_of_thumb(9, 2), 36.0);
+    }
+
+    #[test]
+    fn single_card_is_outs_over_unseen() {
+        let pct = outs_to_percent(8, 46, 1);
+        assert!((pct - 8.0 / 46.0 * 100.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn edge_cases() {
+        assert_eq!(outs_to_percent(0, 47, 2), 0.0);
+        assert_eq!(outs_to_percent(47, 47, 1), 100.0);
+        assert_eq!(outs_rule_of_thumb(30, 2), 100.0);
+    }
+
+    #[test]
+    fn percent_round_trips_to_outs() {
+        assert_eq!(percent_to_outs(34.0, 47, 2), Some(9));
+        assert_eq!(percent_to_outs(0.0, 47, 2), Some(0));
+        assert_eq!(percent_to_outs(101.0, 47, 2), None);
+    }
+
+    #[test]
+    fn odds_against_ratio() {
+        assert!((percent_to_odds_against(20.0) - 4.0).abs() < 1e-9);
+        assert!(percent_to_odds_against(0.0).is_infinite());
+    }
+
+    #[test]
+    fn stack_off_equity_by_spr() {
+        assert!((stack_to_pot_ratio(850.0, 120.0) - 7.0833).abs() < 1e-3);
+        // SPR 1: call 1 into 2 → 33.3%
+        assert!((stack_off_equity_percent(1.0) - 100.0 / 3.0).abs() < 1e-9);
+        assert!(stack_off_equity_percent(100.0) < 50.0);
+    }
+
+    #[test]
+    fn pot_odds_for_half_pot_call() {
+        assert!((pot_odds_percent(150.0, 50.0) - 25.0).abs() < 1e-9);
+    }
+}
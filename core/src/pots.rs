@@ -0,0 +1,378 @@
+use crate::estimate::estimate_range_equity_with_options;
+use crate::input::HoleCardsInput;
+use crate::types::{EquityOptions, SnapError};
+
+/// A main or side pot from [`GameState::pots`]: `amount` chips that only
+/// the seats in `eligible` can win.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Pot {
+    pub amount: f64,
+    pub eligible: Vec<usize>,
+}
+
+impl Pot {
+    /// Whether two or more players are still fighting for this pot. An
+    /// uncontested pot is an uncalled bet that goes back to its one player.
+    pub fn is_contested(&self) -> bool {
+        self.eligible.len() >= 2
+    }
+}
+
+/// One pot's share of a [`GameState::pot_equity`] result.
+///
+/// `equities` has a percentage per seat, 0 for seats not eligible for the
+/// pot; `samples` is 0 when the pot needed no showdown.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PotShare {
+    pub pot: Pot,
+    pub equities: Vec<f64>,
+    pub samples: usize,
+}
+
+/// Equity in every pot and the chips each seat expects to win back,
+/// counting all pots.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PotEquity {
+    pub pots: Vec<PotShare>,
+    pub expected_chips: Vec<f64>,
+}
+
+impl PotEquity {
+    /// Chips `seat` expects to win minus what it put in.
+    pub fn net(&self, state: &GameState, seat: usize) -> f64 {
+        self.expected_chips[seat] - state.committed(seat)
+    }
+}
+
+/// Chips in play for one hand: each seat's stack behind, what it has put
+/// into the pot so far, and whether it has folded.
+///
+/// Seats are indices into the starting `stacks`. Bets are cumulative over
+/// the whole hand, which is all [`pots`](Self::pots) needs to split the
+/// chips into a main pot and side pots.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GameState {
+    stacks: Vec<f64>,
+    committed: Vec<f64>,
+    folded: Vec<bool>,
+}
+
+impl GameState {
+    /// Starts a hand with `stacks` behind and nothing in the pot.
+    ///
+    /// # Errors
+    /// Returns [`SnapError::InvalidArgument`] for fewer than 2 players or a
+    /// negative stack.
+    pub fn new(stacks: &[f64]) -> Result<Self, SnapError> {
+        if stacks.len() < 2 {
+            return Err(SnapError::InvalidArgument(format!(
+                "Need at least 2 players, got {}",
+                stacks.len()
+            )));
+        }
+        if let Some(stack) = stacks.iter().find(|s| !s.is_finite() || **s < 0.0) {
+            return Err(SnapError::InvalidArgument(format!(
+                "Stacks must not be negative, got {}",
+                stack
+            )));
+        }
+        Ok(Self {
+            stacks: stacks.to_vec(),
+            committed: vec![0.0; stacks.len()],
+            folded: vec![false; stacks.len()],
+        })
+    }
+
+    /// A hand where every seat has put its whole stack in.
+    ///
+    /// # Errors
+    /// Same as [`new`](Self::new).
+    pub fn all_in(stacks: &[f64]) -> Result<Self, SnapError> {
+        let mut state = Self::new(stacks)?;
+        for seat in 0..stacks.len() {
+            state.bet(seat, f64::INFINITY)?;
+        }
+        Ok(state)
+    }
+
+    /// Puts up to `amount` more of `seat`'s stack into the pot and returns
+    /// what actually went in: a bet bigger than the stack is an all-in for
+    /// the stack.
+    ///
+    /// # Errors
+    /// Returns [`SnapError::InvalidArgument`] for an unknown or folded seat
+    /// or a negative amount.
+    pub fn bet(&mut self, seat: usize, amount: f64) -> Result<f64, SnapError> {
+        self.check_live(seat)?;
+        if amount.is_nan() || amount < 0.0 {
+            return Err(SnapError::InvalidArgument(format!(
+                "Bet must not be negative, got {}",
+                amount
+            )));
+        }
+        let amount = amount.min(self.stacks[seat]);
+        self.stacks[seat] -= amount;
+        self.committed[seat] += amount;
+        Ok(amount)
+    }
+
+    /// Folds `seat`; its chips stay in the pot.
+    ///
+    /// # Errors
+    /// Returns [`SnapError::InvalidArgument`] for an unknown or folded seat.
+    pub fn fold(&mut self, seat: usize) -> Result<(), SnapError> {
+        self.check_live(seat)?;
+        self.folded[seat] = true;
+        Ok(())
+    }
+
+    fn check_live(&self, seat: usize) -> Result<(), SnapError> {
+        if seat >= self.stacks.len() {
+            return Err(SnapError::InvalidArgument(format!(
+                "Seat {} is out of range for {} players",
+                seat,
+                self.stacks.len()
+            )));
+        }
+        if self.folded[seat] {
+            return Err(SnapError::InvalidArgument(format!(
+                "Seat {} has already folded",
+                seat
+            )));
+        }
+        Ok(())
+    }
+
+    /// Number of seats, folded or not.
+    pub fn players(&self) -> usize {
+        self.stacks.len()
+    }
+
+    /// Chips `seat` has behind.
+    pub fn stack(&self, seat: usize) -> f64 {
+        self.stacks[seat]
+    }
+
+    /// Chips `seat` has put into the pot this hand.
+    pub fn committed(&self, seat: usize) -> f64 {
+        self.committed[seat]
+    }
+
+    pub fn is_folded(&self, seat: usize) -> bool {
+        self.folded[seat]
+    }
+
+    /// Whether `seat` is still in the hand with no chips behind.
+    pub fn is_all_in(&self, seat: usize) -> bool {
+        !self.folded[seat] && self.stacks[seat] == 0.0 && self.committed[seat] > 0.0
+    }
+
+    /// Every chip in the middle, across all pots.
+    pub fn total_pot(&self) -> f64 {
+        self.committed.iter().sum()
+    }
+
+    /// The main pot followed by any side pots, smallest all-in first.
+    ///
+    /// Each all-in caps a pot at what that player put in: everyone pays
+    /// into it up to that level, and only players who reached it can win
+    /// it. Folded chips count toward the pots but their seats are never
+    /// eligible. Chips no live player can win (a folded seat that bet more
+    /// than everyone left) go to the last pot.
+    pub fn pots(&self) -> Vec<Pot> {
+        let live = |seat: &usize| !self.folded[*seat];
+        let mut levels: Vec<f64> = (0..self.players())
+            .filter(|&seat| self.is_all_in(seat))
+            .map(|seat| self.committed[seat])
+            .collect();
+        levels.push(self.committed.iter().copied().fold(0.0, f64::max));
+        levels.sort_by(f64::total_cmp);
+        levels.dedup();
+
+        let mut pots: Vec<Pot> = Vec::new();
+        let mut previous = 0.0;
+        for level in levels {
+            let amount: f64 = self
+                .committed
+                .iter()
+                .map(|&c| c.min(level) - c.min(previous))
+                .sum();
+            previous = level;
+            if amount <= 0.0 {
+                continue;
+            }
+            let eligible: Vec<usize> = (0..self.players())
+                .filter(live)
+                .filter(|&seat| self.committed[seat] >= level)
+                .collect();
+            match pots.last_mut() {
+                Some(last) if eligible.is_empty() => last.amount += amount,
+                _ => pots.push(Pot { amount, eligible }),
+            }
+        }
+        pots
+    }
+
+    /// Each seat's equity in each of [`pots`](Self::pots) and the chips it
+    /// expects to win, with `hands` holding every seat's hole cards or
+    /// range (`""` for unknown or folded seats).
+    ///
+    /// Every contested pot is estimated separately among its eligible
+    /// players. Exact hands of the other seats are dead cards for it, so
+    /// card removal still counts; their ranges are not.
+    ///
+    /// # Errors
+    /// Returns [`SnapError::InvalidArgument`] when `hands` does not have one
+    /// entry per seat, plus any [`estimate_range_equity_with_options`]
+    /// error for a contested pot.
+    pub fn pot_equity(
+        &self,
+        board: &str,
+        hands: &[&str],
+        options: &EquityOptions,
+    ) -> Result<PotEquity, SnapError> {
+        if hands.len() != self.players() {
+            return Err(SnapError::InvalidArgument(format!(
+                "Need a hand for each of {} seats, got {}",
+                self.players(),
+                hands.len()
+            )));
+        }
+        let known = hands
+            .iter()
+            .map(|hand| match hand.parse()? {
+                HoleCardsInput::Exact(cards) => Ok(cards.iter().copied().collect()),
+                _ => Ok(Vec::new()),
+            })
+            .collect::<Result<Vec<Vec<_>>, SnapError>>()?;
+
+        let mut shares = Vec::new();
+        let mut expected_chips = vec![0.0; self.players()];
+        for pot in self.pots() {
+            let mut equities = vec![0.0; self.players()];
+            let mut samples = 0;
+            if pot.is_contested() {
+                let players: Vec<&str> = pot.eligible.iter().map(|&seat| hands[seat]).collect();
+                let mut pot_options = options.clone();
+                for (seat, cards) in known.iter().enumerate() {
+                    if !pot.eligible.contains(&seat) {
+                        pot_options.dead_cards.extend(cards);
+                    }
+                }
+                let result = estimate_range_equity_with_options(board, &players, &pot_options)?;
+                for (&seat, &equity) in pot.eligible.iter().zip(&result.equities) {
+                    equities[seat] = equity;
+                }
+                samples = result.samples;
+            } else if let Some(&seat) = pot.eligible.first() {
+                equities[seat] = 100.0;
+            }
+            for (chips, equity) in expected_chips.iter_mut().zip(&equities) {
+                *chips += pot.amount * equity / 100.0;
+            }
+            shares.push(PotShare {
+                pot,
+                equities,
+                samples,
+            });
+        }
+        Ok(PotEquity {
+            pots: shares,
+            expected_chips,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unequal_all_ins_make_side_pots() {
+        let state = GameState::all_in(&[100.0, 300.0, 200.0]).unwrap();
+        assert!((0..3).all(|seat| state.is_all_in(seat)));
+        let pots = state.pots();
+        assert_eq!(
+            pots,
+            vec![
+                Pot {
+                    amount: 300.0,
+                    eligible: vec![0, 1, 2]
+                },
+                Pot {
+                    amount: 200.0,
+                    eligible: vec![1, 2]
+                },
+                // The big stack's last 100 is uncalled
+                Pot {
+                    amount: 100.0,
+                    eligible: vec![1]
+                },
+            ]
+        );
+        assert!(!pots[2].is_contested());
+        assert_eq!(state.total_pot(), 600.0);
+    }
+
+    #[test]
+    fn folded_chips_stay_in_the_pot() {
+        let mut state = GameState::new(&[50.0, 500.0, 500.0]).unwrap();
+        state.bet(1, 20.0).unwrap();
+        state.bet(2, 20.0).unwrap();
+        assert_eq!(state.bet(0, 80.0).unwrap(), 50.0);
+        state.bet(1, 100.0).unwrap();
+        state.fold(2).unwrap();
+        let pots = state.pots();
+        // 50 from each player, then the 70 seat 1 has over the all-in
+        assert_eq!(pots.len(), 2);
+        assert_eq!(pots[0].amount, 120.0);
+        assert_eq!(pots[0].eligible, vec![0, 1]);
+        assert_eq!(pots[1].amount, 70.0);
+        assert_eq!(pots[1].eligible, vec![1]);
+        assert_eq!(state.stack(1), 380.0);
+    }
+
+    #[test]
+    fn short_stack_only_plays_for_the_main_pot() {
+        // Aces are short, kings cover the queens
+        let state = GameState::all_in(&[100.0, 300.0, 200.0]).unwrap();
+        let hands = ["AsAh", "KsKh", "QsQh"];
+        let result = state
+            .pot_equity("", &hands, &EquityOptions::new(20_000))
+            .unwrap();
+        assert_eq!(result.pots.len(), 3);
+        let main = &result.pots[0];
+        assert!(main.equities[0] > main.equities[1]);
+        assert!((main.equities.iter().sum::<f64>() - 100.0).abs() < 1e-6);
+        // Kings are big favourites once the aces are out of the side pot
+        let side = &result.pots[1];
+        assert_eq!(side.equities[0], 0.0);
+        assert!(side.equities[1] > 75.0);
+        assert_eq!(result.pots[2].equities[1], 100.0);
+        assert_eq!(result.pots[2].samples, 0);
+
+        let total: f64 = result.expected_chips.iter().sum();
+        assert!((total - state.total_pot()).abs() < 1e-6);
+        // The aces win the most often but can only ever get 300 back
+        assert!(result.expected_chips[0] < 300.0);
+        assert!(result.net(&state, 0) > 0.0);
+        assert!(state
+            .pot_equity("", &hands[..2], &EquityOptions::new(100))
+            .is_err());
+    }
+
+    #[test]
+    fn no_all_in_is_one_pot() {
+        let mut state = GameState::new(&[100.0, 100.0]).unwrap();
+        state.bet(0, 10.0).unwrap();
+        state.bet(1, 10.0).unwrap();
+        assert_eq!(state.pots().len(), 1);
+        assert!(state.pots()[0].is_contested());
+        assert!(GameState::new(&[100.0]).is_err());
+        assert!(GameState::new(&[100.0, -1.0]).is_err());
+        state.fold(0).unwrap();
+        assert!(state.bet(0, 10.0).is_err());
+        assert!(state.bet(2, 10.0).is_err());
+        assert!(state.bet(1, -1.0).is_err());
+    }
+}
@@ -0,0 +1,349 @@
+use rs_poker::core::Card;
+
+use crate::range::{HandClass, TOTAL_COMBOS};
+use crate::types::SnapError;
+
+/// Number of starting-hand classes (cells of the 13×13 grid).
+pub(crate) const NUM_CLASSES: usize = 169;
+
+/// Fictitious-play rounds; enough for the averaged strategies to settle to
+/// within a class or two of the equilibrium at any stack depth.
+const SOLVER_ITERATIONS: usize = 1_000;
+
+/// Most players [`solve_blind_vs_blind`] accepts at the table.
+pub const MAX_TABLE_PLAYERS: usize = 10;
+
+/// Preflop all-in equity of class `i` against class `j`, as little-endian
+/// `u16` basis points (0–10 000), row-major over grid indices
+/// (`row * 13 + col`, see [`HandClass::grid_position`]).
+///
+/// Generated with `estimate_range_equity` at 20 000 samples per matchup;
+/// `cargo test -p snapcall-core -- --ignored regenerate_preflop_equity`
+/// rebuilds it into the temp directory and compares.
+static PREFLOP_EQUITY: &[u8; NUM_CLASSES * NUM_CLASSES * 2] =
+    include_bytes!("data/preflop_equity.bin");
+
+/// Heads-up push/fold equilibrium: small blind shoves or folds, big blind
+/// calls or folds.
+///
+/// Frequencies are per starting-hand class in grid order and range from 0
+/// (never) to 1 (always); a handful of classes near the threshold may mix.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PushFoldSolution {
+    pub stack_bb: f64,
+    pub ante_bb: f64,
+    /// Antes of players who folded before the blinds, already in the pot.
+    pub dead_bb: f64,
+    shove: Vec<f64>,
+    call: Vec<f64>,
+}
+
+impl PushFoldSolution {
+    /// How often the small blind shoves `class`.
+    pub fn shove_frequency(&self, class: HandClass) -> f64 {
+        self.shove[grid_index(class)]
+    }
+
+    /// How often the big blind calls a shove with `class`.
+    pub fn call_frequency(&self, class: HandClass) -> f64 {
+        self.call[grid_index(class)]
+    }
+
+    /// Classes the small blind shoves at least half the time, in grid order.
+    pub fn shove_range(&self) -> Vec<HandClass> {
+        classes_above_half(&self.shove)
+    }
+
+    /// Classes the big blind calls with at least half the time, in grid order.
+    pub fn call_range(&self) -> Vec<HandClass> {
+        classes_above_half(&self.call)
+    }
+
+    /// Share of all 1326 combos the small blind shoves, in percent.
+    pub fn shove_percent(&self) -> f64 {
+        combo_percent(&self.shove)
+    }
+
+    /// Share of all 1326 combos the big blind calls with, in percent.
+    pub fn call_percent(&self) -> f64 {
+        combo_percent(&self.call)
+    }
+}
+
+/// Solves heads-up push/fold for an effective stack of `stack_bb` big blinds
+/// with both players posting an ante of `ante_bb`.
+///
+/// The small blind (0.5 bb) either shoves or folds; the big blind (1 bb)
+/// calls or folds. Equities come from a precomputed class-vs-class table and
+/// card removal between the two hands is accounted for. Strategies are found
+/// by fictitious play, i.e. averaged best responses.
+///
+/// # Errors
+/// Returns [`SnapError::InvalidArgument`] if the stack is below 1 bb or the
+/// ante is negative.
+pub fn solve_push_fold(stack_bb: f64, ante_bb: f64) -> Result<PushFoldSolution, SnapError> {
+    solve_blind_vs_blind(stack_bb, ante_bb, 2)
+}
+
+/// Like [`solve_push_fold`] for a blind-vs-blind spot at a table of
+/// `players`: everyone before the small blind has folded, leaving their
+/// antes in the pot as dead money the small blind can steal.
+///
+/// # Errors
+/// As [`solve_push_fold`], plus [`SnapError::InvalidArgument`] for fewer
+/// than 2 or more than [`MAX_TABLE_PLAYERS`] players.
+pub fn solve_blind_vs_blind(
+    stack_bb: f64,
+    ante_bb: f64,
+    players: usize,
+) -> Result<PushFoldSolution, SnapError> {
+    if !stack_bb.is_finite() || stack_bb < 1.0 {
+        return Err(SnapError::InvalidArgument(format!(
+            "Stack must be at least 1 bb, got {}",
+            stack_bb
+        )));
+    }
+    if !ante_bb.is_finite() || ante_bb < 0.0 {
+        return Err(SnapError::InvalidArgument(format!(
+            "Ante must not be negative, got {}",
+            ante_bb
+        )));
+    }
+    if !(2..=MAX_TABLE_PLAYERS).contains(&players) {
+        return Err(SnapError::InvalidArgument(format!(
+            "A table seats 2 to {} players, got {}",
+            MAX_TABLE_PLAYERS, players
+        )));
+    }
+    let dead_bb = ante_bb * (players - 2) as f64;
+    let classes: Vec<HandClass> = (0..NUM_CLASSES).map(class_at).collect();
+    let weights = removal_weights(&classes);
+    let pot = 2.0 * (stack_bb + ante_bb) + dead_bb;
+
+    // Chip results relative to the start of the hand.
+    let sb_fold = -0.5 - ante_bb;
+    let sb_steal = 1.0 + ante_bb + dead_bb;
+    let bb_fold = -1.0 - ante_bb;
+    let showdown = |eq: f64| eq * pot - stack_bb - ante_bb;
+
+    // Card-removal-weighted payoffs, so each best response is a dot product:
+    // - `sb_gain[i][j]`: extra SB result when class j calls instead of folding
+    // - `bb_call[j][i]`: BB result calling class i's shove with class j
+    let n = NUM_CLASSES;
+    let mut sb_gain = vec![0.0; n * n];
+    let mut bb_call = vec![0.0; n * n];
+    let mut sb_weight = vec![0.0; n];
+    for i in 0..n {
+        for j in 0..n {
+            let w = weights[i * n + j];
+            sb_gain[i * n + j] = w * (showdown(equity(i, j)) - sb_steal);
+            bb_call[i * n + j] = w * showdown(equity(i, j));
+            sb_weight[i] += w;
+        }
+    }
+
+    let mut shove = vec![0.0; n];
+    let mut call = vec![0.0; n];
+    let mut shove_br = vec![0.0; n];
+    let mut call_br = vec![0.0; n];
+
+    for t in 0..SOLVER_ITERATIONS {
+        // Small blind best response to the big blind's average calling range.
+        for i in 0..n {
+            let row = &sb_gain[i * n..(i + 1) * n];
+            let gain: f64 = row.iter().zip(&call).map(|(g, c)| g * c).sum();
+            let ev = sb_steal + gain / sb_weight[i];
+            shove_br[i] = if ev > sb_fold { 1.0 } else { 0.0 };
+        }
+
+        // Big blind best response to the small blind's average shoving range.
+        for j in 0..n {
+            let payoff = &bb_call[j * n..(j + 1) * n];
+            let w = &weights[j * n..(j + 1) * n];
+            let ev: f64 = payoff.iter().zip(&shove).map(|(p, s)| p * s).sum();
+            let total: f64 = w.iter().zip(&shove).map(|(w, s)| w * s).sum();
+            call_br[j] = if total > 0.0 && ev / total > bb_fold {
+                1.0
+            } else {
+                0.0
+            };
+        }
+
+        let step = 1.0 / (t + 1) as f64;
+        for k in 0..n {
+            shove[k] += (shove_br[k] - shove[k]) * step;
+            call[k] += (call_br[k] - call[k]) * step;
+        }
+    }
+
+    Ok(PushFoldSolution {
+        stack_bb,
+        ante_bb,
+        dead_bb,
+        shove,
+        call,
+    })
+}
+
+/// All-in equity (0–1) of class `i` against class `j`.
+pub(crate) fn equity(i: usize, j: usize) -> f64 {
+    let k = 2 * (i * NUM_CLASSES + j);
+    u16::from_le_bytes([PREFLOP_EQUITY[k], PREFLOP_EQUITY[k + 1]]) as f64 / 10_000.0
+}
+
+/// `weights[i * 169 + j]`: combos of class `j` still possible when holding a
+/// combo of class `i`. Every combo of a class is equivalent up to suit
+/// relabelling, so checking the first combo suffices.
+pub(crate) fn removal_weights(classes: &[HandClass]) -> Vec<f64> {
+    let combos: Vec<Vec<[Card; 2]>> = classes.iter().map(|c| c.cards()).collect();
+    let mut weights = vec![0.0; NUM_CLASSES * NUM_CLASSES];
+    for (i, hero) in combos.iter().enumerate() {
+        let held = hero[0];
+        for (j, villain) in combos.iter().enumerate() {
+            weights[i * NUM_CLASSES + j] = villain
+                .iter()
+                .filter(|v| !v.iter().any(|c| held.contains(c)))
+                .count() as f64;
+        }
+    }
+    weights
+}
+
+pub(crate) fn grid_index(class: HandClass) -> usize {
+    let (row, col) = class.grid_position();
+    row * 13 + col
+}
+
+pub(crate) fn class_at(index: usize) -> HandClass {
+    HandClass::at_grid(index / 13, index % 13)
+}
+
+fn classes_above_half(freqs: &[f64]) -> Vec<HandClass> {
+    (0..NUM_CLASSES)
+        .filter(|&i| freqs[i] >= 0.5)
+        .map(class_at)
+        .collect()
+}
+
+fn combo_percent(freqs: &[f64]) -> f64 {
+    let combos: f64 = (0..NUM_CLASSES)
+        .map(|i| freqs[i] * class_at(i).combos() as f64)
+        .sum();
+    combos / TOTAL_COMBOS as f64 * 100.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn class(label: &str) -> HandClass {
+        label.parse().unwrap()
+    }
+
+    #[test]
+    fn equity_table_is_antisymmetric() {
+        for i in 0..NUM_CLASSES {
+            for j in 0..NUM_CLASSES {
+                assert!((equity(i, j) + equity(j, i) - 1.0).abs() < 1e-9);
+            }
+        }
+        let aa = grid_index(class("AA"));
+        let seven_two = grid_index(class("72o"));
+        assert!(equity(aa, seven_two) > 0.85);
+    }
+
+    #[test]
+    fn removal_blocks_shared_cards() {
+        let classes: Vec<HandClass> = (0..NUM_CLASSES).map(class_at).collect();
+        let weights = removal_weights(&classes);
+        let aa = grid_index(class("AA"));
+        let ako = grid_index(class("AKo"));
+        // Holding two aces leaves one ace: 2 × 4 AK combos minus suited ones → 6 offsuit
+        assert_eq!(weights[aa * NUM_CLASSES + ako], 6.0);
+        assert_eq!(weights[aa * NUM_CLASSES + aa], 1.0);
+    }
+
+    #[test]
+    fn ten_big_blinds_shoves_aces_not_trash() {
+        let solution = solve_push_fold(10.0, 0.0).unwrap();
+        assert_eq!(solution.shove_frequency(class("AA")), 1.0);
+        assert!(solution.shove_frequency(class("32o")) < 0.5);
+        assert!(solution.call_frequency(class("AA")) > 0.99);
+        assert!(solution.call_frequency(class("72o")) < 0.5);
+        assert!(solution.shove_percent() > solution.call_percent());
+    }
+
+    #[test]
+    fn shorter_stacks_shove_wider() {
+        let short = solve_push_fold(3.0, 0.0).unwrap();
+        let deep = solve_push_fold(20.0, 0.0).unwrap();
+        assert!(short.shove_percent() > deep.shove_percent());
+        assert!(
+            short.shove_percent() > 70.0,
+            "got {}",
+            short.shove_percent()
+        );
+    }
+
+    #[test]
+    fn antes_widen_the_shoving_range() {
+        let no_ante = solve_push_fold(12.0, 0.0).unwrap();
+        let ante = solve_push_fold(12.0, 0.125).unwrap();
+        assert!(ante.shove_percent() >= no_ante.shove_percent());
+    }
+
+    #[test]
+    fn dead_antes_widen_blind_vs_blind_shoves() {
+        let heads_up = solve_push_fold(15.0, 0.1).unwrap();
+        let nine_handed = solve_blind_vs_blind(15.0, 0.1, 9).unwrap();
+        assert_eq!(heads_up.dead_bb, 0.0);
+        assert!((nine_handed.dead_bb - 0.7).abs() < 1e-9);
+        assert!(nine_handed.shove_percent() > heads_up.shove_percent());
+        assert!(nine_handed.call_percent() > heads_up.call_percent());
+    }
+
+    #[test]
+    fn rejects_invalid_inputs() {
+        assert!(solve_blind_vs_blind(10.0, 0.0, 1).is_err());
+        assert!(solve_blind_vs_blind(10.0, 0.0, MAX_TABLE_PLAYERS + 1).is_err());
+        assert!(solve_push_fold(0.5, 0.0).is_err());
+        assert!(solve_push_fold(10.0, -1.0).is_err());
+    }
+
+    /// Rebuilds the preflop equity table into the temp directory and checks
+    /// it against `data/preflop_equity.bin`; takes several minutes. Copy the
+    /// printed file over the checked-in one to update it.
+    #[test]
+    #[ignore]
+    fn regenerate_preflop_equity() {
+        let labels: Vec<String> = (0..NUM_CLASSES).map(|i| class_at(i).to_string()).collect();
+        let mut table = vec![0u16; NUM_CLASSES * NUM_CLASSES];
+        for i in 0..NUM_CLASSES {
+            table[i * NUM_CLASSES + i] = 5_000;
+            for j in i + 1..NUM_CLASSES {
+                let result =
+                    crate::estimate_range_equity("", &[&labels[i], &labels[j]], 20_000).unwrap();
+                let eq = (result.equities[0] * 100.0).round() as u16;
+                table[i * NUM_CLASSES + j] = eq;
+                table[j * NUM_CLASSES + i] = 10_000 - eq;
+            }
+        }
+        let bytes: Vec<u8> = table.iter().flat_map(|v| v.to_le_bytes()).collect();
+        let path = std::env::temp_dir().join("preflop_equity.bin");
+        std::fs::write(&path, bytes).unwrap();
+        println!("wrote {}", path.display());
+
+        // 20 000 samples leave about 0.35% standard error per matchup
+        for (idx, &fresh) in table.iter().enumerate() {
+            let stored = u16::from_le_bytes([PREFLOP_EQUITY[2 * idx], PREFLOP_EQUITY[2 * idx + 1]]);
+            assert!(
+                fresh.abs_diff(stored) <= 200,
+                "{} vs {}: {} regenerated, {} stored",
+                labels[idx / NUM_CLASSES],
+                labels[idx % NUM_CLASSES],
+                fresh,
+                stored
+            );
+        }
+    }
+}
@@ -0,0 +1,153 @@
+use crate::odds::pot_rake;
+use crate::types::SnapError;
+
+/// How the house takes its cut of a pot.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RakeModel {
+    /// `percent` of every pot, limited to `cap` when one applies.
+    Percent { percent: f64, cap: Option<f64> },
+    /// A fixed `amount` from every pot (never more than the pot itself).
+    Drop { amount: f64 },
+    /// Time-charged games: the seat fee is paid outside the hand, so
+    /// nothing comes out of the pot.
+    TimeCharge,
+}
+
+impl std::fmt::Display for RakeModel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RakeModel::Percent { percent, cap: None } => write!(f, "{}%", percent),
+            RakeModel::Percent {
+                percent,
+                cap: Some(cap),
+            } => write!(f, "{}%, cap {}", percent, cap),
+            RakeModel::Drop { amount } => write!(f, "drop {}", amount),
+            RakeModel::TimeCharge => write!(f, "time charge"),
+        }
+    }
+}
+
+impl RakeModel {
+    /// Checks the parameters are usable.
+    ///
+    /// # Errors
+    /// Returns [`SnapError::InvalidArgument`] for a percentage outside
+    /// `[0, 100)` or a negative cap or drop.
+    pub fn validate(&self) -> Result<(), SnapError> {
+        match *self {
+            RakeModel::Percent { percent, cap } => {
+                if !(0.0..100.0).contains(&percent) {
+                    return Err(SnapError::InvalidArgument(format!(
+                        "Rake percent must be in [0, 100), got {}",
+                        percent
+                    )));
+                }
+                if let Some(cap) = cap.filter(|cap| cap.is_nan() || *cap < 0.0) {
+                    return Err(SnapError::InvalidArgument(format!(
+                        "Rake cap must not be negative, got {}",
+                        cap
+                    )));
+                }
+            }
+            RakeModel::Drop { amount } => {
+                if !amount.is_finite() || amount < 0.0 {
+                    return Err(SnapError::InvalidArgument(format!(
+                        "Rake drop must not be negative, got {}",
+                        amount
+                    )));
+                }
+            }
+            RakeModel::TimeCharge => {}
+        }
+        Ok(())
+    }
+
+    /// Rake taken from a final pot of `pot`.
+    pub fn rake(&self, pot: f64) -> f64 {
+        match *self {
+            RakeModel::Percent { percent, cap } => pot_rake(pot, percent, cap),
+            RakeModel::Drop { amount } => amount.min(pot),
+            RakeModel::TimeCharge => 0.0,
+        }
+    }
+
+    /// What the winner of a final pot of `pot` takes home.
+    pub fn raked_pot(&self, pot: f64) -> f64 {
+        pot - self.rake(pot)
+    }
+
+    /// Pot odds (in percent) once the pot is raked; `pot` includes the bet
+    /// being faced, as for [`pot_odds_percent`](crate::pot_odds_percent).
+    pub fn pot_odds_percent(&self, pot: f64, call_amount: f64) -> f64 {
+        call_amount / self.raked_pot(pot + call_amount) * 100.0
+    }
+
+    /// [`call_ev`](crate::call_ev) with the final pot raked.
+    pub fn call_ev(&self, pot: f64, call_amount: f64, equity_percent: f64) -> f64 {
+        equity_percent / 100.0 * self.raked_pot(pot + call_amount) - call_amount
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::odds::{pot_odds_percent, raked_pot_odds_percent};
+
+    fn assert_close(a: f64, b: f64) {
+        assert!((a - b).abs() < 1e-9, "{a} vs {b}");
+    }
+
+    #[test]
+    fn models_take_their_cut() {
+        let capped = RakeModel::Percent {
+            percent: 5.0,
+            cap: Some(3.0),
+        };
+        assert_eq!(capped.rake(40.0), 2.0);
+        assert_eq!(capped.rake(200.0), 3.0);
+        assert_eq!(RakeModel::Drop { amount: 1.0 }.rake(200.0), 1.0);
+        assert_eq!(RakeModel::Drop { amount: 1.0 }.rake(0.5), 0.5);
+        assert_eq!(RakeModel::TimeCharge.rake(200.0), 0.0);
+        assert_eq!(capped.to_string(), "5%, cap 3");
+    }
+
+    #[test]
+    fn rake_raises_the_price_of_a_call() {
+        let rake = RakeModel::Percent {
+            percent: 5.0,
+            cap: None,
+        };
+        assert_close(
+            rake.pot_odds_percent(150.0, 50.0),
+            raked_pot_odds_percent(150.0, 50.0, 5.0, None),
+        );
+        assert_eq!(
+            RakeModel::TimeCharge.pot_odds_percent(150.0, 50.0),
+            pot_odds_percent(150.0, 50.0)
+        );
+        // Breaking even unraked loses to the rake
+        assert!(rake.call_ev(150.0, 50.0, pot_odds_percent(150.0, 50.0)) < 0.0);
+        assert_close(
+            rake.call_ev(150.0, 50.0, rake.pot_odds_percent(150.0, 50.0)),
+            0.0,
+        );
+    }
+
+    #[test]
+    fn rejects_bad_parameters() {
+        assert!(RakeModel::Percent {
+            percent: 100.0,
+            cap: None
+        }
+        .validate()
+        .is_err());
+        assert!(RakeModel::Percent {
+            percent: 5.0,
+            cap: Some(-1.0)
+        }
+        .validate()
+        .is_err());
+        assert!(RakeModel::Drop { amount: -1.0 }.validate().is_err());
+        assert!(RakeModel::TimeCharge.validate().is_ok());
+    }
+}
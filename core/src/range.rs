@@ -0,0 +1,420 @@
+use std::collections::HashMap;
+
+use rs_poker::core::{Card, Deck, Suit, Value};
+
+use crate::input::HoleCardsInput;
+use crate::types::SnapError;
+
+/// Number of distinct two-card starting hands in a 52-card deck.
+pub const TOTAL_COMBOS: usize = 1326;
+
+/// Starting-hand class such as `AA`, `AKs`, or `T9o`.
+///
+/// `high` is always ≥ `low`; pairs are never suited.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct HandClass {
+    pub high: Value,
+    pub low: Value,
+    pub suited: bool,
+}
+
+impl HandClass {
+    /// Class of a concrete two-card combo.
+    pub fn from_cards(a: Card, b: Card) -> Self {
+        let (high, low) = if a.value >= b.value {
+            (a.value, b.value)
+        } else {
+            (b.value, a.value)
+        };
+        Self {
+            high,
+            low,
+            suited: high != low && a.suit == b.suit,
+        }
+    }
+
+    /// Whether both cards share a value.
+    pub fn is_pair(&self) -> bool {
+        self.high == self.low
+    }
+
+    /// Number of concrete combos in this class (6 pairs, 4 suited, 12 offsuit).
+    pub fn combos(&self) -> usize {
+        if self.is_pair() {
+            6
+        } else if self.suited {
+            4
+        } else {
+            12
+        }
+    }
+
+    /// Position in the conventional 13×13 grid as `(row, col)`.
+    ///
+    /// Aces are row/column 0; pairs sit on the diagonal, suited hands above
+    /// it and offsuit hands below it.
+    pub fn grid_position(&self) -> (usize, usize) {
+        let hi = 12 - self.high as usize;
+        let lo = 12 - self.low as usize;
+        if self.suited {
+            (hi, lo)
+        } else {
+            (lo, hi)
+        }
+    }
+
+    /// Class occupying the given grid cell (see [`HandClass::grid_position`]).
+    pub fn at_grid(row: usize, col: usize) -> Self {
+        let row_value = Value::from_u8(12 - row.min(12) as u8);
+        let col_value = Value::from_u8(12 - col.min(12) as u8);
+        if row <= col {
+            Self {
+                high: row_value,
+                low: col_value,
+                suited: row < col,
+            }
+        } else {
+            Self {
+                high: col_value,
+                low: row_value,
+                suited: false,
+            }
+        }
+    }
+}
+
+impl HandClass {
+    /// Every concrete combo of this class, higher card first.
+    pub fn cards(&self) -> Vec<[Card; 2]> {
+        let suits = Suit::suits();
+        let mut combos = Vec::with_capacity(self.combos());
+        for (i, &s1) in suits.iter().enumerate() {
+            for (j, &s2) in suits.iter().enumerate() {
+                let keep = if self.is_pair() {
+                    i < j
+                } else if self.suited {
+                    i == j
+                } else {
+                    i != j
+                };
+                if keep {
+                    combos.push([Card::new(self.high, s1), Card::new(self.low, s2)]);
+                }
+            }
+        }
+        combos
+    }
+}
+
+impl std::str::FromStr for HandClass {
+    type Err = SnapError;
+
+    /// Parses a class label such as `"AA"`, `"AKs"`, or `"T9o"`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || SnapError::InvalidRange(format!("Invalid hand class '{}'", s));
+        let mut chars = s.trim().chars();
+        let first = chars
+            .next()
+            .and_then(Value::from_char)
+            .ok_or_else(invalid)?;
+        let second = chars
+            .next()
+            .and_then(Value::from_char)
+            .ok_or_else(invalid)?;
+        let suited = match (chars.next(), chars.next()) {
+            (None, None) if first == second => false,
+            (Some('s' | 'S'), None) if first != second => true,
+            (Some('o' | 'O'), None) if first != second => false,
+            _ => return Err(invalid()),
+        };
+        Ok(Self {
+            high: first.max(second),
+            low: first.min(second),
+            suited,
+        })
+    }
+}
+
+impl std::fmt::Display for HandClass {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}{}", self.high.to_char(), self.low.to_char())?;
+        if !self.is_pair() {
+            write!(f, "{}", if self.suited { 's' } else { 'o' })?;
+        }
+        Ok(())
+    }
+}
+
+/// All 169 starting-hand classes ordered strongest first, by all-in equity
+/// against one random hand.
+const PREFLOP_RANKING: [&str; 169] = [
+    "AA", "KK", "QQ", "JJ", "TT", "99", "88", "AKs", "77", "AQs", "AJs", "AKo", "ATs", "AQo",
+    "AJo", "66", "KQs", "ATo", "A9s", "KJs", "KTs", "A8s", "KQo", "A7s", "A9o", "KJo", "55", "QJs",
+    "K9s", "A5s", "A6s", "A8o", "KTo", "QTs", "A4s", "A7o", "K8s", "QJo", "A3s", "K9o", "Q9s",
+    "A6o", "A5o", "JTs", "K7s", "QTo", "A2s", "44", "A4o", "K6s", "Q8s", "K8o", "A3o", "K5s",
+    "J9s", "Q9o", "JTo", "K7o", "A2o", "K4s", "Q7s", "K6o", "T9s", "J8s", "K3s", "33", "Q6s",
+    "Q8o", "K5o", "J9o", "K2s", "Q5s", "J7s", "T8s", "K4o", "Q4s", "Q7o", "T9o", "J8o", "K3o",
+    "Q6o", "Q3s", "98s", "T7s", "J6s", "K2o", "22", "Q2s", "Q5o", "J5s", "T8o", "J7o", "Q4o",
+    "J4s", "97s", "T6s", "Q3o", "J3s", "98o", "87s", "T7o", "J6o", "96s", "J2s", "Q2o", "T5s",
+    "J5o", "T4s", "97o", "86s", "J4o", "T6o", "95s", "T3s", "76s", "J3o", "87o", "T2s", "85s",
+    "96o", "J2o", "T5o", "94s", "75s", "T4o", "86o", "93s", "65s", "84s", "95o", "T3o", "92s",
+    "76o", "74s", "T2o", "54s", "85o", "64s", "83s", "94o", "75o", "82s", "65o", "93o", "73s",
+    "53s", "63s", "84o", "92o", "43s", "74o", "54o", "72s", "64o", "52s", "62s", "83o", "42s",
+    "82o", "73o", "53o", "63o", "32s", "43o", "72o", "52o", "62o", "42o", "32o",
+];
+
+/// The strongest classes covering `percent` of all 1326 combos.
+///
+/// Classes are added in [`PREFLOP_RANKING`] order until the cumulative combo
+/// count reaches the target, so the result may slightly overshoot.
+pub fn top_percent_classes(percent: f64) -> Vec<HandClass> {
+    let target = (percent.clamp(0.0, 100.0) / 100.0 * TOTAL_COMBOS as f64).round() as usize;
+    let mut classes = Vec::new();
+    let mut combos = 0usize;
+    for label in PREFLOP_RANKING {
+        if combos >= target {
+            break;
+        }
+        let Ok(class) = label.parse::<HandClass>() else {
+            continue;
+        };
+        combos += class.combos();
+        classes.push(class);
+    }
+    classes
+}
+
+/// One cell of a 13×13 range grid.
+///
+/// `combos` is how many of the class's `total` combos are in the range.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RangeGridCell {
+    pub class: HandClass,
+    pub combos: usize,
+    pub total: usize,
+}
+
+/// Expands a hand or range string into its concrete two-card combos.
+///
+/// Accepts everything [`HoleCardsInput`] does: `""` yields all 1326 combos,
+/// a single card yields every combo containing it, and exact cards yield one
+/// combo. Combos are grouped by class in grid order (`AA`, `AKs`, `AKo`, …,
+/// `KK`, …) with the higher card first within each combo.
+///
+/// # Errors
+/// Returns [`SnapError`] if the string is neither cards nor a valid range.
+pub fn expand_range(range: &str) -> Result<Vec<[Card; 2]>, SnapError> {
+    let input: HoleCardsInput = range.parse()?;
+    let mut combos: Vec<[Card; 2]> = match input {
+        HoleCardsInput::Exact(hand) => {
+            let mut iter = hand.iter().copied();
+            let (Some(c1), Some(c2)) = (iter.next(), iter.next()) else {
+                return Err(SnapError::InvalidHand(format!("Invalid hand '{}'", range)));
+            };
+            if c1 == c2 {
+                return Err(SnapError::InvalidHand(
+                    "Hand contains duplicate cards".to_string(),
+                ));
+            }
+            vec![[c1, c2]]
+        }
+        HoleCardsInput::Range(hands) | HoleCardsInput::WeightedRange(hands, _) => hands
+            .iter()
+            .filter_map(|h| {
+                let mut iter = h.iter().copied();
+                Some([iter.next()?, iter.next()?])
+            })
+            .collect(),
+        HoleCardsInput::Partial(card) => Deck::default()
+            .into_iter()
+            .filter(|&c| c != card)
+            .map(|c| [card, c])
+            .collect(),
+        HoleCardsInput::Unknown => {
+            let deck: Vec<Card> = Deck::default().into_iter().collect();
+            let mut all = Vec::with_capacity(TOTAL_COMBOS);
+            for (i, &a) in deck.iter().enumerate() {
+                for &b in &deck[i + 1..] {
+                    all.push([a, b]);
+                }
+            }
+            all
+        }
+    };
+
+    for combo in &mut combos {
+        if combo[1] > combo[0] {
+            combo.swap(0, 1);
+        }
+    }
+    combos.sort_by_key(|c| {
+        let (row, col) = HandClass::from_cards(c[0], c[1]).grid_position();
+        (row.min(col), row.max(col), row > col, std::cmp::Reverse(*c))
+    });
+    combos.dedup();
+    Ok(combos)
+}
+
+/// Like [`expand_range`], with each combo's weight: the `:weight` the range
+/// gives it (e.g. `"AKs:0.5"`), or 1.
+///
+/// # Errors
+/// Returns [`SnapError`] if the string is neither cards nor a valid range.
+pub fn expand_weighted_range(range: &str) -> Result<Vec<([Card; 2], f64)>, SnapError> {
+    let weights: HashMap<(Card, Card), f64> = match range.parse()? {
+        HoleCardsInput::WeightedRange(hands, weights) => hands
+            .iter()
+            .zip(weights)
+            .filter_map(|(hand, weight)| {
+                let mut iter = hand.iter().copied();
+                let (a, b) = (iter.next()?, iter.next()?);
+                Some(((a.max(b), a.min(b)), weight))
+            })
+            .collect(),
+        _ => HashMap::new(),
+    };
+    Ok(expand_range(range)?
+        .into_iter()
+        .map(|combo| {
+            let weight = weights.get(&(combo[0], combo[1])).copied();
+            (combo, weight.unwrap_or(1.0))
+        })
+        .collect())
+}
+
+/// Builds a 13×13 grid (rows/columns from ace down to deuce) counting how
+/// many combos of each starting-hand class are present in `combos`.
+pub fn range_grid(combos: &[[Card; 2]]) -> Vec<Vec<RangeGridCell>> {
+    let mut grid: Vec<Vec<RangeGridCell>> = (0..13)
+        .map(|row| {
+            (0..13)
+                .map(|col| {
+                    let class = HandClass::at_grid(row, col);
+                    RangeGridCell {
+                        class,
+                        combos: 0,
+                        total: class.combos(),
+                    }
+                })
+                .collect()
+        })
+        .collect();
+
+    for combo in combos {
+        let (row, col) = HandClass::from_cards(combo[0], combo[1]).grid_position();
+        grid[row][col].combos += 1;
+    }
+    grid
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hand_class_labels() {
+        let ak =
+            HandClass::from_cards(Card::try_from("Kh").unwrap(), Card::try_from("Ah").unwrap());
+        assert_eq!(ak.to_string(), "AKs");
+        let t9 =
+            HandClass::from_cards(Card::try_from("Ts").unwrap(), Card::try_from("9d").unwrap());
+        assert_eq!(t9.to_string(), "T9o");
+        let sevens =
+            HandClass::from_cards(Card::try_from("7s").unwrap(), Card::try_from("7d").unwrap());
+        assert_eq!(sevens.to_string(), "77");
+    }
+
+    #[test]
+    fn grid_position_round_trips() {
+        for row in 0..13 {
+            for col in 0..13 {
+                assert_eq!(HandClass::at_grid(row, col).grid_position(), (row, col));
+            }
+        }
+    }
+
+    #[test]
+    fn grid_totals_sum_to_all_combos() {
+        let total: usize = range_grid(&[])
+            .iter()
+            .flatten()
+            .map(|cell| cell.total)
+            .sum();
+        assert_eq!(total, TOTAL_COMBOS);
+    }
+
+    #[test]
+    fn expand_counts_common_ranges() {
+        assert_eq!(expand_range("AKs").unwrap().len(), 4);
+        assert_eq!(expand_range("TT+").unwrap().len(), 30);
+        assert_eq!(expand_range("AhKd").unwrap().len(), 1);
+        assert_eq!(expand_range("Ah").unwrap().len(), 51);
+        assert_eq!(expand_range("").unwrap().len(), TOTAL_COMBOS);
+    }
+
+    #[test]
+    fn expand_orders_strongest_first() {
+        let combos = expand_range("22+").unwrap();
+        assert_eq!(combos[0][0].value, Value::Ace);
+        assert_eq!(combos[combos.len() - 1][0].value, Value::Two);
+    }
+
+    #[test]
+    fn grid_marks_range_cells() {
+        let grid = range_grid(&expand_range("AKs,QQ").unwrap());
+        assert_eq!(grid[0][1].class.to_string(), "AKs");
+        assert_eq!(grid[0][1].combos, 4);
+        assert_eq!(grid[2][2].combos, 6);
+        assert_eq!(grid[1][0].combos, 0);
+    }
+
+    #[test]
+    fn class_cards_match_combo_counts() {
+        for label in ["AA", "AKs", "AKo"] {
+            let class: HandClass = label.parse().unwrap();
+            let cards = class.cards();
+            assert_eq!(cards.len(), class.combos(), "{label}");
+            for [a, b] in cards {
+                assert_eq!(HandClass::from_cards(a, b), class);
+            }
+        }
+    }
+
+    #[test]
+    fn class_parse_rejects_bad_labels() {
+        assert!("AAs".parse::<HandClass>().is_err());
+        assert!("AK".parse::<HandClass>().is_err());
+        assert!("AKx".parse::<HandClass>().is_err());
+    }
+
+    #[test]
+    fn ranking_covers_every_class_once() {
+        let classes: std::collections::HashSet<HandClass> = PREFLOP_RANKING
+            .iter()
+            .map(|label| label.parse().unwrap())
+            .collect();
+        assert_eq!(classes.len(), 169);
+    }
+
+    #[test]
+    fn top_percent_starts_with_aces() {
+        let top = top_percent_classes(0.5);
+        assert_eq!(top[0].to_string(), "AA");
+        let all: usize = top_percent_classes(100.0).iter().map(|c| c.combos()).sum();
+        assert_eq!(all, TOTAL_COMBOS);
+    }
+
+    #[test]
+    fn expand_rejects_garbage() {
+        assert!(expand_range("XYZ").is_err());
+    }
+
+    #[test]
+    fn expand_weighted_keeps_weights() {
+        let combos = expand_weighted_range("KK, AKs:0.5").unwrap();
+        assert_eq!(combos.len(), 10);
+        let half: Vec<_> = combos.iter().filter(|(_, w)| *w == 0.5).collect();
+        assert_eq!(half.len(), 4);
+        assert!(half.iter().all(|(c, _)| c[0].value == Value::Ace));
+    }
+}
@@ -0,0 +1,143 @@
+use crate::range::HandClass;
+
+/// Hero's seat relative to the opponent for the rest of the hand.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Position {
+    /// Acting last on every later street.
+    InPosition,
+    /// Acting first on every later street.
+    OutOfPosition,
+}
+
+impl std::fmt::Display for Position {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Position::InPosition => write!(f, "ip"),
+            Position::OutOfPosition => write!(f, "oop"),
+        }
+    }
+}
+
+/// A simple equity realization model: how much of its raw all-in equity a
+/// hand turns into pot share when there is still betting to come.
+///
+/// The multiplier starts from `in_position` or `out_of_position` and adds
+/// `pair`, `suited`, and `connected` (cards one or two ranks apart) for the
+/// hands they fit. Less is left to realize as stacks get shorter, so the
+/// adjustment shrinks in proportion to SPR below `full_spr` and vanishes
+/// all-in. Every field is public so the numbers can be tuned; the defaults
+/// are rough heads-up single-raised-pot figures.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RealizationModel {
+    pub in_position: f64,
+    pub out_of_position: f64,
+    pub pair: f64,
+    pub suited: f64,
+    pub connected: f64,
+    pub full_spr: f64,
+}
+
+impl Default for RealizationModel {
+    fn default() -> Self {
+        Self {
+            in_position: 1.0,
+            out_of_position: 0.8,
+            pair: 0.0,
+            suited: 0.05,
+            connected: 0.03,
+            full_spr: 10.0,
+        }
+    }
+}
+
+impl RealizationModel {
+    /// Realization multiplier for `class` in `position` at stack-to-pot
+    /// ratio `spr`: 1 all-in (`spr` 0), never below 0.
+    pub fn factor(&self, class: HandClass, position: Position, spr: f64) -> f64 {
+        let mut factor = match position {
+            Position::InPosition => self.in_position,
+            Position::OutOfPosition => self.out_of_position,
+        };
+        if class.is_pair() {
+            factor += self.pair;
+        } else {
+            if class.suited {
+                factor += self.suited;
+            }
+            if class.high as u8 - class.low as u8 <= 2 {
+                factor += self.connected;
+            }
+        }
+        let depth = if self.full_spr > 0.0 {
+            (spr / self.full_spr).clamp(0.0, 1.0)
+        } else {
+            1.0
+        };
+        (1.0 + (factor - 1.0) * depth).max(0.0)
+    }
+
+    /// Raw `equity_percent` scaled by [`factor`](Self::factor), capped at 100.
+    pub fn realized_equity_percent(
+        &self,
+        equity_percent: f64,
+        class: HandClass,
+        position: Position,
+        spr: f64,
+    ) -> f64 {
+        (equity_percent * self.factor(class, position, spr)).min(100.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rs_poker::core::Card;
+
+    fn class(a: &str, b: &str) -> HandClass {
+        HandClass::from_cards(Card::try_from(a).unwrap(), Card::try_from(b).unwrap())
+    }
+
+    #[test]
+    fn position_and_shape_adjust_the_factor() {
+        let model = RealizationModel::default();
+        let suited_connector = class("9h", "8h");
+        let offsuit_gapper = class("Kc", "4d");
+        assert!((model.factor(suited_connector, Position::InPosition, 20.0) - 1.08).abs() < 1e-12);
+        assert!((model.factor(offsuit_gapper, Position::OutOfPosition, 20.0) - 0.8).abs() < 1e-12);
+        assert!(
+            model.factor(suited_connector, Position::OutOfPosition, 20.0)
+                > model.factor(offsuit_gapper, Position::OutOfPosition, 20.0)
+        );
+    }
+
+    #[test]
+    fn short_stacks_realize_raw_equity() {
+        let model = RealizationModel::default();
+        let hand = class("Kc", "4d");
+        assert_eq!(model.factor(hand, Position::OutOfPosition, 0.0), 1.0);
+        assert!((model.factor(hand, Position::OutOfPosition, 5.0) - 0.9).abs() < 1e-12);
+        assert_eq!(
+            model.realized_equity_percent(40.0, hand, Position::OutOfPosition, 0.0),
+            40.0
+        );
+    }
+
+    #[test]
+    fn parameters_are_tunable() {
+        let model = RealizationModel {
+            out_of_position: 0.7,
+            full_spr: 4.0,
+            ..RealizationModel::default()
+        };
+        let hand = class("As", "Ad");
+        assert!((model.factor(hand, Position::OutOfPosition, 8.0) - 0.7).abs() < 1e-12);
+        let eager = RealizationModel {
+            in_position: 1.5,
+            ..RealizationModel::default()
+        };
+        assert_eq!(
+            eager.realized_equity_percent(80.0, hand, Position::InPosition, 10.0),
+            100.0
+        );
+    }
+}
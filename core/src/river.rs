@@ -0,0 +1,575 @@
+use std::collections::HashSet;
+
+use rs_poker::core::{Card, Rank, Rankable};
+
+use crate::input::parse_cards;
+use crate::range::expand_weighted_range;
+use crate::types::SnapError;
+
+/// Iterations for [`RiverOptions::new`]; enough for ranges of a few hundred
+/// combos to settle within a fraction of a percent of the pot.
+pub const DEFAULT_RIVER_ITERATIONS: usize = 1_000;
+
+/// Stakes and bet size for [`solve_river`], in chips.
+///
+/// - `pot` is the pot before river action.
+/// - `stack` is the effective stack behind; a bet never exceeds it.
+/// - `bet_fraction` sizes the single bet each player may make as a share of
+///   `pot` (`0.75` bets three quarters of the pot).
+#[derive(Debug, Clone, PartialEq)]
+pub struct RiverOptions {
+    pub pot: f64,
+    pub stack: f64,
+    pub bet_fraction: f64,
+    pub iterations: usize,
+}
+
+impl RiverOptions {
+    /// A 75% pot bet and [`DEFAULT_RIVER_ITERATIONS`].
+    pub fn new(pot: f64, stack: f64) -> Self {
+        Self {
+            pot,
+            stack,
+            bet_fraction: 0.75,
+            iterations: DEFAULT_RIVER_ITERATIONS,
+        }
+    }
+}
+
+/// A decision point in the [`solve_river`] game tree. The out-of-position
+/// player acts first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RiverNode {
+    /// Out of position first to act: check or bet.
+    OopFirst,
+    /// In position after a check: check behind or bet.
+    IpVsCheck,
+    /// Out of position facing a bet after checking: fold or call.
+    OopVsBet,
+    /// In position facing a bet: fold or call.
+    IpVsBet,
+}
+
+impl RiverNode {
+    /// Every decision point, in tree order.
+    pub const ALL: [RiverNode; 4] = [
+        RiverNode::OopFirst,
+        RiverNode::IpVsCheck,
+        RiverNode::OopVsBet,
+        RiverNode::IpVsBet,
+    ];
+
+    /// Actions available here, in the order strategies list them.
+    pub fn actions(self) -> [RiverAction; 2] {
+        match self {
+            RiverNode::OopFirst | RiverNode::IpVsCheck => [RiverAction::Check, RiverAction::Bet],
+            RiverNode::OopVsBet | RiverNode::IpVsBet => [RiverAction::Fold, RiverAction::Call],
+        }
+    }
+
+    /// Whether the in-position player acts here.
+    pub fn is_ip(self) -> bool {
+        matches!(self, RiverNode::IpVsCheck | RiverNode::IpVsBet)
+    }
+}
+
+/// An action in the [`solve_river`] game tree.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RiverAction {
+    Check,
+    Bet,
+    Fold,
+    Call,
+}
+
+impl std::fmt::Display for RiverAction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RiverAction::Check => write!(f, "check"),
+            RiverAction::Bet => write!(f, "bet"),
+            RiverAction::Fold => write!(f, "fold"),
+            RiverAction::Call => write!(f, "call"),
+        }
+    }
+}
+
+/// Equilibrium strategies and values from [`solve_river`].
+///
+/// EVs are the chips a player ends the hand with out of the pot, minus what
+/// they put in on the river, so `oop_ev + ip_ev == pot`. Per-combo lists
+/// follow `oop_combos` and `ip_combos`, which leave out combos blocked by
+/// the board.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RiverSolution {
+    pub bet: f64,
+    pub oop_ev: f64,
+    pub ip_ev: f64,
+    pub oop_combos: Vec<[Card; 2]>,
+    pub ip_combos: Vec<[Card; 2]>,
+    pub oop_combo_evs: Vec<f64>,
+    pub ip_combo_evs: Vec<f64>,
+    /// `strategies[node][combo * 2 + action]`.
+    strategies: Vec<Vec<f64>>,
+    /// `reach[node][combo]`: how often the acting player's combo gets there.
+    reach: Vec<Vec<f64>>,
+    oop_weights: Vec<f64>,
+    ip_weights: Vec<f64>,
+}
+
+impl RiverSolution {
+    /// How often `combo` takes each action at `node`, or `None` when the
+    /// acting player's range does not hold it.
+    pub fn strategy(&self, node: RiverNode, combo: [Card; 2]) -> Option<[(RiverAction, f64); 2]> {
+        let combos = if node.is_ip() {
+            &self.ip_combos
+        } else {
+            &self.oop_combos
+        };
+        let index = combos
+            .iter()
+            .position(|c| c == &combo || c == &[combo[1], combo[0]])?;
+        let [first, second] = node.actions();
+        let slots = &self.strategies[node as usize][index * 2..index * 2 + 2];
+        Some([(first, slots[0]), (second, slots[1])])
+    }
+
+    /// How often the acting player's whole range takes `action` at `node`,
+    /// over the combos that get there (by weight); 0 for an action not
+    /// available there or a node never reached.
+    pub fn frequency(&self, node: RiverNode, action: RiverAction) -> f64 {
+        let Some(k) = node.actions().iter().position(|&a| a == action) else {
+            return 0.0;
+        };
+        let weights = if node.is_ip() {
+            &self.ip_weights
+        } else {
+            &self.oop_weights
+        };
+        let strategy = &self.strategies[node as usize];
+        let reach = &self.reach[node as usize];
+        let (taken, total) = weights
+            .iter()
+            .enumerate()
+            .fold((0.0, 0.0), |(t, n), (i, w)| {
+                let mass = w * reach[i];
+                (t + mass * strategy[i * 2 + k], n + mass)
+            });
+        if total > 0.0 {
+            taken / total
+        } else {
+            0.0
+        }
+    }
+}
+
+/// Solves a single river street between two ranges with CFR+: the
+/// out-of-position player checks or bets, the other player checks behind,
+/// bets, calls, or folds, and there are no raises. Every bet is
+/// `options.bet_fraction` of the pot, capped at the stack.
+///
+/// Hands are compared combo by combo, so card removal between the ranges
+/// and the board is exact. Work grows with the product of the two ranges'
+/// sizes times `options.iterations`.
+///
+/// # Errors
+/// Returns [`SnapError::InvalidHand`] unless `board` is five distinct
+/// cards, any range parsing error, and [`SnapError::InvalidArgument`] for a
+/// non-positive pot, a negative stack or bet fraction, zero iterations, or
+/// ranges with no combos left to play each other.
+pub fn solve_river(
+    board: &str,
+    oop_range: &str,
+    ip_range: &str,
+    options: &RiverOptions,
+) -> Result<RiverSolution, SnapError> {
+    let board = parse_cards(board)?;
+    if board.len() != 5 {
+        return Err(SnapError::InvalidHand(format!(
+            "The river needs a 5-card board, got {}",
+            board.len()
+        )));
+    }
+    if !options.pot.is_finite() || options.pot <= 0.0 {
+        return Err(SnapError::InvalidArgument(format!(
+            "Pot must be positive, got {}",
+            options.pot
+        )));
+    }
+    if [options.stack, options.bet_fraction]
+        .iter()
+        .any(|v| !v.is_finite() || *v < 0.0)
+    {
+        return Err(SnapError::InvalidArgument(
+            "Stack and bet fraction must be finite and not negative".to_string(),
+        ));
+    }
+    if options.iterations == 0 {
+        return Err(SnapError::InvalidArgument(
+            "The solver needs at least one iteration".to_string(),
+        ));
+    }
+
+    let on_board: HashSet<Card> = board.iter().copied().collect();
+    let live = |range: &str| -> Result<(Vec<[Card; 2]>, Vec<f64>), SnapError> {
+        Ok(expand_weighted_range(range)?
+            .into_iter()
+            .filter(|(combo, _)| !combo.iter().any(|c| on_board.contains(c)))
+            .unzip())
+    };
+    let (oop_combos, oop_weights) = live(oop_range)?;
+    let (ip_combos, ip_weights) = live(ip_range)?;
+    let strength = |combo: &[Card; 2]| -> Rank {
+        let mut cards = board.clone();
+        cards.extend_from_slice(combo);
+        cards.as_slice().rank()
+    };
+    let oop_ranks: Vec<Rank> = oop_combos.iter().map(strength).collect();
+    let ip_ranks: Vec<Rank> = ip_combos.iter().map(strength).collect();
+
+    // `pairs[i * n + j]`: weight of OOP combo i meeting IP combo j (0 when
+    // they share a card), and the share of the pot OOP wins at showdown
+    let n = ip_combos.len();
+    let mut pairs = vec![0.0; oop_combos.len() * n];
+    let mut share = vec![0.0; oop_combos.len() * n];
+    for (i, oop) in oop_combos.iter().enumerate() {
+        for (j, ip) in ip_combos.iter().enumerate() {
+            if oop.iter().any(|c| ip.contains(c)) {
+                continue;
+            }
+            pairs[i * n + j] = oop_weights[i] * ip_weights[j];
+            share[i * n + j] = match oop_ranks[i].cmp(&ip_ranks[j]) {
+                std::cmp::Ordering::Greater => 1.0,
+                std::cmp::Ordering::Equal => 0.5,
+                std::cmp::Ordering::Less => 0.0,
+            };
+        }
+    }
+    let total: f64 = pairs.iter().sum();
+    if total <= 0.0 {
+        return Err(SnapError::InvalidArgument(
+            "The ranges have no combos left to play each other".to_string(),
+        ));
+    }
+
+    let mut game = River {
+        pot: options.pot,
+        bet: (options.pot * options.bet_fraction).min(options.stack),
+        sizes: [oop_combos.len(), n],
+        pairs,
+        share,
+        regrets: Vec::new(),
+        totals: Vec::new(),
+    };
+    game.regrets = RiverNode::ALL
+        .iter()
+        .map(|node| vec![0.0; game.sizes[node.is_ip() as usize] * 2])
+        .collect();
+    game.totals = game.regrets.clone();
+
+    for t in 1..=options.iterations {
+        // CFR+ updates one player at a time and weights later iterations more
+        for player in 0..2 {
+            let reach = [vec![1.0; game.sizes[0]], vec![1.0; game.sizes[1]]];
+            game.walk(RiverNode::OopFirst, player, reach, Some(t as f64));
+        }
+    }
+
+    let strategies: Vec<Vec<f64>> = RiverNode::ALL
+        .iter()
+        .map(|&node| game.average(node))
+        .collect();
+    let mut reach = vec![Vec::new(); RiverNode::ALL.len()];
+    let mut evs = [Vec::new(), Vec::new()];
+    for (player, ev) in evs.iter_mut().enumerate() {
+        let start = [vec![1.0; game.sizes[0]], vec![1.0; game.sizes[1]]];
+        *ev = game.evaluate(RiverNode::OopFirst, player, start, &strategies, &mut reach);
+    }
+    let [oop_values, ip_values] = evs;
+    let per_combo = |values: &[f64], player: usize| -> Vec<f64> {
+        values
+            .iter()
+            .enumerate()
+            .map(|(k, v)| {
+                let mass = game.opponent_mass(player, k);
+                if mass > 0.0 {
+                    v / mass
+                } else {
+                    0.0
+                }
+            })
+            .collect()
+    };
+    let oop_ev = oop_values.iter().sum::<f64>() / total;
+    Ok(RiverSolution {
+        bet: game.bet,
+        oop_ev,
+        ip_ev: options.pot - oop_ev,
+        oop_combo_evs: per_combo(&oop_values, 0),
+        ip_combo_evs: per_combo(&ip_values, 1),
+        oop_combos,
+        ip_combos,
+        strategies,
+        reach,
+        oop_weights,
+        ip_weights,
+    })
+}
+
+struct River {
+    pot: f64,
+    bet: f64,
+    /// Combos per player, OOP first.
+    sizes: [usize; 2],
+    pairs: Vec<f64>,
+    share: Vec<f64>,
+    /// Cumulative regrets per node, `[combo * 2 + action]`, floored at 0.
+    regrets: Vec<Vec<f64>>,
+    /// Iteration-weighted sums of the strategies played.
+    totals: Vec<Vec<f64>>,
+}
+
+/// What follows an action: another decision, a showdown for a pot with
+/// `bet` from each player, or a fold by the given player.
+enum Next {
+    Node(RiverNode),
+    Showdown { bet: bool },
+    Fold { ip: bool },
+}
+
+impl River {
+    fn next(node: RiverNode, action: usize) -> Next {
+        match (node, action) {
+            (RiverNode::OopFirst, 0) => Next::Node(RiverNode::IpVsCheck),
+            (RiverNode::OopFirst, _) => Next::Node(RiverNode::IpVsBet),
+            (RiverNode::IpVsCheck, 0) => Next::Showdown { bet: false },
+            (RiverNode::IpVsCheck, _) => Next::Node(RiverNode::OopVsBet),
+            (RiverNode::OopVsBet, 0) => Next::Fold { ip: false },
+            (RiverNode::IpVsBet, 0) => Next::Fold { ip: true },
+            (RiverNode::OopVsBet | RiverNode::IpVsBet, _) => Next::Showdown { bet: true },
+        }
+    }
+
+    /// One CFR+ pass below `node` updating `player` (0 = OOP). `reach` holds
+    /// each player's per-combo probability of playing to `node`; returns
+    /// `player`'s counterfactual value per combo. `weight` is the iteration
+    /// number for the average strategy, `None` when only evaluating.
+    fn walk(
+        &mut self,
+        node: RiverNode,
+        player: usize,
+        reach: [Vec<f64>; 2],
+        weight: Option<f64>,
+    ) -> Vec<f64> {
+        let actor = node.is_ip() as usize;
+        let strategy = regret_matching(&self.regrets[node as usize]);
+        let mut children = Vec::with_capacity(2);
+        for action in 0..2 {
+            let mut next_reach = reach.clone();
+            for (k, r) in next_reach[actor].iter_mut().enumerate() {
+                *r *= strategy[k * 2 + action];
+            }
+            let values = match Self::next(node, action) {
+                Next::Node(next) => self.walk(next, player, next_reach, weight),
+                leaf => self.leaf(&leaf, player, &next_reach[1 - player]),
+            };
+            children.push(values);
+        }
+
+        let size = self.sizes[player];
+        if actor != player {
+            return (0..size).map(|k| children[0][k] + children[1][k]).collect();
+        }
+        let value: Vec<f64> = (0..size)
+            .map(|k| strategy[k * 2] * children[0][k] + strategy[k * 2 + 1] * children[1][k])
+            .collect();
+        if let Some(t) = weight {
+            let regrets = &mut self.regrets[node as usize];
+            let totals = &mut self.totals[node as usize];
+            for k in 0..size {
+                for (action, child) in children.iter().enumerate() {
+                    let slot = k * 2 + action;
+                    regrets[slot] = (regrets[slot] + child[k] - value[k]).max(0.0);
+                    totals[slot] += t * reach[player][k] * strategy[slot];
+                }
+            }
+        }
+        value
+    }
+
+    /// Like [`walk`](Self::walk) with fixed `strategies`, recording each
+    /// node's reach for its acting player.
+    fn evaluate(
+        &self,
+        node: RiverNode,
+        player: usize,
+        reach: [Vec<f64>; 2],
+        strategies: &[Vec<f64>],
+        reached: &mut [Vec<f64>],
+    ) -> Vec<f64> {
+        let actor = node.is_ip() as usize;
+        let strategy = &strategies[node as usize];
+        reached[node as usize] = reach[actor].clone();
+        let mut children = Vec::with_capacity(2);
+        for action in 0..2 {
+            let mut next_reach = reach.clone();
+            for (k, r) in next_reach[actor].iter_mut().enumerate() {
+                *r *= strategy[k * 2 + action];
+            }
+            let values = match Self::next(node, action) {
+                Next::Node(next) => self.evaluate(next, player, next_reach, strategies, reached),
+                leaf => self.leaf(&leaf, player, &next_reach[1 - player]),
+            };
+            children.push(values);
+        }
+        (0..self.sizes[player])
+            .map(|k| {
+                if actor == player {
+                    strategy[k * 2] * children[0][k] + strategy[k * 2 + 1] * children[1][k]
+                } else {
+                    children[0][k] + children[1][k]
+                }
+            })
+            .collect()
+    }
+
+    /// `player`'s value per combo at a leaf, summed over the opponent's
+    /// combos weighted by `opponent_reach`.
+    fn leaf(&self, leaf: &Next, player: usize, opponent_reach: &[f64]) -> Vec<f64> {
+        let n = self.sizes[1];
+        (0..self.sizes[player])
+            .map(|k| {
+                (0..self.sizes[1 - player])
+                    .map(|o| {
+                        let (i, j) = if player == 0 { (k, o) } else { (o, k) };
+                        let mass = self.pairs[i * n + j] * opponent_reach[o];
+                        if mass == 0.0 {
+                            return 0.0;
+                        }
+                        mass * self.payoff(leaf, player, self.share[i * n + j])
+                    })
+                    .sum()
+            })
+            .collect()
+    }
+
+    /// Chips `player` ends with out of the pot, minus river bets, given the
+    /// share of the pot OOP wins at showdown.
+    fn payoff(&self, leaf: &Next, player: usize, oop_share: f64) -> f64 {
+        let share = if player == 0 {
+            oop_share
+        } else {
+            1.0 - oop_share
+        };
+        match *leaf {
+            Next::Showdown { bet: false } => share * self.pot,
+            Next::Showdown { bet: true } => share * (self.pot + 2.0 * self.bet) - self.bet,
+            Next::Fold { ip } => {
+                if ip == (player == 1) {
+                    0.0
+                } else {
+                    self.pot
+                }
+            }
+            Next::Node(_) => 0.0,
+        }
+    }
+
+    /// Total pair weight `player`'s combo `k` meets across the opponent's
+    /// range.
+    fn opponent_mass(&self, player: usize, k: usize) -> f64 {
+        let n = self.sizes[1];
+        (0..self.sizes[1 - player])
+            .map(|o| {
+                let (i, j) = if player == 0 { (k, o) } else { (o, k) };
+                self.pairs[i * n + j]
+            })
+            .sum()
+    }
+
+    fn average(&self, node: RiverNode) -> Vec<f64> {
+        let totals = &self.totals[node as usize];
+        totals
+            .chunks(2)
+            .flat_map(|slots| {
+                let sum = slots[0] + slots[1];
+                if sum > 0.0 {
+                    [slots[0] / sum, slots[1] / sum]
+                } else {
+                    [0.5, 0.5]
+                }
+            })
+            .collect()
+    }
+}
+
+/// Each combo plays its two actions in proportion to their positive
+/// regrets, evenly when neither is positive.
+fn regret_matching(regrets: &[f64]) -> Vec<f64> {
+    regrets
+        .chunks(2)
+        .flat_map(|slots| {
+            let sum = slots[0].max(0.0) + slots[1].max(0.0);
+            if sum > 0.0 {
+                [slots[0].max(0.0) / sum, slots[1].max(0.0) / sum]
+            } else {
+                [0.5, 0.5]
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn combo(cards: &str) -> [Card; 2] {
+        let cards = parse_cards(cards).unwrap();
+        [cards[0], cards[1]]
+    }
+
+    #[test]
+    fn the_nuts_bets_and_air_folds() {
+        // Top set against queen high
+        let solution =
+            solve_river("AhKd7c2s3h", "AA", "QJs", &RiverOptions::new(10.0, 50.0)).unwrap();
+        assert!((solution.oop_ev - 10.0).abs() < 0.05, "{}", solution.oop_ev);
+        assert!(solution.frequency(RiverNode::IpVsBet, RiverAction::Fold) > 0.99);
+        assert!((solution.oop_ev + solution.ip_ev - 10.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn polarized_bettor_meets_bluff_catchers() {
+        // IP holds a set or nothing; OOP's king beats only the bluffs. With
+        // a pot-sized bet IP bluffs one combo per two value combos, and OOP
+        // calls half the time.
+        let options = RiverOptions {
+            bet_fraction: 1.0,
+            iterations: 2_000,
+            ..RiverOptions::new(10.0, 100.0)
+        };
+        let solution = solve_river("AhKd7c2s3h", "KTo", "77, QJs", &options).unwrap();
+        let set = solution
+            .strategy(RiverNode::IpVsCheck, combo("7h7s"))
+            .unwrap();
+        assert!(set[1].1 > 0.95, "{set:?}");
+        let bluff = solution.frequency(RiverNode::IpVsCheck, RiverAction::Bet) * 7.0 - 3.0;
+        assert!((bluff - 1.5).abs() < 0.3, "{bluff}");
+        let call = solution.frequency(RiverNode::OopVsBet, RiverAction::Call);
+        assert!((call - 0.5).abs() < 0.05, "{call}");
+        assert!(solution
+            .strategy(RiverNode::OopFirst, combo("AhKd"))
+            .is_none());
+    }
+
+    #[test]
+    fn rejects_invalid_spots() {
+        let options = RiverOptions::new(10.0, 50.0);
+        assert!(solve_river("AhKd7c2s", "AA", "QJs", &options).is_err());
+        assert!(solve_river("AhKd7c2s3h", "AhAd", "AdAc", &options).is_err());
+        let free = RiverOptions {
+            pot: 0.0,
+            ..options
+        };
+        assert!(solve_river("AhKd7c2s3h", "AA", "QJs", &free).is_err());
+    }
+}
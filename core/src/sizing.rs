@@ -0,0 +1,117 @@
+use crate::odds::{geometric_bet_fraction, pot_odds_percent};
+use crate::types::SnapError;
+
+/// Bet of `fraction` of the pot, e.g. `0.75` for a three-quarter-pot bet.
+pub fn pot_fraction_bet(pot: f64, fraction: f64) -> f64 {
+    pot * fraction
+}
+
+/// Total a pot-sized raise makes it facing `bet` into `pot` (the pot before
+/// the bet): call the bet, then raise by the pot after the call, so
+/// `pot + 3 · bet`.
+pub fn pot_size_raise(pot: f64, bet: f64) -> f64 {
+    pot + 3.0 * bet
+}
+
+/// Equity (in percent) hero needs to call an all-in raise after betting
+/// `bet` into `pot` with `stack` (the effective stack before betting): the
+/// point where the bet commits hero. Above about a third, folding to the
+/// raise gives up a big part of the pot hero just built.
+pub fn stack_off_after_bet_percent(pot: f64, bet: f64, stack: f64) -> f64 {
+    let to_call = (stack - bet).max(0.0);
+    pot_odds_percent(pot + bet + stack, to_call)
+}
+
+/// One street of a [`geometric_plan`]: `bet` goes into `pot`, leaving
+/// `stack_behind` once it is called.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StreetBet {
+    pub pot: f64,
+    pub bet: f64,
+    pub stack_behind: f64,
+}
+
+/// Equal pot-fraction bets over `streets` streets that, each called, get
+/// `stack` all in by the last one, starting from `pot`. Every bet is
+/// [`geometric_bet_fraction`] of the pot it goes into.
+///
+/// # Errors
+/// Returns [`SnapError::InvalidArgument`] for a non-positive pot or stack or
+/// zero streets.
+pub fn geometric_plan(pot: f64, stack: f64, streets: u32) -> Result<Vec<StreetBet>, SnapError> {
+    if [pot, stack].iter().any(|v| !v.is_finite() || *v <= 0.0) {
+        return Err(SnapError::InvalidArgument(format!(
+            "Pot and stack must be positive, got {} and {}",
+            pot, stack
+        )));
+    }
+    if streets == 0 {
+        return Err(SnapError::InvalidArgument(
+            "Need at least one street to bet".to_string(),
+        ));
+    }
+
+    let fraction = geometric_bet_fraction(stack / pot, streets);
+    let (mut pot, mut stack) = (pot, stack);
+    let mut plan = Vec::with_capacity(streets as usize);
+    for street in 1..=streets {
+        // The last bet is whatever is left, free of rounding drift
+        let bet = if street == streets {
+            stack
+        } else {
+            pot_fraction_bet(pot, fraction)
+        };
+        stack -= bet;
+        plan.push(StreetBet {
+            pot,
+            bet,
+            stack_behind: stack,
+        });
+        pot += 2.0 * bet;
+    }
+    Ok(plan)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(a: f64, b: f64) {
+        assert!((a - b).abs() < 1e-9, "{a} vs {b}");
+    }
+
+    #[test]
+    fn pot_sized_bets_and_raises() {
+        assert_eq!(pot_fraction_bet(120.0, 0.75), 90.0);
+        // Facing 10 into 10: calling makes the pot 30, so raise 30 more
+        assert_eq!(pot_size_raise(10.0, 10.0), 40.0);
+    }
+
+    #[test]
+    fn bigger_bets_commit_hero() {
+        // A pot bet of 10 with 30 behind, jammed on: call 20 into 50
+        assert_close(
+            stack_off_after_bet_percent(10.0, 10.0, 30.0),
+            20.0 / 70.0 * 100.0,
+        );
+        assert!(
+            stack_off_after_bet_percent(10.0, 5.0, 100.0)
+                > stack_off_after_bet_percent(10.0, 50.0, 100.0)
+        );
+        assert_eq!(stack_off_after_bet_percent(10.0, 30.0, 30.0), 0.0);
+    }
+
+    #[test]
+    fn geometric_plan_ends_all_in() {
+        let plan = geometric_plan(10.0, 130.0, 3).unwrap();
+        let bets: Vec<f64> = plan.iter().map(|s| s.bet).collect();
+        assert_close(bets[0], 10.0);
+        assert_close(bets[1], 30.0);
+        assert_close(bets[2], 90.0);
+        assert_eq!(plan[2].stack_behind, 0.0);
+        assert_close(plan[2].pot, 90.0);
+
+        assert!(geometric_plan(10.0, 130.0, 0).is_err());
+        assert!(geometric_plan(0.0, 130.0, 2).is_err());
+    }
+}
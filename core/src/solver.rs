@@ -0,0 +1,317 @@
+//! Experimental counterfactual regret minimization (CFR) for a small
+//! heads-up preflop game, for exploring mixed strategies rather than
+//! producing play-ready charts.
+
+use rand::distr::weighted::WeightedIndex;
+use rand::distr::Distribution;
+
+use crate::monte_carlo::seeded_rng;
+use crate::pushfold::{class_at, equity, grid_index, removal_weights, NUM_CLASSES};
+use crate::range::HandClass;
+use crate::types::SnapError;
+
+/// An action in the [`solve_preflop_cfr`] game tree.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PreflopAction {
+    Fold,
+    Call,
+    /// Raise to 2 bb.
+    MinRaise,
+    /// All in for the effective stack.
+    Shove,
+}
+
+impl std::fmt::Display for PreflopAction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PreflopAction::Fold => write!(f, "fold"),
+            PreflopAction::Call => write!(f, "call"),
+            PreflopAction::MinRaise => write!(f, "min_raise"),
+            PreflopAction::Shove => write!(f, "shove"),
+        }
+    }
+}
+
+/// A decision point of the [`solve_preflop_cfr`] game tree.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PreflopNode {
+    /// Small blind first in: fold, min-raise, or shove.
+    SbOpen,
+    /// Big blind facing a shove: fold or call.
+    BbVsShove,
+    /// Big blind facing a min-raise: fold, call, or shove.
+    BbVsMinRaise,
+    /// Small blind facing a shove over its min-raise: fold or call.
+    SbVsShove,
+}
+
+impl PreflopNode {
+    /// Every decision point, in tree order.
+    pub const ALL: [PreflopNode; 4] = [
+        PreflopNode::SbOpen,
+        PreflopNode::BbVsShove,
+        PreflopNode::BbVsMinRaise,
+        PreflopNode::SbVsShove,
+    ];
+
+    /// Actions available here, in the order strategies list them.
+    pub fn actions(self) -> &'static [PreflopAction] {
+        use PreflopAction::*;
+        match self {
+            PreflopNode::SbOpen => &[Fold, MinRaise, Shove],
+            PreflopNode::BbVsShove | PreflopNode::SbVsShove => &[Fold, Call],
+            PreflopNode::BbVsMinRaise => &[Fold, Call, Shove],
+        }
+    }
+}
+
+/// Average strategies from [`solve_preflop_cfr`], per decision point and
+/// starting-hand class.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PreflopSolution {
+    pub stack_bb: f64,
+    pub iterations: usize,
+    /// `strategies[node][class * 3 + action]`, unused slots zero.
+    strategies: Vec<Vec<f64>>,
+}
+
+impl PreflopSolution {
+    /// How often `class` takes each of `node`'s actions; the frequencies sum
+    /// to 1.
+    pub fn strategy(&self, node: PreflopNode, class: HandClass) -> Vec<(PreflopAction, f64)> {
+        let slots = &self.strategies[node as usize][grid_index(class) * MAX_ACTIONS..];
+        node.actions()
+            .iter()
+            .zip(slots)
+            .map(|(&action, &frequency)| (action, frequency))
+            .collect()
+    }
+
+    /// How often `class` takes `action` at `node`; 0 for an action not
+    /// available there.
+    pub fn frequency(&self, node: PreflopNode, class: HandClass, action: PreflopAction) -> f64 {
+        self.strategy(node, class)
+            .into_iter()
+            .find_map(|(a, frequency)| (a == action).then_some(frequency))
+            .unwrap_or(0.0)
+    }
+}
+
+const MAX_ACTIONS: usize = 3;
+
+/// Solves a heads-up preflop game with chance-sampled CFR: each iteration
+/// deals a pair of starting-hand classes (weighted by combos, with card
+/// removal) and updates regrets over the whole betting tree.
+///
+/// The tree has no antes and is deliberately small: the small blind folds,
+/// min-raises, or shoves; the big blind folds, calls, or shoves over a
+/// min-raise; the small blind then folds or calls. A called min-raise is
+/// treated as checked down, so the game rewards raw equity rather than
+/// playability. Equities come from the same class-vs-class table as
+/// [`solve_push_fold`](crate::solve_push_fold).
+///
+/// The same `seed` always gives the same strategies; `None` draws a fresh
+/// one.
+///
+/// # Errors
+/// Returns [`SnapError::InvalidArgument`] for a stack of 2 bb or less (no
+/// room to shove over a min-raise) or zero `iterations`.
+pub fn solve_preflop_cfr(
+    stack_bb: f64,
+    iterations: usize,
+    seed: Option<u64>,
+) -> Result<PreflopSolution, SnapError> {
+    if !stack_bb.is_finite() || stack_bb <= 2.0 {
+        return Err(SnapError::InvalidArgument(format!(
+            "Stack must be above 2 bb, got {}",
+            stack_bb
+        )));
+    }
+    if iterations == 0 {
+        return Err(SnapError::InvalidArgument(
+            "CFR needs at least one iteration".to_string(),
+        ));
+    }
+
+    let classes: Vec<HandClass> = (0..NUM_CLASSES).map(class_at).collect();
+    let weights = removal_weights(&classes);
+    let deal_sb = WeightedIndex::new(classes.iter().map(|c| c.combos() as f64))
+        .map_err(|e| SnapError::InvalidArgument(e.to_string()))?;
+    let deal_bb = (0..NUM_CLASSES)
+        .map(|i| WeightedIndex::new(&weights[i * NUM_CLASSES..(i + 1) * NUM_CLASSES]))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| SnapError::InvalidArgument(e.to_string()))?;
+    let mut rng = seeded_rng(seed);
+
+    let mut cfr = Cfr {
+        stack_bb,
+        regrets: vec![vec![0.0; NUM_CLASSES * MAX_ACTIONS]; PreflopNode::ALL.len()],
+        totals: vec![vec![0.0; NUM_CLASSES * MAX_ACTIONS]; PreflopNode::ALL.len()],
+    };
+    for _ in 0..iterations {
+        let sb = deal_sb.sample(&mut rng);
+        let bb = deal_bb[sb].sample(&mut rng);
+        cfr.walk(PreflopNode::SbOpen, [sb, bb], [1.0, 1.0]);
+    }
+
+    let strategies = PreflopNode::ALL
+        .iter()
+        .map(|&node| cfr.average(node))
+        .collect();
+    Ok(PreflopSolution {
+        stack_bb,
+        iterations,
+        strategies,
+    })
+}
+
+struct Cfr {
+    stack_bb: f64,
+    /// Cumulative regrets, laid out like [`PreflopSolution::strategies`].
+    regrets: Vec<Vec<f64>>,
+    /// Reach-weighted sums of the current strategies.
+    totals: Vec<Vec<f64>>,
+}
+
+impl Cfr {
+    /// Updates regrets below `node` for the dealt `classes` (small blind
+    /// first) and returns the small blind's expected result there; `reach`
+    /// is each player's probability of playing to `node`.
+    fn walk(&mut self, node: PreflopNode, classes: [usize; 2], reach: [f64; 2]) -> f64 {
+        let player = match node {
+            PreflopNode::SbOpen | PreflopNode::SbVsShove => 0,
+            PreflopNode::BbVsShove | PreflopNode::BbVsMinRaise => 1,
+        };
+        let actions = node.actions();
+        let slot = classes[player] * MAX_ACTIONS;
+        let strategy = current_strategy(&self.regrets[node as usize][slot..slot + actions.len()]);
+
+        let mut values = [0.0; MAX_ACTIONS];
+        let mut value = 0.0;
+        for (k, &action) in actions.iter().enumerate() {
+            let mut next_reach = reach;
+            next_reach[player] *= strategy[k];
+            values[k] = match self.next(node, action, equity(classes[0], classes[1])) {
+                Next::Node(next) => self.walk(next, classes, next_reach),
+                Next::Payoff(payoff) => payoff,
+            };
+            value += strategy[k] * values[k];
+        }
+
+        // Regrets are from the acting player's side of the zero-sum payoff
+        let sign = if player == 0 { 1.0 } else { -1.0 };
+        let opponent_reach = reach[1 - player];
+        for k in 0..actions.len() {
+            self.regrets[node as usize][slot + k] += sign * opponent_reach * (values[k] - value);
+            self.totals[node as usize][slot + k] += reach[player] * strategy[k];
+        }
+        value
+    }
+
+    /// What follows `action` at `node` given the small blind's all-in
+    /// equity `eq`.
+    fn next(&self, node: PreflopNode, action: PreflopAction, eq: f64) -> Next {
+        use PreflopAction::*;
+        let stack = self.stack_bb;
+        match (node, action) {
+            (PreflopNode::SbOpen, Fold) => Next::Payoff(-0.5),
+            (PreflopNode::SbOpen, MinRaise) => Next::Node(PreflopNode::BbVsMinRaise),
+            (PreflopNode::SbOpen, _) => Next::Node(PreflopNode::BbVsShove),
+            (PreflopNode::BbVsShove | PreflopNode::BbVsMinRaise, Fold) => Next::Payoff(1.0),
+            (PreflopNode::BbVsMinRaise, Call) => Next::Payoff(eq * 4.0 - 2.0),
+            (PreflopNode::BbVsMinRaise, _) => Next::Node(PreflopNode::SbVsShove),
+            (PreflopNode::SbVsShove, Fold) => Next::Payoff(-2.0),
+            (PreflopNode::BbVsShove | PreflopNode::SbVsShove, _) => {
+                Next::Payoff(eq * 2.0 * stack - stack)
+            }
+        }
+    }
+
+    /// Average strategy at `node` for every class.
+    fn average(&self, node: PreflopNode) -> Vec<f64> {
+        let count = node.actions().len();
+        let mut average = vec![0.0; NUM_CLASSES * MAX_ACTIONS];
+        for class in 0..NUM_CLASSES {
+            let slot = class * MAX_ACTIONS;
+            let totals = &self.totals[node as usize][slot..slot + count];
+            let sum: f64 = totals.iter().sum();
+            for k in 0..count {
+                average[slot + k] = if sum > 0.0 {
+                    totals[k] / sum
+                } else {
+                    1.0 / count as f64
+                };
+            }
+        }
+        average
+    }
+}
+
+/// A decision point, or the end of the hand with the small blind's result
+/// in big blinds relative to its starting stack.
+enum Next {
+    Node(PreflopNode),
+    Payoff(f64),
+}
+
+/// Regret matching: play each action in proportion to its positive regret,
+/// uniformly when none is positive.
+fn current_strategy(regrets: &[f64]) -> [f64; MAX_ACTIONS] {
+    let mut strategy = [0.0; MAX_ACTIONS];
+    let positive: f64 = regrets.iter().map(|r| r.max(0.0)).sum();
+    for (k, r) in regrets.iter().enumerate() {
+        strategy[k] = if positive > 0.0 {
+            r.max(0.0) / positive
+        } else {
+            1.0 / regrets.len() as f64
+        };
+    }
+    strategy
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn class(label: &str) -> HandClass {
+        label.parse().unwrap()
+    }
+
+    #[test]
+    fn strategies_are_distributions() {
+        let solution = solve_preflop_cfr(10.0, 20_000, Some(1)).unwrap();
+        for node in PreflopNode::ALL {
+            let strategy = solution.strategy(node, class("T9s"));
+            assert_eq!(strategy.len(), node.actions().len());
+            let total: f64 = strategy.iter().map(|(_, f)| f).sum();
+            assert!((total - 1.0).abs() < 1e-9, "{node:?}: {total}");
+        }
+    }
+
+    #[test]
+    fn plays_aces_and_folds_trash() {
+        let solution = solve_preflop_cfr(10.0, 300_000, Some(7)).unwrap();
+        let aces = class("AA");
+        let trash = class("72o");
+        assert!(solution.frequency(PreflopNode::SbOpen, aces, PreflopAction::Fold) < 0.05);
+        assert!(solution.frequency(PreflopNode::BbVsShove, aces, PreflopAction::Call) > 0.95);
+        assert!(solution.frequency(PreflopNode::BbVsShove, trash, PreflopAction::Call) < 0.2);
+        assert_eq!(
+            solution.frequency(PreflopNode::BbVsShove, aces, PreflopAction::MinRaise),
+            0.0
+        );
+    }
+
+    #[test]
+    fn same_seed_same_strategies() {
+        let a = solve_preflop_cfr(15.0, 5_000, Some(3)).unwrap();
+        let b = solve_preflop_cfr(15.0, 5_000, Some(3)).unwrap();
+        assert_eq!(a, b);
+    }
+
+    #[test]
+    fn rejects_invalid_inputs() {
+        assert!(solve_preflop_cfr(2.0, 1_000, None).is_err());
+        assert!(solve_preflop_cfr(10.0, 0, None).is_err());
+    }
+}
@@ -0,0 +1,354 @@
+use std::collections::HashSet;
+
+use rs_poker::core::{Card, Deck, Rank, Rankable};
+
+use crate::range::expand_weighted_range;
+use crate::types::SnapError;
+
+/// How hero's made hand compares with every opponent holding on a board.
+///
+/// Counts are hole-card combos not blocked by hero's cards or the board.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct HandStrength {
+    pub ahead: usize,
+    pub tied: usize,
+    pub behind: usize,
+}
+
+impl HandStrength {
+    /// Number of opponent combos compared.
+    pub fn combos(&self) -> usize {
+        self.ahead + self.tied + self.behind
+    }
+
+    /// Share of opponent combos hero beats, counting ties as half, in percent.
+    pub fn percentile(&self) -> f64 {
+        let total = self.combos();
+        if total == 0 {
+            return 0.0;
+        }
+        (self.ahead as f64 + self.tied as f64 / 2.0) / total as f64 * 100.0
+    }
+}
+
+/// Ranks hero's current hand against all opponent hole-card combos.
+///
+/// This is made-hand strength only: no cards are dealt, so draws count for
+/// nothing.
+///
+/// # Errors
+/// Returns [`SnapError::InvalidHand`] unless hero has 2 cards, the board
+/// 3–5, and all cards are distinct.
+pub fn hand_strength(hero: &[Card], board: &[Card]) -> Result<HandStrength, SnapError> {
+    let remaining = validate(hero, board)?;
+    Ok(compare_all(hero, board, &remaining))
+}
+
+/// Hand strength after each possible next board card (the turn on a flop,
+/// the river on a turn), ordered by card.
+///
+/// # Errors
+/// Returns [`SnapError::InvalidHand`] unless hero has 2 cards, the board
+/// 3 or 4, and all cards are distinct.
+pub fn next_card_strengths(
+    hero: &[Card],
+    board: &[Card],
+) -> Result<Vec<(Card, HandStrength)>, SnapError> {
+    let remaining = validate(hero, board)?;
+    if board.len() == 5 {
+        return Err(SnapError::InvalidHand(
+            "Board is complete; no cards to come".to_string(),
+        ));
+    }
+
+    let mut next_board = board.to_vec();
+    next_board.push(remaining[0]);
+    let last = board.len();
+    let mut deck = Vec::with_capacity(remaining.len() - 1);
+    let mut strengths = Vec::with_capacity(remaining.len());
+    for &card in &remaining {
+        next_board[last] = card;
+        deck.clear();
+        deck.extend(remaining.iter().copied().filter(|&c| c != card));
+        strengths.push((card, compare_all(hero, &next_board, &deck)));
+    }
+    Ok(strengths)
+}
+
+/// Billings et al.'s hand strength and potential against a weighted range,
+/// all as fractions from 0 to 1.
+///
+/// - `hand_strength` is the share of the range hero beats now, ties half.
+/// - `positive_potential` is the share of the time hero, behind (or tied)
+///   now, is ahead by the river; `negative_potential` the reverse.
+/// - `ehs2` is the mean of hand strength squared over river runouts, which
+///   rewards draws that end up far ahead more than made hands that stay
+///   middling.
+///
+/// Potentials look to the river, two cards ahead on the flop; both are 0 on
+/// the river.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct HandPotential {
+    pub hand_strength: f64,
+    pub positive_potential: f64,
+    pub negative_potential: f64,
+    pub ehs2: f64,
+}
+
+impl HandPotential {
+    /// Effective hand strength: wins now that hold, plus losses that turn
+    /// into wins.
+    pub fn ehs(&self) -> f64 {
+        self.hand_strength * (1.0 - self.negative_potential)
+            + (1.0 - self.hand_strength) * self.positive_potential
+    }
+}
+
+/// Hand strength, potential, and EHS² for hero against `villain_range`
+/// (e.g. `"TT+, AQs+"`), enumerating every runout to the river and every
+/// range combo not blocked by the cards in play.
+///
+/// # Errors
+/// Returns [`SnapError::InvalidHand`] unless hero has 2 cards, the board
+/// 3–5, and all cards are distinct; any range parsing error; and
+/// [`SnapError::InvalidArgument`] when the board and hero block the whole
+/// range.
+pub fn hand_potential(
+    hero: &[Card],
+    board: &[Card],
+    villain_range: &str,
+) -> Result<HandPotential, SnapError> {
+    let remaining = validate(hero, board)?;
+    let villains: Vec<([Card; 2], f64)> = expand_weighted_range(villain_range)?
+        .into_iter()
+        .filter(|(combo, _)| combo.iter().all(|c| remaining.contains(c)))
+        .collect();
+    if villains.is_empty() {
+        return Err(SnapError::InvalidArgument(
+            "Villain range has no combos left on this board".to_string(),
+        ));
+    }
+
+    let rank = |hole: &[Card], runout: &[Card]| -> Rank {
+        let cards: Vec<Card> = hole.iter().chain(board).chain(runout).copied().collect();
+        cards.as_slice().rank()
+    };
+    // 0 = ahead, 1 = tied, 2 = behind
+    let standing = |hero: Rank, villain: Rank| match hero.cmp(&villain) {
+        std::cmp::Ordering::Greater => 0,
+        std::cmp::Ordering::Equal => 1,
+        std::cmp::Ordering::Less => 2,
+    };
+    let hero_now = rank(hero, &[]);
+    let now: Vec<usize> = villains
+        .iter()
+        .map(|(combo, _)| standing(hero_now, rank(combo, &[])))
+        .collect();
+    let mut totals = [0.0; 3];
+    for ((_, weight), &index) in villains.iter().zip(&now) {
+        totals[index] += weight;
+    }
+    let strength = |counts: &[f64; 3]| {
+        let sum: f64 = counts.iter().sum();
+        if sum > 0.0 {
+            (counts[0] + counts[1] / 2.0) / sum
+        } else {
+            0.0
+        }
+    };
+
+    let runouts: Vec<Vec<Card>> = match 5 - board.len() {
+        0 => vec![Vec::new()],
+        1 => remaining.iter().map(|&c| vec![c]).collect(),
+        _ => remaining
+            .iter()
+            .enumerate()
+            .flat_map(|(i, &a)| remaining[i + 1..].iter().map(move |&b| vec![a, b]))
+            .collect(),
+    };
+    // `transitions[now][river]` by weight
+    let mut transitions = [[0.0; 3]; 3];
+    let mut ehs2 = 0.0;
+    for runout in &runouts {
+        let hero_river = rank(hero, runout);
+        let mut river = [0.0; 3];
+        for ((combo, weight), &before) in villains.iter().zip(&now) {
+            if combo.iter().any(|c| runout.contains(c)) {
+                continue;
+            }
+            let after = standing(hero_river, rank(combo, runout));
+            transitions[before][after] += weight;
+            river[after] += weight;
+        }
+        ehs2 += strength(&river).powi(2);
+    }
+
+    let ratio = |gained: f64, base: f64| if base > 0.0 { gained / base } else { 0.0 };
+    let t = &transitions;
+    let row = |i: usize| t[i].iter().sum::<f64>();
+    let (positive_potential, negative_potential) = if board.len() == 5 {
+        (0.0, 0.0)
+    } else {
+        (
+            ratio(
+                t[2][0] + t[2][1] / 2.0 + t[1][0] / 2.0,
+                row(2) + row(1) / 2.0,
+            ),
+            ratio(
+                t[0][2] + t[1][2] / 2.0 + t[0][1] / 2.0,
+                row(0) + row(1) / 2.0,
+            ),
+        )
+    };
+    Ok(HandPotential {
+        hand_strength: strength(&totals),
+        positive_potential,
+        negative_potential,
+        ehs2: ehs2 / runouts.len() as f64,
+    })
+}
+
+/// Checks the card counts and returns the cards not in hero's hand or on the board.
+fn validate(hero: &[Card], board: &[Card]) -> Result<Vec<Card>, SnapError> {
+    if hero.len() != 2 {
+        return Err(SnapError::InvalidHand(format!(
+            "Hero needs exactly 2 cards, got {}",
+            hero.len()
+        )));
+    }
+    if !(3..=5).contains(&board.len()) {
+        return Err(SnapError::InvalidHand(format!(
+            "Board needs 3-5 cards, got {}",
+            board.len()
+        )));
+    }
+    let known: HashSet<Card> = hero.iter().chain(board).copied().collect();
+    if known.len() != hero.len() + board.len() {
+        return Err(SnapError::InvalidHand(
+            "Duplicate card in hero hand and board".to_string(),
+        ));
+    }
+    let mut remaining: Vec<Card> = Deck::default()
+        .into_iter()
+        .filter(|c| !known.contains(c))
+        .collect();
+    remaining.sort();
+    Ok(remaining)
+}
+
+fn compare_all(hero: &[Card], board: &[Card], deck: &[Card]) -> HandStrength {
+    let mut cards = [hero[0]; 7];
+    let n = board.len() + 2;
+    cards[2..n].copy_from_slice(board);
+    cards[1] = hero[1];
+    let hero_rank: Rank = cards[..n].rank();
+
+    let mut strength = HandStrength::default();
+    for (i, &first) in deck.iter().enumerate() {
+        for &second in &deck[i + 1..] {
+            cards[0] = first;
+            cards[1] = second;
+            let villain_rank: Rank = cards[..n].rank();
+            match hero_rank.cmp(&villain_rank) {
+                std::cmp::Ordering::Greater => strength.ahead += 1,
+                std::cmp::Ordering::Equal => strength.tied += 1,
+                std::cmp::Ordering::Less => strength.behind += 1,
+            }
+        }
+    }
+    strength
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::input::parse_cards;
+
+    fn strength(hero: &str, board: &str) -> HandStrength {
+        hand_strength(&parse_cards(hero).unwrap(), &parse_cards(board).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn counts_every_unblocked_combo() {
+        // 47 unseen cards on the flop → C(47, 2)
+        let s = strength("KhQh", "Qs7d2c");
+        assert_eq!(s.combos(), 1081);
+        assert!(
+            s.percentile() > 80.0 && s.percentile() < 100.0,
+            "got {}",
+            s.percentile()
+        );
+    }
+
+    #[test]
+    fn nuts_beat_everything() {
+        let s = strength("AhKh", "QhJhTh");
+        assert_eq!(s.behind, 0);
+        assert_eq!(s.tied, 0);
+        assert_eq!(s.percentile(), 100.0);
+    }
+
+    #[test]
+    fn board_plays_ties() {
+        // Royal flush on board: every holding chops
+        let s = strength("2c3d", "AsKsQsJsTs");
+        assert_eq!(s.tied, s.combos());
+        assert_eq!(s.percentile(), 50.0);
+    }
+
+    #[test]
+    fn next_card_covers_the_deck() {
+        let hero = parse_cards("KhQh").unwrap();
+        let board = parse_cards("Qs7d2c").unwrap();
+        let turns = next_card_strengths(&hero, &board).unwrap();
+        assert_eq!(turns.len(), 47);
+        assert!(turns.iter().all(|(_, s)| s.combos() == 1035));
+        let (_, on_king) = turns.iter().find(|(c, _)| c.to_string() == "Kd").unwrap();
+        assert!(on_king.percentile() > strength("KhQh", "Qs7d2c").percentile());
+    }
+
+    fn potential(hero: &str, board: &str, range: &str) -> HandPotential {
+        hand_potential(
+            &parse_cards(hero).unwrap(),
+            &parse_cards(board).unwrap(),
+            range,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn river_potential_is_strength_alone() {
+        let p = potential("AhKh", "QhJhTh2c3d", "AA, KK, QQ");
+        assert_eq!(p.hand_strength, 1.0);
+        assert_eq!((p.positive_potential, p.negative_potential), (0.0, 0.0));
+        assert_eq!(p.ehs(), 1.0);
+        assert_eq!(p.ehs2, 1.0);
+    }
+
+    #[test]
+    fn flush_draws_have_positive_potential() {
+        // Behind an overpair now, but nine hearts (and the aces and kings)
+        // get there
+        let p = potential("AhKh", "9h6h2c8d", "QcQs");
+        assert_eq!(p.hand_strength, 0.0);
+        assert!(
+            p.positive_potential > 0.3 && p.positive_potential < 0.5,
+            "{p:?}"
+        );
+        assert!((p.ehs() - p.positive_potential).abs() < 1e-12);
+
+        let made = potential("QcQs", "9h6h2c8d", "AhKh");
+        assert_eq!(made.hand_strength, 1.0);
+        assert!((made.negative_potential - p.positive_potential).abs() < 1e-12);
+    }
+
+    #[test]
+    fn rejects_bad_inputs() {
+        let hero = parse_cards("KhQh").unwrap();
+        assert!(hand_strength(&hero, &parse_cards("Qs7d").unwrap()).is_err());
+        assert!(hand_strength(&hero, &parse_cards("Kh7d2c").unwrap()).is_err());
+        assert!(next_card_strengths(&hero, &parse_cards("Qs7d2c3h4h").unwrap()).is_err());
+        let board = parse_cards("Qs7d2c").unwrap();
+        assert!(hand_potential(&hero, &board, "KhQh").is_err());
+    }
+}
@@ -0,0 +1,153 @@
+use rs_poker::core::Suit;
+
+use crate::format::symbol_suit;
+use crate::input::{CardDiagnostic, CardIssue};
+
+const RANKS: &str = "AKQJT98765432";
+const SUITS: [char; 4] = ['s', 'h', 'd', 'c'];
+
+/// Suggestions further than this many edits away are not worth showing.
+const MAX_DISTANCE: usize = 1;
+
+/// Likely corrections for the card in `diagnostic`, closest first. Empty
+/// when nothing is close.
+pub fn card_suggestions(diagnostic: &CardDiagnostic) -> Vec<String> {
+    let token = &diagnostic.token;
+    match diagnostic.issue {
+        CardIssue::Rank('1') if token.starts_with("10") => token
+            .chars()
+            .nth(2)
+            .map(|suit| vec![format!("T{suit}")])
+            .unwrap_or_default(),
+        CardIssue::Rank(_) => nearest(token, all_cards()),
+        CardIssue::Suit(symbol) => match symbol_suit(symbol) {
+            Some(suit) => vec![format!("{}{}", &token[..1], suit)],
+            None => nearest(token, all_cards()),
+        },
+        CardIssue::MissingSuit => SUITS.iter().map(|s| format!("{token}{s}")).collect(),
+    }
+}
+
+/// Likely corrections for a range token that does not parse, closest first.
+///
+/// A run of ranks such as `"AKQ"` becomes the single suggestion
+/// `"AK, AQ"`: the first rank with each of the others.
+pub fn range_suggestions(token: &str) -> Vec<String> {
+    let ranks: Vec<char> = token.to_ascii_uppercase().chars().collect();
+    if ranks.len() > 2 && ranks.iter().all(|c| RANKS.contains(*c)) {
+        let split: Vec<String> = ranks[1..]
+            .iter()
+            .map(|c| format!("{}{}", ranks[0], c))
+            .collect();
+        return vec![split.join(", ")];
+    }
+    nearest(token, class_labels())
+}
+
+/// Whether hole-card input reads as cards (`"AhKd"`, `"10h"`, `"A♠"`) rather
+/// than a range, for choosing between [`card_suggestions`] and
+/// [`range_suggestions`].
+pub fn looks_like_cards(input: &str) -> bool {
+    let compact: String = input.chars().filter(|c| !c.is_whitespace()).collect();
+    let only_ranks = compact
+        .chars()
+        .all(|c| RANKS.contains(c.to_ascii_uppercase()));
+    let second = compact.chars().nth(1);
+    let suit_follows = compact.starts_with("10")
+        || second.is_some_and(|c| Suit::from_char(c).is_some() || symbol_suit(c).is_some());
+    !only_ranks
+        && suit_follows
+        && compact.chars().count() <= 5
+        && !compact.contains([',', '+', '-', '%', ':', '!'])
+}
+
+fn all_cards() -> Vec<String> {
+    RANKS
+        .chars()
+        .flat_map(|r| SUITS.iter().map(move |s| format!("{r}{s}")))
+        .collect()
+}
+
+/// Pair, suited, and offsuit labels (`"AA"`, `"AKs"`, `"AKo"`), each also
+/// with a `+` suffix.
+fn class_labels() -> Vec<String> {
+    let ranks: Vec<char> = RANKS.chars().collect();
+    let mut labels = Vec::new();
+    for (i, &high) in ranks.iter().enumerate() {
+        labels.push(format!("{high}{high}"));
+        for &low in &ranks[i + 1..] {
+            labels.push(format!("{high}{low}"));
+            labels.push(format!("{high}{low}s"));
+            labels.push(format!("{high}{low}o"));
+        }
+    }
+    let plus: Vec<String> = labels.iter().map(|l| format!("{l}+")).collect();
+    labels.extend(plus);
+    labels
+}
+
+/// Candidates within [`MAX_DISTANCE`] edits of `token` (ignoring case),
+/// closest first.
+fn nearest(token: &str, candidates: Vec<String>) -> Vec<String> {
+    let token = token.to_ascii_lowercase();
+    let mut scored: Vec<(usize, String)> = candidates
+        .into_iter()
+        .map(|c| (edit_distance(&token, &c.to_ascii_lowercase()), c))
+        .filter(|(d, _)| *d <= MAX_DISTANCE)
+        .collect();
+    scored.sort_by_key(|(d, _)| *d);
+    scored.into_iter().map(|(_, c)| c).collect()
+}
+
+/// Levenshtein distance over chars.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let above = row[j + 1];
+            row[j + 1] = if ca == *cb {
+                diagonal
+            } else {
+                1 + diagonal.min(above).min(row[j])
+            };
+            diagonal = above;
+        }
+    }
+    row[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::input::{diagnose_cards, diagnose_range};
+
+    fn for_cards(input: &str) -> Vec<String> {
+        card_suggestions(&diagnose_cards(input).unwrap())
+    }
+
+    #[test]
+    fn suggests_card_fixes() {
+        assert_eq!(for_cards("Ah 10h"), ["Th"]);
+        assert_eq!(for_cards("A♠"), ["As"]);
+        assert_eq!(for_cards("Kd Q"), ["Qs", "Qh", "Qd", "Qc"]);
+        assert!(for_cards("Zz").is_empty());
+    }
+
+    #[test]
+    fn suggests_range_fixes() {
+        let token = diagnose_range("TT+, AKQ").unwrap().token;
+        assert_eq!(range_suggestions(&token), ["AK, AQ"]);
+        assert_eq!(range_suggestions("AKx"), ["AK", "AKs", "AKo", "AK+"]);
+    }
+
+    #[test]
+    fn tells_cards_from_ranges() {
+        assert!(looks_like_cards("AhKd"));
+        assert!(looks_like_cards("10h 9c"));
+        assert!(!looks_like_cards("AK"));
+        assert!(!looks_like_cards("AsKs, QQ+"));
+    }
+}
@@ -1,3 +1,7 @@
+use std::time::Duration;
+
+use rs_poker::core::Card;
+
 /// Errors that can occur in the core engine.
 ///
 /// Covers invalid card strings, malformed hands, and unparseable ranges.
@@ -12,6 +16,27 @@ pub enum SnapError {
     /// A range expression could not be parsed or produced no hands.
     #[error("Invalid range: {0}")]
     InvalidRange(String),
+    /// A numeric or option argument is out of range (e.g. a negative stack).
+    #[error("Invalid argument: {0}")]
+    InvalidArgument(String),
+    /// The requested computation exceeds a configured limit.
+    #[error("Resource limit exceeded: {0}")]
+    ResourceLimit(String),
+}
+
+impl SnapError {
+    /// Stable, machine-readable name of the variant (`"invalid_card"`,
+    /// `"invalid_hand"`, `"invalid_range"`, `"invalid_argument"`, or
+    /// `"resource_limit"`), the `code` the JS bindings put on their errors.
+    pub fn code(&self) -> &'static str {
+        match self {
+            SnapError::InvalidCard(_) => "invalid_card",
+            SnapError::InvalidHand(_) => "invalid_hand",
+            SnapError::InvalidRange(_) => "invalid_range",
+            SnapError::InvalidArgument(_) => "invalid_argument",
+            SnapError::ResourceLimit(_) => "resource_limit",
+        }
+    }
 }
 
 /// Solve mode used by the equity engine.
@@ -30,16 +55,221 @@ impl std::fmt::Display for EquityEstimateMode {
     }
 }
 
+/// Default cap on the state space accepted when exact enumeration is forced.
+pub const DEFAULT_MAX_EXACT_STATES: usize = 50_000_000;
+
+/// Tuning knobs for an equity calculation.
+///
+/// - `iterations` is the sample budget; with `mode == None` it is also the
+///   exact-enumeration threshold.
+/// - `mode` forces a solver when set; `None` picks automatically.
+/// - `max_exact_states` bounds forced exact enumeration; larger state spaces
+///   fail with [`SnapError::ResourceLimit`].
+/// - `dead_cards` are removed from the deck (exposed or mucked cards).
+/// - `time_budget`, when set, also bounds Monte Carlo by wall-clock time:
+///   sampling stops at the deadline or after `iterations` samples, whichever
+///   comes first. Needs a system clock, so leave it `None` on
+///   `wasm32-unknown-unknown`.
+/// - The same `seed` reproduces the same Monte Carlo result for the same
+///   inputs and iteration budget; `None` draws a fresh seed. Exact
+///   enumeration ignores it.
+/// - `threads` splits Monte Carlo in [`estimate_equity_with_options`] and
+///   [`EquityChunks`] over that many OS threads (0 and 1 both mean the
+///   calling thread only), capped at [`std::thread::available_parallelism`].
+///   Each thread samples its own stream derived from `seed`, so a seeded
+///   result also depends on the thread count actually used. Other entry
+///   points always sample on the calling thread.
+///
+/// [`estimate_equity_with_options`]: crate::estimate_equity_with_options
+/// [`EquityChunks`]: crate::EquityChunks
+#[derive(Debug, Clone, PartialEq)]
+pub struct EquityOptions {
+    pub iterations: usize,
+    pub mode: Option<EquityEstimateMode>,
+    pub max_exact_states: usize,
+    pub dead_cards: Vec<Card>,
+    pub time_budget: Option<Duration>,
+    pub seed: Option<u64>,
+    pub threads: usize,
+}
+
+impl EquityOptions {
+    /// Automatic mode selection with the given sample budget.
+    pub fn new(iterations: usize) -> Self {
+        Self {
+            iterations,
+            mode: None,
+            max_exact_states: DEFAULT_MAX_EXACT_STATES,
+            dead_cards: Vec::new(),
+            time_budget: None,
+            seed: None,
+            threads: 1,
+        }
+    }
+}
+
+/// Size of the exact-enumeration state space, as seen by the mode heuristic.
+///
+/// - `assignment_combinations` is the product of every range's size (after
+///   filtering known cards), i.e. how many ways ranges can be assigned.
+/// - `runout_combinations` is C(`available_cards`, `runout_slots`): the ways to
+///   deal partial/unknown hole cards plus the missing board.
+/// - `total_states` is their product, or `None` if it overflows.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct EquityMath {
+    pub assignment_combinations: usize,
+    pub available_cards: usize,
+    pub runout_slots: usize,
+    pub runout_combinations: usize,
+    pub total_states: Option<usize>,
+}
+
 /// Full equity result with per-player equity percentages and computation metadata.
 ///
 /// - `equities[0]` is hero's equity; all values sum to 100.0.
+/// - `wins[i]` / `ties[i]` are the percentages of samples in which player `i`
+///   holds the best hand alone / shares it with others.
 /// - `mode` indicates which algorithm was used.
 /// - `samples` is the number of valid iterations completed.
+/// - `math` describes the state space that drove the mode decision.
 #[derive(Debug, Clone, PartialEq)]
 pub struct EquityResult {
     pub equities: Vec<f64>,
+    pub wins: Vec<f64>,
+    pub ties: Vec<f64>,
     pub mode: EquityEstimateMode,
     pub samples: usize,
+    pub math: EquityMath,
+}
+
+impl EquityResult {
+    /// Builds a result from per-player showdown counts.
+    ///
+    /// `best[i]` counts samples where player `i` had a share of the best hand
+    /// (ties included), `outright[i]` those where it was theirs alone.
+    pub(crate) fn from_counts(
+        best: &[usize],
+        outright: &[usize],
+        samples: usize,
+        mode: EquityEstimateMode,
+        math: EquityMath,
+    ) -> Self {
+        let to_f64 = |counts: &[usize]| counts.iter().map(|&c| c as f64).collect::<Vec<_>>();
+        Self::from_weights(
+            &to_f64(best),
+            &to_f64(outright),
+            samples as f64,
+            samples,
+            mode,
+            math,
+        )
+    }
+
+    /// Like [`from_counts`](Self::from_counts) where each sample carries a
+    /// weight (e.g. weighted range combos): `best` and `outright` are summed
+    /// weights and `total_weight` the weight of all `samples`.
+    pub(crate) fn from_weights(
+        best: &[f64],
+        outright: &[f64],
+        total_weight: f64,
+        samples: usize,
+        mode: EquityEstimateMode,
+        math: EquityMath,
+    ) -> Self {
+        let num_players = best.len();
+        let total: f64 = best.iter().sum();
+        let equities = if total == 0.0 {
+            vec![100.0 / num_players as f64; num_players]
+        } else {
+            best.iter().map(|&w| (w / total) * 100.0).collect()
+        };
+        let percent = |weight: f64| {
+            if total_weight == 0.0 {
+                0.0
+            } else {
+                weight / total_weight * 100.0
+            }
+        };
+        Self {
+            equities,
+            wins: outright.iter().map(|&w| percent(w)).collect(),
+            ties: best
+                .iter()
+                .zip(outright)
+                .map(|(&b, &w)| percent(b - w))
+                .collect(),
+            mode,
+            samples,
+            math,
+        }
+    }
+
+    /// Folds another run of the same spot into this one, as if both had
+    /// been a single run (e.g. Monte Carlo chunks). `mode` and `math` are
+    /// kept from `self`.
+    pub fn merge(&mut self, other: &EquityResult) {
+        let samples = self.samples + other.samples;
+        if samples == 0 || self.equities.len() != other.equities.len() {
+            return;
+        }
+        // Back to counts: share of the best hand and outright wins per player
+        let counts = |r: &EquityResult, i: usize| {
+            let scale = r.samples as f64 / 100.0;
+            ((r.wins[i] + r.ties[i]) * scale, r.wins[i] * scale)
+        };
+        let mut best = vec![0.0; self.equities.len()];
+        let mut outright = vec![0.0; self.equities.len()];
+        for (i, (b, w)) in best.iter_mut().zip(&mut outright).enumerate() {
+            let (b1, w1) = counts(self, i);
+            let (b2, w2) = counts(other, i);
+            *b = b1 + b2;
+            *w = w1 + w2;
+        }
+        let total: f64 = best.iter().sum();
+        let n = best.len() as f64;
+        for i in 0..best.len() {
+            self.equities[i] = if total == 0.0 {
+                100.0 / n
+            } else {
+                best[i] / total * 100.0
+            };
+            self.wins[i] = outright[i] / samples as f64 * 100.0;
+            self.ties[i] = (best[i] - outright[i]) / samples as f64 * 100.0;
+        }
+        self.samples = samples;
+    }
+
+    /// Half-width, in percentage points, of an approximate confidence
+    /// interval around each player's equity at normal quantile `z` (1.96 for
+    /// 95%).
+    ///
+    /// A sample's pot share lies between 0 and 1, so `p(1 − p)` bounds its
+    /// variance. Exact results carry no sampling error and return zeros.
+    pub fn margin_of_error(&self, z: f64) -> Vec<f64> {
+        if self.mode == EquityEstimateMode::ExactEnumeration || self.samples == 0 {
+            return vec![0.0; self.equities.len()];
+        }
+        self.equities
+            .iter()
+            .map(|e| {
+                let p = e / 100.0;
+                z * (p * (1.0 - p) / self.samples as f64).sqrt() * 100.0
+            })
+            .collect()
+    }
+}
+
+/// Equity across several boards dealt from one deck (e.g. double-board bomb pots).
+///
+/// - `board_equities[b][i]` is player `i`'s equity on board `b`; each board sums to 100.0.
+/// - `pot_share[i]` is player `i`'s share of the whole pot, each board being
+///   worth an equal part of it.
+/// - `samples` is the number of valid iterations completed.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MultiBoardResult {
+    pub board_equities: Vec<Vec<f64>>,
+    pub pot_share: Vec<f64>,
+    pub samples: usize,
 }
 
 #[cfg(test)]
@@ -64,6 +294,68 @@ mod tests {
         assert!(e.to_string().contains("r"));
     }
 
+    #[test]
+    fn snap_error_display_invalid_argument_includes_input() {
+        let e = SnapError::InvalidArgument("stack".into());
+        assert!(e.to_string().contains("stack"));
+    }
+
+    #[test]
+    fn snap_error_display_resource_limit_includes_input() {
+        let e = SnapError::ResourceLimit("too big".into());
+        assert!(e.to_string().contains("too big"));
+    }
+
+    #[test]
+    fn snap_error_codes_are_distinct() {
+        let codes = [
+            SnapError::InvalidCard(String::new()).code(),
+            SnapError::InvalidHand(String::new()).code(),
+            SnapError::InvalidRange(String::new()).code(),
+            SnapError::InvalidArgument(String::new()).code(),
+            SnapError::ResourceLimit(String::new()).code(),
+        ];
+        let unique: std::collections::HashSet<_> = codes.iter().collect();
+        assert_eq!(unique.len(), codes.len());
+        assert_eq!(codes[0], "invalid_card");
+    }
+
+    #[test]
+    fn margin_of_error_shrinks_with_samples() {
+        let math = EquityMath::default();
+        let mode = EquityEstimateMode::MonteCarlo;
+        let small = EquityResult::from_counts(&[50, 50], &[50, 50], 100, mode, math);
+        let large = EquityResult::from_counts(&[5000, 5000], &[5000, 5000], 10_000, mode, math);
+        // 1.96 · sqrt(.25 / 100) = 9.8 points
+        assert!((small.margin_of_error(1.96)[0] - 9.8).abs() < 1e-9);
+        assert!((large.margin_of_error(1.96)[0] - 0.98).abs() < 1e-9);
+
+        let exact = EquityResult::from_counts(
+            &[1, 1],
+            &[1, 1],
+            2,
+            EquityEstimateMode::ExactEnumeration,
+            math,
+        );
+        assert_eq!(exact.margin_of_error(1.96), vec![0.0, 0.0]);
+    }
+
+    #[test]
+    fn merge_matches_a_single_run() {
+        let math = EquityMath::default();
+        let mode = EquityEstimateMode::MonteCarlo;
+        let mut first = EquityResult::from_counts(&[3, 1], &[3, 1], 4, mode, math);
+        let second = EquityResult::from_counts(&[1, 5], &[0, 4], 5, mode, math);
+        first.merge(&second);
+        let whole = EquityResult::from_counts(&[4, 6], &[3, 5], 9, mode, math);
+        assert_eq!(first.samples, 9);
+        for i in 0..2 {
+            assert!((first.equities[i] - whole.equities[i]).abs() < 1e-9);
+            assert!((first.wins[i] - whole.wins[i]).abs() < 1e-9);
+            assert!((first.ties[i] - whole.ties[i]).abs() < 1e-9);
+        }
+    }
+
     #[test]
     fn snap_error_is_std_error() {
         let e: Box<dyn std::error::Error> = Box::new(SnapError::InvalidCard("x".into()));
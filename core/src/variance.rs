@@ -0,0 +1,231 @@
+use rand::rngs::StdRng;
+use rand::RngExt;
+
+use crate::monte_carlo::seeded_rng;
+use crate::types::SnapError;
+
+/// Hands per simulated block; win rates and deviations are quoted per 100 hands.
+const BLOCK_HANDS: u64 = 100;
+
+/// Inputs for a bankroll simulation.
+///
+/// - `winrate` and `stddev` are in big blinds per 100 hands.
+/// - `bankroll` is the starting bankroll in big blinds; reaching zero is ruin.
+/// - `trials` is the number of independent trajectories simulated.
+/// - The same `seed` always reproduces the same report; `None` draws a fresh seed.
+#[derive(Debug, Clone, PartialEq)]
+pub struct VarianceConfig {
+    pub winrate: f64,
+    pub stddev: f64,
+    pub hands: u64,
+    pub bankroll: f64,
+    pub trials: usize,
+    pub seed: Option<u64>,
+}
+
+/// Outcome of [`simulate_bankroll`]. Amounts are in big blinds.
+///
+/// - `risk_of_ruin` is the fraction of trials whose bankroll hit zero.
+/// - `analytic_risk_of_ruin` is the closed-form `exp(-2 · winrate · bankroll / stddev²)`
+///   for an unlimited number of hands.
+/// - `final_percentiles` and `downswing_percentiles` pair a percentile (0–100)
+///   with net winnings at the end / the deepest peak-to-trough drop.
+#[derive(Debug, Clone, PartialEq)]
+pub struct VarianceReport {
+    pub expected_winnings: f64,
+    pub expected_stddev: f64,
+    pub risk_of_ruin: f64,
+    pub analytic_risk_of_ruin: f64,
+    pub final_percentiles: Vec<(f64, f64)>,
+    pub downswing_percentiles: Vec<(f64, f64)>,
+}
+
+/// Percentiles reported for net winnings.
+const FINAL_PERCENTILES: [f64; 5] = [5.0, 25.0, 50.0, 75.0, 95.0];
+
+/// Percentiles reported for the deepest downswing.
+const DOWNSWING_PERCENTILES: [f64; 5] = [50.0, 75.0, 90.0, 95.0, 99.0];
+
+/// Simulates bankroll trajectories with normally distributed results per
+/// 100-hand block and summarises risk of ruin and downswings.
+///
+/// Ruin is absorbing: a trajectory that drops to zero stops there.
+///
+/// # Errors
+/// Returns [`SnapError::InvalidArgument`] on a negative deviation, a
+/// non-positive bankroll, or zero hands or trials.
+pub fn simulate_bankroll(config: &VarianceConfig) -> Result<VarianceReport, SnapError> {
+    let VarianceConfig {
+        winrate,
+        stddev,
+        hands,
+        bankroll,
+        trials,
+        seed,
+    } = *config;
+    if !winrate.is_finite() || !stddev.is_finite() || stddev < 0.0 {
+        return Err(SnapError::InvalidArgument(format!(
+            "Win rate and deviation must be finite, deviation not negative (got {}, {})",
+            winrate, stddev
+        )));
+    }
+    if !bankroll.is_finite() || bankroll <= 0.0 {
+        return Err(SnapError::InvalidArgument(format!(
+            "Bankroll must be positive, got {}",
+            bankroll
+        )));
+    }
+    if hands == 0 || trials == 0 {
+        return Err(SnapError::InvalidArgument(
+            "Need at least one hand and one trial".to_string(),
+        ));
+    }
+
+    let mut rng = seeded_rng(seed);
+
+    let full_blocks = hands / BLOCK_HANDS;
+    let remainder = (hands % BLOCK_HANDS) as f64 / BLOCK_HANDS as f64;
+
+    let mut finals = Vec::with_capacity(trials);
+    let mut downswings = Vec::with_capacity(trials);
+    let mut ruined = 0usize;
+
+    for _ in 0..trials {
+        let mut net = 0.0;
+        let mut peak = 0.0;
+        let mut deepest: f64 = 0.0;
+        let mut broke = false;
+
+        let blocks = (0..full_blocks)
+            .map(|_| 1.0)
+            .chain((remainder > 0.0).then_some(remainder));
+        for fraction in blocks {
+            net += winrate * fraction + stddev * fraction.sqrt() * standard_normal(&mut rng);
+            if net > peak {
+                peak = net;
+            }
+            deepest = deepest.max(peak - net);
+            if bankroll + net <= 0.0 {
+                net = -bankroll;
+                broke = true;
+                break;
+            }
+        }
+
+        if broke {
+            ruined += 1;
+        }
+        finals.push(net);
+        downswings.push(deepest);
+    }
+
+    let blocks = hands as f64 / BLOCK_HANDS as f64;
+    let analytic_risk_of_ruin = if winrate <= 0.0 {
+        1.0
+    } else if stddev == 0.0 {
+        0.0
+    } else {
+        (-2.0 * winrate * bankroll / (stddev * stddev)).exp()
+    };
+
+    Ok(VarianceReport {
+        expected_winnings: winrate * blocks,
+        expected_stddev: stddev * blocks.sqrt(),
+        risk_of_ruin: ruined as f64 / trials as f64,
+        analytic_risk_of_ruin,
+        final_percentiles: percentiles(&mut finals, &FINAL_PERCENTILES),
+        downswing_percentiles: percentiles(&mut downswings, &DOWNSWING_PERCENTILES),
+    })
+}
+
+/// Standard normal sample via the Box–Muller transform.
+fn standard_normal(rng: &mut StdRng) -> f64 {
+    let u1: f64 = 1.0 - rng.random::<f64>(); // (0, 1], keeps ln finite
+    let u2: f64 = rng.random();
+    (-2.0 * u1.ln()).sqrt() * (std::f64::consts::TAU * u2).cos()
+}
+
+/// Nearest-rank percentiles of `values` (sorted in place).
+fn percentiles(values: &mut [f64], points: &[f64]) -> Vec<(f64, f64)> {
+    values.sort_by(f64::total_cmp);
+    points
+        .iter()
+        .map(|&p| {
+            let rank = ((p / 100.0) * values.len() as f64).ceil() as usize;
+            (p, values[rank.clamp(1, values.len()) - 1])
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> VarianceConfig {
+        VarianceConfig {
+            winrate: 5.0,
+            stddev: 80.0,
+            hands: 100_000,
+            bankroll: 5_000.0,
+            trials: 500,
+            seed: Some(7),
+        }
+    }
+
+    #[test]
+    fn seeded_runs_are_reproducible() {
+        assert_eq!(
+            simulate_bankroll(&config()).unwrap(),
+            simulate_bankroll(&config()).unwrap()
+        );
+    }
+
+    #[test]
+    fn median_tracks_expected_winnings() {
+        let report = simulate_bankroll(&config()).unwrap();
+        assert_eq!(report.expected_winnings, 5_000.0);
+        let median = report.final_percentiles[2].1;
+        assert!((median - 5_000.0).abs() < 1_500.0, "median {median}");
+    }
+
+    #[test]
+    fn risk_of_ruin_matches_closed_form_roughly() {
+        let report = simulate_bankroll(&VarianceConfig {
+            bankroll: 1_000.0,
+            ..config()
+        })
+        .unwrap();
+        // exp(-2 · 5 · 1000 / 6400) ≈ 21%; a finite horizon can only lower it
+        assert!((report.analytic_risk_of_ruin - 0.2096).abs() < 1e-3);
+        assert!(report.risk_of_ruin > 0.10 && report.risk_of_ruin < 0.25);
+    }
+
+    #[test]
+    fn downswings_are_non_negative_and_sorted() {
+        let report = simulate_bankroll(&config()).unwrap();
+        let values: Vec<f64> = report.downswing_percentiles.iter().map(|p| p.1).collect();
+        assert!(values[0] >= 0.0);
+        assert!(values.windows(2).all(|w| w[0] <= w[1]));
+    }
+
+    #[test]
+    fn rejects_invalid_inputs() {
+        let bad = [
+            VarianceConfig {
+                bankroll: 0.0,
+                ..config()
+            },
+            VarianceConfig {
+                stddev: -1.0,
+                ..config()
+            },
+            VarianceConfig {
+                trials: 0,
+                ..config()
+            },
+        ];
+        for c in &bad {
+            assert!(simulate_bankroll(c).is_err());
+        }
+    }
+}
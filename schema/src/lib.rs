@@ -0,0 +1,125 @@
+//! JSON shapes shared by the `snapcall` CLI and `snapcall-server`.
+//!
+//! A `{"scenario", "result"}` line written by `snapcall equity -o` is the
+//! same record `/v1/equity` returns, and `--error-format json` prints the
+//! same `{"error": ...}` body the server answers with.
+
+use serde::{Deserialize, Serialize};
+use snapcall_core::SnapError;
+
+/// Sample budget when neither a scenario nor the user sets one.
+pub const DEFAULT_ITERATIONS: u32 = 100_000;
+
+fn default_iterations() -> u32 {
+    DEFAULT_ITERATIONS
+}
+
+/// Inputs of an equity calculation, recorded next to its result.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct EquityScenario {
+    #[serde(default)]
+    pub board: String,
+    /// Second board for double-board bomb pots (always Monte Carlo)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub board2: Option<String>,
+    pub hero: String,
+    pub villains: Vec<String>,
+    /// Exposed or mucked cards removed from the deck, e.g. `"Ah 7c"`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dead: Option<String>,
+    #[serde(default = "default_iterations")]
+    pub iterations: u32,
+    /// Stop sampling after this long, or after `iterations` if that comes first
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub time_ms: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub seed: Option<u64>,
+}
+
+/// Outcome of an equity calculation; `board_equities` is only set with
+/// `board2`, and `equities` is then each player's share of the whole pot.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct EquityOutcome {
+    pub mode: String,
+    pub samples: usize,
+    pub equities: Vec<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub board_equities: Option<Vec<Vec<f64>>>,
+}
+
+/// What was asked and what came back: one `--output` line or one
+/// `/v1/equity` response.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct Record<S, R> {
+    pub scenario: S,
+    pub result: R,
+}
+
+/// What went wrong, as far as a calling script cares.
+///
+/// Each kind has its own code (the CLI's exit code) so callers can tell bad
+/// input from a failed computation or a filesystem problem.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorKind {
+    /// Invalid cards, ranges, argument values, or request bodies (code 2,
+    /// like clap usage errors)
+    Input,
+    /// The engine could not produce a result, e.g. a resource limit (code 3)
+    Compute,
+    /// Reading or writing files and streams failed (code 4)
+    Io,
+}
+
+impl ErrorKind {
+    /// The kind of a core error: resource limits are compute failures,
+    /// everything else is bad input.
+    pub fn of(err: &SnapError) -> Self {
+        match err {
+            SnapError::ResourceLimit(_) => ErrorKind::Compute,
+            SnapError::InvalidCard(_)
+            | SnapError::InvalidHand(_)
+            | SnapError::InvalidRange(_)
+            | SnapError::InvalidArgument(_) => ErrorKind::Input,
+        }
+    }
+
+    /// The CLI exit code for this kind, reported as `code`.
+    pub fn code(self) -> u8 {
+        match self {
+            ErrorKind::Input => 2,
+            ErrorKind::Compute => 3,
+            ErrorKind::Io => 4,
+        }
+    }
+}
+
+/// The `error` object of a failure:
+/// `{"error":{"kind":"input","code":2,"message":"...","hint":"..."}}`.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct ErrorBody {
+    pub kind: ErrorKind,
+    pub code: u8,
+    pub message: String,
+    /// A likely correction for mistyped input
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hint: Option<String>,
+}
+
+impl ErrorBody {
+    pub fn new(kind: ErrorKind, message: impl Into<String>) -> Self {
+        Self {
+            kind,
+            code: kind.code(),
+            message: message.into(),
+            hint: None,
+        }
+    }
+}
+
+/// A whole error response: `{"error": ...}`.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct ErrorResponse {
+    pub error: ErrorBody,
+}
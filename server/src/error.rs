@@ -0,0 +1,110 @@
+use axum::extract::rejection::JsonRejection;
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::Json;
+use snapcall_core::SnapError;
+use snapcall_schema::{ErrorBody, ErrorKind, ErrorResponse};
+
+/// A failed request: its HTTP status plus the JSON error body.
+#[derive(Debug)]
+pub struct ApiError {
+    status: StatusCode,
+    body: ErrorBody,
+}
+
+impl ApiError {
+    /// Wraps a core error, prefixing `context` to its message. Resource
+    /// limits answer `422 Unprocessable Entity`, everything else `400`.
+    pub fn snap(context: &str, err: SnapError) -> Self {
+        let kind = ErrorKind::of(&err);
+        let status = match kind {
+            ErrorKind::Compute => StatusCode::UNPROCESSABLE_ENTITY,
+            ErrorKind::Input | ErrorKind::Io => StatusCode::BAD_REQUEST,
+        };
+        Self::new(status, kind, format!("{context}: {err}"))
+    }
+
+    /// A computation that did not finish, e.g. a panicked worker.
+    pub fn compute(message: impl Into<String>) -> Self {
+        Self::new(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            ErrorKind::Compute,
+            message.into(),
+        )
+    }
+
+    pub fn body(&self) -> &ErrorBody {
+        &self.body
+    }
+
+    fn new(status: StatusCode, kind: ErrorKind, message: String) -> Self {
+        Self {
+            status,
+            body: ErrorBody::new(kind, message),
+        }
+    }
+}
+
+/// Malformed or oversized bodies keep axum's status but get the JSON error
+/// shape.
+impl From<JsonRejection> for ApiError {
+    fn from(rejection: JsonRejection) -> Self {
+        Self::new(rejection.status(), ErrorKind::Input, rejection.body_text())
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        let body = ErrorResponse { error: self.body };
+        (self.status, Json(body)).into_response()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::body::to_bytes;
+
+    use super::*;
+
+    #[test]
+    fn resource_limits_answer_422_as_compute_errors() {
+        let err = ApiError::snap("reading batch", SnapError::ResourceLimit("too big".into()));
+        assert_eq!(err.status, StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(err.body().kind, ErrorKind::Compute);
+        assert_eq!(err.body().code, 3);
+        assert!(err.body().message.starts_with("reading batch: "));
+    }
+
+    #[test]
+    fn bad_input_answers_400() {
+        for core in [
+            SnapError::InvalidCard("Zz".into()),
+            SnapError::InvalidHand("AhAh".into()),
+            SnapError::InvalidRange("QQ-".into()),
+            SnapError::InvalidArgument("0 trials".into()),
+        ] {
+            let err = ApiError::snap("parsing", core);
+            assert_eq!(err.status, StatusCode::BAD_REQUEST);
+            assert_eq!(err.body().kind, ErrorKind::Input);
+            assert_eq!(err.body().code, 2);
+        }
+    }
+
+    #[test]
+    fn failed_workers_answer_500() {
+        let err = ApiError::compute("calculation failed");
+        assert_eq!(err.status, StatusCode::INTERNAL_SERVER_ERROR);
+        assert_eq!(err.body().kind, ErrorKind::Compute);
+    }
+
+    #[tokio::test]
+    async fn responses_wrap_the_body_in_error() {
+        let response = ApiError::snap("x", SnapError::ResourceLimit("y".into())).into_response();
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+        let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body: ErrorResponse = serde_json::from_slice(&bytes).unwrap();
+        assert_eq!(body.error.kind, ErrorKind::Compute);
+        assert_eq!(body.error.code, 3);
+        assert_eq!(body.error.hint, None);
+    }
+}
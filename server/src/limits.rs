@@ -0,0 +1,182 @@
+use std::time::Duration;
+
+use snapcall_core::{SnapError, DEFAULT_MAX_EXACT_STATES};
+
+/// Per-request bounds on the work one request may ask for.
+///
+/// Requests over a limit fail with a `resource_limit` error (`422`) before
+/// any computation starts; nothing is silently clamped. The one budget
+/// enforced while running is a batch's total time: scenarios sample no
+/// longer than the time it has left, and fail once it is spent.
+/// `max_concurrent` is server-wide rather than per request: calculations
+/// beyond it wait for a free slot.
+#[derive(Debug, Clone, Copy)]
+pub struct Limits {
+    /// Most Monte Carlo samples (or ICM trials) one scenario may request.
+    pub max_iterations: usize,
+    /// Longest `time_ms` budget one scenario may request.
+    pub max_time: Duration,
+    /// State-space bound for exact enumeration.
+    pub max_exact_states: usize,
+    /// Most scenarios in one batch.
+    pub max_batch: usize,
+    /// Most Monte Carlo samples all scenarios of one batch may request together.
+    pub max_batch_iterations: usize,
+    /// Wall-clock budget for a whole batch.
+    pub max_batch_time: Duration,
+    /// Most players (stacks or payouts) in one ICM request.
+    pub max_icm_players: usize,
+    /// Largest accepted request body, in bytes.
+    pub max_body_bytes: usize,
+    /// Most calculations running on the blocking pool at once; at least 1.
+    pub max_concurrent: usize,
+}
+
+impl Default for Limits {
+    fn default() -> Self {
+        Self {
+            max_iterations: 1_000_000,
+            max_time: Duration::from_secs(5),
+            max_exact_states: DEFAULT_MAX_EXACT_STATES,
+            max_batch: 100,
+            max_batch_iterations: 10_000_000,
+            max_batch_time: Duration::from_secs(30),
+            max_icm_players: 100,
+            max_body_bytes: 256 * 1024,
+            max_concurrent: std::thread::available_parallelism().map_or(1, usize::from),
+        }
+    }
+}
+
+impl Limits {
+    /// Checks a requested sample count.
+    pub fn iterations(&self, requested: usize) -> Result<usize, SnapError> {
+        if requested > self.max_iterations {
+            return Err(SnapError::ResourceLimit(format!(
+                "iterations {} exceed the limit of {}",
+                requested, self.max_iterations
+            )));
+        }
+        Ok(requested)
+    }
+
+    /// Checks a requested time budget in milliseconds.
+    pub fn time_budget(&self, time_ms: Option<u64>) -> Result<Option<Duration>, SnapError> {
+        let Some(time) = time_ms.map(Duration::from_millis) else {
+            return Ok(None);
+        };
+        if time > self.max_time {
+            return Err(SnapError::ResourceLimit(format!(
+                "time_ms {} exceeds the limit of {}",
+                time.as_millis(),
+                self.max_time.as_millis()
+            )));
+        }
+        Ok(Some(time))
+    }
+
+    /// Checks the number of scenarios in a batch and the samples they ask
+    /// for together.
+    pub fn batch(&self, scenarios: usize, iterations: usize) -> Result<(), SnapError> {
+        if scenarios > self.max_batch {
+            return Err(SnapError::ResourceLimit(format!(
+                "{} scenarios exceed the batch limit of {}",
+                scenarios, self.max_batch
+            )));
+        }
+        if iterations > self.max_batch_iterations {
+            return Err(SnapError::ResourceLimit(format!(
+                "{} total iterations exceed the batch limit of {}",
+                iterations, self.max_batch_iterations
+            )));
+        }
+        Ok(())
+    }
+
+    /// Time a batch that has run for `elapsed` has left, or an error once
+    /// its budget is spent.
+    pub fn batch_time_left(&self, elapsed: Duration) -> Result<Duration, SnapError> {
+        match self.max_batch_time.checked_sub(elapsed) {
+            Some(left) if !left.is_zero() => Ok(left),
+            _ => Err(SnapError::ResourceLimit(format!(
+                "the batch time budget of {} ms is spent",
+                self.max_batch_time.as_millis()
+            ))),
+        }
+    }
+
+    /// Checks the player count of an ICM request.
+    pub fn icm_players(&self, stacks: usize, payouts: usize) -> Result<(), SnapError> {
+        let players = stacks.max(payouts);
+        if players > self.max_icm_players {
+            return Err(SnapError::ResourceLimit(format!(
+                "{} ICM players exceed the limit of {}",
+                players, self.max_icm_players
+            )));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn limits() -> Limits {
+        Limits {
+            max_iterations: 1_000,
+            max_time: Duration::from_millis(500),
+            max_batch: 3,
+            max_batch_iterations: 2_000,
+            max_batch_time: Duration::from_secs(2),
+            max_icm_players: 4,
+            ..Limits::default()
+        }
+    }
+
+    fn is_limit<T>(result: Result<T, SnapError>) -> bool {
+        matches!(result, Err(SnapError::ResourceLimit(_)))
+    }
+
+    #[test]
+    fn iterations_up_to_the_limit_pass_unchanged() {
+        assert_eq!(limits().iterations(1_000).unwrap(), 1_000);
+        assert!(is_limit(limits().iterations(1_001)));
+    }
+
+    #[test]
+    fn time_budget_is_checked_not_clamped() {
+        assert_eq!(limits().time_budget(None).unwrap(), None);
+        assert_eq!(
+            limits().time_budget(Some(500)).unwrap(),
+            Some(Duration::from_millis(500))
+        );
+        assert!(is_limit(limits().time_budget(Some(501))));
+    }
+
+    #[test]
+    fn batch_checks_size_and_total_iterations() {
+        assert!(limits().batch(3, 2_000).is_ok());
+        assert!(is_limit(limits().batch(4, 10)));
+        assert!(is_limit(limits().batch(2, 2_001)));
+    }
+
+    #[test]
+    fn batch_time_runs_out() {
+        assert_eq!(
+            limits()
+                .batch_time_left(Duration::from_millis(1_500))
+                .unwrap(),
+            Duration::from_millis(500)
+        );
+        assert!(is_limit(limits().batch_time_left(Duration::from_secs(2))));
+        assert!(is_limit(limits().batch_time_left(Duration::from_secs(3))));
+    }
+
+    #[test]
+    fn icm_players_count_stacks_and_payouts() {
+        assert!(limits().icm_players(4, 3).is_ok());
+        assert!(is_limit(limits().icm_players(5, 3)));
+        assert!(is_limit(limits().icm_players(2, 5)));
+    }
+}
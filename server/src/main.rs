@@ -0,0 +1,103 @@
+use std::net::SocketAddr;
+use std::process::ExitCode;
+use std::time::Duration;
+
+use clap::builder::RangedU64ValueParser;
+use clap::Parser;
+use snapcall_core::DEFAULT_MAX_EXACT_STATES;
+
+mod error;
+mod limits;
+mod routes;
+mod schema;
+
+use limits::Limits;
+
+/// HTTP JSON API for the SnapCall equity engine.
+#[derive(Parser)]
+#[command(name = "snapcall-server", version, about)]
+struct Args {
+    /// Address to listen on
+    #[arg(long, default_value = "127.0.0.1:8080")]
+    addr: SocketAddr,
+
+    /// Most Monte Carlo samples (or ICM trials) one scenario may request
+    #[arg(long = "max-iterations", default_value_t = 1_000_000)]
+    max_iterations: usize,
+
+    /// Longest time_ms budget one scenario may request
+    #[arg(long = "max-time-ms", default_value_t = 5_000)]
+    max_time_ms: u64,
+
+    /// State-space bound for exact enumeration
+    #[arg(long = "max-states", default_value_t = DEFAULT_MAX_EXACT_STATES)]
+    max_states: usize,
+
+    /// Most scenarios in one /v1/batch request
+    #[arg(long = "max-batch", default_value_t = 100)]
+    max_batch: usize,
+
+    /// Most Monte Carlo samples all scenarios of one /v1/batch request may ask for together
+    #[arg(long = "max-batch-iterations", default_value_t = 10_000_000)]
+    max_batch_iterations: usize,
+
+    /// Wall-clock budget for one /v1/batch request; later scenarios fail once it is spent
+    #[arg(long = "max-batch-time-ms", default_value_t = 30_000)]
+    max_batch_time_ms: u64,
+
+    /// Most players (stacks or payouts) in one /v1/icm request
+    #[arg(long = "max-icm-players", default_value_t = 100)]
+    max_icm_players: usize,
+
+    /// Largest accepted request body, in bytes
+    #[arg(long = "max-body-bytes", default_value_t = 256 * 1024)]
+    max_body_bytes: usize,
+
+    /// Most calculations running at once; further requests wait for a free slot [default: one per core]
+    #[arg(long = "max-concurrent", value_parser = RangedU64ValueParser::<usize>::new().range(1..))]
+    max_concurrent: Option<usize>,
+}
+
+impl Args {
+    fn limits(&self) -> Limits {
+        Limits {
+            max_iterations: self.max_iterations,
+            max_time: Duration::from_millis(self.max_time_ms),
+            max_exact_states: self.max_states,
+            max_batch: self.max_batch,
+            max_batch_iterations: self.max_batch_iterations,
+            max_batch_time: Duration::from_millis(self.max_batch_time_ms),
+            max_icm_players: self.max_icm_players,
+            max_body_bytes: self.max_body_bytes,
+            max_concurrent: self
+                .max_concurrent
+                .unwrap_or(Limits::default().max_concurrent),
+        }
+    }
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let args = Args::parse();
+    let listener = match tokio::net::TcpListener::bind(args.addr).await {
+        Ok(listener) => listener,
+        Err(e) => {
+            eprintln!("Error: binding {}: {e}", args.addr);
+            return ExitCode::from(4);
+        }
+    };
+    eprintln!("snapcall-server listening on http://{}", args.addr);
+
+    let app = routes::router(args.limits());
+    let shutdown = async {
+        let _ = tokio::signal::ctrl_c().await;
+    };
+    if let Err(e) = axum::serve(listener, app)
+        .with_graceful_shutdown(shutdown)
+        .await
+    {
+        eprintln!("Error: serving: {e}");
+        return ExitCode::from(4);
+    }
+    ExitCode::SUCCESS
+}
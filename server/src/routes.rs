@@ -0,0 +1,327 @@
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use axum::extract::rejection::JsonRejection;
+use axum::extract::{DefaultBodyLimit, State};
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use snapcall_core::{
+    best_hand, estimate_equity_with_options, estimate_icm_equity,
+    estimate_multi_board_equity_with_options, expand_range, parse_cards, Card, EquityEstimateMode,
+    EquityOptions, DEFAULT_ICM_TRIALS, TOTAL_COMBOS,
+};
+use snapcall_schema::{EquityOutcome, EquityScenario, Record};
+use tokio::sync::Semaphore;
+
+use crate::error::ApiError;
+use crate::limits::Limits;
+use crate::schema::{
+    BatchEntry, BatchReport, BatchRequest, EvaluateReport, EvaluateRequest, IcmReport, IcmRequest,
+    RangeReport, RangeRequest,
+};
+
+/// What handlers share: the limits plus one permit per calculation allowed
+/// to run at once.
+#[derive(Clone)]
+struct AppState {
+    limits: Limits,
+    calculations: Arc<Semaphore>,
+}
+
+/// Every endpoint, sharing `limits`.
+pub fn router(limits: Limits) -> Router {
+    let state = AppState {
+        limits,
+        calculations: Arc::new(Semaphore::new(limits.max_concurrent)),
+    };
+    Router::new()
+        .route("/health", get(health))
+        .route("/v1/equity", post(equity))
+        .route("/v1/batch", post(batch))
+        .route("/v1/range", post(range))
+        .route("/v1/evaluate", post(evaluate))
+        .route("/v1/icm", post(icm))
+        .layer(DefaultBodyLimit::max(limits.max_body_bytes))
+        .with_state(state)
+}
+
+async fn health() -> &'static str {
+    "ok"
+}
+
+async fn equity(
+    State(state): State<AppState>,
+    body: Result<Json<EquityScenario>, JsonRejection>,
+) -> Result<Json<Record<EquityScenario, EquityOutcome>>, ApiError> {
+    let Json(scenario) = body?;
+    let limits = state.limits;
+    blocking(&state, move || {
+        let result = solve_equity(&scenario, &limits, None)?;
+        Ok(Json(Record { scenario, result }))
+    })
+    .await
+}
+
+async fn batch(
+    State(state): State<AppState>,
+    body: Result<Json<BatchRequest>, JsonRejection>,
+) -> Result<Json<BatchReport>, ApiError> {
+    let Json(request) = body?;
+    let limits = state.limits;
+    let iterations = request
+        .scenarios
+        .iter()
+        .map(|scenario| scenario.iterations as usize)
+        .sum();
+    limits
+        .batch(request.scenarios.len(), iterations)
+        .map_err(|e| ApiError::snap("reading batch", e))?;
+    blocking(&state, move || {
+        let start = Instant::now();
+        let results = request
+            .scenarios
+            .into_iter()
+            .map(|scenario| {
+                let solved = limits
+                    .batch_time_left(start.elapsed())
+                    .map_err(|e| ApiError::snap("solving batch", e))
+                    .and_then(|left| solve_equity(&scenario, &limits, Some(left)));
+                match solved {
+                    Ok(result) => BatchEntry {
+                        scenario,
+                        result: Some(result),
+                        error: None,
+                    },
+                    Err(err) => BatchEntry {
+                        scenario,
+                        result: None,
+                        error: Some(err.body().clone()),
+                    },
+                }
+            })
+            .collect();
+        Ok(Json(BatchReport { results }))
+    })
+    .await
+}
+
+async fn range(
+    body: Result<Json<RangeRequest>, JsonRejection>,
+) -> Result<Json<RangeReport>, ApiError> {
+    let Json(request) = body?;
+    let combos = expand_range(&request.range).map_err(|e| ApiError::snap("parsing range", e))?;
+    Ok(Json(RangeReport {
+        range: request.range.trim().to_string(),
+        combos: combos.len(),
+        share_percent: combos.len() as f64 / TOTAL_COMBOS as f64 * 100.0,
+        hands: combos.iter().map(|c| format!("{}{}", c[0], c[1])).collect(),
+    }))
+}
+
+async fn evaluate(
+    body: Result<Json<EvaluateRequest>, JsonRejection>,
+) -> Result<Json<EvaluateReport>, ApiError> {
+    let Json(request) = body?;
+    let cards = parse_cards(&request.cards).map_err(|e| ApiError::snap("parsing cards", e))?;
+    let best = best_hand(&cards).map_err(|e| ApiError::snap("evaluating hand", e))?;
+    Ok(Json(EvaluateReport {
+        category: best.category.to_string(),
+        description: best.describe(),
+        best_five: card_strings(&best.cards),
+        made: card_strings(best.made()),
+        kickers: card_strings(best.kickers()),
+    }))
+}
+
+async fn icm(
+    State(state): State<AppState>,
+    body: Result<Json<IcmRequest>, JsonRejection>,
+) -> Result<Json<IcmReport>, ApiError> {
+    let Json(request) = body?;
+    let limits = state.limits;
+    limits
+        .icm_players(request.stacks.len(), request.payouts.len())
+        .map_err(|e| ApiError::snap("reading ICM options", e))?;
+    let trials = limits
+        .iterations(request.trials.unwrap_or(DEFAULT_ICM_TRIALS))
+        .map_err(|e| ApiError::snap("reading ICM options", e))?;
+    blocking(&state, move || {
+        let equities = estimate_icm_equity(&request.stacks, &request.payouts, trials, request.seed)
+            .map_err(|e| ApiError::snap("calculating ICM equity", e))?;
+        Ok(Json(IcmReport { equities }))
+    })
+    .await
+}
+
+/// Runs one equity scenario within `limits`, sampling for at most
+/// `time_left` when it runs as part of a batch.
+fn solve_equity(
+    scenario: &EquityScenario,
+    limits: &Limits,
+    time_left: Option<Duration>,
+) -> Result<EquityOutcome, ApiError> {
+    let iterations = limits
+        .iterations(scenario.iterations as usize)
+        .map_err(|e| ApiError::snap("reading equity options", e))?;
+    let time_budget = limits
+        .time_budget(scenario.time_ms)
+        .map_err(|e| ApiError::snap("reading equity options", e))?;
+    let time_budget = match (time_budget, time_left) {
+        (Some(budget), Some(left)) => Some(budget.min(left)),
+        (budget, left) => budget.or(left),
+    };
+    let villains: Vec<&str> = scenario.villains.iter().map(|s| s.as_str()).collect();
+    let options = EquityOptions {
+        max_exact_states: limits.max_exact_states,
+        dead_cards: parse_cards(scenario.dead.as_deref().unwrap_or_default())
+            .map_err(|e| ApiError::snap("parsing dead cards", e))?,
+        time_budget,
+        seed: scenario.seed,
+        ..EquityOptions::new(iterations)
+    };
+
+    if let Some(board2) = &scenario.board2 {
+        let boards = [scenario.board.as_str(), board2.as_str()];
+        let result =
+            estimate_multi_board_equity_with_options(&boards, &scenario.hero, &villains, &options)
+                .map_err(|e| ApiError::snap("calculating equity", e))?;
+        return Ok(EquityOutcome {
+            mode: EquityEstimateMode::MonteCarlo.to_string(),
+            samples: result.samples,
+            equities: result.pot_share,
+            board_equities: Some(result.board_equities),
+        });
+    }
+
+    let result = estimate_equity_with_options(&scenario.board, &scenario.hero, &villains, &options)
+        .map_err(|e| ApiError::snap("calculating equity", e))?;
+    Ok(EquityOutcome {
+        mode: result.mode.to_string(),
+        samples: result.samples,
+        equities: result.equities,
+        board_equities: None,
+    })
+}
+
+/// Runs `work` on the blocking pool so long calculations never stall the
+/// async workers, once one of `state`'s calculation permits is free.
+async fn blocking<T: Send + 'static>(
+    state: &AppState,
+    work: impl FnOnce() -> Result<T, ApiError> + Send + 'static,
+) -> Result<T, ApiError> {
+    let permit = Arc::clone(&state.calculations)
+        .acquire_owned()
+        .await
+        .map_err(|e| ApiError::compute(format!("calculation failed: {e}")))?;
+    tokio::task::spawn_blocking(move || {
+        let _permit = permit;
+        work()
+    })
+    .await
+    .map_err(|e| ApiError::compute(format!("calculation failed: {e}")))?
+}
+
+fn card_strings(cards: &[Card]) -> Vec<String> {
+    cards.iter().map(Card::to_string).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::body::{to_bytes, Body};
+    use axum::http::{Request, StatusCode};
+    use serde_json::{json, Value};
+    use tower::ServiceExt;
+
+    use super::*;
+
+    fn limits() -> Limits {
+        Limits {
+            max_iterations: 50_000,
+            max_batch: 4,
+            max_concurrent: 1,
+            ..Limits::default()
+        }
+    }
+
+    /// Posts `body` to `path` and returns the status and parsed JSON body.
+    async fn post(limits: Limits, path: &str, body: Value) -> (StatusCode, Value) {
+        let request = Request::post(path)
+            .header("content-type", "application/json")
+            .body(Body::from(body.to_string()))
+            .unwrap();
+        let response = router(limits).oneshot(request).await.unwrap();
+        let status = response.status();
+        let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        (status, serde_json::from_slice(&bytes).unwrap())
+    }
+
+    #[tokio::test]
+    async fn over_limit_requests_answer_422() {
+        let scenario = json!({"hero": "AhKh", "villains": ["QQ"], "iterations": 50_001});
+        let (status, body) = post(limits(), "/v1/equity", scenario).await;
+        assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(body["error"]["kind"], "compute");
+        assert_eq!(body["error"]["code"], 3);
+
+        let scenarios = vec![json!({"hero": "AhKh", "villains": ["QQ"]}); 5];
+        let (status, _) = post(limits(), "/v1/batch", json!({"scenarios": scenarios})).await;
+        assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
+    #[tokio::test]
+    async fn bad_ranges_answer_400() {
+        let scenario = json!({"hero": "AhKh", "villains": ["QQ-"], "iterations": 1_000});
+        let (status, body) = post(limits(), "/v1/equity", scenario).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body["error"]["kind"], "input");
+        assert_eq!(body["error"]["code"], 2);
+
+        let (status, body) = post(limits(), "/v1/range", json!({"range": "QQ-"})).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body["error"]["code"], 2);
+    }
+
+    #[tokio::test]
+    async fn batches_report_each_scenario() {
+        let scenarios = json!([
+            {"hero": "AhAd", "villains": ["KhKd"], "iterations": 5_000, "seed": 1},
+            {"hero": "AhKh", "villains": ["TT+"], "board": "2h7c9d", "board2": "KsKd3c",
+             "iterations": 2_000, "seed": 2},
+            {"hero": "AhKh", "villains": ["QQ-"], "iterations": 1_000},
+        ]);
+        let (status, body) = post(limits(), "/v1/batch", json!({"scenarios": scenarios})).await;
+        assert_eq!(status, StatusCode::OK);
+        let results = body["results"].as_array().unwrap();
+        assert_eq!(results.len(), 3);
+        assert_eq!(results[0]["result"]["samples"], 5_000);
+        assert_eq!(results[0]["scenario"]["hero"], "AhAd");
+        assert_eq!(results[1]["result"]["samples"], 2_000);
+        assert_eq!(
+            results[1]["result"]["board_equities"]
+                .as_array()
+                .unwrap()
+                .len(),
+            2
+        );
+        assert_eq!(results[2]["error"]["code"], 2);
+        assert!(results[2].get("result").is_none());
+    }
+
+    #[tokio::test]
+    async fn multi_board_batches_stop_at_the_batch_deadline() {
+        let limits = Limits {
+            max_iterations: 100_000_000,
+            max_batch_iterations: 100_000_000,
+            max_batch_time: Duration::from_millis(50),
+            ..limits()
+        };
+        let scenarios = json!([
+            {"hero": "AhKh", "villains": ["TT+"], "board": "", "board2": "",
+             "iterations": 100_000_000},
+        ]);
+        let (status, body) = post(limits, "/v1/batch", json!({"scenarios": scenarios})).await;
+        assert_eq!(status, StatusCode::OK);
+        let samples = body["results"][0]["result"]["samples"].as_u64().unwrap();
+        assert!(samples > 0 && samples < 100_000_000, "{samples}");
+    }
+}
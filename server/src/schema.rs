@@ -0,0 +1,80 @@
+//! Request and response bodies.
+//!
+//! Equity scenarios, results, and records come from `snapcall-schema`, so a
+//! `{"scenario", "result"}` line written by `snapcall equity -o` reads the
+//! same as a response here.
+
+use serde::{Deserialize, Serialize};
+use snapcall_schema::{EquityOutcome, EquityScenario, ErrorBody};
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct BatchRequest {
+    pub scenarios: Vec<EquityScenario>,
+}
+
+/// One batch entry: the scenario plus either its `result` or its `error`.
+/// A failed scenario does not stop the batch.
+#[derive(Debug, Serialize)]
+pub struct BatchEntry {
+    pub scenario: EquityScenario,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub result: Option<EquityOutcome>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<ErrorBody>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct BatchReport {
+    pub results: Vec<BatchEntry>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RangeRequest {
+    pub range: String,
+}
+
+/// A range's combos, as listed by `snapcall range --list`.
+#[derive(Debug, Serialize)]
+pub struct RangeReport {
+    pub range: String,
+    pub combos: usize,
+    /// Share of all 1326 starting hands, in percent
+    pub share_percent: f64,
+    pub hands: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct EvaluateRequest {
+    /// 5-7 cards, e.g. `"AsKsQsJsTs"`
+    pub cards: String,
+}
+
+/// The best five-card hand, as printed by `snapcall eval`.
+#[derive(Debug, Serialize)]
+pub struct EvaluateReport {
+    pub category: String,
+    pub description: String,
+    pub best_five: Vec<String>,
+    pub made: Vec<String>,
+    pub kickers: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct IcmRequest {
+    pub stacks: Vec<f64>,
+    pub payouts: Vec<f64>,
+    /// Finishing orders sampled when too many players have chips to solve
+    /// exactly
+    pub trials: Option<usize>,
+    pub seed: Option<u64>,
+}
+
+/// Prize equity per player, in the units of `payouts`.
+#[derive(Debug, Serialize)]
+pub struct IcmReport {
+    pub equities: Vec<f64>,
+}